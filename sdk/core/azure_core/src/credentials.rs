@@ -89,7 +89,23 @@ impl AccessToken {
 
 /// Options for getting a token from a [`TokenCredential`]
 #[derive(Clone, Default, SafeDebug)]
-pub struct TokenRequestOptions;
+pub struct TokenRequestOptions {
+    /// Additional claims required in the token, such as those from a claims challenge returned by a service.
+    ///
+    /// This is the JSON claims request, decoded from the base64 value in a `WWW-Authenticate` challenge.
+    pub claims: Option<String>,
+
+    /// Whether to request a token that supports Continuous Access Evaluation (CAE).
+    ///
+    /// Services that support CAE may revoke these tokens before they expire and return a claims challenge,
+    /// so only enable CAE if the caller handles claims challenges.
+    pub enable_cae: bool,
+
+    /// The tenant to request a token from instead of the tenant the credential was configured for.
+    ///
+    /// This is typically discovered from an authentication challenge returned by a service.
+    pub tenant_id: Option<String>,
+}
 
 /// Represents a credential capable of providing an OAuth token.
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
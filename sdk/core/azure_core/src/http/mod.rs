@@ -16,6 +16,7 @@ pub use models::*;
 pub use options::*;
 pub use pager::*;
 pub use pipeline::*;
+pub use poller::{Poller, PollerResult};
 pub use request::{Body, Request, RequestContent};
 pub use response::{RawResponse, Response};
 
@@ -25,5 +26,10 @@ pub use typespec_client_core::http::{
     StatusCode, Url,
 };
 
+#[cfg(all(feature = "hyper", not(target_arch = "wasm32")))]
+pub use typespec_client_core::http::{new_hyper_client, HyperBody, HyperClient};
+#[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+pub use typespec_client_core::http::{ReqwestTransportOptions, TlsIdentity};
+
 #[cfg(feature = "xml")]
 pub use typespec_client_core::http::XmlFormat;
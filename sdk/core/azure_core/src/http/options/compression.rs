@@ -0,0 +1,110 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::{fmt, str::FromStr};
+
+/// Policy options to compress request bodies and decode compressed response bodies.
+///
+/// Compression is only performed by the [`CompressionPolicy`](crate::http::policies::CompressionPolicy)
+/// independent of the [`HttpClient`](crate::http::HttpClient) used, so it also applies to custom transports.
+#[derive(Clone, Debug)]
+pub struct CompressionOptions {
+    /// The encoding used to compress request bodies, if any.
+    ///
+    /// Only enable request compression for services that support a compressed request body.
+    pub request_encoding: Option<ContentEncoding>,
+
+    /// The minimum size in bytes of a request body to compress. The default is 1 KiB.
+    pub request_threshold: usize,
+
+    /// The encodings sent in the `Accept-Encoding` header and decoded from response bodies.
+    ///
+    /// The default is all encodings enabled by crate features.
+    pub response_encodings: Vec<ContentEncoding>,
+}
+
+impl Default for CompressionOptions {
+    fn default() -> Self {
+        Self {
+            request_encoding: None,
+            request_threshold: 1024,
+            response_encodings: ContentEncoding::ALL.to_vec(),
+        }
+    }
+}
+
+/// An HTTP content encoding supported by the [`CompressionPolicy`](crate::http::policies::CompressionPolicy).
+///
+/// Each encoding requires a crate feature:
+/// * `compression_gzip` for [`ContentEncoding::Gzip`] and [`ContentEncoding::Deflate`].
+/// * `compression_brotli` for [`ContentEncoding::Brotli`].
+/// * `compression_zstd` for [`ContentEncoding::Zstd`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContentEncoding {
+    /// The `gzip` encoding.
+    #[cfg(feature = "compression_gzip")]
+    Gzip,
+
+    /// The `deflate` encoding, which is zlib-wrapped DEFLATE.
+    #[cfg(feature = "compression_gzip")]
+    Deflate,
+
+    /// The `br` encoding.
+    #[cfg(feature = "compression_brotli")]
+    Brotli,
+
+    /// The `zstd` encoding.
+    #[cfg(feature = "compression_zstd")]
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// All encodings enabled by crate features, in order of preference.
+    pub const ALL: &'static [ContentEncoding] = &[
+        #[cfg(feature = "compression_zstd")]
+        ContentEncoding::Zstd,
+        #[cfg(feature = "compression_brotli")]
+        ContentEncoding::Brotli,
+        #[cfg(feature = "compression_gzip")]
+        ContentEncoding::Gzip,
+        #[cfg(feature = "compression_gzip")]
+        ContentEncoding::Deflate,
+    ];
+
+    /// The value of the encoding in the `Content-Encoding` and `Accept-Encoding` headers.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "compression_gzip")]
+            ContentEncoding::Gzip => "gzip",
+            #[cfg(feature = "compression_gzip")]
+            ContentEncoding::Deflate => "deflate",
+            #[cfg(feature = "compression_brotli")]
+            ContentEncoding::Brotli => "br",
+            #[cfg(feature = "compression_zstd")]
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+}
+
+impl fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        ContentEncoding::ALL
+            .iter()
+            .find(|encoding| encoding.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| {
+                crate::Error::with_message(crate::error::ErrorKind::DataConversion, || {
+                    format!("unsupported content encoding '{s}'")
+                })
+            })
+    }
+}
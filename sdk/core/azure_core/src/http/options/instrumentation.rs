@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{metrics::MeterProvider, tracing::TracerProvider};
+use std::{fmt, sync::Arc};
+
+/// Policy options for distributed tracing and client metrics.
+#[derive(Clone, Default)]
+pub struct InstrumentationOptions {
+    /// The [`TracerProvider`] used to create spans for client methods and HTTP requests.
+    ///
+    /// If [`None`], no spans are created.
+    pub tracer_provider: Option<Arc<dyn TracerProvider + Send + Sync>>,
+
+    /// The [`MeterProvider`] used to record metrics for HTTP requests and token acquisition.
+    ///
+    /// If [`None`], no metrics are recorded.
+    pub meter_provider: Option<Arc<dyn MeterProvider + Send + Sync>>,
+}
+
+impl fmt::Debug for InstrumentationOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstrumentationOptions")
+            .field("tracer_provider", &self.tracer_provider.is_some())
+            .field("meter_provider", &self.meter_provider.is_some())
+            .finish()
+    }
+}
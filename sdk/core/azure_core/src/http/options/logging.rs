@@ -0,0 +1,25 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::borrow::Cow;
+
+/// Policy options to log HTTP requests and responses at the `debug` level.
+///
+/// Only the values of allowed headers and query parameters are logged; all other values are redacted.
+#[derive(Clone, Debug, Default)]
+pub struct LoggingOptions {
+    /// Header names, in addition to the default allowed header names, whose values can be logged.
+    pub additional_allowed_header_names: Vec<Cow<'static, str>>,
+
+    /// Query parameter names, in addition to the default allowed query parameter names, whose values can be logged.
+    pub additional_allowed_query_params: Vec<Cow<'static, str>>,
+
+    /// Disable to prevent logging requests and responses.
+    pub disabled: bool,
+
+    /// Log request and response bodies at the `trace` level, which may contain personally identifiable information (PII) or secrets.
+    ///
+    /// Response bodies are read into memory before they are returned.
+    #[cfg(feature = "test")]
+    pub log_body: bool,
+}
@@ -1,13 +1,22 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+mod compression;
+mod instrumentation;
+mod logging;
 mod user_agent;
 
+pub use compression::*;
+pub use instrumentation::*;
+pub use logging::*;
 use std::sync::Arc;
-use typespec_client_core::http::policies::Policy;
 pub use typespec_client_core::http::{
     ClientMethodOptions, ExponentialRetryOptions, FixedRetryOptions, RetryOptions, TransportOptions,
 };
+use typespec_client_core::{
+    http::{policies::Policy, Context},
+    time::Duration,
+};
 pub use user_agent::*;
 
 /// Client options allow customization of general client policies, retry options, and more.
@@ -27,6 +36,68 @@ pub struct ClientOptions {
 
     /// User-Agent telemetry options.
     pub user_agent: Option<UserAgentOptions>,
+
+    /// Request and response logging options.
+    pub logging: Option<LoggingOptions>,
+
+    /// Distributed tracing options.
+    pub instrumentation: Option<InstrumentationOptions>,
+
+    /// Request and response body compression options.
+    ///
+    /// If [`None`], request bodies are not compressed and response bodies are only decoded by the [`HttpClient`](crate::http::HttpClient), if supported.
+    /// Ignored unless the `compression_gzip`, `compression_brotli`, or `compression_zstd` feature is enabled.
+    pub compression: Option<CompressionOptions>,
+}
+
+/// Method options allow customization of client methods that return a [`Pager`](crate::http::Pager) or [`PageIterator`](crate::http::PageIterator).
+#[derive(Clone, Debug, Default)]
+pub struct PagerOptions<'a> {
+    /// The [`Context`] for this method call.
+    pub context: Context<'a>,
+
+    /// A continuation token returned from [`PageIterator::continuation_token()`](crate::http::PageIterator::continuation_token) to resume paging.
+    ///
+    /// If [`None`], paging starts from the first page.
+    pub continuation_token: Option<String>,
+}
+
+impl PagerOptions<'_> {
+    /// Converts these options into owned options with a `'static` lifetime.
+    pub fn into_owned(self) -> PagerOptions<'static> {
+        PagerOptions {
+            context: self.context.into_owned(),
+            continuation_token: self.continuation_token,
+        }
+    }
+}
+
+/// Method options allow customization of client methods that return a [`Poller`](crate::http::Poller).
+#[derive(Clone, Debug, Default)]
+pub struct PollerOptions<'a> {
+    /// The [`Context`] for this method call.
+    pub context: Context<'a>,
+
+    /// How long to wait between polling the status of the operation if the service does not return a `retry-after` header.
+    ///
+    /// If [`None`], the client method's default is used.
+    pub frequency: Option<Duration>,
+
+    /// A continuation token returned from [`Poller::continuation_token()`](crate::http::Poller::continuation_token) to resume polling an operation.
+    ///
+    /// If [`None`], the operation is started.
+    pub continuation_token: Option<String>,
+}
+
+impl PollerOptions<'_> {
+    /// Converts these options into owned options with a `'static` lifetime.
+    pub fn into_owned(self) -> PollerOptions<'static> {
+        PollerOptions {
+            context: self.context.into_owned(),
+            frequency: self.frequency,
+            continuation_token: self.continuation_token,
+        }
+    }
 }
 
 impl ClientOptions {
@@ -35,7 +106,12 @@ impl ClientOptions {
     /// If instead we implemented [`Into`], we'd have to clone Azure-specific options instead of moving memory of [`Some`] values.
     pub(in crate::http) fn deconstruct(
         self,
-    ) -> (UserAgentOptions, typespec_client_core::http::ClientOptions) {
+    ) -> (
+        UserAgentOptions,
+        LoggingOptions,
+        InstrumentationOptions,
+        typespec_client_core::http::ClientOptions,
+    ) {
         let options = typespec_client_core::http::ClientOptions {
             per_call_policies: self.per_call_policies,
             per_try_policies: self.per_try_policies,
@@ -43,6 +119,11 @@ impl ClientOptions {
             transport: self.transport,
         };
 
-        (self.user_agent.unwrap_or_default(), options)
+        (
+            self.user_agent.unwrap_or_default(),
+            self.logging.unwrap_or_default(),
+            self.instrumentation.unwrap_or_default(),
+            options,
+        )
     }
 }
@@ -1,10 +1,20 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::http::{headers::HeaderName, response::Response};
+use crate::{
+    cancellation::CancellationToken,
+    error::ErrorKind,
+    http::{headers::HeaderName, response::Response},
+};
 use async_trait::async_trait;
 use futures::{stream::unfold, FutureExt, Stream};
-use std::{fmt, future::Future, pin::Pin, task};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task,
+};
 use typespec::Error;
 use typespec_client_core::http::{DeserializeWith, Format, JsonFormat};
 
@@ -75,10 +85,10 @@ where
 pub type Pager<P, F = JsonFormat> = ItemIterator<Response<P, F>>;
 
 #[cfg(not(target_arch = "wasm32"))]
-type BoxedStream<P> = Box<dyn Stream<Item = Result<P, Error>> + Send>;
+pub(crate) type BoxedStream<P> = Box<dyn Stream<Item = Result<P, Error>> + Send>;
 
 #[cfg(target_arch = "wasm32")]
-type BoxedStream<P> = Box<dyn Stream<Item = Result<P, Error>>>;
+pub(crate) type BoxedStream<P> = Box<dyn Stream<Item = Result<P, Error>>>;
 
 /// Iterates over a collection of items or individual pages of items from a service.
 ///
@@ -89,13 +99,16 @@ pub struct ItemIterator<P: Page> {
     #[pin]
     stream: Pin<BoxedStream<P>>,
     current: Option<P::IntoIter>,
+    continuation: Continuation,
+    cancellation: Cancellation,
 }
 
 impl<P: Page> ItemIterator<P> {
     /// Creates a [`ItemIterator<P>`] from a callback that will be called repeatedly to request each page.
     ///
     /// This method expect a callback that accepts a single `Option<N>` parameter, and returns a [`PagerResult<T, N>`] value asynchronously.
-    /// The `N` type parameter is the type of the next link/token. It may be any [`Send`]able type.
+    /// The `N` type parameter is the type of the next link/token. It may be any [`Send`]able type that can be referenced as a [`str`],
+    /// which is what [`PageIterator::continuation_token()`] will return to resume paging later.
     /// The result will be an asynchronous stream of [`Result<T>`](typespec::Result<T>) values.
     ///
     /// The first time your callback is called, it will be called with [`Option::None`], indicating no next link/token is present.
@@ -202,16 +215,41 @@ impl<P: Page> ItemIterator<P> {
     /// ```
     pub fn from_callback<
         // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
-        #[cfg(not(target_arch = "wasm32"))] N: Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
         #[cfg(not(target_arch = "wasm32"))] F: Fn(Option<N>) -> Fut + Send + 'static,
         #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + Send + 'static,
-        #[cfg(target_arch = "wasm32")] N: 'static,
+        #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
         #[cfg(target_arch = "wasm32")] F: Fn(Option<N>) -> Fut + 'static,
         #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + 'static,
     >(
         make_request: F,
     ) -> Self {
-        Self::from_stream(iter_from_callback(make_request))
+        Self::from_callback_with_continuation(make_request, None)
+    }
+
+    /// Creates a [`ItemIterator<P>`] from a callback that will be called repeatedly to request each page, starting with the given `continuation`.
+    ///
+    /// This is the same as [`ItemIterator::from_callback()`] except that the first time your callback is called, it will be called with `continuation`.
+    /// Pass a value parsed from a [`PageIterator::continuation_token()`] saved earlier to resume paging where it left off,
+    /// or [`None`] to start paging from the beginning.
+    pub fn from_callback_with_continuation<
+        // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
+        #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] F: Fn(Option<N>) -> Fut + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + Send + 'static,
+        #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
+        #[cfg(target_arch = "wasm32")] F: Fn(Option<N>) -> Fut + 'static,
+        #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + 'static,
+    >(
+        make_request: F,
+        continuation: Option<N>,
+    ) -> Self {
+        let tracker = Continuation::new(continuation.as_ref().map(AsRef::as_ref));
+        let stream = iter_from_callback(make_request, continuation, tracker.clone());
+        Self {
+            continuation: tracker,
+            ..Self::from_stream(stream)
+        }
     }
 
     /// Creates a [`ItemIterator<P>`] from a raw stream of [`Result<P>`](typespec::Result<P>) values.
@@ -227,6 +265,24 @@ impl<P: Page> ItemIterator<P> {
         Self {
             stream: Box::pin(stream),
             current: None,
+            continuation: Continuation::default(),
+            cancellation: Cancellation::None,
+        }
+    }
+
+    /// Stops requesting pages once the `token` is cancelled.
+    ///
+    /// Items from a page already received are still returned, and a request for a page already in progress is allowed to finish.
+    /// When the next page would be requested, the iterator instead returns an error with
+    /// [`ErrorKind::Cancelled`](crate::error::ErrorKind::Cancelled) and then ends.
+    #[must_use]
+    pub fn with_cancellation_token(self, token: &CancellationToken) -> Self {
+        Self {
+            cancellation: Cancellation::Waiting {
+                token: token.clone(),
+                requesting: false,
+            },
+            ..self
         }
     }
 
@@ -238,6 +294,8 @@ impl<P: Page> ItemIterator<P> {
     pub fn into_pages(self) -> PageIterator<P> {
         PageIterator {
             stream: self.stream,
+            continuation: self.continuation,
+            cancellation: self.cancellation,
         }
     }
 }
@@ -259,7 +317,15 @@ impl<P: Page> futures::Stream for ItemIterator<P> {
                 *projected_self.current = None;
             }
 
-            match projected_self.stream.as_mut().poll_next(cx) {
+            if let Some(item) = projected_self.cancellation.check() {
+                return task::Poll::Ready(item);
+            }
+
+            let page = projected_self.stream.as_mut().poll_next(cx);
+            projected_self
+                .cancellation
+                .set_requesting(page.is_pending());
+            match page {
                 task::Poll::Ready(page) => match page {
                     Some(Ok(page)) => match page.into_items().poll_unpin(cx) {
                         task::Poll::Ready(Ok(iter)) => {
@@ -289,13 +355,16 @@ impl<P: Page> fmt::Debug for ItemIterator<P> {
 pub struct PageIterator<P> {
     #[pin]
     stream: Pin<BoxedStream<P>>,
+    continuation: Continuation,
+    cancellation: Cancellation,
 }
 
 impl<P> PageIterator<P> {
     /// Creates a [`PageIterator<P>`] from a callback that will be called repeatedly to request each page.
     ///
     /// This method expect a callback that accepts a single `Option<N>` parameter, and returns a [`PagerResult<T, N>`] value asynchronously.
-    /// The `N` type parameter is the type of the next link/token. It may be any [`Send`]able type.
+    /// The `N` type parameter is the type of the next link/token. It may be any [`Send`]able type that can be referenced as a [`str`],
+    /// which is what [`PageIterator::continuation_token()`] will return to resume paging later.
     /// The result will be an asynchronous stream of [`Result<T>`](typespec::Result<T>) values.
     ///
     /// The first time your callback is called, it will be called with [`Option::None`], indicating no next link/token is present.
@@ -384,16 +453,41 @@ impl<P> PageIterator<P> {
     /// ```
     pub fn from_callback<
         // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
-        #[cfg(not(target_arch = "wasm32"))] N: Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
         #[cfg(not(target_arch = "wasm32"))] F: Fn(Option<N>) -> Fut + Send + 'static,
         #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + Send + 'static,
-        #[cfg(target_arch = "wasm32")] N: 'static,
+        #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
         #[cfg(target_arch = "wasm32")] F: Fn(Option<N>) -> Fut + 'static,
         #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + 'static,
     >(
         make_request: F,
     ) -> Self {
-        Self::from_stream(iter_from_callback(make_request))
+        Self::from_callback_with_continuation(make_request, None)
+    }
+
+    /// Creates a [`PageIterator<P>`] from a callback that will be called repeatedly to request each page, starting with the given `continuation`.
+    ///
+    /// This is the same as [`PageIterator::from_callback()`] except that the first time your callback is called, it will be called with `continuation`.
+    /// Pass a value parsed from a [`PageIterator::continuation_token()`] saved earlier to resume paging where it left off,
+    /// or [`None`] to start paging from the beginning.
+    pub fn from_callback_with_continuation<
+        // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
+        #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] F: Fn(Option<N>) -> Fut + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + Send + 'static,
+        #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
+        #[cfg(target_arch = "wasm32")] F: Fn(Option<N>) -> Fut + 'static,
+        #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + 'static,
+    >(
+        make_request: F,
+        continuation: Option<N>,
+    ) -> Self {
+        let tracker = Continuation::new(continuation.as_ref().map(AsRef::as_ref));
+        let stream = iter_from_callback(make_request, continuation, tracker.clone());
+        Self {
+            continuation: tracker,
+            ..Self::from_stream(stream)
+        }
     }
 
     /// Creates a [`PageIterator<P>`] from a raw stream of [`Result<P>`](typespec::Result<P>) values.
@@ -408,8 +502,37 @@ impl<P> PageIterator<P> {
     ) -> Self {
         Self {
             stream: Box::pin(stream),
+            continuation: Continuation::default(),
+            cancellation: Cancellation::None,
+        }
+    }
+
+    /// Stops requesting pages once the `token` is cancelled.
+    ///
+    /// A request for a page already in progress is allowed to finish. When the next page would be requested,
+    /// the iterator instead returns an error with [`ErrorKind::Cancelled`](crate::error::ErrorKind::Cancelled) and then ends.
+    #[must_use]
+    pub fn with_cancellation_token(self, token: &CancellationToken) -> Self {
+        Self {
+            cancellation: Cancellation::Waiting {
+                token: token.clone(),
+                requesting: false,
+            },
+            ..self
         }
     }
+
+    /// Gets the continuation token that can be used to resume paging from the next page not yet returned.
+    ///
+    /// Save this value and pass it to the client method's [`PagerOptions::continuation_token`](crate::http::PagerOptions::continuation_token),
+    /// or parse it and pass it to [`PageIterator::from_callback_with_continuation()`], to resume paging later even in a different process.
+    /// If fetching a page fails, the continuation token still refers to the page that failed so that it may be retried.
+    ///
+    /// Returns [`None`] if no page has been fetched and paging was not resumed from a continuation token,
+    /// if there are no more pages, or if this [`PageIterator<P>`] was created using [`PageIterator::from_stream()`].
+    pub fn continuation_token(&self) -> Option<String> {
+        self.continuation.get()
+    }
 }
 
 impl<P> futures::Stream for PageIterator<P> {
@@ -419,7 +542,15 @@ impl<P> futures::Stream for PageIterator<P> {
         self: Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        self.project().stream.poll_next(cx)
+        let projected_self = self.project();
+        if let Some(item) = projected_self.cancellation.check() {
+            return std::task::Poll::Ready(item);
+        }
+        let page = projected_self.stream.poll_next(cx);
+        projected_self
+            .cancellation
+            .set_requesting(page.is_pending());
+        page
     }
 }
 
@@ -429,6 +560,48 @@ impl<P> fmt::Debug for PageIterator<P> {
     }
 }
 
+/// Whether an [`ItemIterator`] or [`PageIterator`] has been cancelled by a [`CancellationToken`].
+#[derive(Debug, Default)]
+enum Cancellation {
+    #[default]
+    None,
+    Waiting {
+        token: CancellationToken,
+        /// Whether a request for a page is in progress and should be allowed to finish.
+        requesting: bool,
+    },
+    Cancelled,
+}
+
+impl Cancellation {
+    /// Returns the item to end the iterator with if cancelled: an error the first time, and then `None`.
+    ///
+    /// Cancellation is not observed while a request for a page is in progress.
+    fn check<T>(&mut self) -> Option<Option<Result<T, Error>>> {
+        match self {
+            Cancellation::None => None,
+            Cancellation::Waiting { token, requesting } => {
+                if *requesting || !token.is_cancelled() {
+                    return None;
+                }
+                *self = Cancellation::Cancelled;
+                Some(Some(Err(Error::message(
+                    ErrorKind::Cancelled,
+                    "paging was cancelled",
+                ))))
+            }
+            Cancellation::Cancelled => Some(None),
+        }
+    }
+
+    /// Records whether a request for a page is in progress.
+    fn set_requesting(&mut self, in_progress: bool) {
+        if let Cancellation::Waiting { requesting, .. } = self {
+            *requesting = in_progress;
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum State<T> {
     Init,
@@ -436,38 +609,74 @@ enum State<T> {
     Done,
 }
 
+/// Tracks the next link/token of a pager or poller so it can be returned as a continuation token.
+///
+/// This is shared between the stream and the [`ItemIterator`], [`PageIterator`], or [`Poller`](crate::http::poller::Poller) since the stream type is erased.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Continuation(Arc<Mutex<Option<String>>>);
+
+impl Continuation {
+    pub(crate) fn new(token: Option<&str>) -> Self {
+        Self(Arc::new(Mutex::new(token.map(ToOwned::to_owned))))
+    }
+
+    pub(crate) fn get(&self) -> Option<String> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    pub(crate) fn set(&self, token: Option<&str>) {
+        *self.0.lock().unwrap_or_else(|err| err.into_inner()) = token.map(ToOwned::to_owned);
+    }
+}
+
 fn iter_from_callback<
     P,
     // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
-    #[cfg(not(target_arch = "wasm32"))] N: Send + 'static,
+    #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
     #[cfg(not(target_arch = "wasm32"))] F: Fn(Option<N>) -> Fut + Send + 'static,
     #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + Send + 'static,
-    #[cfg(target_arch = "wasm32")] N: 'static,
+    #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
     #[cfg(target_arch = "wasm32")] F: Fn(Option<N>) -> Fut + 'static,
     #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PagerResult<P, N>, typespec::Error>> + 'static,
 >(
     make_request: F,
+    continuation: Option<N>,
+    tracker: Continuation,
 ) -> impl Stream<Item = Result<P, Error>> + 'static {
+    let state = match continuation {
+        Some(continuation) => State::More(continuation),
+        None => State::Init,
+    };
     unfold(
         // We flow the `make_request` callback through the state value so that we can avoid cloning.
-        (State::Init, make_request),
-        |(state, make_request)| async move {
-            let result = match state {
-                State::Init => make_request(None).await,
-                State::More(c) => make_request(Some(c)).await,
-                State::Done => return None,
-            };
-            let (item, next_state) = match result {
-                Err(e) => return Some((Err(e), (State::Done, make_request))),
-                Ok(PagerResult::More {
-                    response,
-                    next: continuation,
-                }) => (Ok(response), State::More(continuation)),
-                Ok(PagerResult::Done { response }) => (Ok(response), State::Done),
-            };
-
-            // Flow 'make_request' through to avoid cloning
-            Some((item, (next_state, make_request)))
+        (state, make_request),
+        move |(state, make_request)| {
+            let tracker = tracker.clone();
+            async move {
+                let result = match state {
+                    State::Init => make_request(None).await,
+                    State::More(c) => make_request(Some(c)).await,
+                    State::Done => return None,
+                };
+                let (item, next_state) = match result {
+                    // Leave the continuation token referring to the failed page so it may be retried.
+                    Err(e) => return Some((Err(e), (State::Done, make_request))),
+                    Ok(PagerResult::More {
+                        response,
+                        next: continuation,
+                    }) => {
+                        tracker.set(Some(continuation.as_ref()));
+                        (Ok(response), State::More(continuation))
+                    }
+                    Ok(PagerResult::Done { response }) => {
+                        tracker.set(None);
+                        (Ok(response), State::Done)
+                    }
+                };
+
+                // Flow 'make_request' through to avoid cloning
+                Some((item, (next_state, make_request)))
+            }
         },
     )
 }
@@ -476,7 +685,7 @@ fn iter_from_callback<
 mod tests {
     use crate::http::{
         headers::{HeaderName, HeaderValue},
-        Pager, PagerResult, RawResponse, StatusCode,
+        Pager, PagerResult, RawResponse, Response, StatusCode,
     };
     use async_trait::async_trait;
     use futures::{StreamExt as _, TryStreamExt as _};
@@ -606,4 +815,126 @@ mod tests {
         assert_eq!(&typespec::error::ErrorKind::Other, err.kind());
         assert_eq!("yon request didst fail", format!("{}", err));
     }
+
+    fn page_response(page: i32) -> Response<Page> {
+        RawResponse::from_bytes(
+            StatusCode::Ok,
+            HashMap::from([(
+                HeaderName::from_static("x-test-header"),
+                HeaderValue::from(format!("page-{page}")),
+            )])
+            .into(),
+            format!(r#"{{"items":[{page}],"page":{page}}}"#),
+        )
+        .into()
+    }
+
+    async fn get_page(
+        continuation: Option<String>,
+    ) -> typespec::Result<PagerResult<Response<Page>, String>> {
+        match continuation.as_deref() {
+            None => Ok(PagerResult::More {
+                response: page_response(1),
+                next: "1".into(),
+            }),
+            Some("1") => Ok(PagerResult::More {
+                response: page_response(2),
+                next: "2".into(),
+            }),
+            Some("2") => Ok(PagerResult::Done {
+                response: page_response(3),
+            }),
+            _ => {
+                panic!("Unexpected continuation value")
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn callback_page_continuation_token() {
+        let mut pages = Pager::<Page>::from_callback(get_page).into_pages();
+        assert_eq!(None, pages.continuation_token());
+
+        let mut tokens = Vec::new();
+        while let Some(page) = pages.next().await {
+            let page = page.unwrap().into_body().await.unwrap();
+            tokens.push((page.page, pages.continuation_token()));
+        }
+        assert_eq!(
+            vec![(1, Some("1".into())), (2, Some("2".into())), (3, None)],
+            tokens
+        );
+    }
+
+    #[tokio::test]
+    async fn callback_item_pagination_resumed() {
+        let pages =
+            Pager::<Page>::from_callback_with_continuation(get_page, Some("1".into())).into_pages();
+        assert_eq!(Some("1".to_string()), pages.continuation_token());
+
+        let items: Vec<i32> =
+            Pager::<Page>::from_callback_with_continuation(get_page, Some("1".into()))
+                .try_collect()
+                .await
+                .unwrap();
+        assert_eq!(vec![2, 3], items.as_slice())
+    }
+
+    #[tokio::test]
+    async fn callback_page_continuation_token_error() {
+        let mut pages = Pager::<Page>::from_callback(|continuation: Option<String>| async move {
+            match continuation.as_deref() {
+                Some("2") => Err(typespec::Error::message(
+                    typespec::error::ErrorKind::Other,
+                    "yon request didst fail",
+                )),
+                _ => get_page(continuation).await,
+            }
+        })
+        .into_pages();
+
+        assert!(pages.next().await.unwrap().is_ok());
+        assert!(pages.next().await.unwrap().is_ok());
+        assert!(pages.next().await.unwrap().is_err());
+        assert!(pages.next().await.is_none());
+
+        // The failed page can be retried.
+        assert_eq!(Some("2".to_string()), pages.continuation_token());
+    }
+
+    #[tokio::test]
+    async fn cancelled_between_pages() {
+        let token = crate::cancellation::CancellationToken::new();
+        let mut items = Pager::<Page>::from_callback(get_page).with_cancellation_token(&token);
+        assert_eq!(1, items.next().await.unwrap().unwrap());
+
+        token.cancel();
+        let err = items.next().await.unwrap().unwrap_err();
+        assert_eq!(&typespec::error::ErrorKind::Cancelled, err.kind());
+        assert!(items.next().await.is_none());
+
+        // A request for a page in progress is allowed to finish once cancelled.
+        let token = crate::cancellation::CancellationToken::new();
+        let mut pages = Pager::<Page>::from_callback(|continuation: Option<String>| async move {
+            if continuation.is_some() {
+                crate::sleep::sleep(crate::time::Duration::milliseconds(50)).await;
+            }
+            get_page(continuation).await
+        })
+        .into_pages()
+        .with_cancellation_token(&token);
+        assert!(pages.next().await.unwrap().is_ok());
+
+        let cancel = async {
+            crate::sleep::sleep(crate::time::Duration::milliseconds(10)).await;
+            token.cancel();
+        };
+        let (page, _) = futures::join!(pages.next(), cancel);
+        assert!(page.unwrap().is_ok());
+        assert_eq!(
+            &typespec::error::ErrorKind::Cancelled,
+            pages.next().await.unwrap().unwrap_err().kind()
+        );
+        assert!(pages.next().await.is_none());
+    }
 }
@@ -3,9 +3,13 @@
 
 use super::policies::ClientRequestIdPolicy;
 use crate::http::{
-    policies::{Policy, UserAgentPolicy},
+    policies::{
+        LoggingPolicy, MeterPolicy, Policy, PublicApiInstrumentationPolicy,
+        RequestInstrumentationPolicy, RequestMetricsPolicy, UserAgentPolicy,
+    },
     ClientOptions,
 };
+use crate::{metrics::Meter, tracing::Tracer};
 use std::{
     any::{Any, TypeId},
     ops::Deref,
@@ -17,16 +21,21 @@ use typespec_client_core::http;
 ///
 /// A pipeline follows a precise flow:
 ///
-/// 1. Client library-specified per-call policies are executed. Per-call policies can fail and bail out of the pipeline
-///    immediately.
-/// 2. User-specified per-call policies in [`ClientOptions::per_call_policies`] are executed.
-/// 3. The retry policy is executed. It allows to re-execute the following policies.
-/// 4. The [`CustomHeadersPolicy`](crate::http::policies::CustomHeadersPolicy) is executed
-/// 5. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are
+/// 1. The [`PublicApiInstrumentationPolicy`] is executed if [`ClientOptions::instrumentation`] has a tracer provider,
+///    and the [`MeterPolicy`] if it has a meter provider.
+/// 2. Client library-specified per-call policies are executed. Per-call policies can fail and bail out of the pipeline
+///    immediately. The `CompressionPolicy` is executed after them if [`ClientOptions::compression`] is set.
+/// 3. User-specified per-call policies in [`ClientOptions::per_call_policies`] are executed.
+/// 4. The retry policy is executed. It allows to re-execute the following policies.
+/// 5. The [`CustomHeadersPolicy`](crate::http::policies::CustomHeadersPolicy) is executed
+/// 6. Client library-specified per-retry policies. Per-retry polices are always executed at least once but are
 ///    re-executed in case of retries.
-/// 6. User-specified per-retry policies in [`ClientOptions::per_try_policies`] are executed.
-/// 7. The transport policy is executed. Transport policy is always the last policy and is the policy that
-///    actually constructs the [`RawResponse`](http::RawResponse) to be passed up the pipeline.
+/// 7. The [`RequestInstrumentationPolicy`] is executed if [`ClientOptions::instrumentation`] has a tracer provider,
+///    and the [`RequestMetricsPolicy`] if it has a meter provider.
+/// 8. The [`LoggingPolicy`] is executed unless disabled in [`ClientOptions::logging`].
+/// 9. User-specified per-retry policies in [`ClientOptions::per_try_policies`] are executed.
+/// 10. The transport policy is executed. Transport policy is always the last policy and is the policy that
+///     actually constructs the [`RawResponse`](http::RawResponse) to be passed up the pipeline.
 ///
 /// A pipeline is immutable. In other words a policy can either succeed and call the following
 /// policy of fail and return to the calling policy. Arbitrary policy "skip" must be avoided (but
@@ -46,17 +55,60 @@ impl Pipeline {
         crate_version: Option<&'static str>,
         options: ClientOptions,
         per_call_policies: Vec<Arc<dyn Policy>>,
-        per_try_policies: Vec<Arc<dyn Policy>>,
+        mut per_try_policies: Vec<Arc<dyn Policy>>,
     ) -> Self {
         let mut per_call_policies = per_call_policies.clone();
         push_unique(&mut per_call_policies, ClientRequestIdPolicy::default());
 
-        let (user_agent, options) = options.deconstruct();
+        #[cfg(any(
+            feature = "compression_brotli",
+            feature = "compression_gzip",
+            feature = "compression_zstd"
+        ))]
+        if let Some(compression) = &options.compression {
+            push_unique(
+                &mut per_call_policies,
+                super::policies::CompressionPolicy::new(compression),
+            );
+        }
+
+        let (user_agent, logging, instrumentation, options) = options.deconstruct();
         if !user_agent.disabled {
             let telemetry_policy = UserAgentPolicy::new(crate_name, crate_version, &user_agent);
             push_unique(&mut per_call_policies, telemetry_policy);
         }
 
+        if let Some(tracer_provider) = instrumentation.tracer_provider {
+            let tracer: Arc<dyn Tracer + Send + Sync> = Arc::from(tracer_provider.get_tracer(
+                crate_name.unwrap_or("unknown"),
+                crate_version.unwrap_or("unknown"),
+            ));
+            per_call_policies.insert(
+                0,
+                Arc::new(PublicApiInstrumentationPolicy::new(tracer.clone())),
+            );
+            push_unique(
+                &mut per_try_policies,
+                RequestInstrumentationPolicy::new(tracer),
+            );
+        }
+
+        if let Some(meter_provider) = instrumentation.meter_provider {
+            let meter: Arc<dyn Meter + Send + Sync> = Arc::from(meter_provider.get_meter(
+                crate_name.unwrap_or("unknown"),
+                crate_version.unwrap_or("unknown"),
+            ));
+            per_call_policies.insert(0, Arc::new(MeterPolicy::new(meter.clone())));
+            push_unique(
+                &mut per_try_policies,
+                RequestMetricsPolicy::new(meter.as_ref()),
+            );
+        }
+
+        if !logging.disabled {
+            push_unique(&mut per_try_policies, LoggingPolicy::new(&logging));
+        }
+
         Self(http::Pipeline::new(
             options,
             per_call_policies,
@@ -1,13 +1,15 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+use super::{challenge::claims_challenge, metrics::GetTokenDuration};
 use crate::{
-    credentials::{AccessToken, TokenCredential},
+    credentials::{AccessToken, TokenCredential, TokenRequestOptions},
     error::{Error, ErrorKind},
     http::{
         headers::AUTHORIZATION,
         policies::{Policy, PolicyResult},
     },
+    tracing::AttributeValue,
 };
 use async_lock::RwLock;
 use async_trait::async_trait;
@@ -16,6 +18,9 @@ use typespec_client_core::http::{Context, Request};
 use typespec_client_core::time::{Duration, OffsetDateTime};
 
 /// Authentication policy for a bearer token.
+///
+/// Tokens are requested with Continuous Access Evaluation (CAE) enabled. If the service responds with an `insufficient_claims` challenge,
+/// a new token with the claims from the challenge is requested and the request is sent again.
 #[derive(Debug, Clone)]
 pub struct BearerTokenCredentialPolicy {
     credential: Arc<dyn TokenCredential>,
@@ -43,6 +48,36 @@ impl BearerTokenCredentialPolicy {
             .collect::<Vec<&str>>()
     }
 
+    fn token_request_options(claims: Option<String>) -> TokenRequestOptions {
+        TokenRequestOptions {
+            claims,
+            enable_cae: true,
+            ..Default::default()
+        }
+    }
+
+    /// Gets a token from the credential, recording how long it took if the [`Context`] has a meter.
+    async fn get_token(
+        &self,
+        ctx: &Context<'_>,
+        claims: Option<String>,
+    ) -> crate::Result<AccessToken> {
+        let start = OffsetDateTime::now_utc();
+        let result = self
+            .credential
+            .get_token(&self.scopes(), Some(Self::token_request_options(claims)))
+            .await;
+        if let Some(GetTokenDuration(histogram)) = ctx.value::<GetTokenDuration>() {
+            let duration = OffsetDateTime::now_utc() - start;
+            let attributes = match &result {
+                Ok(_) => Vec::new(),
+                Err(err) => vec![("error.type", AttributeValue::String(err.kind().to_string()))],
+            };
+            histogram.record(duration.as_seconds_f64(), &attributes);
+        }
+        result
+    }
+
     async fn access_token(&self) -> Option<String> {
         let access_token = self.access_token.read().await;
         access_token.as_ref().map(|s| s.token.secret().to_string())
@@ -66,7 +101,7 @@ impl Policy for BearerTokenCredentialPolicy {
                 drop(access_token);
                 let mut access_token = self.access_token.write().await;
                 if access_token.is_none() {
-                    *access_token = Some(self.credential.get_token(&self.scopes(), None).await?);
+                    *access_token = Some(self.get_token(ctx, None).await?);
                 }
             }
             Some(token) if should_refresh(&token.expires_on) => {
@@ -78,7 +113,7 @@ impl Policy for BearerTokenCredentialPolicy {
                 // access_token shouldn't be None here, but check anyway to guarantee unwrap won't panic
                 if access_token.is_none() || access_token.as_ref().unwrap().expires_on == expires_on
                 {
-                    match self.credential.get_token(&self.scopes(), None).await {
+                    match self.get_token(ctx, None).await {
                         Ok(new_token) => {
                             *access_token = Some(new_token);
                         }
@@ -108,11 +143,27 @@ impl Policy for BearerTokenCredentialPolicy {
         })?;
         request.insert_header(AUTHORIZATION, format!("Bearer {}", access_token));
 
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        let Some(claims) = claims_challenge(&response) else {
+            return Ok(response);
+        };
+
+        // The token was revoked or does not satisfy a policy, so send the request once more with a token containing the required claims.
+        let access_token = self.get_token(ctx, Some(claims)).await?;
+        request.insert_header(
+            AUTHORIZATION,
+            format!("Bearer {}", access_token.token.secret()),
+        );
+        *self.access_token.write().await = Some(access_token);
+
+        let mut body = request.body().clone();
+        body.reset().await?;
+        request.set_body(body);
         next[0].send(ctx, request, &next[1..]).await
     }
 }
 
-fn should_refresh(expires_on: &OffsetDateTime) -> bool {
+pub(super) fn should_refresh(expires_on: &OffsetDateTime) -> bool {
     *expires_on <= OffsetDateTime::now_utc() + Duration::minutes(5)
 }
 
@@ -122,7 +173,7 @@ mod tests {
     use crate::{
         credentials::{Secret, TokenCredential, TokenRequestOptions},
         http::{
-            headers::{Headers, AUTHORIZATION},
+            headers::{Headers, AUTHORIZATION, WWW_AUTHENTICATE},
             policies::Policy,
             Request, StatusCode,
         },
@@ -134,7 +185,7 @@ mod tests {
     use futures::FutureExt;
     use std::sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     };
     use typespec_client_core::{
         http::{policies::TransportPolicy, Method, RawResponse, TransportOptions},
@@ -144,6 +195,7 @@ mod tests {
     #[derive(Debug, Clone)]
     struct MockCredential {
         calls: Arc<AtomicUsize>,
+        claims: Arc<Mutex<Vec<Option<String>>>>,
         tokens: Arc<[AccessToken]>,
     }
 
@@ -151,6 +203,7 @@ mod tests {
         fn new(tokens: &[AccessToken]) -> Self {
             Self {
                 calls: Arc::new(AtomicUsize::new(0)),
+                claims: Arc::default(),
                 tokens: tokens.into(),
             }
         }
@@ -176,8 +229,11 @@ mod tests {
         async fn get_token(
             &self,
             _: &[&str],
-            _: Option<TokenRequestOptions>,
+            options: Option<TokenRequestOptions>,
         ) -> Result<AccessToken> {
+            let options = options.expect("token request options");
+            assert!(options.enable_cae);
+            self.claims.lock().unwrap().push(options.claims);
             let i = self.calls.fetch_add(1, Ordering::SeqCst);
             self.tokens
                 .get(i)
@@ -274,4 +330,56 @@ mod tests {
         ])
         .await;
     }
+
+    #[tokio::test]
+    async fn handles_claims_challenge() {
+        let expires_on = OffsetDateTime::now_utc() + Duration::seconds(3600);
+        let credential = Arc::new(MockCredential::new(&[
+            AccessToken::new("revoked", expires_on),
+            AccessToken::new("with-claims", expires_on),
+        ]));
+        let policy = BearerTokenCredentialPolicy::new(credential.clone(), ["scope"]);
+        let client = Arc::new(MockHttpClient::new(|req| {
+            async move {
+                if req.headers().get_str(&AUTHORIZATION)? == "Bearer with-claims" {
+                    assert_eq!(req.body().len(), 4, "expected the body to be sent again");
+                    return Ok(RawResponse::from_bytes(
+                        StatusCode::Ok,
+                        Headers::new(),
+                        Bytes::new(),
+                    ));
+                }
+                let mut headers = Headers::new();
+                headers.insert(
+                    WWW_AUTHENTICATE,
+                    r#"Bearer realm="", error="insufficient_claims", claims="eyJhY2Nlc3NfdG9rZW4iOnsibmJmIjp7ImVzc2VudGlhbCI6dHJ1ZSwidmFsdWUiOiIxNzI2MjU4MTIyIn19fQ==""#,
+                );
+                Ok(RawResponse::from_bytes(
+                    StatusCode::Unauthorized,
+                    headers,
+                    Bytes::new(),
+                ))
+            }
+            .boxed()
+        }));
+        let transport = Arc::new(TransportPolicy::new(TransportOptions::new(client)));
+
+        let mut req = Request::new("https://localhost".parse().unwrap(), Method::Put);
+        req.set_body(Bytes::from_static(b"body"));
+        let response = policy
+            .send(&Context::default(), &mut req, &[transport])
+            .await
+            .expect("successful request");
+        assert_eq!(StatusCode::Ok, response.status());
+        assert_eq!(
+            *credential.claims.lock().unwrap(),
+            vec![
+                None,
+                Some(
+                    r#"{"access_token":{"nbf":{"essential":true,"value":"1726258122"}}}"#
+                        .to_string()
+                )
+            ]
+        );
+    }
 }
@@ -0,0 +1,239 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Parsing of `WWW-Authenticate` authentication challenges.
+
+use crate::{
+    base64,
+    http::{headers::WWW_AUTHENTICATE, RawResponse, StatusCode},
+};
+use std::{collections::HashMap, iter::Peekable, str::CharIndices};
+
+/// An authentication challenge from a `WWW-Authenticate` header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Challenge {
+    /// The authentication scheme e.g., `Bearer`.
+    pub scheme: String,
+
+    /// The authentication parameters keyed by lowercase parameter name.
+    pub parameters: HashMap<String, String>,
+}
+
+impl Challenge {
+    /// Gets the value of a parameter, ignoring case of the parameter `name`.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+/// Parses all challenges from a `WWW-Authenticate` header value.
+///
+/// Parameters may be separated by commas as defined by RFC 7235 or by whitespace as returned by some services,
+/// and values may be tokens or quoted strings.
+pub(crate) fn parse_challenges(value: &str) -> Vec<Challenge> {
+    let mut challenges: Vec<Challenge> = Vec::new();
+    let mut chars = value.char_indices().peekable();
+    loop {
+        skip_separators(&mut chars);
+        let Some(&(start, _)) = chars.peek() else {
+            break;
+        };
+        let end = take_until(&mut chars, |c| c.is_whitespace() || c == '=' || c == ',');
+        let name = &value[start..end];
+
+        skip_whitespace(&mut chars);
+        if matches!(chars.peek(), Some((_, '='))) {
+            chars.next();
+            skip_whitespace(&mut chars);
+            let param = match chars.peek() {
+                Some(&(_, '"')) => take_quoted(&mut chars),
+                Some(&(start, _)) => {
+                    let end = take_until(&mut chars, |c| c.is_whitespace() || c == ',');
+                    value[start..end].to_string()
+                }
+                None => String::new(),
+            };
+            if let Some(challenge) = challenges.last_mut() {
+                challenge
+                    .parameters
+                    .insert(name.to_ascii_lowercase(), param);
+            }
+        } else {
+            challenges.push(Challenge {
+                scheme: name.to_string(),
+                ..Default::default()
+            });
+        }
+    }
+
+    challenges
+}
+
+/// Gets the decoded claims from an `insufficient_claims` challenge if the `response` is `401 Unauthorized`.
+///
+/// Services that support Continuous Access Evaluation (CAE) return this challenge when a token was revoked
+/// or does not satisfy a policy, and the request should be sent again with a token containing these claims.
+pub(crate) fn claims_challenge(response: &RawResponse) -> Option<String> {
+    if response.status() != StatusCode::Unauthorized {
+        return None;
+    }
+    let value = response.headers().get_optional_str(&WWW_AUTHENTICATE)?;
+    let challenge = parse_challenges(value).into_iter().find(|c| {
+        c.scheme.eq_ignore_ascii_case("bearer")
+            && c.parameter("error") == Some("insufficient_claims")
+    })?;
+    let claims = challenge.parameter("claims")?;
+    let claims = base64::decode(claims)
+        .or_else(|_| base64::decode_url_safe(claims))
+        .ok()?;
+    String::from_utf8(claims)
+        .ok()
+        .filter(|claims| !claims.is_empty())
+}
+
+fn skip_separators(chars: &mut Peekable<CharIndices>) {
+    while chars
+        .next_if(|(_, c)| c.is_whitespace() || *c == ',')
+        .is_some()
+    {}
+}
+
+fn skip_whitespace(chars: &mut Peekable<CharIndices>) {
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+}
+
+fn take_until(chars: &mut Peekable<CharIndices>, f: impl Fn(char) -> bool) -> usize {
+    let mut end = 0;
+    while let Some(&(i, c)) = chars.peek() {
+        if f(c) {
+            return i;
+        }
+        end = i + c.len_utf8();
+        chars.next();
+    }
+    end
+}
+
+fn take_quoted(chars: &mut Peekable<CharIndices>) -> String {
+    // Skip the opening quote.
+    chars.next();
+
+    let mut value = String::new();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => {
+                if let Some((_, c)) = chars.next() {
+                    value.push(c);
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_key_vault_challenge() {
+        let challenges = parse_challenges(
+            r#"Bearer authorization="https://login.microsoftonline.com/00000000-0000-0000-0000-000000000000", resource="https://vault.azure.net""#,
+        );
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0].scheme, "Bearer");
+        assert_eq!(
+            challenges[0].parameter("authorization"),
+            Some("https://login.microsoftonline.com/00000000-0000-0000-0000-000000000000")
+        );
+        assert_eq!(
+            challenges[0].parameter("Resource"),
+            Some("https://vault.azure.net")
+        );
+    }
+
+    #[test]
+    fn parse_storage_challenge() {
+        let challenges = parse_challenges(
+            "Bearer authorization_uri=https://login.microsoftonline.com/00000000-0000-0000-0000-000000000000/oauth2/authorize resource_id=https://storage.azure.com",
+        );
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(
+            challenges[0].parameter("authorization_uri"),
+            Some("https://login.microsoftonline.com/00000000-0000-0000-0000-000000000000/oauth2/authorize")
+        );
+        assert_eq!(
+            challenges[0].parameter("resource_id"),
+            Some("https://storage.azure.com")
+        );
+    }
+
+    #[test]
+    fn parse_multiple_challenges() {
+        let challenges = parse_challenges(
+            r#"Basic realm="simple", Bearer realm="", error="insufficient_claims", claims="eyJhY2Nlc3NfdG9rZW4iOnt9fQ==""#,
+        );
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].scheme, "Basic");
+        assert_eq!(challenges[0].parameter("realm"), Some("simple"));
+        assert_eq!(challenges[1].scheme, "Bearer");
+        assert_eq!(challenges[1].parameter("realm"), Some(""));
+        assert_eq!(
+            challenges[1].parameter("error"),
+            Some("insufficient_claims")
+        );
+        assert_eq!(
+            challenges[1].parameter("claims"),
+            Some("eyJhY2Nlc3NfdG9rZW4iOnt9fQ==")
+        );
+    }
+
+    #[test]
+    fn parse_escaped_quotes() {
+        let challenges = parse_challenges(r#"Bearer error_description="a \"quoted\" value""#);
+        assert_eq!(
+            challenges[0].parameter("error_description"),
+            Some(r#"a "quoted" value"#)
+        );
+    }
+
+    #[test]
+    fn claims_from_challenge() {
+        let mut headers = crate::http::headers::Headers::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            r#"Bearer realm="", authorization_uri="https://login.microsoftonline.com/common/oauth2/authorize", error="insufficient_claims", claims="eyJhY2Nlc3NfdG9rZW4iOnsibmJmIjp7ImVzc2VudGlhbCI6dHJ1ZSwidmFsdWUiOiIxNzI2MjU4MTIyIn19fQ==""#,
+        );
+        let response = RawResponse::from_bytes(
+            StatusCode::Unauthorized,
+            headers.clone(),
+            crate::Bytes::new(),
+        );
+        assert_eq!(
+            claims_challenge(&response).as_deref(),
+            Some(r#"{"access_token":{"nbf":{"essential":true,"value":"1726258122"}}}"#)
+        );
+
+        let response = RawResponse::from_bytes(StatusCode::Forbidden, headers, crate::Bytes::new());
+        assert_eq!(claims_challenge(&response), None);
+
+        let mut headers = crate::http::headers::Headers::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            r#"Bearer authorization="https://login.microsoftonline.com/tenant", resource="https://vault.azure.net""#,
+        );
+        let response =
+            RawResponse::from_bytes(StatusCode::Unauthorized, headers, crate::Bytes::new());
+        assert_eq!(claims_challenge(&response), None);
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert!(parse_challenges("").is_empty());
+        assert!(parse_challenges(" , ").is_empty());
+    }
+}
@@ -0,0 +1,626 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::{
+    bearer_token_policy::should_refresh,
+    challenge::{claims_challenge, parse_challenges, Challenge},
+};
+use crate::{
+    credentials::{AccessToken, TokenCredential, TokenRequestOptions, DEFAULT_SCOPE_SUFFIX},
+    error::{Error, ErrorKind},
+    http::{
+        headers::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, WWW_AUTHENTICATE},
+        policies::{Policy, PolicyResult},
+        RawResponse, StatusCode, Url,
+    },
+};
+use async_lock::RwLock;
+use async_trait::async_trait;
+use std::{collections::HashMap, sync::Arc};
+use typespec_client_core::http::{Context, Request};
+use typespec_client_core::time::OffsetDateTime;
+
+/// Options for a [`ChallengeBearerTokenCredentialPolicy`].
+#[derive(Clone, Debug, Default)]
+pub struct ChallengeOptions {
+    /// Send the first request to each host without a body or authorization header to discover the authentication challenge.
+    ///
+    /// If the service does not return a challenge, the request is sent with a token for the scopes passed to the policy.
+    pub discover_challenge: bool,
+
+    /// Request tokens for the `scope` or `resource` in the challenge instead of the scopes passed to the policy.
+    ///
+    /// The challenge scope must be in the same domain as the request host, or the request will fail.
+    pub use_challenge_scope: bool,
+}
+
+/// Authentication policy for a bearer token that handles `WWW-Authenticate` challenges.
+///
+/// When a service responds with `401 Unauthorized` and a `Bearer` challenge, the tenant and optionally the scope are read from the challenge,
+/// cached for the request host, and the request is sent again with a token for the discovered tenant and scope.
+/// Like [`BearerTokenCredentialPolicy`](super::BearerTokenCredentialPolicy), tokens are requested with Continuous Access Evaluation (CAE) enabled
+/// and `insufficient_claims` challenges are handled.
+#[derive(Debug, Clone)]
+pub struct ChallengeBearerTokenCredentialPolicy {
+    credential: Arc<dyn TokenCredential>,
+    scopes: Vec<String>,
+    options: ChallengeOptions,
+    challenges: Arc<RwLock<HashMap<String, Authority>>>,
+    access_tokens: Arc<RwLock<HashMap<Authority, AccessToken>>>,
+}
+
+/// The tenant and scopes used to authenticate with a host.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+struct Authority {
+    tenant_id: Option<String>,
+    scopes: Vec<String>,
+}
+
+impl ChallengeBearerTokenCredentialPolicy {
+    /// Creates a [`ChallengeBearerTokenCredentialPolicy`] that authenticates requests with tokens from the `credential`.
+    ///
+    /// Tokens are requested for the `scopes` until the service returns a challenge for another tenant or,
+    /// if [`ChallengeOptions::use_challenge_scope`] is set, another scope.
+    pub fn new<A, B>(
+        credential: Arc<dyn TokenCredential>,
+        scopes: A,
+        options: ChallengeOptions,
+    ) -> Self
+    where
+        A: IntoIterator<Item = B>,
+        B: Into<String>,
+    {
+        Self {
+            credential,
+            scopes: scopes.into_iter().map(|s| s.into()).collect(),
+            options,
+            challenges: Arc::new(RwLock::new(HashMap::new())),
+            access_tokens: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    fn default_authority(&self) -> Authority {
+        Authority {
+            tenant_id: None,
+            scopes: self.scopes.clone(),
+        }
+    }
+
+    /// Gets the [`Authority`] from a `401 Unauthorized` response, if it contains a `Bearer` challenge.
+    fn authority_from_challenge(
+        &self,
+        url: &Url,
+        response: &RawResponse,
+    ) -> crate::Result<Option<Authority>> {
+        if response.status() != StatusCode::Unauthorized {
+            return Ok(None);
+        }
+        let Some(value) = response.headers().get_optional_str(&WWW_AUTHENTICATE) else {
+            return Ok(None);
+        };
+        let Some(challenge) = parse_challenges(value)
+            .into_iter()
+            .find(|c| c.scheme.eq_ignore_ascii_case("bearer"))
+        else {
+            return Ok(None);
+        };
+
+        let scopes = match challenge_scope(&challenge) {
+            Some(scope) if self.options.use_challenge_scope => {
+                verify_challenge_scope(url, &scope)?;
+                vec![scope]
+            }
+            _ => self.scopes.clone(),
+        };
+        Ok(Some(Authority {
+            tenant_id: challenge_tenant_id(&challenge),
+            scopes,
+        }))
+    }
+
+    async fn access_token(
+        &self,
+        authority: &Authority,
+        claims: Option<String>,
+    ) -> crate::Result<String> {
+        // A token with additional claims is always requested because the cached token was rejected.
+        if claims.is_none() {
+            if let Some(token) = self.access_tokens.read().await.get(authority) {
+                if !should_refresh(&token.expires_on) {
+                    return Ok(token.token.secret().to_string());
+                }
+            }
+        }
+
+        // Get a write lock and check again in case another thread refreshed the token while we were waiting.
+        let mut access_tokens = self.access_tokens.write().await;
+        if claims.is_none() {
+            if let Some(token) = access_tokens.get(authority) {
+                if !should_refresh(&token.expires_on) {
+                    return Ok(token.token.secret().to_string());
+                }
+            }
+        }
+
+        let scopes: Vec<&str> = authority.scopes.iter().map(String::as_str).collect();
+        let options = TokenRequestOptions {
+            claims,
+            enable_cae: true,
+            tenant_id: authority.tenant_id.clone(),
+        };
+        match self.credential.get_token(&scopes, Some(options)).await {
+            Ok(token) => {
+                let secret = token.token.secret().to_string();
+                access_tokens.insert(authority.clone(), token);
+                Ok(secret)
+            }
+            Err(err) => match access_tokens.get(authority) {
+                // Ignore this error because the cached token is still valid.
+                Some(token) if token.expires_on > OffsetDateTime::now_utc() => {
+                    Ok(token.token.secret().to_string())
+                }
+                _ => Err(err),
+            },
+        }
+    }
+
+    async fn send_authorized(
+        &self,
+        ctx: &Context<'_>,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+        authority: &Authority,
+        claims: Option<String>,
+    ) -> PolicyResult {
+        let access_token = self.access_token(authority, claims).await?;
+        request.insert_header(AUTHORIZATION, format!("Bearer {}", access_token));
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Policy for ChallengeBearerTokenCredentialPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let host = host(request.url());
+        let cached = self.challenges.read().await.get(&host).cloned();
+        let authority = match cached {
+            Some(authority) => authority,
+            None if self.options.discover_challenge => {
+                // Send the request without a body, which the service will not process without authorization.
+                let mut probe = Request::new(request.url().clone(), *request.method());
+                for (name, value) in request.headers().iter() {
+                    if *name != CONTENT_TYPE && *name != CONTENT_LENGTH {
+                        probe.insert_header(name.clone(), value.clone());
+                    }
+                }
+                let response = next[0].send(ctx, &mut probe, &next[1..]).await?;
+                match self.authority_from_challenge(request.url(), &response)? {
+                    Some(authority) => {
+                        self.challenges
+                            .write()
+                            .await
+                            .insert(host.clone(), authority.clone());
+                        authority
+                    }
+                    // The probe was not authorized, so send the request with a token for the scopes passed to the policy.
+                    None => self.default_authority(),
+                }
+            }
+            None => self.default_authority(),
+        };
+
+        let response = self
+            .send_authorized(ctx, request, next, &authority, None)
+            .await?;
+        let (authority, claims) = match claims_challenge(&response) {
+            // The token was revoked or does not satisfy a policy, so request a token containing the required claims.
+            Some(claims) => (authority, Some(claims)),
+            None => match self.authority_from_challenge(request.url(), &response)? {
+                Some(challenged) if challenged != authority => {
+                    self.challenges
+                        .write()
+                        .await
+                        .insert(host, challenged.clone());
+                    (challenged, None)
+                }
+                _ => return Ok(response),
+            },
+        };
+
+        // Send the request again with a token for the tenant and scopes in the challenge.
+        let mut body = request.body().clone();
+        body.reset().await?;
+        request.set_body(body);
+        self.send_authorized(ctx, request, next, &authority, claims)
+            .await
+    }
+}
+
+/// Gets the host and port used to cache challenges.
+fn host(url: &Url) -> String {
+    match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (Some(host), None) => host.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Gets the tenant ID from the first path segment of the `authorization` or `authorization_uri` parameter.
+fn challenge_tenant_id(challenge: &Challenge) -> Option<String> {
+    let authorization = challenge
+        .parameter("authorization")
+        .or_else(|| challenge.parameter("authorization_uri"))?;
+    let url = Url::parse(authorization).ok()?;
+    url.path_segments()?
+        .next()
+        .filter(|segment| !segment.is_empty())
+        .map(String::from)
+}
+
+/// Gets the scope from the `scope` parameter, or from the `resource` or `resource_id` parameter.
+fn challenge_scope(challenge: &Challenge) -> Option<String> {
+    if let Some(scope) = challenge.parameter("scope") {
+        return Some(scope.to_string());
+    }
+    let resource = challenge
+        .parameter("resource")
+        .or_else(|| challenge.parameter("resource_id"))?;
+    Some(format!(
+        "{}{DEFAULT_SCOPE_SUFFIX}",
+        resource.trim_end_matches('/')
+    ))
+}
+
+/// Verifies the `scope` is in the same domain as the `url` so tokens are not sent to a different service.
+fn verify_challenge_scope(url: &Url, scope: &str) -> crate::Result<()> {
+    let scope_host = Url::parse(scope)
+        .ok()
+        .and_then(|scope| scope.host_str().map(str::to_ascii_lowercase));
+    let request_host = url.host_str().map(str::to_ascii_lowercase);
+    match (scope_host, request_host) {
+        (Some(scope_host), Some(request_host))
+            if request_host == scope_host || request_host.ends_with(&format!(".{scope_host}")) =>
+        {
+            Ok(())
+        }
+        _ => Err(Error::message(
+            ErrorKind::Credential,
+            format!(
+                "challenge scope '{scope}' does not match the domain of request host '{}'",
+                url.host_str().unwrap_or_default()
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        credentials::Secret,
+        http::{headers::Headers, Method},
+        Bytes, Result,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt;
+    use std::sync::Mutex;
+    use typespec_client_core::{
+        http::{policies::TransportPolicy, TransportOptions},
+        time::Duration,
+    };
+
+    const TENANT_ID: &str = "00000000-0000-0000-0000-000000000000";
+
+    /// The authorization header and body of a request sent to the transport.
+    type SentRequest = (Option<String>, Bytes);
+
+    #[derive(Debug, Default)]
+    struct MockCredential {
+        requests: Mutex<Vec<(Vec<String>, Option<String>)>>,
+    }
+
+    impl MockCredential {
+        fn requests(&self) -> Vec<(Vec<String>, Option<String>)> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl TokenCredential for MockCredential {
+        async fn get_token(
+            &self,
+            scopes: &[&str],
+            options: Option<TokenRequestOptions>,
+        ) -> Result<AccessToken> {
+            let tenant_id = options.and_then(|o| o.tenant_id);
+            let token = tenant_id.clone().unwrap_or_else(|| "default".to_string());
+            self.requests
+                .lock()
+                .unwrap()
+                .push((scopes.iter().map(ToString::to_string).collect(), tenant_id));
+            Ok(AccessToken::new(
+                Secret::new(token),
+                OffsetDateTime::now_utc() + Duration::hours(1),
+            ))
+        }
+    }
+
+    fn challenge_response(challenge: &str) -> RawResponse {
+        let mut headers = Headers::new();
+        headers.insert(WWW_AUTHENTICATE, challenge.to_string());
+        RawResponse::from_bytes(StatusCode::Unauthorized, headers, Bytes::new())
+    }
+
+    /// Responds with the `challenge` unless the request is authorized for [`TENANT_ID`], and records each request's authorization and body.
+    fn transport(
+        challenge: &'static str,
+        requests: Arc<Mutex<Vec<SentRequest>>>,
+    ) -> Arc<dyn Policy> {
+        let client = MockHttpClient::new(move |req| {
+            let requests = requests.clone();
+            async move {
+                let authorization = req.headers().get_optional_string(&AUTHORIZATION);
+                let body = match req.body() {
+                    typespec_client_core::http::Body::Bytes(bytes) => bytes.clone(),
+                    #[allow(unreachable_patterns)]
+                    _ => panic!("expected bytes"),
+                };
+                requests.lock().unwrap().push((authorization.clone(), body));
+                if authorization == Some(format!("Bearer {TENANT_ID}")) {
+                    return Ok(RawResponse::from_bytes(
+                        StatusCode::Ok,
+                        Headers::new(),
+                        Bytes::new(),
+                    ));
+                }
+                Ok(challenge_response(challenge))
+            }
+            .boxed()
+        });
+        Arc::new(TransportPolicy::new(TransportOptions::new(Arc::new(
+            client,
+        ))))
+    }
+
+    #[tokio::test]
+    async fn discovers_challenge() {
+        let credential = Arc::new(MockCredential::default());
+        let policy = ChallengeBearerTokenCredentialPolicy::new(
+            credential.clone(),
+            ["https://vault.azure.net/.default"],
+            ChallengeOptions {
+                discover_challenge: true,
+                use_challenge_scope: true,
+            },
+        );
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let transport = transport(
+            r#"Bearer authorization="https://login.microsoftonline.com/00000000-0000-0000-0000-000000000000", resource="https://vault.azure.net""#,
+            requests.clone(),
+        );
+
+        for _ in 0..2 {
+            let mut req = Request::new(
+                "https://myvault.vault.azure.net/secrets/name"
+                    .parse()
+                    .unwrap(),
+                Method::Put,
+            );
+            req.set_body(Bytes::from_static(b"{}"));
+            let response = policy
+                .send(
+                    &Context::default(),
+                    &mut req,
+                    std::slice::from_ref(&transport),
+                )
+                .await
+                .expect("successful request");
+            assert_eq!(response.status(), StatusCode::Ok);
+        }
+
+        // Only the first request should be probed, and the challenge cached.
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(
+            requests,
+            vec![
+                (None, Bytes::new()),
+                (
+                    Some(format!("Bearer {TENANT_ID}")),
+                    Bytes::from_static(b"{}")
+                ),
+                (
+                    Some(format!("Bearer {TENANT_ID}")),
+                    Bytes::from_static(b"{}")
+                ),
+            ]
+        );
+        assert_eq!(
+            credential.requests(),
+            vec![(
+                vec!["https://vault.azure.net/.default".to_string()],
+                Some(TENANT_ID.to_string())
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_request_when_probe_not_challenged() {
+        let credential = Arc::new(MockCredential::default());
+        let policy = ChallengeBearerTokenCredentialPolicy::new(
+            credential.clone(),
+            ["https://vault.azure.net/.default"],
+            ChallengeOptions {
+                discover_challenge: true,
+                use_challenge_scope: true,
+            },
+        );
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let transport: Arc<dyn Policy> = {
+            let requests = requests.clone();
+            let client = MockHttpClient::new(move |req| {
+                let requests = requests.clone();
+                async move {
+                    requests.lock().unwrap().push((
+                        req.headers().get_optional_string(&AUTHORIZATION),
+                        req.headers().get_optional_string(&CONTENT_TYPE),
+                        req.headers().get_optional_string(&CONTENT_LENGTH),
+                    ));
+                    Ok(RawResponse::from_bytes(
+                        StatusCode::Ok,
+                        Headers::new(),
+                        Bytes::new(),
+                    ))
+                }
+                .boxed()
+            });
+            Arc::new(TransportPolicy::new(TransportOptions::new(Arc::new(
+                client,
+            ))))
+        };
+
+        let mut req = Request::new(
+            "https://myvault.vault.azure.net/secrets/name"
+                .parse()
+                .unwrap(),
+            Method::Put,
+        );
+        req.insert_header(CONTENT_TYPE, "application/json");
+        req.insert_header(CONTENT_LENGTH, "2");
+        req.set_body(Bytes::from_static(b"{}"));
+        let response = policy
+            .send(&Context::default(), &mut req, &[transport])
+            .await
+            .expect("successful request");
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        // The probe has no content type and the transport sets the length of its empty body,
+        // and the request is still sent with a token.
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(
+            requests,
+            vec![
+                (None, None, Some("0".to_string())),
+                (
+                    Some("Bearer default".to_string()),
+                    Some("application/json".to_string()),
+                    Some("2".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_with_challenge_tenant() {
+        let credential = Arc::new(MockCredential::default());
+        let policy = ChallengeBearerTokenCredentialPolicy::new(
+            credential.clone(),
+            ["https://storage.azure.com/.default"],
+            ChallengeOptions::default(),
+        );
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let transport = transport(
+            "Bearer authorization_uri=https://login.microsoftonline.com/00000000-0000-0000-0000-000000000000/oauth2/authorize resource_id=https://storage.azure.com",
+            requests.clone(),
+        );
+
+        let mut req = Request::new(
+            "https://account.blob.core.windows.net/container/blob"
+                .parse()
+                .unwrap(),
+            Method::Put,
+        );
+        req.set_body(Bytes::from_static(b"data"));
+        let response = policy
+            .send(
+                &Context::default(),
+                &mut req,
+                std::slice::from_ref(&transport),
+            )
+            .await
+            .expect("successful request");
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(
+            requests,
+            vec![
+                (
+                    Some("Bearer default".to_string()),
+                    Bytes::from_static(b"data")
+                ),
+                (
+                    Some(format!("Bearer {TENANT_ID}")),
+                    Bytes::from_static(b"data")
+                ),
+            ]
+        );
+        assert_eq!(
+            credential.requests(),
+            vec![
+                (vec!["https://storage.azure.com/.default".to_string()], None),
+                (
+                    vec!["https://storage.azure.com/.default".to_string()],
+                    Some(TENANT_ID.to_string())
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_challenge_for_other_domain() {
+        let credential = Arc::new(MockCredential::default());
+        let policy = ChallengeBearerTokenCredentialPolicy::new(
+            credential.clone(),
+            ["https://vault.azure.net/.default"],
+            ChallengeOptions {
+                discover_challenge: true,
+                use_challenge_scope: true,
+            },
+        );
+        let transport = transport(
+            r#"Bearer authorization="https://login.microsoftonline.com/00000000-0000-0000-0000-000000000000", scope="https://attacker.example.com/.default""#,
+            Arc::default(),
+        );
+
+        let mut req = Request::new(
+            "https://myvault.vault.azure.net/secrets/name"
+                .parse()
+                .unwrap(),
+            Method::Get,
+        );
+        let err = policy
+            .send(&Context::default(), &mut req, &[transport])
+            .await
+            .expect_err("challenge scope should be rejected");
+        assert_eq!(*err.kind(), ErrorKind::Credential);
+        assert!(credential.requests().is_empty());
+    }
+
+    #[test]
+    fn challenge_tenant_and_scope() {
+        let challenge = &parse_challenges(
+            r#"Bearer authorization="https://login.microsoftonline.com/tenant", resource="https://vault.azure.net/""#,
+        )[0];
+        assert_eq!(challenge_tenant_id(challenge), Some("tenant".to_string()));
+        assert_eq!(
+            challenge_scope(challenge),
+            Some("https://vault.azure.net/.default".to_string())
+        );
+
+        let challenge = &parse_challenges(
+            r#"Bearer authorization="https://login.microsoftonline.com/", scope="https://managedhsm.azure.net/.default""#,
+        )[0];
+        assert_eq!(challenge_tenant_id(challenge), None);
+        assert_eq!(
+            challenge_scope(challenge),
+            Some("https://managedhsm.azure.net/.default".to_string())
+        );
+    }
+}
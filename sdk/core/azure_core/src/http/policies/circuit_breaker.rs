@@ -0,0 +1,447 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    error::{Error, ErrorKind},
+    http::{
+        policies::{Policy, PolicyResult},
+        Context, Request,
+    },
+    time::{Duration, OffsetDateTime},
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+use tracing::{debug, warn};
+
+/// Stops sending requests to a host after consecutive failures, failing fast until the host recovers.
+///
+/// The circuit for each host starts closed and requests are sent normally. After `failure_threshold` consecutive failures
+/// the circuit opens, and requests fail immediately with a [`CircuitBreakerOpenError`] for the `break_duration`.
+/// The circuit is then half-open: a single probe request is sent while other requests continue to fail fast.
+/// If the probe succeeds the circuit closes; otherwise, it opens again. Requests that were sent before the circuit
+/// opened do not change its state when they complete.
+///
+/// A failure is an I/O error, timeout, 429 (Too Many Requests) or 5xx response. Other responses, including other 4xx responses,
+/// indicate the service is available and reset the count of consecutive failures.
+///
+/// Add to [`ClientOptions::per_call_policies`](crate::http::ClientOptions::per_call_policies)
+/// so that each failure is an operation that failed after any retries.
+///
+/// A request that fails fast returns an [`Error`] with [`ErrorKind::Other`] wrapping the [`CircuitBreakerOpenError`],
+/// which can be retrieved with [`Error::downcast_ref`]:
+///
+/// ```
+/// use azure_core::{http::policies::CircuitBreakerOpenError, Error};
+///
+/// fn retry_after(error: &Error) -> Option<azure_core::time::Duration> {
+///     error
+///         .downcast_ref::<CircuitBreakerOpenError>()
+///         .and_then(CircuitBreakerOpenError::retry_after)
+/// }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use azure_core::{http::{policies::CircuitBreakerPolicy, ClientOptions}, time::Duration};
+/// use std::sync::Arc;
+///
+/// let policy = CircuitBreakerPolicy::new()
+///     .with_failure_threshold(3)
+///     .with_break_duration(Duration::seconds(10));
+/// let options = ClientOptions {
+///     per_call_policies: vec![Arc::new(policy)],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug)]
+pub struct CircuitBreakerPolicy {
+    failure_threshold: u32,
+    break_duration: Duration,
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Circuit {
+    Closed { failures: u32 },
+    Open { until: OffsetDateTime },
+    HalfOpen,
+}
+
+impl CircuitBreakerPolicy {
+    /// Creates a new `CircuitBreakerPolicy` that opens after 5 consecutive failures for 30 seconds.
+    pub fn new() -> Self {
+        Self {
+            failure_threshold: 5,
+            break_duration: Duration::seconds(30),
+            circuits: Mutex::default(),
+        }
+    }
+
+    /// Sets the number of consecutive failures after which the circuit opens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `failure_threshold` is 0.
+    #[must_use]
+    pub fn with_failure_threshold(self, failure_threshold: u32) -> Self {
+        assert!(
+            failure_threshold > 0,
+            "failure_threshold must be greater than 0"
+        );
+        Self {
+            failure_threshold,
+            ..self
+        }
+    }
+
+    /// Sets how long the circuit stays open before a probe request is sent.
+    #[must_use]
+    pub fn with_break_duration(self, break_duration: Duration) -> Self {
+        Self {
+            break_duration,
+            ..self
+        }
+    }
+
+    /// Determines whether a request to the `host` may be sent, or returns the error to fail fast.
+    ///
+    /// Returns `true` if the request is the probe sent while the circuit is half-open.
+    fn try_acquire(
+        &self,
+        host: &str,
+        now: OffsetDateTime,
+    ) -> Result<bool, CircuitBreakerOpenError> {
+        let mut circuits = self.circuits.lock().expect("circuits poisoned");
+        let circuit = circuits
+            .entry(host.to_string())
+            .or_insert(Circuit::Closed { failures: 0 });
+        match *circuit {
+            Circuit::Closed { .. } => Ok(false),
+            Circuit::Open { until } if now >= until => {
+                debug!("circuit for {host} is half-open; sending probe request");
+                *circuit = Circuit::HalfOpen;
+                Ok(true)
+            }
+            Circuit::Open { until } => Err(CircuitBreakerOpenError {
+                host: host.to_string(),
+                retry_after: Some(until - now),
+            }),
+            Circuit::HalfOpen => Err(CircuitBreakerOpenError {
+                host: host.to_string(),
+                retry_after: None,
+            }),
+        }
+    }
+
+    /// Records the outcome of a request sent to the `host`, where `probe` is the value returned by [`Self::try_acquire`].
+    fn record(&self, host: &str, probe: bool, failed: bool, now: OffsetDateTime) {
+        let mut circuits = self.circuits.lock().expect("circuits poisoned");
+        let circuit = circuits
+            .entry(host.to_string())
+            .or_insert(Circuit::Closed { failures: 0 });
+        *circuit = match (*circuit, probe, failed) {
+            // Only the probe changes the state of a circuit that opened after other requests were sent.
+            (Circuit::Open { .. } | Circuit::HalfOpen, false, _) => return,
+            (_, _, false) => Circuit::Closed { failures: 0 },
+            (Circuit::Closed { failures }, _, true) if failures + 1 < self.failure_threshold => {
+                Circuit::Closed {
+                    failures: failures + 1,
+                }
+            }
+            (_, _, true) => {
+                warn!(
+                    "circuit for {host} opened for {} after consecutive failures",
+                    self.break_duration
+                );
+                Circuit::Open {
+                    until: now + self.break_duration,
+                }
+            }
+        };
+    }
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        CircuitBreakerPolicy::new()
+    }
+}
+
+/// Reopens a half-open circuit if the probe request is cancelled before it completes.
+struct ProbeGuard<'a> {
+    policy: &'a CircuitBreakerPolicy,
+    host: &'a str,
+    completed: bool,
+}
+
+impl Drop for ProbeGuard<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            let mut circuits = self.policy.circuits.lock().expect("circuits poisoned");
+            if let Some(circuit @ Circuit::HalfOpen) = circuits.get_mut(self.host) {
+                *circuit = Circuit::Open {
+                    until: OffsetDateTime::now_utc(),
+                };
+            }
+        }
+    }
+}
+
+fn is_failure(result: &PolicyResult) -> bool {
+    let error = match result {
+        Ok(response) => {
+            let status = u16::from(response.status());
+            return status == 429 || status >= 500;
+        }
+        Err(error) => error,
+    };
+    match error.kind() {
+        ErrorKind::Io | ErrorKind::Timeout => true,
+        ErrorKind::HttpResponse { status, .. } => {
+            let status = u16::from(*status);
+            status == 429 || status >= 500
+        }
+        _ => false,
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for CircuitBreakerPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let host = request.url().host_str().unwrap_or_default().to_string();
+        let probe = self
+            .try_acquire(&host, OffsetDateTime::now_utc())
+            .map_err(|error| Error::new(ErrorKind::Other, error))?;
+
+        // Only a cancelled probe reopens the circuit; other requests are simply not recorded.
+        let mut guard = ProbeGuard {
+            policy: self,
+            host: &host,
+            completed: !probe,
+        };
+        let result = next[0].send(ctx, request, &next[1..]).await;
+        guard.completed = true;
+
+        self.record(&host, probe, is_failure(&result), OffsetDateTime::now_utc());
+        result
+    }
+}
+
+/// The error returned by a [`CircuitBreakerPolicy`] when it is failing fast because a host is unavailable.
+///
+/// Use [`Error::downcast_ref`] to determine if an operation failed because the circuit was open.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerOpenError {
+    host: String,
+    retry_after: Option<Duration>,
+}
+
+impl CircuitBreakerOpenError {
+    /// The host to which the request was not sent.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// How long until a probe request will be sent to the host, if known.
+    ///
+    /// This is `None` if a probe request is currently being sent.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+}
+
+impl fmt::Display for CircuitBreakerOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "circuit breaker is open for {}", self.host)
+    }
+}
+
+impl std::error::Error for CircuitBreakerOpenError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{headers::Headers, Method, RawResponse, StatusCode, TransportOptions},
+        Bytes,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use typespec_client_core::http::policies::TransportPolicy;
+
+    fn request() -> Request {
+        Request::new(
+            "https://contoso.blob.core.windows.net".parse().unwrap(),
+            Method::Get,
+        )
+    }
+
+    #[test]
+    fn transitions() {
+        let policy = CircuitBreakerPolicy::new()
+            .with_failure_threshold(2)
+            .with_break_duration(Duration::seconds(10));
+        let host = "contoso.blob.core.windows.net";
+        let now = OffsetDateTime::now_utc();
+
+        // Successes reset the count of consecutive failures.
+        policy.record(host, false, true, now);
+        policy.record(host, false, false, now);
+        policy.record(host, false, true, now);
+        assert_eq!(policy.try_acquire(host, now).ok(), Some(false));
+
+        policy.record(host, false, true, now);
+        let err = policy.try_acquire(host, now).expect_err("open");
+        assert_eq!(err.host(), host);
+        assert_eq!(err.retry_after(), Some(Duration::seconds(10)));
+
+        // Other hosts are unaffected.
+        assert!(policy.try_acquire("other.contoso.com", now).is_ok());
+
+        // Only one probe is sent when half-open.
+        let later = now + Duration::seconds(10);
+        assert_eq!(policy.try_acquire(host, later).ok(), Some(true));
+        let err = policy.try_acquire(host, later).expect_err("half-open");
+        assert_eq!(err.retry_after(), None);
+
+        // A failed probe opens the circuit again.
+        policy.record(host, true, true, later);
+        assert!(policy.try_acquire(host, later).is_err());
+
+        // A successful probe closes the circuit.
+        let later = later + Duration::seconds(10);
+        assert_eq!(policy.try_acquire(host, later).ok(), Some(true));
+        policy.record(host, true, false, later);
+        assert_eq!(policy.try_acquire(host, later).ok(), Some(false));
+        assert_eq!(policy.try_acquire(host, later).ok(), Some(false));
+    }
+
+    #[test]
+    fn ignores_requests_sent_before_open() {
+        let policy = CircuitBreakerPolicy::new()
+            .with_failure_threshold(1)
+            .with_break_duration(Duration::seconds(10));
+        let host = "contoso.blob.core.windows.net";
+        let now = OffsetDateTime::now_utc();
+        policy.record(host, false, true, now);
+
+        // A straggler succeeding does not close an open circuit.
+        policy.record(host, false, false, now);
+        assert!(policy.try_acquire(host, now).is_err());
+
+        // Nor does it close a half-open circuit before the probe completes.
+        let later = now + Duration::seconds(10);
+        assert_eq!(policy.try_acquire(host, later).ok(), Some(true));
+        policy.record(host, false, false, later);
+        assert_eq!(
+            policy
+                .try_acquire(host, later)
+                .expect_err("half-open")
+                .retry_after(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_fast_when_open() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let transport: Arc<dyn Policy> = {
+            let count = count.clone();
+            Arc::new(TransportPolicy::new(TransportOptions::new(Arc::new(
+                MockHttpClient::new(move |_| {
+                    count.fetch_add(1, Ordering::SeqCst);
+                    async {
+                        Ok(RawResponse::from_bytes(
+                            StatusCode::ServiceUnavailable,
+                            Headers::new(),
+                            Bytes::new(),
+                        ))
+                    }
+                    .boxed()
+                }),
+            ))))
+        };
+        let policy = CircuitBreakerPolicy::new().with_failure_threshold(2);
+        let ctx = Context::new();
+
+        for _ in 0..2 {
+            let response = policy
+                .send(&ctx, &mut request(), std::slice::from_ref(&transport))
+                .await
+                .expect("response");
+            assert_eq!(response.status(), StatusCode::ServiceUnavailable);
+        }
+
+        let err = policy
+            .send(&ctx, &mut request(), std::slice::from_ref(&transport))
+            .await
+            .expect_err("circuit should be open");
+        let open = err
+            .downcast_ref::<CircuitBreakerOpenError>()
+            .expect("CircuitBreakerOpenError");
+        assert_eq!(open.host(), "contoso.blob.core.windows.net");
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn cancelled_probe_reopens() {
+        let transport: Arc<dyn Policy> = Arc::new(TransportPolicy::new(TransportOptions::new(
+            Arc::new(MockHttpClient::new(|_| futures::future::pending().boxed())),
+        )));
+        let policy = CircuitBreakerPolicy::new()
+            .with_failure_threshold(1)
+            .with_break_duration(Duration::ZERO);
+        let host = "contoso.blob.core.windows.net";
+        policy.record(host, false, true, OffsetDateTime::now_utc());
+
+        let ctx = Context::new();
+        let mut request = request();
+        let next = [transport];
+        let probe = policy.send(&ctx, &mut request, &next);
+        assert!(probe.now_or_never().is_none());
+
+        // The circuit is open rather than stuck half-open, so another probe may be sent.
+        assert_eq!(
+            policy.try_acquire(host, OffsetDateTime::now_utc()).ok(),
+            Some(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_request_does_not_reopen() {
+        let transport: Arc<dyn Policy> = Arc::new(TransportPolicy::new(TransportOptions::new(
+            Arc::new(MockHttpClient::new(|_| futures::future::pending().boxed())),
+        )));
+        let policy = CircuitBreakerPolicy::new()
+            .with_failure_threshold(1)
+            .with_break_duration(Duration::ZERO);
+        let host = "contoso.blob.core.windows.net";
+
+        // A request is sent before the circuit opens, then the probe is sent.
+        let ctx = Context::new();
+        let mut request = request();
+        let next = [transport];
+        let mut straggler = Box::pin(policy.send(&ctx, &mut request, &next));
+        assert!((&mut straggler).now_or_never().is_none());
+        policy.record(host, false, true, OffsetDateTime::now_utc());
+        assert_eq!(
+            policy.try_acquire(host, OffsetDateTime::now_utc()).ok(),
+            Some(true)
+        );
+
+        // Cancelling the straggler leaves the circuit half-open while the probe is sent.
+        drop(straggler);
+        assert!(policy.try_acquire(host, OffsetDateTime::now_utc()).is_err());
+    }
+}
@@ -0,0 +1,477 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    error::{ErrorKind, ResultExt},
+    http::{
+        headers::{HeaderValue, Headers, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH},
+        policies::{Policy, PolicyResult},
+        request::Body,
+        CompressionOptions, ContentEncoding, Context, Method, RawResponse, Request, StatusCode,
+    },
+    Bytes,
+};
+use futures::StreamExt as _;
+use std::{io, io::Write as _, sync::Arc};
+
+/// Compresses request bodies and decodes compressed response bodies.
+///
+/// Request bodies at least [`CompressionOptions::request_threshold`] bytes long are compressed using the
+/// [`CompressionOptions::request_encoding`], if any, unless the request already has a `Content-Encoding`.
+/// Streaming request bodies are read into memory to be compressed.
+///
+/// The [`CompressionOptions::response_encodings`] are sent in the `Accept-Encoding` header unless the request already has one,
+/// and response bodies with a supported `Content-Encoding` are decoded as they are received.
+///
+/// A `CompressionPolicy` is added to a [`Pipeline`](crate::http::Pipeline) when [`ClientOptions::compression`](crate::http::ClientOptions::compression) is set.
+#[derive(Debug)]
+pub struct CompressionPolicy {
+    request_encoding: Option<ContentEncoding>,
+    request_threshold: usize,
+    accept_encoding: Option<HeaderValue>,
+    response_encodings: Vec<ContentEncoding>,
+}
+
+impl CompressionPolicy {
+    /// Creates a new `CompressionPolicy` from the `options`.
+    pub fn new(options: &CompressionOptions) -> Self {
+        let accept_encoding = (!options.response_encodings.is_empty()).then(|| {
+            options
+                .response_encodings
+                .iter()
+                .map(ContentEncoding::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+                .into()
+        });
+        Self {
+            request_encoding: options.request_encoding,
+            request_threshold: options.request_threshold,
+            accept_encoding,
+            response_encodings: options.response_encodings.clone(),
+        }
+    }
+
+    async fn compress_request(
+        &self,
+        encoding: ContentEncoding,
+        request: &mut Request,
+    ) -> crate::Result<()> {
+        let body = match request.body() {
+            Body::Bytes(bytes) => encode(encoding, bytes),
+            #[cfg(not(target_arch = "wasm32"))]
+            Body::SeekableStream(stream) => {
+                use futures::AsyncReadExt as _;
+
+                let mut stream = stream.clone();
+                stream.reset().await?;
+                let mut bytes = Vec::with_capacity(stream.len());
+                stream.read_to_end(&mut bytes).await?;
+                encode(encoding, &bytes)
+            }
+        }
+        .with_context(ErrorKind::Io, || {
+            format!("failed to compress the request body using {encoding}")
+        })?;
+
+        if request
+            .headers()
+            .get_optional_str(&CONTENT_LENGTH)
+            .is_some()
+        {
+            request.insert_header(CONTENT_LENGTH, body.len().to_string());
+        }
+        request.insert_header(CONTENT_ENCODING, encoding.as_str());
+        request.set_body(body);
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for CompressionPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if let Some(encoding) = self.request_encoding {
+            if request.body().len() >= self.request_threshold
+                && !request.body().is_empty()
+                && request
+                    .headers()
+                    .get_optional_str(&CONTENT_ENCODING)
+                    .is_none()
+            {
+                self.compress_request(encoding, request).await?;
+            }
+        }
+        if let Some(accept_encoding) = &self.accept_encoding {
+            if request
+                .headers()
+                .get_optional_str(&ACCEPT_ENCODING)
+                .is_none()
+            {
+                request.insert_header(ACCEPT_ENCODING, accept_encoding.clone());
+            }
+        }
+
+        let response = next[0].send(ctx, request, &next[1..]).await?;
+        if *request.method() == Method::Head
+            || matches!(
+                response.status(),
+                StatusCode::NoContent | StatusCode::NotModified
+            )
+        {
+            return Ok(response);
+        }
+        let Some(encoding) = response
+            .headers()
+            .get_optional_str(&CONTENT_ENCODING)
+            .and_then(|value| value.parse::<ContentEncoding>().ok())
+            .filter(|encoding| self.response_encodings.contains(encoding))
+        else {
+            return Ok(response);
+        };
+
+        // The decoded body has a different encoding and length.
+        let (status, headers, body) = response.deconstruct();
+        let mut decoded_headers = Headers::new();
+        for (name, value) in headers.iter() {
+            if *name != CONTENT_ENCODING && *name != CONTENT_LENGTH {
+                decoded_headers.insert(name.clone(), value.clone());
+            }
+        }
+
+        let decoder = Decoder::new(encoding).with_context(ErrorKind::Io, || {
+            format!("failed to decode the response body using {encoding}")
+        })?;
+        let stream =
+            futures::stream::unfold(Some((body, decoder, false)), move |state| async move {
+                let (mut body, mut decoder, mut decoded) = state?;
+                loop {
+                    let result = match body.next().await {
+                        Some(Ok(chunk)) if chunk.is_empty() => continue,
+                        Some(Ok(chunk)) => {
+                            decoded = true;
+                            match decoder.decode(&chunk) {
+                                Ok(bytes) if bytes.is_empty() => continue,
+                                Ok(bytes) => {
+                                    return Some((Ok(bytes), Some((body, decoder, decoded))))
+                                }
+                                Err(err) => Err(err),
+                            }
+                        }
+                        Some(Err(err)) => return Some((Err(err), None)),
+                        None if !decoded => return None,
+                        None => match decoder.finish() {
+                            Ok(bytes) if bytes.is_empty() => return None,
+                            result => result,
+                        },
+                    };
+                    let result = result.with_context(ErrorKind::Io, || {
+                        format!("failed to decode the response body using {encoding}")
+                    });
+                    return Some((result, None));
+                }
+            });
+
+        Ok(RawResponse::new(status, decoded_headers, Box::pin(stream)))
+    }
+}
+
+/// Compresses the `data` using the `encoding`.
+fn encode(encoding: ContentEncoding, data: &[u8]) -> io::Result<Bytes> {
+    let encoded = match encoding {
+        #[cfg(feature = "compression_gzip")]
+        ContentEncoding::Gzip => {
+            let mut writer =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            writer.write_all(data)?;
+            writer.finish()?
+        }
+        #[cfg(feature = "compression_gzip")]
+        ContentEncoding::Deflate => {
+            let mut writer =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            writer.write_all(data)?;
+            writer.finish()?
+        }
+        #[cfg(feature = "compression_brotli")]
+        ContentEncoding::Brotli => {
+            let mut writer = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+            writer.write_all(data)?;
+            writer.into_inner()
+        }
+        #[cfg(feature = "compression_zstd")]
+        ContentEncoding::Zstd => zstd::encode_all(data, 0)?,
+    };
+    Ok(encoded.into())
+}
+
+/// Decodes a response body incrementally as each chunk is received.
+enum Decoder {
+    #[cfg(feature = "compression_gzip")]
+    Gzip(flate2::write::GzDecoder<Vec<u8>>),
+    #[cfg(feature = "compression_gzip")]
+    Deflate(flate2::write::ZlibDecoder<Vec<u8>>),
+    #[cfg(feature = "compression_brotli")]
+    Brotli(Box<brotli::DecompressorWriter<Vec<u8>>>),
+    #[cfg(feature = "compression_zstd")]
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+}
+
+impl Decoder {
+    fn new(encoding: ContentEncoding) -> io::Result<Self> {
+        Ok(match encoding {
+            #[cfg(feature = "compression_gzip")]
+            ContentEncoding::Gzip => Self::Gzip(flate2::write::GzDecoder::new(Vec::new())),
+            #[cfg(feature = "compression_gzip")]
+            ContentEncoding::Deflate => Self::Deflate(flate2::write::ZlibDecoder::new(Vec::new())),
+            #[cfg(feature = "compression_brotli")]
+            ContentEncoding::Brotli => {
+                Self::Brotli(Box::new(brotli::DecompressorWriter::new(Vec::new(), 4096)))
+            }
+            #[cfg(feature = "compression_zstd")]
+            ContentEncoding::Zstd => Self::Zstd(zstd::stream::write::Decoder::new(Vec::new())?),
+        })
+    }
+
+    /// Decodes a `chunk`, returning any decoded bytes.
+    fn decode(&mut self, chunk: &[u8]) -> io::Result<Bytes> {
+        let decoded = match self {
+            #[cfg(feature = "compression_gzip")]
+            Self::Gzip(writer) => {
+                writer.write_all(chunk)?;
+                std::mem::take(writer.get_mut())
+            }
+            #[cfg(feature = "compression_gzip")]
+            Self::Deflate(writer) => {
+                writer.write_all(chunk)?;
+                std::mem::take(writer.get_mut())
+            }
+            #[cfg(feature = "compression_brotli")]
+            Self::Brotli(writer) => {
+                writer.write_all(chunk)?;
+                std::mem::take(writer.get_mut())
+            }
+            #[cfg(feature = "compression_zstd")]
+            Self::Zstd(writer) => {
+                writer.write_all(chunk)?;
+                std::mem::take(writer.get_mut())
+            }
+        };
+        Ok(decoded.into())
+    }
+
+    /// Finishes decoding, returning any remaining decoded bytes.
+    fn finish(self) -> io::Result<Bytes> {
+        let decoded = match self {
+            #[cfg(feature = "compression_gzip")]
+            Self::Gzip(writer) => writer.finish()?,
+            #[cfg(feature = "compression_gzip")]
+            Self::Deflate(writer) => writer.finish()?,
+            #[cfg(feature = "compression_brotli")]
+            Self::Brotli(mut writer) => {
+                writer.close()?;
+                writer.into_inner().map_err(|_| {
+                    io::Error::new(io::ErrorKind::UnexpectedEof, "incomplete brotli stream")
+                })?
+            }
+            #[cfg(feature = "compression_zstd")]
+            Self::Zstd(mut writer) => {
+                writer.flush()?;
+                writer.into_inner()
+            }
+        };
+        Ok(decoded.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::TransportOptions;
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt as _;
+    use std::sync::Mutex;
+    use typespec_client_core::http::policies::TransportPolicy;
+
+    fn encodings() -> impl Iterator<Item = ContentEncoding> {
+        ContentEncoding::ALL.iter().copied()
+    }
+
+    fn transport(
+        status: StatusCode,
+        headers: Headers,
+        chunks: Vec<Bytes>,
+        requests: Arc<Mutex<Vec<Request>>>,
+    ) -> Arc<dyn Policy> {
+        Arc::new(TransportPolicy::new(TransportOptions::new(Arc::new(
+            MockHttpClient::new(move |request| {
+                requests.lock().unwrap().push(request.clone());
+                let headers = headers.clone();
+                let chunks = chunks.clone();
+                async move {
+                    Ok(RawResponse::new(
+                        status,
+                        headers,
+                        Box::pin(futures::stream::iter(chunks.into_iter().map(Ok))),
+                    ))
+                }
+                .boxed()
+            }),
+        ))))
+    }
+
+    fn decode_all(encoding: ContentEncoding, data: &[u8]) -> Vec<u8> {
+        let mut decoder = Decoder::new(encoding).unwrap();
+        let mut decoded = decoder.decode(data).unwrap().to_vec();
+        decoded.extend(decoder.finish().unwrap());
+        decoded
+    }
+
+    #[tokio::test]
+    async fn compresses_request_body() {
+        let body = Bytes::from("hello world ".repeat(100));
+        for encoding in encodings() {
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let policy = CompressionPolicy::new(&CompressionOptions {
+                request_encoding: Some(encoding),
+                ..Default::default()
+            });
+            let transport = transport(StatusCode::Ok, Headers::new(), vec![], requests.clone());
+
+            let mut request = Request::new("https://example.com".parse().unwrap(), Method::Put);
+            request.set_body(body.clone());
+            policy
+                .send(
+                    &Context::new(),
+                    &mut request,
+                    std::slice::from_ref(&transport),
+                )
+                .await
+                .unwrap();
+
+            let mut small = Request::new("https://example.com".parse().unwrap(), Method::Put);
+            small.set_body("hello");
+            policy
+                .send(&Context::new(), &mut small, &[transport])
+                .await
+                .unwrap();
+
+            let requests = requests.lock().unwrap();
+            assert_eq!(
+                requests[0].headers().get_optional_str(&CONTENT_ENCODING),
+                Some(encoding.as_str())
+            );
+            let Body::Bytes(compressed) = requests[0].body() else {
+                panic!("expected bytes");
+            };
+            assert!(compressed.len() < body.len(), "{encoding}");
+            assert_eq!(decode_all(encoding, compressed), body);
+
+            assert_eq!(
+                requests[1].headers().get_optional_str(&CONTENT_ENCODING),
+                None
+            );
+            assert!(matches!(requests[1].body(), Body::Bytes(b) if b == "hello"));
+        }
+    }
+
+    #[tokio::test]
+    async fn decodes_response_body() {
+        let body = "hello world ".repeat(100);
+        for encoding in encodings() {
+            let compressed = encode(encoding, body.as_bytes()).unwrap();
+            let chunks = compressed.chunks(7).map(Bytes::copy_from_slice).collect();
+            let mut headers = Headers::new();
+            headers.insert(CONTENT_ENCODING, encoding.as_str());
+            headers.insert(CONTENT_LENGTH, compressed.len().to_string());
+            headers.insert("x-ms-request-id", "1");
+
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let policy = CompressionPolicy::new(&CompressionOptions::default());
+            let mut request = Request::new("https://example.com".parse().unwrap(), Method::Get);
+            let response = policy
+                .send(
+                    &Context::new(),
+                    &mut request,
+                    &[transport(StatusCode::Ok, headers, chunks, requests.clone())],
+                )
+                .await
+                .unwrap();
+
+            let accept_encoding = requests.lock().unwrap()[0]
+                .headers()
+                .get_optional_string(&ACCEPT_ENCODING)
+                .unwrap();
+            assert!(accept_encoding.contains(encoding.as_str()));
+            assert_eq!(response.headers().get_optional_str(&CONTENT_ENCODING), None);
+            assert_eq!(response.headers().get_optional_str(&CONTENT_LENGTH), None);
+            assert_eq!(
+                response
+                    .headers()
+                    .get_optional_str(&"x-ms-request-id".into()),
+                Some("1")
+            );
+            assert_eq!(
+                response.into_body().collect_string().await.unwrap(),
+                body,
+                "{encoding}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn ignores_unsupported_response_encoding() {
+        let mut headers = Headers::new();
+        headers.insert(CONTENT_ENCODING, "compress");
+        let policy = CompressionPolicy::new(&CompressionOptions::default());
+        let mut request = Request::new("https://example.com".parse().unwrap(), Method::Get);
+        let response = policy
+            .send(
+                &Context::new(),
+                &mut request,
+                &[transport(
+                    StatusCode::Ok,
+                    headers,
+                    vec![Bytes::from_static(b"raw")],
+                    Arc::default(),
+                )],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers().get_optional_str(&CONTENT_ENCODING),
+            Some("compress")
+        );
+        assert_eq!(response.into_body().collect().await.unwrap(), "raw");
+    }
+
+    #[cfg(feature = "compression_gzip")]
+    #[tokio::test]
+    async fn truncated_response_body() {
+        let compressed = encode(ContentEncoding::Gzip, &[1; 1024]).unwrap();
+        let mut headers = Headers::new();
+        headers.insert(CONTENT_ENCODING, "gzip");
+        let policy = CompressionPolicy::new(&CompressionOptions::default());
+        let mut request = Request::new("https://example.com".parse().unwrap(), Method::Get);
+        let response = policy
+            .send(
+                &Context::new(),
+                &mut request,
+                &[transport(
+                    StatusCode::Ok,
+                    headers,
+                    vec![compressed.slice(..compressed.len() - 4)],
+                    Arc::default(),
+                )],
+            )
+            .await
+            .unwrap();
+        let err = response.into_body().collect().await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Io);
+    }
+}
@@ -0,0 +1,359 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    http::{
+        policies::{Policy, PolicyResult},
+        Body, Context, Request,
+    },
+    sleep::sleep,
+    time::{Duration, OffsetDateTime},
+    tracing::{AttributeValue, Span},
+};
+use futures::future::{select, Either};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+use tracing::debug;
+
+/// The number of latencies used to calculate the hedging delay.
+const MAX_SAMPLES: usize = 100;
+
+/// The number of latencies required before the hedging delay is calculated instead of using the initial delay.
+const MIN_SAMPLES: usize = 10;
+
+/// Marks whether a request is idempotent and can be hedged by the [`HedgingPolicy`].
+///
+/// `GET` and `HEAD` requests are considered idempotent unless the [`Context`] contains `Idempotent(false)`.
+/// Other requests are only hedged if the [`Context`] contains `Idempotent(true)`.
+///
+/// # Examples
+///
+/// ```
+/// use azure_core::http::{policies::Idempotent, Context};
+///
+/// let ctx = Context::new().with_value(Idempotent(true));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Idempotent(pub bool);
+
+/// Sends a second attempt of an idempotent request if the first attempt has not completed
+/// after a delay, and returns whichever attempt completes first.
+///
+/// The delay is the `quantile` of the latencies of recent successful attempts e.g., `0.95` sends
+/// a second attempt only when the first attempt is slower than 95% of recent attempts.
+/// Until enough latencies have been recorded, the initial delay is used.
+///
+/// The attempt that completes last is cancelled by dropping it. If the attempt that completes first fails
+/// or returns an unsuccessful status, the response of the other attempt is awaited and returned if successful.
+/// The attempt that won is logged and, if the request is instrumented, recorded on the request span
+/// in the `az.hedging.attempt` attribute: `1` for the original attempt and `2` for the hedged attempt.
+///
+/// Only requests with a [`Body::Bytes`] body that are [`Idempotent`] are hedged.
+/// Add to [`ClientOptions::per_try_policies`](crate::http::ClientOptions::per_try_policies)
+/// so that each retry attempt is hedged, and share the same policy across operations with similar latencies.
+///
+/// # Examples
+///
+/// ```
+/// use azure_core::{http::{policies::HedgingPolicy, ClientOptions}, time::Duration};
+/// use std::sync::Arc;
+///
+/// // Send a second attempt if the first is slower than 95% of recent attempts.
+/// let policy = Arc::new(HedgingPolicy::new(0.95).with_initial_delay(Duration::milliseconds(500)));
+/// let options = ClientOptions {
+///     per_try_policies: vec![policy],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug)]
+pub struct HedgingPolicy {
+    quantile: f64,
+    initial_delay: Duration,
+    /// Latencies of recent successful requests.
+    samples: Mutex<VecDeque<Duration>>,
+}
+
+impl HedgingPolicy {
+    /// Creates a new `HedgingPolicy` that hedges requests slower than the `quantile` of recent latencies.
+    ///
+    /// The initial delay defaults to 1 second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quantile` is not between 0 and 1.
+    pub fn new(quantile: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&quantile),
+            "quantile must be between 0 and 1"
+        );
+        Self {
+            quantile,
+            initial_delay: Duration::seconds(1),
+            samples: Mutex::new(VecDeque::with_capacity(MAX_SAMPLES)),
+        }
+    }
+
+    /// Sets the delay used before enough latencies have been recorded to calculate the quantile.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_delay` is negative.
+    #[must_use]
+    pub fn with_initial_delay(self, initial_delay: Duration) -> Self {
+        assert!(
+            !initial_delay.is_negative(),
+            "initial delay must not be negative"
+        );
+        Self {
+            initial_delay,
+            ..self
+        }
+    }
+
+    /// Returns how long to wait for the first attempt before sending a hedged attempt.
+    fn delay(&self) -> Duration {
+        let samples = self.samples.lock().expect("hedging samples poisoned");
+        if samples.len() < MIN_SAMPLES {
+            return self.initial_delay;
+        }
+
+        let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let index = (self.quantile * sorted.len() as f64).ceil() as usize;
+        sorted[index.clamp(1, sorted.len()) - 1]
+    }
+
+    fn record(&self, latency: Duration) {
+        let mut samples = self.samples.lock().expect("hedging samples poisoned");
+        if samples.len() == MAX_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+}
+
+fn is_idempotent(ctx: &Context, request: &Request) -> bool {
+    ctx.value::<Idempotent>()
+        .map_or_else(|| request.method().is_safe(), |idempotent| idempotent.0)
+}
+
+fn is_success(result: &PolicyResult) -> bool {
+    matches!(result, Ok(response) if response.status().is_success())
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for HedgingPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if !is_idempotent(ctx, request) || !matches!(request.body(), Body::Bytes(_)) {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+
+        let start = OffsetDateTime::now_utc();
+        let mut hedged_request = request.clone();
+        let first = next[0].send(ctx, request, &next[1..]);
+        let result = match select(first, Box::pin(sleep(self.delay()))).await {
+            Either::Left((result, _)) => result,
+            Either::Right((_, first)) => {
+                debug!(
+                    "sending hedged attempt for {} {}",
+                    hedged_request.method(),
+                    hedged_request.url()
+                );
+                let second = next[0].send(ctx, &mut hedged_request, &next[1..]);
+                let (result, attempt, other) = match select(first, second).await {
+                    Either::Left((result, other)) => (result, 1, other),
+                    Either::Right((result, other)) => (result, 2, other),
+                };
+
+                // Prefer whichever attempt succeeds; dropping the other attempt cancels it.
+                let (result, attempt) = if is_success(&result) {
+                    (result, attempt)
+                } else {
+                    let other_result = other.await;
+                    if is_success(&other_result) {
+                        (other_result, 3 - attempt)
+                    } else {
+                        (result, attempt)
+                    }
+                };
+
+                debug!("hedged request completed by attempt {attempt}");
+                if let Some(span) = ctx.value::<Arc<dyn Span + Send + Sync>>() {
+                    let _ = span.set_attribute("az.hedging.attempt", AttributeValue::I64(attempt));
+                }
+                result
+            }
+        };
+
+        // Record the latency including any delay before hedging, which is a lower bound
+        // for the original attempt so the delay does not drift lower when hedged attempts win.
+        if is_success(&result) {
+            self.record(OffsetDateTime::now_utc() - start);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{headers::Headers, Method, RawResponse, StatusCode},
+        Bytes,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Responds to each attempt after a delay in milliseconds with a status, and counts attempts.
+    ///
+    /// Attempts are sent concurrently, so the transport cannot serialize them.
+    #[derive(Debug)]
+    struct MockTransport {
+        attempts: Arc<AtomicUsize>,
+        responses: &'static [(i64, StatusCode)],
+    }
+
+    #[async_trait::async_trait]
+    impl Policy for MockTransport {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+            let (delay, status) = self.responses[attempt];
+            sleep(Duration::milliseconds(delay)).await;
+            let mut headers = Headers::new();
+            headers.insert("x-attempt", (attempt + 1).to_string());
+            Ok(RawResponse::from_bytes(status, headers, Bytes::new()))
+        }
+    }
+
+    fn mock_transport(
+        attempts: Arc<AtomicUsize>,
+        responses: &'static [(i64, StatusCode)],
+    ) -> Arc<dyn Policy> {
+        Arc::new(MockTransport {
+            attempts,
+            responses,
+        })
+    }
+
+    fn request(method: Method) -> Request {
+        Request::new(
+            "https://contoso.blob.core.windows.net".parse().unwrap(),
+            method,
+        )
+    }
+
+    async fn send(
+        policy: &HedgingPolicy,
+        ctx: &Context<'_>,
+        method: Method,
+        transport: Arc<dyn Policy>,
+    ) -> String {
+        let response = policy
+            .send(ctx, &mut request(method), &[transport])
+            .await
+            .expect("response");
+        response
+            .headers()
+            .get_str(&"x-attempt".into())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn delay_uses_quantile() {
+        let policy = HedgingPolicy::new(0.9).with_initial_delay(Duration::seconds(5));
+        for ms in 1..MIN_SAMPLES as i64 {
+            policy.record(Duration::milliseconds(ms * 10));
+        }
+        assert_eq!(policy.delay(), Duration::seconds(5));
+
+        policy.record(Duration::milliseconds(100));
+        assert_eq!(policy.delay(), Duration::milliseconds(90));
+
+        for _ in 0..MAX_SAMPLES {
+            policy.record(Duration::milliseconds(20));
+        }
+        assert_eq!(policy.delay(), Duration::milliseconds(20));
+    }
+
+    #[tokio::test]
+    async fn hedged_attempt_wins() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let transport = mock_transport(
+            attempts.clone(),
+            &[(5_000, StatusCode::Ok), (10, StatusCode::Ok)],
+        );
+        let policy = HedgingPolicy::new(0.95).with_initial_delay(Duration::milliseconds(20));
+
+        let start = OffsetDateTime::now_utc();
+        let attempt = send(&policy, &Context::new(), Method::Get, transport).await;
+        assert_eq!(attempt, "2");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert!(OffsetDateTime::now_utc() - start < Duration::seconds(1));
+    }
+
+    #[tokio::test]
+    async fn fast_attempt_not_hedged() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let transport = mock_transport(attempts.clone(), &[(0, StatusCode::Ok)]);
+        let policy = HedgingPolicy::new(0.95).with_initial_delay(Duration::seconds(1));
+
+        let attempt = send(&policy, &Context::new(), Method::Head, transport).await;
+        assert_eq!(attempt, "1");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_attempt_awaits_other() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let transport = mock_transport(
+            attempts.clone(),
+            &[(100, StatusCode::Ok), (0, StatusCode::ServiceUnavailable)],
+        );
+        let policy = HedgingPolicy::new(0.95).with_initial_delay(Duration::milliseconds(10));
+
+        let attempt = send(&policy, &Context::new(), Method::Get, transport).await;
+        assert_eq!(attempt, "1");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn only_idempotent_requests_hedged() {
+        let policy = HedgingPolicy::new(0.95).with_initial_delay(Duration::ZERO);
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let transport = mock_transport(attempts.clone(), &[(10, StatusCode::Ok)]);
+        let attempt = send(&policy, &Context::new(), Method::Post, transport).await;
+        assert_eq!(attempt, "1");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let transport = mock_transport(attempts.clone(), &[(10, StatusCode::Ok)]);
+        let ctx = Context::new().with_value(Idempotent(false));
+        let attempt = send(&policy, &ctx, Method::Get, transport).await;
+        assert_eq!(attempt, "1");
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let transport = mock_transport(
+            attempts.clone(),
+            &[(100, StatusCode::Ok), (0, StatusCode::Ok)],
+        );
+        let ctx = Context::new().with_value(Idempotent(true));
+        let attempt = send(&policy, &ctx, Method::Post, transport).await;
+        assert_eq!(attempt, "2");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,500 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::{
+    logging::{redact_url, DEFAULT_ALLOWED_QUERY_PARAMS},
+    metrics::error_type,
+};
+use crate::{
+    error::Error,
+    http::{
+        headers::{HeaderName, TRACEPARENT, TRACESTATE},
+        policies::{Policy, PolicyResult},
+        Context, Method, Request,
+    },
+    tracing::{AttributeValue, FutureExt as _, Span, SpanKind, SpanStatus, Tracer},
+};
+use std::{collections::HashSet, fmt, sync::Arc};
+use tracing::warn;
+
+const X_MS_REQUEST_ID: HeaderName = HeaderName::from_static("x-ms-request-id");
+
+/// Identifies the client method to trace as a public API span.
+///
+/// Service clients insert this into the [`Context`] passed to the [`Pipeline`](crate::http::Pipeline)
+/// so that the [`PublicApiInstrumentationPolicy`] creates a span for the method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicApiInstrumentationInformation {
+    /// The name of the span, typically the client and method name e.g., `SecretClient.get_secret`.
+    pub api_name: &'static str,
+}
+
+/// Creates a [`SpanKind::Internal`] span for a client method identified by [`PublicApiInstrumentationInformation`] in the [`Context`].
+///
+/// The span is added to the [`Context`] passed to subsequent policies as the parent of spans for each HTTP request.
+/// If the [`Context`] already contains a span, e.g. when a client method calls another client method, no span is created.
+///
+/// A `PublicApiInstrumentationPolicy` is added to a [`Pipeline`](crate::http::Pipeline)
+/// when [`InstrumentationOptions::tracer_provider`](crate::http::InstrumentationOptions::tracer_provider) is set.
+pub struct PublicApiInstrumentationPolicy {
+    tracer: Arc<dyn Tracer + Send + Sync>,
+}
+
+impl PublicApiInstrumentationPolicy {
+    /// Creates a new `PublicApiInstrumentationPolicy` that creates spans using the `tracer`.
+    pub fn new(tracer: Arc<dyn Tracer + Send + Sync>) -> Self {
+        Self { tracer }
+    }
+}
+
+impl fmt::Debug for PublicApiInstrumentationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicApiInstrumentationPolicy")
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for PublicApiInstrumentationPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let Some(info) = ctx.value::<PublicApiInstrumentationInformation>() else {
+            return next[0].send(ctx, request, &next[1..]).await;
+        };
+        if ctx.value::<Arc<dyn Span + Send + Sync>>().is_some() {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+
+        let span = match self
+            .tracer
+            .start_span_with_current(info.api_name, SpanKind::Internal)
+        {
+            Ok(span) => span,
+            Err(err) => {
+                warn!("failed to start span for {}: {err}", info.api_name);
+                return next[0].send(ctx, request, &next[1..]).await;
+            }
+        };
+
+        let ctx = ctx.clone().with_value(span.clone());
+        let result = next[0]
+            .send(&ctx, request, &next[1..])
+            .with_context(ctx.clone())
+            .await;
+
+        if let Err(err) = &result {
+            record_error(span.as_ref(), err);
+        }
+        let _ = span.end();
+
+        result
+    }
+}
+
+/// Creates a [`SpanKind::Client`] span for each HTTP request attempt with OpenTelemetry HTTP semantic convention attributes.
+///
+/// If the [`Context`] contains a span, e.g. one created by the [`PublicApiInstrumentationPolicy`], it is the parent span;
+/// otherwise, the current span is the parent span. The span is added to the [`Context`] passed to subsequent policies.
+///
+/// The W3C `traceparent` and `tracestate` headers of the span are added to the request so that services can continue the trace.
+///
+/// The `url.full` attribute is redacted like the [`LoggingPolicy`](crate::http::policies::LoggingPolicy) redacts URLs.
+///
+/// A `RequestInstrumentationPolicy` is added to a [`Pipeline`](crate::http::Pipeline)
+/// when [`InstrumentationOptions::tracer_provider`](crate::http::InstrumentationOptions::tracer_provider) is set.
+pub struct RequestInstrumentationPolicy {
+    tracer: Arc<dyn Tracer + Send + Sync>,
+    allowed_query_params: HashSet<String>,
+}
+
+impl RequestInstrumentationPolicy {
+    /// Creates a new `RequestInstrumentationPolicy` that creates spans using the `tracer`.
+    pub fn new(tracer: Arc<dyn Tracer + Send + Sync>) -> Self {
+        Self {
+            tracer,
+            allowed_query_params: DEFAULT_ALLOWED_QUERY_PARAMS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for RequestInstrumentationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInstrumentationPolicy")
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for RequestInstrumentationPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let method = method_name(*request.method());
+        // Spans for methods that aren't known are named "HTTP" by convention.
+        let name = if method == OTHER_METHOD {
+            "HTTP"
+        } else {
+            method
+        };
+        let span = match ctx.value::<Arc<dyn Span + Send + Sync>>() {
+            Some(parent) => {
+                self.tracer
+                    .start_span_with_parent(name, SpanKind::Client, parent.clone())
+            }
+            None => self.tracer.start_span_with_current(name, SpanKind::Client),
+        };
+        let span = match span {
+            Ok(span) => span,
+            Err(err) => {
+                warn!("failed to start span for {method} request: {err}");
+                return next[0].send(ctx, request, &next[1..]).await;
+            }
+        };
+
+        let trace_context = span.trace_context();
+        if trace_context.is_valid() {
+            request.insert_header(TRACEPARENT, trace_context.traceparent());
+            if let Some(trace_state) = trace_context.trace_state {
+                request.insert_header(TRACESTATE, trace_state);
+            }
+        }
+
+        let url = request.url();
+        let _ = span.set_attribute(
+            "http.request.method",
+            AttributeValue::String(method.to_string()),
+        );
+        let _ = span.set_attribute(
+            "url.full",
+            AttributeValue::String(redact_url(url, &self.allowed_query_params).to_string()),
+        );
+        if let Some(host) = url.host_str() {
+            let _ = span.set_attribute("server.address", AttributeValue::String(host.to_string()));
+        }
+        if let Some(port) = url.port_or_known_default() {
+            let _ = span.set_attribute("server.port", AttributeValue::I64(port.into()));
+        }
+
+        let ctx = ctx.clone().with_value(span.clone());
+        let result = next[0]
+            .send(&ctx, request, &next[1..])
+            .with_context(ctx.clone())
+            .await;
+
+        match &result {
+            Ok(response) => {
+                let status = u16::from(response.status());
+                let _ = span.set_attribute(
+                    "http.response.status_code",
+                    AttributeValue::I64(status.into()),
+                );
+                if let Some(request_id) = response.headers().get_optional_str(&X_MS_REQUEST_ID) {
+                    let _ = span.set_attribute(
+                        "az.service_request_id",
+                        AttributeValue::String(request_id.to_string()),
+                    );
+                }
+                if status >= 400 {
+                    let _ = span
+                        .set_attribute("error.type", AttributeValue::String(status.to_string()));
+                    let _ = span.set_status(SpanStatus::Error {
+                        description: String::new(),
+                    });
+                }
+            }
+            Err(err) => record_error(span.as_ref(), err),
+        }
+        let _ = span.end();
+
+        result
+    }
+}
+
+fn record_error(span: &(dyn Span + Send + Sync), err: &Error) {
+    let _ = span.set_attribute("error.type", AttributeValue::String(error_type(err)));
+    let _ = span.record_error(err);
+}
+
+/// The `http.request.method` of methods that aren't known.
+const OTHER_METHOD: &str = "_OTHER";
+
+pub(super) fn method_name(method: Method) -> &'static str {
+    match method {
+        Method::Delete => "DELETE",
+        Method::Get => "GET",
+        Method::Head => "HEAD",
+        Method::Patch => "PATCH",
+        Method::Post => "POST",
+        Method::Put => "PUT",
+        _ => OTHER_METHOD,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{headers::Headers, RawResponse, StatusCode, TransportOptions},
+        tracing::{AsAny, SpanGuard, TraceContext},
+        Bytes,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt;
+    use std::sync::Mutex;
+    use typespec_client_core::http::policies::TransportPolicy;
+
+    #[derive(Debug, Default)]
+    struct RecordedSpan {
+        name: &'static str,
+        kind: String,
+        parent: Option<[u8; 8]>,
+        attributes: Vec<(&'static str, String)>,
+        error: bool,
+        ended: bool,
+    }
+
+    #[derive(Default)]
+    struct MockTracer {
+        spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    }
+
+    struct MockSpan {
+        id: usize,
+        spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    }
+
+    impl MockTracer {
+        fn start(
+            &self,
+            name: &'static str,
+            kind: SpanKind,
+            parent: Option<[u8; 8]>,
+        ) -> crate::Result<Arc<dyn Span + Send + Sync>> {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(RecordedSpan {
+                name,
+                kind: format!("{kind:?}"),
+                parent,
+                ..Default::default()
+            });
+            Ok(Arc::new(MockSpan {
+                id: spans.len() - 1,
+                spans: self.spans.clone(),
+            }))
+        }
+    }
+
+    impl Tracer for MockTracer {
+        fn start_span(
+            &self,
+            name: &'static str,
+            kind: SpanKind,
+        ) -> crate::Result<Arc<dyn Span + Send + Sync>> {
+            self.start(name, kind, None)
+        }
+
+        fn start_span_with_current(
+            &self,
+            name: &'static str,
+            kind: SpanKind,
+        ) -> crate::Result<Arc<dyn Span + Send + Sync>> {
+            self.start(name, kind, None)
+        }
+
+        fn start_span_with_parent(
+            &self,
+            name: &'static str,
+            kind: SpanKind,
+            parent: Arc<dyn Span + Send + Sync>,
+        ) -> crate::Result<Arc<dyn Span + Send + Sync>> {
+            self.start(name, kind, Some(parent.span_id()))
+        }
+    }
+
+    impl MockSpan {
+        fn update(&self, f: impl FnOnce(&mut RecordedSpan)) -> crate::Result<()> {
+            f(&mut self.spans.lock().unwrap()[self.id]);
+            Ok(())
+        }
+    }
+
+    impl Span for MockSpan {
+        fn span_id(&self) -> [u8; 8] {
+            (self.id as u64 + 1).to_be_bytes()
+        }
+
+        fn trace_context(&self) -> TraceContext {
+            TraceContext {
+                trace_id: [1; 16],
+                span_id: self.span_id(),
+                sampled: true,
+                trace_state: Some("az=1".to_string()),
+            }
+        }
+
+        fn end(&self) -> crate::Result<()> {
+            self.update(|span| span.ended = true)
+        }
+
+        fn set_status(&self, status: SpanStatus) -> crate::Result<()> {
+            self.update(|span| span.error = matches!(status, SpanStatus::Error { .. }))
+        }
+
+        fn set_attribute(&self, key: &'static str, value: AttributeValue) -> crate::Result<()> {
+            let value = match value {
+                AttributeValue::String(value) => value,
+                AttributeValue::Bool(value) => value.to_string(),
+                AttributeValue::I64(value) => value.to_string(),
+                AttributeValue::U64(value) => value.to_string(),
+                AttributeValue::Array(value) => format!("{value:?}"),
+            };
+            self.update(|span| span.attributes.push((key, value)))
+        }
+
+        fn record_error(&self, _error: &dyn std::error::Error) -> crate::Result<()> {
+            self.update(|span| span.error = true)
+        }
+
+        fn set_current(&self, _context: &Context) -> crate::Result<Box<dyn SpanGuard>> {
+            struct Guard;
+            impl SpanGuard for Guard {
+                fn end(self) -> crate::Result<()> {
+                    Ok(())
+                }
+            }
+            Ok(Box::new(Guard))
+        }
+    }
+
+    impl AsAny for MockSpan {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn transport(status: StatusCode) -> Arc<dyn Policy> {
+        Arc::new(TransportPolicy::new(TransportOptions::new(Arc::new(
+            MockHttpClient::new(move |_| {
+                async move {
+                    let mut headers = Headers::new();
+                    headers.insert(X_MS_REQUEST_ID, "request-id");
+                    Ok(RawResponse::from_bytes(status, headers, Bytes::new()))
+                }
+                .boxed()
+            }),
+        ))))
+    }
+
+    #[tokio::test]
+    async fn creates_spans() {
+        let tracer = Arc::new(MockTracer::default());
+        let policies: Vec<Arc<dyn Policy>> = vec![
+            Arc::new(PublicApiInstrumentationPolicy::new(tracer.clone())),
+            Arc::new(RequestInstrumentationPolicy::new(tracer.clone())),
+            transport(StatusCode::Ok),
+        ];
+
+        let ctx = Context::new().with_value(PublicApiInstrumentationInformation {
+            api_name: "SecretClient.get_secret",
+        });
+        let mut request = Request::new(
+            "https://vault.azure.net/secrets/name?api-version=7.6&secret=value"
+                .parse()
+                .unwrap(),
+            Method::Get,
+        );
+        policies[0]
+            .send(&ctx, &mut request, &policies[1..])
+            .await
+            .expect("response");
+
+        assert_eq!(
+            request.headers().get_optional_str(&TRACEPARENT),
+            Some("00-01010101010101010101010101010101-0000000000000002-01")
+        );
+        assert_eq!(
+            request.headers().get_optional_str(&TRACESTATE),
+            Some("az=1")
+        );
+
+        let spans = tracer.spans.lock().unwrap();
+        assert_eq!(spans.len(), 2);
+
+        assert_eq!(spans[0].name, "SecretClient.get_secret");
+        assert_eq!(spans[0].kind, "Internal");
+        assert!(spans[0].attributes.is_empty());
+        assert!(spans[0].ended);
+
+        assert_eq!(spans[1].name, "GET");
+        assert_eq!(spans[1].kind, "Client");
+        assert_eq!(spans[1].parent, Some(1u64.to_be_bytes()));
+        assert_eq!(
+            spans[1].attributes,
+            vec![
+                ("http.request.method", "GET".to_string()),
+                (
+                    "url.full",
+                    "https://vault.azure.net/secrets/name?api-version=7.6&secret=REDACTED"
+                        .to_string()
+                ),
+                ("server.address", "vault.azure.net".to_string()),
+                ("server.port", "443".to_string()),
+                ("http.response.status_code", "200".to_string()),
+                ("az.service_request_id", "request-id".to_string()),
+            ]
+        );
+        assert!(!spans[1].error);
+        assert!(spans[1].ended);
+    }
+
+    #[tokio::test]
+    async fn records_errors() {
+        let tracer = Arc::new(MockTracer::default());
+        let policy = RequestInstrumentationPolicy::new(tracer.clone());
+
+        let mut request = Request::new("https://vault.azure.net".parse().unwrap(), Method::Put);
+        policy
+            .send(
+                &Context::new(),
+                &mut request,
+                &[transport(StatusCode::ServiceUnavailable)],
+            )
+            .await
+            .expect("response");
+
+        let spans = tracer.spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].name, "PUT");
+        assert_eq!(spans[0].parent, None);
+        assert!(spans[0]
+            .attributes
+            .contains(&("error.type", "503".to_string())));
+        assert!(spans[0].error);
+    }
+
+    #[tokio::test]
+    async fn public_api_requires_information() {
+        let tracer = Arc::new(MockTracer::default());
+        let policy = PublicApiInstrumentationPolicy::new(tracer.clone());
+
+        let mut request = Request::new("https://vault.azure.net".parse().unwrap(), Method::Get);
+        policy
+            .send(&Context::new(), &mut request, &[transport(StatusCode::Ok)])
+            .await
+            .expect("response");
+
+        assert!(tracer.spans.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::instrumentation::method_name;
+use crate::{
+    error::{Error, ErrorKind},
+    http::{
+        policies::{Policy, PolicyResult, RetryAttempt},
+        Context, Request,
+    },
+    metrics::{Attribute, Counter, Histogram, Meter},
+    time::OffsetDateTime,
+    tracing::AttributeValue,
+};
+use std::{fmt, sync::Arc};
+
+/// Adds the client's [`Meter`] to the [`Context`] passed to subsequent policies.
+///
+/// Policies can record metrics using the `Arc<dyn Meter + Send + Sync>` in the [`Context`], if any.
+/// The `MeterPolicy` also creates the histogram the [`BearerTokenCredentialPolicy`](crate::http::policies::BearerTokenCredentialPolicy)
+/// uses to record how long it takes to acquire tokens. If the [`Context`] already contains a meter, it is not replaced.
+///
+/// A `MeterPolicy` is added to a [`Pipeline`](crate::http::Pipeline)
+/// when [`InstrumentationOptions::meter_provider`](crate::http::InstrumentationOptions::meter_provider) is set.
+pub struct MeterPolicy {
+    meter: Arc<dyn Meter + Send + Sync>,
+    get_token_duration: GetTokenDuration,
+}
+
+impl MeterPolicy {
+    /// Creates a new `MeterPolicy` that adds the `meter` to the [`Context`].
+    pub fn new(meter: Arc<dyn Meter + Send + Sync>) -> Self {
+        let get_token_duration = GetTokenDuration(meter.create_histogram(
+            "az.credential.get_token.duration",
+            "Duration of token acquisition by the bearer token credential policy.",
+            "s",
+        ));
+        Self {
+            meter,
+            get_token_duration,
+        }
+    }
+}
+
+/// The histogram added to the [`Context`] by the [`MeterPolicy`] to record how long it takes to acquire tokens.
+#[derive(Clone)]
+pub(crate) struct GetTokenDuration(pub(crate) Arc<dyn Histogram + Send + Sync>);
+
+impl fmt::Debug for MeterPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeterPolicy").finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for MeterPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        if ctx.value::<Arc<dyn Meter + Send + Sync>>().is_some() {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+        let ctx = ctx
+            .clone()
+            .with_value(self.meter.clone())
+            .with_value(self.get_token_duration.clone());
+        next[0].send(&ctx, request, &next[1..]).await
+    }
+}
+
+/// Records the duration of each HTTP request attempt and the number of retries
+/// using OpenTelemetry HTTP semantic convention instruments and attributes.
+///
+/// Durations are recorded to the `http.client.request.duration` histogram in seconds,
+/// and each attempt after the first increments the `http.client.request.retries` counter.
+///
+/// A `RequestMetricsPolicy` is added to a [`Pipeline`](crate::http::Pipeline)
+/// when [`InstrumentationOptions::meter_provider`](crate::http::InstrumentationOptions::meter_provider) is set.
+pub struct RequestMetricsPolicy {
+    duration: Arc<dyn Histogram + Send + Sync>,
+    retries: Arc<dyn Counter + Send + Sync>,
+}
+
+impl RequestMetricsPolicy {
+    /// Creates a new `RequestMetricsPolicy` that records metrics using instruments created by the `meter`.
+    pub fn new(meter: &dyn Meter) -> Self {
+        Self {
+            duration: meter.create_histogram(
+                "http.client.request.duration",
+                "Duration of HTTP client requests.",
+                "s",
+            ),
+            retries: meter.create_counter(
+                "http.client.request.retries",
+                "Number of HTTP client requests that were retries of a previous attempt.",
+                "{request}",
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for RequestMetricsPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestMetricsPolicy")
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for RequestMetricsPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let mut attributes: Vec<Attribute> = vec![(
+            "http.request.method",
+            AttributeValue::String(method_name(*request.method()).to_string()),
+        )];
+        let url = request.url();
+        if let Some(host) = url.host_str() {
+            attributes.push(("server.address", AttributeValue::String(host.to_string())));
+        }
+        if let Some(port) = url.port_or_known_default() {
+            attributes.push(("server.port", AttributeValue::I64(port.into())));
+        }
+        if ctx.value::<RetryAttempt>().is_some() {
+            self.retries.add(1, &attributes);
+        }
+
+        let start = OffsetDateTime::now_utc();
+        let result = next[0].send(ctx, request, &next[1..]).await;
+        let duration = OffsetDateTime::now_utc() - start;
+
+        match &result {
+            Ok(response) => {
+                let status = u16::from(response.status());
+                attributes.push((
+                    "http.response.status_code",
+                    AttributeValue::I64(status.into()),
+                ));
+                if status >= 400 {
+                    attributes.push(("error.type", AttributeValue::String(status.to_string())));
+                }
+            }
+            Err(err) => attributes.push(("error.type", AttributeValue::String(error_type(err)))),
+        }
+        self.duration.record(duration.as_seconds_f64(), &attributes);
+
+        result
+    }
+}
+
+/// Gets the `error.type` attribute value for an [`Error`].
+pub(super) fn error_type(err: &Error) -> String {
+    match err.kind() {
+        ErrorKind::HttpResponse { status, .. } => u16::from(*status).to_string(),
+        kind => kind.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        credentials::{AccessToken, TokenCredential, TokenRequestOptions},
+        http::{
+            headers::Headers, policies::BearerTokenCredentialPolicy, Method, RawResponse,
+            StatusCode, TransportOptions,
+        },
+        time::Duration,
+        Bytes,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt;
+    use std::sync::Mutex;
+    use typespec_client_core::http::policies::TransportPolicy;
+
+    type Recorded = Arc<Mutex<Vec<(&'static str, f64, Vec<(&'static str, String)>)>>>;
+
+    #[derive(Default)]
+    struct MockMeter {
+        created: Mutex<Vec<&'static str>>,
+        recorded: Recorded,
+    }
+
+    struct MockInstrument {
+        name: &'static str,
+        recorded: Recorded,
+    }
+
+    impl MockInstrument {
+        fn push(&self, value: f64, attributes: &[Attribute]) {
+            let attributes = attributes
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        AttributeValue::String(value) => value.clone(),
+                        AttributeValue::Bool(value) => value.to_string(),
+                        AttributeValue::I64(value) => value.to_string(),
+                        AttributeValue::U64(value) => value.to_string(),
+                        AttributeValue::Array(value) => format!("{value:?}"),
+                    };
+                    (*key, value)
+                })
+                .collect();
+            self.recorded
+                .lock()
+                .unwrap()
+                .push((self.name, value, attributes));
+        }
+    }
+
+    impl Meter for MockMeter {
+        fn create_histogram(
+            &self,
+            name: &'static str,
+            _description: &'static str,
+            _unit: &'static str,
+        ) -> Arc<dyn Histogram + Send + Sync> {
+            self.created.lock().unwrap().push(name);
+            Arc::new(MockInstrument {
+                name,
+                recorded: self.recorded.clone(),
+            })
+        }
+
+        fn create_counter(
+            &self,
+            name: &'static str,
+            _description: &'static str,
+            _unit: &'static str,
+        ) -> Arc<dyn Counter + Send + Sync> {
+            self.created.lock().unwrap().push(name);
+            Arc::new(MockInstrument {
+                name,
+                recorded: self.recorded.clone(),
+            })
+        }
+    }
+
+    impl Histogram for MockInstrument {
+        fn record(&self, value: f64, attributes: &[Attribute]) {
+            self.push(value, attributes);
+        }
+    }
+
+    impl Counter for MockInstrument {
+        fn add(&self, value: u64, attributes: &[Attribute]) {
+            self.push(value as f64, attributes);
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockCredential;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl TokenCredential for MockCredential {
+        async fn get_token(
+            &self,
+            _scopes: &[&str],
+            _options: Option<TokenRequestOptions>,
+        ) -> crate::Result<AccessToken> {
+            Ok(AccessToken {
+                token: "token".into(),
+                expires_on: OffsetDateTime::now_utc() + Duration::hours(1),
+            })
+        }
+    }
+
+    fn transport(status: StatusCode) -> Arc<dyn Policy> {
+        Arc::new(TransportPolicy::new(TransportOptions::new(Arc::new(
+            MockHttpClient::new(move |_| {
+                async move {
+                    Ok(RawResponse::from_bytes(
+                        status,
+                        Headers::new(),
+                        Bytes::new(),
+                    ))
+                }
+                .boxed()
+            }),
+        ))))
+    }
+
+    #[tokio::test]
+    async fn records_request_duration() {
+        let meter = MockMeter::default();
+        let policy = RequestMetricsPolicy::new(&meter);
+
+        let mut request = Request::new(
+            "https://vault.azure.net/secrets".parse().unwrap(),
+            Method::Get,
+        );
+        policy
+            .send(&Context::new(), &mut request, &[transport(StatusCode::Ok)])
+            .await
+            .expect("response");
+        policy
+            .send(
+                &Context::new().with_value(RetryAttempt(1)),
+                &mut request,
+                &[transport(StatusCode::ServiceUnavailable)],
+            )
+            .await
+            .expect("response");
+
+        let recorded = meter.recorded.lock().unwrap();
+        let names: Vec<_> = recorded.iter().map(|(name, ..)| *name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "http.client.request.duration",
+                "http.client.request.retries",
+                "http.client.request.duration"
+            ]
+        );
+        assert_eq!(
+            recorded[0].2,
+            vec![
+                ("http.request.method", "GET".to_string()),
+                ("server.address", "vault.azure.net".to_string()),
+                ("server.port", "443".to_string()),
+                ("http.response.status_code", "200".to_string()),
+            ]
+        );
+        assert_eq!(recorded[1].1, 1.0);
+        assert!(recorded[2].2.contains(&("error.type", "503".to_string())));
+    }
+
+    #[tokio::test]
+    async fn adds_meter_to_context() {
+        #[derive(Debug)]
+        struct AssertMeterPolicy;
+
+        #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+        #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+        impl Policy for AssertMeterPolicy {
+            async fn send(
+                &self,
+                ctx: &Context,
+                request: &mut Request,
+                next: &[Arc<dyn Policy>],
+            ) -> PolicyResult {
+                assert!(ctx.value::<Arc<dyn Meter + Send + Sync>>().is_some());
+                assert!(ctx.value::<GetTokenDuration>().is_some());
+                next[0].send(ctx, request, &next[1..]).await
+            }
+        }
+
+        let policy = MeterPolicy::new(Arc::new(MockMeter::default()));
+        let mut request = Request::new("https://vault.azure.net".parse().unwrap(), Method::Get);
+        policy
+            .send(
+                &Context::new(),
+                &mut request,
+                &[Arc::new(AssertMeterPolicy), transport(StatusCode::Ok)],
+            )
+            .await
+            .expect("response");
+    }
+
+    #[tokio::test]
+    async fn records_get_token_duration() {
+        let meter = Arc::new(MockMeter::default());
+        let policy = MeterPolicy::new(meter.clone());
+        let auth: Arc<dyn Policy> = Arc::new(BearerTokenCredentialPolicy::new(
+            Arc::new(MockCredential),
+            ["https://vault.azure.net/.default"],
+        ));
+        let next = [auth, transport(StatusCode::Ok)];
+
+        for _ in 0..2 {
+            let mut request = Request::new("https://vault.azure.net".parse().unwrap(), Method::Get);
+            policy
+                .send(&Context::new(), &mut request, &next)
+                .await
+                .expect("response");
+        }
+
+        // The histogram is created once, and the token is only acquired for the first request.
+        assert_eq!(
+            *meter.created.lock().unwrap(),
+            vec!["az.credential.get_token.duration"]
+        );
+        let recorded = meter.recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].0, "az.credential.get_token.duration");
+        assert!(recorded[0].2.is_empty());
+    }
+}
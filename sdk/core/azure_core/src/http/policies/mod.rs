@@ -4,10 +4,40 @@
 //! HTTP pipeline policies.
 
 mod bearer_token_policy;
+mod challenge;
+mod challenge_bearer_token_policy;
+mod circuit_breaker;
 mod client_request_id;
+#[cfg(any(
+    feature = "compression_brotli",
+    feature = "compression_gzip",
+    feature = "compression_zstd"
+))]
+mod compression;
+mod hedging;
+mod instrumentation;
+mod logging;
+mod metrics;
+mod rate_limit;
 mod user_agent;
 
 pub use bearer_token_policy::BearerTokenCredentialPolicy;
+pub use challenge_bearer_token_policy::{ChallengeBearerTokenCredentialPolicy, ChallengeOptions};
+pub use circuit_breaker::{CircuitBreakerOpenError, CircuitBreakerPolicy};
 pub use client_request_id::*;
+#[cfg(any(
+    feature = "compression_brotli",
+    feature = "compression_gzip",
+    feature = "compression_zstd"
+))]
+pub use compression::CompressionPolicy;
+pub use hedging::{HedgingPolicy, Idempotent};
+pub use instrumentation::{
+    PublicApiInstrumentationInformation, PublicApiInstrumentationPolicy,
+    RequestInstrumentationPolicy,
+};
+pub use logging::LoggingPolicy;
+pub use metrics::{MeterPolicy, RequestMetricsPolicy};
+pub use rate_limit::RateLimitPolicy;
 pub use typespec_client_core::http::policies::*;
 pub use user_agent::*;
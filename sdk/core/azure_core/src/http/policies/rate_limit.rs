@@ -0,0 +1,268 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    cancellation::CancellationToken,
+    http::{
+        policies::{Policy, PolicyResult},
+        Context, Request,
+    },
+    sleep::sleep,
+    time::{Duration, OffsetDateTime},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tracing::debug;
+
+/// Limits the rate of requests sent to each host using a token bucket.
+///
+/// Each host has a bucket that holds up to `burst` permits and is refilled at `permits` per `interval`.
+/// A request takes a permit from the bucket for its host, waiting until one is available if the bucket is empty.
+/// Requests wait in the order they were sent, unless cancelled by a [`CancellationToken`] in the [`Context`],
+/// in which case the permit is returned to the bucket.
+///
+/// Add to [`ClientOptions::per_call_policies`](crate::http::ClientOptions::per_call_policies)
+/// to limit each operation rather than each retry, and share the same policy across clients to limit them together.
+///
+/// # Examples
+///
+/// ```
+/// use azure_core::{http::{policies::RateLimitPolicy, ClientOptions}, time::Duration};
+/// use std::sync::Arc;
+///
+/// // Send at most 100 requests per second to each host, with bursts of up to 20 requests.
+/// let policy = Arc::new(RateLimitPolicy::new(100, Duration::seconds(1)).with_burst(20));
+/// let options = ClientOptions {
+///     per_call_policies: vec![policy],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug)]
+pub struct RateLimitPolicy {
+    /// Permits added to a bucket per second.
+    rate: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available permits, which are negative when requests are waiting.
+    permits: f64,
+    updated: OffsetDateTime,
+}
+
+impl RateLimitPolicy {
+    /// Creates a new `RateLimitPolicy` that allows `permits` requests per `interval` to each host.
+    ///
+    /// The burst size defaults to `permits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is 0 or `interval` is not positive.
+    pub fn new(permits: u32, interval: Duration) -> Self {
+        assert!(permits > 0, "permits must be greater than 0");
+        assert!(interval.is_positive(), "interval must be positive");
+        Self {
+            rate: f64::from(permits) / interval.as_seconds_f64(),
+            burst: f64::from(permits),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the maximum number of requests that can be sent to a host at once after being idle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is 0.
+    #[must_use]
+    pub fn with_burst(self, burst: u32) -> Self {
+        assert!(burst > 0, "burst must be greater than 0");
+        Self {
+            burst: f64::from(burst),
+            ..self
+        }
+    }
+
+    /// Takes a permit for the `host` and returns how long to wait before sending the request.
+    fn acquire(&self, host: &str, now: OffsetDateTime) -> Duration {
+        let mut buckets = self.buckets.lock().expect("rate limit buckets poisoned");
+        let bucket = buckets.entry(host.to_string()).or_insert(Bucket {
+            permits: self.burst,
+            updated: now,
+        });
+
+        let elapsed = (now - bucket.updated).max(Duration::ZERO);
+        bucket.permits = (bucket.permits + elapsed.as_seconds_f64() * self.rate).min(self.burst);
+        bucket.updated = now;
+        bucket.permits -= 1.0;
+
+        if bucket.permits >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::seconds_f64(-bucket.permits / self.rate)
+        }
+    }
+
+    /// Returns a permit taken for the `host` by a request that will not be sent.
+    fn release(&self, host: &str) {
+        let mut buckets = self.buckets.lock().expect("rate limit buckets poisoned");
+        if let Some(bucket) = buckets.get_mut(host) {
+            bucket.permits = (bucket.permits + 1.0).min(self.burst);
+        }
+    }
+}
+
+/// A permit taken by a waiting request, which is returned if the request is cancelled or dropped while waiting.
+struct Reservation<'a> {
+    policy: &'a RateLimitPolicy,
+    host: &'a str,
+    waiting: bool,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.waiting {
+            self.policy.release(self.host);
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl Policy for RateLimitPolicy {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let host = request.url().host_str().unwrap_or_default().to_string();
+        let wait = self.acquire(&host, OffsetDateTime::now_utc());
+        if wait.is_positive() {
+            debug!("rate limit reached for {host}; waiting {wait}");
+            let mut reservation = Reservation {
+                policy: self,
+                host: &host,
+                waiting: true,
+            };
+            match ctx.value::<CancellationToken>() {
+                Some(token) => token.run_until_cancelled(sleep(wait)).await?,
+                None => sleep(wait).await,
+            }
+            reservation.waiting = false;
+        }
+
+        next[0].send(ctx, request, &next[1..]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        http::{headers::Headers, Method, RawResponse, StatusCode, TransportOptions},
+        Bytes,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt;
+    use typespec_client_core::http::policies::TransportPolicy;
+
+    #[test]
+    fn acquire_refills_per_host() {
+        let policy = RateLimitPolicy::new(10, Duration::seconds(1)).with_burst(2);
+        let now = OffsetDateTime::now_utc();
+
+        assert_eq!(policy.acquire("a.contoso.com", now), Duration::ZERO);
+        assert_eq!(policy.acquire("a.contoso.com", now), Duration::ZERO);
+        assert_eq!(
+            policy.acquire("a.contoso.com", now),
+            Duration::milliseconds(100)
+        );
+        assert_eq!(
+            policy.acquire("a.contoso.com", now),
+            Duration::milliseconds(200)
+        );
+
+        // Other hosts have their own bucket.
+        assert_eq!(policy.acquire("b.contoso.com", now), Duration::ZERO);
+
+        // Waiting requests are repaid before new permits are available.
+        let later = now + Duration::milliseconds(300);
+        assert_eq!(policy.acquire("a.contoso.com", later), Duration::ZERO);
+        assert_eq!(
+            policy.acquire("a.contoso.com", later),
+            Duration::milliseconds(100)
+        );
+
+        // Permits do not accumulate beyond the burst size.
+        let idle = now + Duration::seconds(60);
+        assert_eq!(policy.acquire("a.contoso.com", idle), Duration::ZERO);
+        assert_eq!(policy.acquire("a.contoso.com", idle), Duration::ZERO);
+        assert!(policy.acquire("a.contoso.com", idle).is_positive());
+    }
+
+    fn transport() -> Arc<dyn Policy> {
+        Arc::new(TransportPolicy::new(TransportOptions::new(Arc::new(
+            MockHttpClient::new(|_| {
+                async {
+                    Ok(RawResponse::from_bytes(
+                        StatusCode::Ok,
+                        Headers::new(),
+                        Bytes::new(),
+                    ))
+                }
+                .boxed()
+            }),
+        ))))
+    }
+
+    #[tokio::test]
+    async fn send_waits_for_permit() {
+        let transport = transport();
+        let policy = RateLimitPolicy::new(20, Duration::seconds(1)).with_burst(1);
+        let ctx = Context::new();
+
+        let start = OffsetDateTime::now_utc();
+        for _ in 0..3 {
+            let mut request = Request::new(
+                "https://contoso.blob.core.windows.net".parse().unwrap(),
+                Method::Get,
+            );
+            policy
+                .send(&ctx, &mut request, std::slice::from_ref(&transport))
+                .await
+                .expect("response");
+        }
+        assert!(OffsetDateTime::now_utc() - start >= Duration::milliseconds(90));
+    }
+
+    #[tokio::test]
+    async fn cancelled_wait_returns_permit() {
+        let policy = RateLimitPolicy::new(10, Duration::seconds(1)).with_burst(1);
+        let host = "contoso.blob.core.windows.net";
+        assert_eq!(
+            policy.acquire(host, OffsetDateTime::now_utc()),
+            Duration::ZERO
+        );
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let ctx = Context::new().with_value(token);
+        let mut request = Request::new(format!("https://{host}").parse().unwrap(), Method::Get);
+        let err = policy
+            .send(&ctx, &mut request, &[transport()])
+            .await
+            .expect_err("cancelled");
+        assert_eq!(err.kind(), &crate::error::ErrorKind::Cancelled);
+
+        // The next request waits only for the permit taken before, not for the cancelled request.
+        let wait = policy.acquire(host, OffsetDateTime::now_utc());
+        assert!(
+            wait.is_positive() && wait <= Duration::milliseconds(100),
+            "wait: {wait}"
+        );
+    }
+}
@@ -3,8 +3,24 @@
 
 //! Types and methods for Long-Running Operations (LROs).
 
-use crate::http::headers::Headers;
-use typespec_client_core::time::{Duration, OffsetDateTime};
+use crate::http::{
+    headers::Headers,
+    pager::{BoxedStream, Continuation},
+    response::Response,
+};
+use futures::{stream::unfold, Stream, TryStreamExt as _};
+use std::{
+    fmt,
+    future::{Future, IntoFuture},
+    pin::Pin,
+    task,
+};
+use typespec::Error;
+use typespec_client_core::{
+    http::JsonFormat,
+    sleep,
+    time::{Duration, OffsetDateTime},
+};
 
 /// Default retry time for long running operations if no retry-after header is present
 ///
@@ -13,7 +29,7 @@ use typespec_client_core::time::{Duration, OffsetDateTime};
 const DEFAULT_RETRY_TIME: Duration = Duration::seconds(30);
 
 /// Long-Running Operation (LRO) status.
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PollerStatus {
     InProgress,
     Succeeded,
@@ -41,8 +57,343 @@ impl From<&str> for PollerStatus {
 
 /// Get the retry duration from the operation response.
 pub fn get_retry_after(headers: &Headers) -> Duration {
-    crate::http::policies::get_retry_after(headers, OffsetDateTime::now_utc)
-        .unwrap_or(DEFAULT_RETRY_TIME)
+    get_retry_after_or(headers, DEFAULT_RETRY_TIME)
+}
+
+/// Get the retry duration from the operation response, or `default` if the response does not specify one.
+///
+/// Pass [`PollerOptions::frequency`](crate::http::PollerOptions::frequency) as the `default` so callers can configure how often to poll.
+pub fn get_retry_after_or(headers: &Headers, default: Duration) -> Duration {
+    crate::http::policies::get_retry_after(headers, OffsetDateTime::now_utc).unwrap_or(default)
+}
+
+/// The result of fetching the status of a long-running operation from a [`Poller`], whether the operation is still in progress or done.
+///
+/// The `T` type parameter of a [`Poller<T, F>`] is the type of both the status responses and the final result.
+pub enum PollerResult<T, N, F = JsonFormat> {
+    /// The operation is still in progress and the [`Poller`] should poll again using the `next` link/token after waiting `retry_after`.
+    InProgress {
+        response: Response<T, F>,
+        retry_after: Duration,
+        next: N,
+    },
+    /// The operation is done - successfully or not - and there is no need to poll any longer.
+    Done { response: Response<T, F> },
+    /// The operation succeeded and the [`Poller`] should fetch the final result by awaiting `target`.
+    ///
+    /// Use this when the final resource is not returned by the status monitor, e.g., when it must be fetched
+    /// from a location found using a [`FinalState`](location::FinalState).
+    /// The `target` response is yielded after the status `response` and is what awaiting the [`Poller`] returns.
+    Succeeded {
+        response: Response<T, F>,
+        target: BoxedFuture<crate::Result<Response<T, F>>>,
+    },
+}
+
+impl<T, N: fmt::Debug, F> fmt::Debug for PollerResult<T, N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InProgress {
+                retry_after, next, ..
+            } => f
+                .debug_struct("InProgress")
+                .field("retry_after", &retry_after)
+                .field("next", &next)
+                .finish_non_exhaustive(),
+            Self::Done { .. } => f.debug_struct("Done").finish_non_exhaustive(),
+            Self::Succeeded { .. } => f.debug_struct("Succeeded").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// A pinned, boxed [`Future`], such as the final result `target` of [`PollerResult::Succeeded`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// A pinned, boxed [`Future`], such as the final result `target` of [`PollerResult::Succeeded`].
+#[cfg(target_arch = "wasm32")]
+pub type BoxedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+/// Polls a long-running operation (LRO) until it is done.
+///
+/// You can asynchronously iterate over each status response returned by the service while the operation is in progress,
+/// or `await` the [`Poller<T, F>`] to wait until the operation is done and get the last status response,
+/// or the final result if the operation returned [`PollerResult::Succeeded`].
+/// The [`Poller<T, F>`] waits the duration specified by the service's `retry-after` header between each request.
+#[pin_project::pin_project]
+pub struct Poller<T, F = JsonFormat> {
+    #[pin]
+    stream: Pin<BoxedStream<Response<T, F>>>,
+    continuation: Continuation,
+}
+
+impl<T, F> Poller<T, F> {
+    /// Creates a [`Poller<T, F>`] from a callback that will be called repeatedly to request the status of an operation.
+    ///
+    /// This method expect a callback that accepts a single `Option<N>` parameter, and returns a [`PollerResult<T, N, F>`] value asynchronously.
+    /// The `N` type parameter is the type of the next link/token used to get the status of the operation. It may be any [`Send`]able type that can be referenced as a [`str`],
+    /// which is what [`Poller::continuation_token()`] will return to resume polling later.
+    ///
+    /// The first time your callback is called, it will be called with [`Option::None`], indicating the operation should be started.
+    /// Each subsequent time your callback is called, it will be called with the `next` value returned in [`PollerResult::InProgress`]
+    /// after waiting for the `retry_after` duration.
+    /// If your callback returns [`PollerResult::Succeeded`], the `target` is awaited to get the final result and your callback is not called again.
+    ///
+    /// Your callback must return one of:
+    /// * `Ok(result)` - The request succeeded, and the provided [`PollerResult`] indicates the value to return and if the operation is still in progress.
+    /// * `Err(..)` - The request failed. The error will be yielded to the stream, the stream will end, and the callback will not be called again.
+    ///
+    /// ## Examples
+    ///
+    /// To poll the status of an operation using the link returned in the `operation-location` header:
+    ///
+    /// ```rust,no_run
+    /// # use azure_core::{Result, http::{Context, Pipeline, RawResponse, Request, Response, Method, Url, poller::{self, location::{self, FinalState}, Poller, PollerResult, PollerStatus}}, json, time::Duration};
+    /// # let pipeline: Pipeline = panic!("Not a runnable example");
+    /// #[derive(serde::Deserialize)]
+    /// struct OperationStatus {
+    ///     status: String,
+    /// }
+    /// let url: Url = "https://example.com/my_operation".parse().unwrap();
+    /// let poller: Poller<OperationStatus> = Poller::from_callback(move |next_link: Option<Url>| {
+    ///     // The callback must be 'static, so you have to clone and move any values you want to use.
+    ///     let pipeline = pipeline.clone();
+    ///     let mut req = match next_link {
+    ///         Some(next_link) => Request::new(next_link, Method::Get),
+    ///         None => Request::new(url.clone(), Method::Post),
+    ///     };
+    ///     async move {
+    ///         let resp = pipeline
+    ///           .send(&Context::new(), &mut req)
+    ///           .await?;
+    ///         let (status, headers, body) = resp.deconstruct();
+    ///         let bytes = body.collect().await?;
+    ///         let result: OperationStatus = json::from_json(&bytes)?;
+    ///         let next_link = location::get_location(&headers, FinalState::OperationLocation)?;
+    ///         let retry_after = poller::get_retry_after_or(&headers, Duration::seconds(5));
+    ///         let resp: Response<OperationStatus> = RawResponse::from_bytes(status, headers, bytes).into();
+    ///         Ok(match (PollerStatus::from(result.status.as_str()), next_link) {
+    ///             (PollerStatus::InProgress, Some(next_link)) => PollerResult::InProgress {
+    ///                 response: resp,
+    ///                 retry_after,
+    ///                 next: next_link,
+    ///             },
+    ///             _ => PollerResult::Done { response: resp }
+    ///         })
+    ///     }
+    /// });
+    /// ```
+    pub fn from_callback<
+        // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
+        #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] C: Fn(Option<N>) -> Fut + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PollerResult<T, N, F>, typespec::Error>> + Send + 'static,
+        #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
+        #[cfg(target_arch = "wasm32")] C: Fn(Option<N>) -> Fut + 'static,
+        #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PollerResult<T, N, F>, typespec::Error>> + 'static,
+    >(
+        make_request: C,
+    ) -> Self
+    where
+        T: 'static,
+        F: 'static,
+    {
+        Self::from_callback_with_continuation(make_request, None)
+    }
+
+    /// Creates a [`Poller<T, F>`] from a callback that will be called repeatedly to request the status of an operation, starting with the given `continuation`.
+    ///
+    /// This is the same as [`Poller::from_callback()`] except that the first time your callback is called, it will be called with `continuation`.
+    /// Pass a value parsed from a [`Poller::continuation_token()`] saved earlier to resume polling an operation that was already started,
+    /// or [`None`] to start the operation.
+    pub fn from_callback_with_continuation<
+        // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
+        #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] C: Fn(Option<N>) -> Fut + Send + 'static,
+        #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PollerResult<T, N, F>, typespec::Error>> + Send + 'static,
+        #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
+        #[cfg(target_arch = "wasm32")] C: Fn(Option<N>) -> Fut + 'static,
+        #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PollerResult<T, N, F>, typespec::Error>> + 'static,
+    >(
+        make_request: C,
+        continuation: Option<N>,
+    ) -> Self
+    where
+        T: 'static,
+        F: 'static,
+    {
+        let tracker = Continuation::new(continuation.as_ref().map(AsRef::as_ref));
+        let stream = poll_from_callback(make_request, continuation, tracker.clone());
+        Self {
+            stream: Box::pin(stream),
+            continuation: tracker,
+        }
+    }
+
+    /// Creates a [`Poller<T, F>`] from a raw stream of [`Result<Response<T, F>>`](typespec::Result) values.
+    ///
+    /// This constructor is used when you are implementing a completely custom stream and want to use it as a poller.
+    /// The stream should end after yielding the final status response.
+    pub fn from_stream<
+        // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
+        #[cfg(not(target_arch = "wasm32"))] S: Stream<Item = Result<Response<T, F>, Error>> + Send + 'static,
+        #[cfg(target_arch = "wasm32")] S: Stream<Item = Result<Response<T, F>, Error>> + 'static,
+    >(
+        stream: S,
+    ) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            continuation: Continuation::default(),
+        }
+    }
+
+    /// Gets the continuation token that can be used to resume polling the operation.
+    ///
+    /// Save this value and pass it to the client method's [`PollerOptions::continuation_token`](crate::http::PollerOptions::continuation_token),
+    /// or parse it and pass it to [`Poller::from_callback_with_continuation()`], to resume polling later even in a different process.
+    ///
+    /// While the final result of a [`PollerResult::Succeeded`] operation is being fetched, this refers to the last status,
+    /// so resuming polls the status again before fetching the final result.
+    ///
+    /// Returns [`None`] if the operation has not yet been started, if the operation is done,
+    /// or if this [`Poller<T, F>`] was created using [`Poller::from_stream()`].
+    pub fn continuation_token(&self) -> Option<String> {
+        self.continuation.get()
+    }
+}
+
+impl<T, F> futures::Stream for Poller<T, F> {
+    type Item = Result<Response<T, F>, Error>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        self.project().stream.poll_next(cx)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + 'static, F: Send + 'static> IntoFuture for Poller<T, F> {
+    type Output = crate::Result<Response<T, F>>;
+    type IntoFuture = BoxedFuture<Self::Output>;
+
+    /// Waits until the operation is done and returns the final status response.
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(wait(self))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: 'static, F: 'static> IntoFuture for Poller<T, F> {
+    type Output = crate::Result<Response<T, F>>;
+    type IntoFuture = BoxedFuture<Self::Output>;
+
+    /// Waits until the operation is done and returns the final status response.
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(wait(self))
+    }
+}
+
+impl<T, F> fmt::Debug for Poller<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
+
+async fn wait<T, F>(mut poller: Poller<T, F>) -> crate::Result<Response<T, F>> {
+    let mut last = None;
+    while let Some(response) = poller.try_next().await? {
+        last = Some(response);
+    }
+    last.ok_or_else(|| {
+        Error::message(
+            crate::error::ErrorKind::Other,
+            "long-running operation did not return a status",
+        )
+    })
+}
+
+enum State<N, T, F> {
+    Init,
+    More(N, Duration),
+    Final(BoxedFuture<crate::Result<Response<T, F>>>),
+    Done,
+}
+
+fn poll_from_callback<
+    T,
+    F,
+    // This is a bit gnarly, but the only thing that differs between the WASM/non-WASM configs is the presence of Send bounds.
+    #[cfg(not(target_arch = "wasm32"))] N: AsRef<str> + Send + 'static,
+    #[cfg(not(target_arch = "wasm32"))] C: Fn(Option<N>) -> Fut + Send + 'static,
+    #[cfg(not(target_arch = "wasm32"))] Fut: Future<Output = Result<PollerResult<T, N, F>, typespec::Error>> + Send + 'static,
+    #[cfg(target_arch = "wasm32")] N: AsRef<str> + 'static,
+    #[cfg(target_arch = "wasm32")] C: Fn(Option<N>) -> Fut + 'static,
+    #[cfg(target_arch = "wasm32")] Fut: Future<Output = Result<PollerResult<T, N, F>, typespec::Error>> + 'static,
+>(
+    make_request: C,
+    continuation: Option<N>,
+    tracker: Continuation,
+) -> impl Stream<Item = Result<Response<T, F>, Error>> + 'static
+where
+    T: 'static,
+    F: 'static,
+{
+    let state = match continuation {
+        // Resume polling immediately.
+        Some(continuation) => State::More(continuation, Duration::ZERO),
+        None => State::Init,
+    };
+    unfold(
+        // We flow the `make_request` callback through the state value so that we can avoid cloning.
+        (state, make_request),
+        move |(state, make_request)| {
+            let tracker = tracker.clone();
+            async move {
+                let result = match state {
+                    State::Init => make_request(None).await,
+                    State::More(c, retry_after) => {
+                        if retry_after.is_positive() {
+                            sleep(retry_after).await;
+                        }
+                        make_request(Some(c)).await
+                    }
+                    State::Final(target) => {
+                        let item = target.await;
+                        if item.is_ok() {
+                            tracker.set(None);
+                        }
+                        return Some((item, (State::Done, make_request)));
+                    }
+                    State::Done => return None,
+                };
+                let (item, next_state) = match result {
+                    // Leave the continuation token referring to the last status so polling may be resumed.
+                    Err(e) => return Some((Err(e), (State::Done, make_request))),
+                    Ok(PollerResult::InProgress {
+                        response,
+                        retry_after,
+                        next,
+                    }) => {
+                        tracker.set(Some(next.as_ref()));
+                        (Ok(response), State::More(next, retry_after))
+                    }
+                    Ok(PollerResult::Done { response }) => {
+                        tracker.set(None);
+                        (Ok(response), State::Done)
+                    }
+                    // Leave the continuation token referring to the last status until the final result is fetched.
+                    Ok(PollerResult::Succeeded { response, target }) => {
+                        (Ok(response), State::Final(target))
+                    }
+                };
+
+                // Flow 'make_request' through to avoid cloning
+                Some((item, (next_state, make_request)))
+            }
+        },
+    )
 }
 
 /// Types and methods for getting Long-Running Operation (LRO) resource locations.
@@ -139,3 +490,184 @@ pub mod body_content {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{headers::Headers, RawResponse, StatusCode};
+    use futures::StreamExt as _;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    struct Operation {
+        status: String,
+    }
+
+    fn operation_response(status: &str) -> Response<Operation> {
+        RawResponse::from_bytes(
+            StatusCode::Ok,
+            Headers::new(),
+            format!(r#"{{"status":"{status}"}}"#),
+        )
+        .into()
+    }
+
+    async fn get_status(next: Option<String>) -> typespec::Result<PollerResult<Operation, String>> {
+        match next.as_deref() {
+            None => Ok(PollerResult::InProgress {
+                response: operation_response("NotStarted"),
+                retry_after: Duration::milliseconds(1),
+                next: "1".into(),
+            }),
+            Some("1") => Ok(PollerResult::InProgress {
+                response: operation_response("InProgress"),
+                retry_after: Duration::milliseconds(1),
+                next: "2".into(),
+            }),
+            Some("2") => Ok(PollerResult::Done {
+                response: operation_response("Succeeded"),
+            }),
+            _ => {
+                panic!("Unexpected next value")
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn callback_status_stream() {
+        let mut poller: Poller<Operation> = Poller::from_callback(get_status);
+        assert_eq!(None, poller.continuation_token());
+
+        let mut statuses = Vec::new();
+        while let Some(response) = poller.next().await {
+            let operation = response.unwrap().into_body().await.unwrap();
+            statuses.push((operation.status, poller.continuation_token()));
+        }
+        assert_eq!(
+            vec![
+                ("NotStarted".to_string(), Some("1".to_string())),
+                ("InProgress".to_string(), Some("2".to_string())),
+                ("Succeeded".to_string(), None),
+            ],
+            statuses
+        );
+    }
+
+    #[tokio::test]
+    async fn callback_await_final_status() {
+        let poller: Poller<Operation> = Poller::from_callback(get_status);
+        let operation = poller.await.unwrap().into_body().await.unwrap();
+        assert_eq!("Succeeded", operation.status);
+    }
+
+    #[tokio::test]
+    async fn callback_resumed() {
+        let poller: Poller<Operation> =
+            Poller::from_callback_with_continuation(get_status, Some("2".into()));
+        assert_eq!(Some("2".to_string()), poller.continuation_token());
+
+        let statuses: Vec<String> = poller
+            .then(|response| async move { response.unwrap().into_body().await.unwrap().status })
+            .collect()
+            .await;
+        assert_eq!(vec!["Succeeded".to_string()], statuses);
+    }
+
+    #[tokio::test]
+    async fn callback_error() {
+        let poller: Poller<Operation> = Poller::from_callback(|next: Option<String>| async move {
+            match next.as_deref() {
+                Some("1") => Err(typespec::Error::message(
+                    typespec::error::ErrorKind::Other,
+                    "yon request didst fail",
+                )),
+                _ => get_status(next).await,
+            }
+        });
+
+        let err = poller.await.unwrap_err();
+        assert_eq!(&typespec::error::ErrorKind::Other, err.kind());
+        assert_eq!("yon request didst fail", format!("{}", err));
+    }
+
+    async fn get_status_with_final_state(
+        next: Option<String>,
+    ) -> typespec::Result<PollerResult<Operation, String>> {
+        match next.as_deref() {
+            Some("2") => Ok(PollerResult::Succeeded {
+                response: operation_response("Succeeded"),
+                target: Box::pin(async { Ok(operation_response("Final")) }),
+            }),
+            _ => get_status(next).await,
+        }
+    }
+
+    #[tokio::test]
+    async fn callback_final_state_stream() {
+        let mut poller: Poller<Operation> = Poller::from_callback(get_status_with_final_state);
+
+        let mut statuses = Vec::new();
+        while let Some(response) = poller.next().await {
+            let operation = response.unwrap().into_body().await.unwrap();
+            statuses.push((operation.status, poller.continuation_token()));
+        }
+        assert_eq!(
+            vec![
+                ("NotStarted".to_string(), Some("1".to_string())),
+                ("InProgress".to_string(), Some("2".to_string())),
+                ("Succeeded".to_string(), Some("2".to_string())),
+                ("Final".to_string(), None),
+            ],
+            statuses
+        );
+    }
+
+    #[tokio::test]
+    async fn callback_await_final_state() {
+        let poller: Poller<Operation> = Poller::from_callback(get_status_with_final_state);
+        let operation = poller.await.unwrap().into_body().await.unwrap();
+        assert_eq!("Final", operation.status);
+    }
+
+    #[tokio::test]
+    async fn callback_final_state_error() {
+        let mut poller: Poller<Operation> =
+            Poller::from_callback(|next: Option<String>| async move {
+                match next.as_deref() {
+                    Some("2") => Ok(PollerResult::Succeeded {
+                        response: operation_response("Succeeded"),
+                        target: Box::pin(async {
+                            Err(typespec::Error::message(
+                                typespec::error::ErrorKind::Other,
+                                "final result not found",
+                            ))
+                        }),
+                    }),
+                    _ => get_status(next).await,
+                }
+            });
+
+        let mut last = None;
+        while let Some(response) = poller.next().await {
+            last = Some(response);
+        }
+        let err = last.unwrap().unwrap_err();
+        assert_eq!("final result not found", format!("{}", err));
+        assert_eq!(Some("2".to_string()), poller.continuation_token());
+    }
+
+    #[test]
+    fn retry_after_or_default() {
+        let mut headers = Headers::new();
+        assert_eq!(
+            Duration::seconds(5),
+            get_retry_after_or(&headers, Duration::seconds(5))
+        );
+
+        headers.insert("retry-after", "10");
+        assert_eq!(
+            Duration::seconds(10),
+            get_retry_after_or(&headers, Duration::seconds(5))
+        );
+    }
+}
@@ -22,14 +22,24 @@ pub use constants::*;
 
 // Re-export modules in typespec_client_core such that azure_core-based crates don't need to reference it directly.
 pub use typespec_client_core::{
-    async_runtime, base64, create_enum, create_extensible_enum,
+    async_runtime, base64, cancellation, create_enum, create_extensible_enum,
     error::{self, Error, Result},
     fmt, json, sleep, stream, time, Bytes, Uuid,
 };
 
+pub mod metrics {
+    pub use typespec_client_core::metrics::*;
+}
+
 pub mod tracing {
     pub use typespec_client_core::tracing::*;
 }
 
+#[cfg(feature = "cbor")]
+pub use typespec_client_core::cbor;
+
+#[cfg(feature = "msgpack")]
+pub use typespec_client_core::msgpack;
+
 #[cfg(feature = "xml")]
 pub use typespec_client_core::xml;
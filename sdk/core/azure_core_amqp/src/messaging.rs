@@ -1165,6 +1165,25 @@ impl AmqpMessage {
         }
     }
 
+    /// Adds an application property to the message.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the application property to add.
+    /// * `value` - The value of the application property to add.
+    ///
+    /// # Examples
+    /// ```
+    /// use azure_core_amqp::AmqpMessage;
+    /// let mut message = AmqpMessage::default();
+    /// message.add_application_property("key".to_string(), "value");
+    /// ```
+    ///
+    pub fn add_application_property(&mut self, key: String, value: impl Into<AmqpSimpleValue>) {
+        self.application_properties
+            .get_or_insert_with(AmqpApplicationProperties::new)
+            .insert(key, value);
+    }
+
     /// Replaces the message body on an existing message.
     ///
     /// This will overwrite any existing message body on the message.
@@ -1,16 +1,18 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-//! Azure Core OpenTelemetry tracing integration.
+//! Azure Core OpenTelemetry tracing and metrics integration.
 //!
-//! This crate provides OpenTelemetry distributed tracing support for the Azure SDK for Rust.
+//! This crate provides OpenTelemetry distributed tracing and client metrics support for the Azure SDK for Rust.
 //! It bridges the standardized typespec_client_core tracing traits with OpenTelemetry implementation,
 //! enabling automatic span creation, context propagation, and telemetry collection for Azure services.
 
 mod attributes;
+mod meter;
 mod span;
 mod telemetry;
 mod tracer;
 
 // Re-export the main types for convenience
+pub use meter::OpenTelemetryMeterProvider;
 pub use telemetry::OpenTelemetryTracerProvider;
@@ -0,0 +1,162 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! OpenTelemetry implementation of typespec_client_core metrics traits.
+
+use crate::attributes::AttributeValue as ConversionAttributeValue;
+use azure_core::metrics::{Attribute, Counter, Histogram, Meter, MeterProvider};
+use opentelemetry::{InstrumentationScope, KeyValue};
+use std::sync::Arc;
+
+/// An OpenTelemetry [`MeterProvider`] to record client metrics for Azure service clients.
+pub struct OpenTelemetryMeterProvider {
+    inner: Arc<dyn opentelemetry::metrics::MeterProvider + Send + Sync>,
+}
+
+impl OpenTelemetryMeterProvider {
+    /// Creates a new Azure meter provider with the given SDK meter provider.
+    pub fn new(
+        provider: Arc<dyn opentelemetry::metrics::MeterProvider + Send + Sync>,
+    ) -> azure_core::Result<Self> {
+        Ok(Self { inner: provider })
+    }
+}
+
+impl MeterProvider for OpenTelemetryMeterProvider {
+    fn get_meter(
+        &self,
+        name: &'static str,
+        package_version: &'static str,
+    ) -> Box<dyn Meter + Send + Sync> {
+        let scope = InstrumentationScope::builder(name)
+            .with_version(package_version)
+            .build();
+        Box::new(OpenTelemetryMeter {
+            inner: self.inner.meter_with_scope(scope),
+        })
+    }
+}
+
+struct OpenTelemetryMeter {
+    inner: opentelemetry::metrics::Meter,
+}
+
+impl Meter for OpenTelemetryMeter {
+    fn create_histogram(
+        &self,
+        name: &'static str,
+        description: &'static str,
+        unit: &'static str,
+    ) -> Arc<dyn Histogram + Send + Sync> {
+        Arc::new(OpenTelemetryHistogram(
+            self.inner
+                .f64_histogram(name)
+                .with_description(description)
+                .with_unit(unit)
+                .build(),
+        ))
+    }
+
+    fn create_counter(
+        &self,
+        name: &'static str,
+        description: &'static str,
+        unit: &'static str,
+    ) -> Arc<dyn Counter + Send + Sync> {
+        Arc::new(OpenTelemetryCounter(
+            self.inner
+                .u64_counter(name)
+                .with_description(description)
+                .with_unit(unit)
+                .build(),
+        ))
+    }
+}
+
+struct OpenTelemetryHistogram(opentelemetry::metrics::Histogram<f64>);
+
+impl Histogram for OpenTelemetryHistogram {
+    fn record(&self, value: f64, attributes: &[Attribute]) {
+        self.0.record(value, &key_values(attributes));
+    }
+}
+
+struct OpenTelemetryCounter(opentelemetry::metrics::Counter<u64>);
+
+impl Counter for OpenTelemetryCounter {
+    fn add(&self, value: u64, attributes: &[Attribute]) {
+        self.0.add(value, &key_values(attributes));
+    }
+}
+
+fn key_values(attributes: &[Attribute]) -> Vec<KeyValue> {
+    attributes
+        .iter()
+        .map(|(key, value)| {
+            KeyValue::new(
+                *key,
+                opentelemetry::Value::from(ConversionAttributeValue(value.clone())),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::tracing::AttributeValue;
+    use opentelemetry_sdk::metrics::{
+        data::{AggregatedMetrics, MetricData},
+        InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+    };
+
+    #[test]
+    fn records_metrics() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let meter_provider = OpenTelemetryMeterProvider::new(Arc::new(provider.clone())).unwrap();
+        let meter = meter_provider.get_meter("test_meter", "1.0.0");
+
+        let attributes = [(
+            "server.address",
+            AttributeValue::String("contoso.com".into()),
+        )];
+        meter
+            .create_histogram("test.duration", "Test duration.", "s")
+            .record(0.5, &attributes);
+        meter
+            .create_counter("test.count", "Test count.", "{request}")
+            .add(2, &attributes);
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let scope_metrics: Vec<_> = metrics[0].scope_metrics().collect();
+        assert_eq!(scope_metrics[0].scope().name(), "test_meter");
+        let metrics: Vec<_> = scope_metrics[0].metrics().collect();
+        assert_eq!(metrics.len(), 2);
+
+        let histogram = metrics
+            .iter()
+            .find(|m| m.name() == "test.duration")
+            .unwrap();
+        assert_eq!(histogram.unit(), "s");
+        let AggregatedMetrics::F64(MetricData::Histogram(data)) = histogram.data() else {
+            panic!("expected an f64 histogram");
+        };
+        let point = data.data_points().next().unwrap();
+        assert_eq!(point.count(), 1);
+        assert_eq!(point.sum(), 0.5);
+        assert_eq!(
+            point.attributes().next(),
+            Some(&KeyValue::new("server.address", "contoso.com"))
+        );
+
+        let counter = metrics.iter().find(|m| m.name() == "test.count").unwrap();
+        let AggregatedMetrics::U64(MetricData::Sum(data)) = counter.data() else {
+            panic!("expected a u64 sum");
+        };
+        assert_eq!(data.data_points().next().unwrap().value(), 2);
+    }
+}
@@ -5,7 +5,7 @@
 
 use crate::attributes::AttributeValue as ConversionAttributeValue;
 use azure_core::{
-    tracing::{AsAny, AttributeValue, Span, SpanGuard, SpanStatus},
+    tracing::{AsAny, AttributeValue, Span, SpanGuard, SpanStatus, TraceContext},
     Result,
 };
 use opentelemetry::trace::TraceContextExt;
@@ -50,6 +50,18 @@ impl Span for OpenTelemetrySpan {
         self.context.span().span_context().span_id().to_bytes()
     }
 
+    fn trace_context(&self) -> TraceContext {
+        let span = self.context.span();
+        let span_context = span.span_context();
+        let trace_state = span_context.trace_state().header();
+        TraceContext {
+            trace_id: span_context.trace_id().to_bytes(),
+            span_id: span_context.span_id().to_bytes(),
+            sampled: span_context.is_sampled(),
+            trace_state: (!trace_state.is_empty()).then_some(trace_state),
+        }
+    }
+
     fn set_attribute(&self, key: &'static str, value: AttributeValue) -> Result<()> {
         let otel_value = opentelemetry::Value::from(ConversionAttributeValue(value));
         self.context
@@ -3,12 +3,15 @@
 
 use crate::span::{OpenTelemetrySpan, OpenTelemetrySpanKind};
 use azure_core::{
-    tracing::{SpanKind, Tracer},
+    tracing::{SpanKind, TraceContext, Tracer},
     Result,
 };
 use opentelemetry::{
     global::BoxedTracer,
-    trace::{TraceContextExt, Tracer as OpenTelemetryTracerTrait},
+    trace::{
+        Link, SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        Tracer as OpenTelemetryTracerTrait,
+    },
     Context,
 };
 use std::sync::Arc;
@@ -76,14 +79,51 @@ impl Tracer for OpenTelemetryTracer {
 
         Ok(OpenTelemetrySpan::new(context.with_span(span)))
     }
+
+    fn start_span_with_links(
+        &self,
+        name: &'static str,
+        kind: SpanKind,
+        links: &[TraceContext],
+    ) -> Result<Arc<dyn azure_core::tracing::Span + Send + Sync>> {
+        let links = links
+            .iter()
+            .filter(|link| link.is_valid())
+            .map(|link| {
+                let trace_state = link
+                    .trace_state
+                    .as_deref()
+                    .and_then(|state| state.parse().ok())
+                    .unwrap_or_else(TraceState::default);
+                Link::with_context(SpanContext::new(
+                    TraceId::from_bytes(link.trace_id),
+                    SpanId::from_bytes(link.span_id),
+                    if link.sampled {
+                        TraceFlags::SAMPLED
+                    } else {
+                        TraceFlags::default()
+                    },
+                    true,
+                    trace_state,
+                ))
+            })
+            .collect();
+        let span_builder = opentelemetry::trace::SpanBuilder::from_name(name)
+            .with_kind(OpenTelemetrySpanKind(kind).into())
+            .with_links(links);
+        let context = Context::current();
+        let span = self.inner.build_with_context(span_builder, &context);
+
+        Ok(OpenTelemetrySpan::new(context.with_span(span)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::telemetry::OpenTelemetryTracerProvider;
-    use azure_core::tracing::{SpanKind, TracerProvider};
+    use azure_core::tracing::{SpanKind, TraceContext, TracerProvider};
     use opentelemetry::trace::noop::NoopTracerProvider;
-    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
     use std::sync::Arc;
 
     #[test]
@@ -109,4 +149,42 @@ mod tests {
         let tracer = otel_provider.get_tracer("test_tracer", "1.0.0");
         let _span = tracer.start_span("test_span", SpanKind::Internal);
     }
+
+    #[test]
+    fn test_start_span_with_links() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let otel_provider = OpenTelemetryTracerProvider::new(Arc::new(provider)).unwrap();
+        let tracer = otel_provider.get_tracer("test_tracer", "1.0.0");
+
+        let sender = tracer.start_span("send", SpanKind::Producer).unwrap();
+        let trace_context = sender.trace_context();
+        assert!(trace_context.is_valid());
+        assert!(trace_context.sampled);
+        sender.end().unwrap();
+
+        let receiver = tracer
+            .start_span_with_links(
+                "receive",
+                SpanKind::Consumer,
+                &[trace_context.clone(), TraceContext::default()],
+            )
+            .unwrap();
+        receiver.end().unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 2);
+        let links = &spans[1].links.links;
+        assert_eq!(links.len(), 1);
+        assert_eq!(
+            links[0].span_context.trace_id().to_bytes(),
+            trace_context.trace_id
+        );
+        assert_eq!(
+            links[0].span_context.span_id().to_bytes(),
+            trace_context.span_id
+        );
+    }
 }
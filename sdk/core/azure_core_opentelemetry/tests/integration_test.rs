@@ -1,11 +1,22 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use azure_core::tracing::{SpanKind, TracerProvider};
+use azure_core::{
+    http::{
+        headers::Headers, policies::PublicApiInstrumentationInformation, ClientOptions, Context,
+        InstrumentationOptions, Method, Pipeline, RawResponse, Request, StatusCode,
+        TransportOptions,
+    },
+    tracing::{SpanKind, TracerProvider},
+    Bytes,
+};
 use azure_core_opentelemetry::OpenTelemetryTracerProvider;
-use opentelemetry_sdk::trace::SdkTracerProvider;
+use azure_core_test::http::MockHttpClient;
+use futures::FutureExt;
+use opentelemetry::{trace::SpanKind as OpenTelemetrySpanKind, KeyValue};
+use opentelemetry_sdk::trace::{in_memory_exporter::InMemorySpanExporter, SdkTracerProvider};
 use std::error::Error;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[tokio::test]
 async fn test_span_creation() -> Result<(), Box<dyn Error>> {
@@ -80,3 +91,98 @@ async fn test_span_attributes() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_pipeline_spans() -> Result<(), Box<dyn Error>> {
+    let exporter = InMemorySpanExporter::default();
+    let sdk_provider = Arc::new(
+        SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build(),
+    );
+    let azure_provider = OpenTelemetryTracerProvider::new(sdk_provider)?;
+
+    let traceparent = Arc::new(Mutex::new(None));
+    let sent_traceparent = traceparent.clone();
+    let transport = TransportOptions::new(Arc::new(MockHttpClient::new(move |req| {
+        *sent_traceparent.lock().unwrap() =
+            req.headers().get_optional_string(&"traceparent".into());
+        async {
+            let mut headers = Headers::new();
+            headers.insert("x-ms-request-id", "request-id");
+            Ok(RawResponse::from_bytes(
+                StatusCode::Ok,
+                headers,
+                Bytes::new(),
+            ))
+        }
+        .boxed()
+    })));
+    let options = ClientOptions {
+        transport: Some(transport),
+        instrumentation: Some(InstrumentationOptions {
+            tracer_provider: Some(Arc::new(azure_provider)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let pipeline = Pipeline::new(
+        Some("test_crate"),
+        Some("1.0.0"),
+        options,
+        Vec::new(),
+        Vec::new(),
+    );
+
+    let ctx = Context::new().with_value(PublicApiInstrumentationInformation {
+        api_name: "TestClient.get",
+    });
+    let mut request = Request::new(
+        "https://contoso.com/path?api-version=2025-01-01&sig=secret".parse()?,
+        Method::Get,
+    );
+    pipeline.send(&ctx, &mut request).await?;
+
+    let spans = exporter.get_finished_spans()?;
+    assert_eq!(spans.len(), 2);
+
+    let public_span = spans.iter().find(|s| s.name == "TestClient.get").unwrap();
+    assert_eq!(public_span.span_kind, OpenTelemetrySpanKind::Internal);
+    assert_eq!(public_span.instrumentation_scope.name(), "test_crate");
+
+    let request_span = spans.iter().find(|s| s.name == "GET").unwrap();
+    assert_eq!(request_span.span_kind, OpenTelemetrySpanKind::Client);
+    assert_eq!(
+        request_span.parent_span_id,
+        public_span.span_context.span_id()
+    );
+    for attribute in [
+        KeyValue::new("http.request.method", "GET"),
+        KeyValue::new(
+            "url.full",
+            "https://contoso.com/path?api-version=2025-01-01&sig=REDACTED",
+        ),
+        KeyValue::new("http.response.status_code", 200),
+        KeyValue::new("az.service_request_id", "request-id"),
+    ] {
+        assert!(
+            request_span.attributes.contains(&attribute),
+            "missing {attribute:?}"
+        );
+    }
+
+    let span_context = &request_span.span_context;
+    assert_eq!(
+        traceparent.lock().unwrap().as_deref(),
+        Some(
+            format!(
+                "00-{}-{}-01",
+                span_context.trace_id(),
+                span_context.span_id()
+            )
+            .as_str()
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,542 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use async_trait::async_trait;
+use azure_core::{
+    error::ErrorKind,
+    http::{
+        headers::{HeaderName, HeaderValue, Headers},
+        request::Request,
+        Body, HttpClient, Method, RawResponse, StatusCode, Url,
+    },
+    Bytes, Error, Result,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    fmt::{self, Write as _},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// An [`HttpClient`] that returns scripted responses for requests matching registered [`Expectation`]s.
+///
+/// Each request is matched against expectations in the order they were registered. The first expectation
+/// that matches and has not already been called as many [`times`](Expectation::times) as expected returns its next response.
+/// A request that matches no expectation returns an error with [`ErrorKind::MockFramework`] and is recorded as unexpected.
+///
+/// Call [`MockTransport::verify()`] to assert that every expectation was called as expected and no unexpected requests were sent.
+/// This is also checked when the last clone of the `MockTransport` is dropped, including any clone passed to a client.
+///
+/// # Examples
+///
+/// ```
+/// use azure_core::http::{ClientOptions, Method, StatusCode, TransportOptions};
+/// use azure_core_test::http::{Expectation, MockResponse, MockTransport};
+/// use azure_identity::DefaultAzureCredential;
+/// use azure_security_keyvault_secrets::{SecretClient, SecretClientOptions};
+/// use serde_json::json;
+/// use std::sync::Arc;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let transport = MockTransport::new();
+/// transport.expect(
+///     Expectation::new(Method::Get, "https://my-vault.vault.azure.net/secrets/*")
+///         .with_query("api-version", "7.6")
+///         .respond(MockResponse::json(StatusCode::Ok, &json!({ "value": "secret" }))),
+/// );
+///
+/// let options = SecretClientOptions {
+///     client_options: ClientOptions {
+///         transport: Some(TransportOptions::new(Arc::new(transport.clone()))),
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// let client = SecretClient::new(
+///     "https://my-vault.vault.azure.net",
+///     DefaultAzureCredential::new()?,
+///     Some(options),
+/// );
+/// # transport.verify_on_drop(false);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    expectations: Vec<Arc<Mutex<Expectation>>>,
+    unexpected: Vec<String>,
+    verify_on_drop: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            expectations: Vec::new(),
+            unexpected: Vec::new(),
+            verify_on_drop: true,
+        }
+    }
+}
+
+impl MockTransport {
+    /// Creates a new `MockTransport` with no expectations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an [`Expectation`] and returns an [`ExpectationHandle`] to get the number of requests it matched.
+    pub fn expect(&self, expectation: Expectation) -> ExpectationHandle {
+        let expectation = Arc::new(Mutex::new(expectation));
+        self.lock().expectations.push(expectation.clone());
+        ExpectationHandle(expectation)
+    }
+
+    /// Sets whether to [`verify`](MockTransport::verify) when the last clone of this `MockTransport` is dropped.
+    ///
+    /// The default is `true`. Verification is always skipped if the thread is already panicking.
+    pub fn verify_on_drop(&self, verify: bool) {
+        self.lock().verify_on_drop = verify;
+    }
+
+    /// Asserts that every expectation was called as expected and no unexpected requests were sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a description of each unmet expectation and unexpected request.
+    #[track_caller]
+    pub fn verify(&self) {
+        if let Err(message) = self.lock().check() {
+            panic!("{message}");
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl State {
+    fn check(&self) -> std::result::Result<(), String> {
+        let mut message = String::new();
+        for expectation in &self.expectations {
+            let expectation = expectation.lock().unwrap_or_else(|err| err.into_inner());
+            let satisfied = match expectation.times {
+                Some(times) => expectation.calls == times,
+                None => expectation.calls > 0,
+            };
+            if !satisfied {
+                let expected = expectation
+                    .times
+                    .map_or_else(|| "at least 1".to_string(), |times| times.to_string());
+                let _ = writeln!(
+                    message,
+                    "expected {expected} request(s) matching {expectation} but got {}",
+                    expectation.calls
+                );
+            }
+        }
+        for request in &self.unexpected {
+            let _ = writeln!(message, "unexpected request {request}");
+        }
+
+        if message.is_empty() {
+            return Ok(());
+        }
+        Err(message)
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        if !self.verify_on_drop || std::thread::panicking() {
+            return;
+        }
+        if let Err(message) = self.check() {
+            panic!("{message}");
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl HttpClient for MockTransport {
+    async fn execute_request(&self, request: &Request) -> Result<RawResponse> {
+        let mut state = self.lock();
+        for expectation in &state.expectations {
+            let mut expectation = expectation.lock().unwrap_or_else(|err| err.into_inner());
+            if expectation.is_saturated() || !expectation.matches(request) {
+                continue;
+            }
+            let index = expectation
+                .calls
+                .min(expectation.responses.len().saturating_sub(1));
+            expectation.calls += 1;
+            return expectation.responses.get(index).map_or_else(
+                || MockResponse::default().to_response(),
+                MockResponse::to_response,
+            );
+        }
+
+        let description = format!("{} {}", request.method(), request.url());
+        state.unexpected.push(description.clone());
+        Err(Error::with_message(ErrorKind::MockFramework, || {
+            format!("no expectation matched request {description}")
+        }))
+    }
+}
+
+/// A handle to an [`Expectation`] registered with a [`MockTransport`].
+#[derive(Clone, Debug)]
+pub struct ExpectationHandle(Arc<Mutex<Expectation>>);
+
+impl ExpectationHandle {
+    /// The number of requests that matched the expectation.
+    pub fn calls(&self) -> usize {
+        self.0.lock().unwrap_or_else(|err| err.into_inner()).calls
+    }
+}
+
+/// A request expected by a [`MockTransport`], and the responses to return in order.
+///
+/// The `url` pattern is matched against the URL without its query string, and `*` matches any characters.
+/// Query parameters, headers, and the body are only matched if specified.
+/// If no response is specified, an empty `200 OK` response is returned.
+#[derive(Clone, Debug)]
+pub struct Expectation {
+    method: Method,
+    url: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(HeaderName, String)>,
+    body: Option<BodyMatcher>,
+    responses: Vec<MockResponse>,
+    times: Option<usize>,
+    calls: usize,
+}
+
+#[derive(Clone, Debug)]
+enum BodyMatcher {
+    Bytes(Bytes),
+    Json(Value),
+}
+
+impl Expectation {
+    /// Creates an expectation of a request with the given `method` and `url` pattern.
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: None,
+            responses: Vec::new(),
+            times: None,
+            calls: 0,
+        }
+    }
+
+    /// Matches only requests with the query parameter `name` set to `value`.
+    #[must_use]
+    pub fn with_query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Matches only requests with the header `name` set to `value`.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<HeaderName>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Matches only requests with exactly the given `body`.
+    #[must_use]
+    pub fn with_body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = Some(BodyMatcher::Bytes(body.into()));
+        self
+    }
+
+    /// Matches only requests with a JSON body equal to `body` when serialized, regardless of formatting or the order of properties.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `body` cannot be serialized to JSON.
+    #[must_use]
+    pub fn with_json_body<T: Serialize>(mut self, body: &T) -> Self {
+        let body = serde_json::to_value(body).expect("serialize expected body");
+        self.body = Some(BodyMatcher::Json(body));
+        self
+    }
+
+    /// Adds a `response` to return. Responses are returned in the order added, and the last response is repeated.
+    #[must_use]
+    pub fn respond(mut self, response: MockResponse) -> Self {
+        self.responses.push(response);
+        self
+    }
+
+    /// Matches exactly `times` requests, after which matching requests are checked against later expectations.
+    #[must_use]
+    pub fn times(self, times: usize) -> Self {
+        Self {
+            times: Some(times),
+            ..self
+        }
+    }
+
+    fn is_saturated(&self) -> bool {
+        self.times.is_some_and(|times| self.calls >= times)
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        if *request.method() != self.method {
+            return false;
+        }
+
+        let url = request.url();
+        if !glob(&self.url, &url[..url::Position::AfterPath]) {
+            return false;
+        }
+        if !self
+            .query
+            .iter()
+            .all(|(name, value)| has_query(url, name, value))
+        {
+            return false;
+        }
+        if !self
+            .headers
+            .iter()
+            .all(|(name, value)| request.headers().get_optional_str(name) == Some(value.as_str()))
+        {
+            return false;
+        }
+
+        match (&self.body, request.body()) {
+            (None, _) => true,
+            (Some(BodyMatcher::Bytes(expected)), Body::Bytes(actual)) => expected == actual,
+            (Some(BodyMatcher::Json(expected)), Body::Bytes(actual)) => {
+                serde_json::from_slice::<Value>(actual).is_ok_and(|actual| actual == *expected)
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            (Some(_), Body::SeekableStream(_)) => false,
+        }
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        for (name, value) in &self.query {
+            write!(f, " {name}={value}")?;
+        }
+        for (name, value) in &self.headers {
+            write!(f, " {}: {value}", name.as_str())?;
+        }
+        if self.body.is_some() {
+            f.write_str(" with body")?;
+        }
+        Ok(())
+    }
+}
+
+fn has_query(url: &Url, name: &str, value: &str) -> bool {
+    url.query_pairs().any(|(k, v)| k == name && v == value)
+}
+
+/// Whether `text` matches the `pattern`, in which `*` matches any characters.
+fn glob(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcards, so the pattern must match exactly.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// A scripted response returned by a [`MockTransport`] for an [`Expectation`].
+#[derive(Clone, Debug)]
+pub struct MockResponse(std::result::Result<(StatusCode, Headers, Bytes), (ErrorKind, String)>);
+
+impl MockResponse {
+    /// Creates a response with the given `status` and an empty body.
+    pub fn new(status: StatusCode) -> Self {
+        Self(Ok((status, Headers::new(), Bytes::new())))
+    }
+
+    /// Creates a response with the given `status` and `value` serialized as a JSON body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` cannot be serialized to JSON.
+    pub fn json<T: Serialize>(status: StatusCode, value: &T) -> Self {
+        let body = serde_json::to_vec(value).expect("serialize response body");
+        Self::new(status)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+    }
+
+    /// Creates an error with the given `kind` and `message` to return instead of a response.
+    pub fn error(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self(Err((kind, message.into())))
+    }
+
+    /// Adds a header to the response.
+    #[must_use]
+    pub fn with_header(
+        mut self,
+        name: impl Into<HeaderName>,
+        value: impl Into<HeaderValue>,
+    ) -> Self {
+        if let Ok((_, headers, _)) = &mut self.0 {
+            headers.insert(name, value);
+        }
+        self
+    }
+
+    /// Sets the body of the response.
+    #[must_use]
+    pub fn with_body(mut self, body: impl Into<Bytes>) -> Self {
+        if let Ok((_, _, bytes)) = &mut self.0 {
+            *bytes = body.into();
+        }
+        self
+    }
+
+    fn to_response(&self) -> Result<RawResponse> {
+        match &self.0 {
+            Ok((status, headers, body)) => Ok(RawResponse::from_bytes(
+                *status,
+                headers.clone(),
+                body.clone(),
+            )),
+            Err((kind, message)) => Err(Error::message(kind.clone(), message.clone())),
+        }
+    }
+}
+
+impl Default for MockResponse {
+    fn default() -> Self {
+        Self::new(StatusCode::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(method: Method, url: &str) -> Request {
+        Request::new(url.parse().unwrap(), method)
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob("https://host/path", "https://host/path"));
+        assert!(!glob("https://host/path", "https://host/path/more"));
+        assert!(glob("https://host/*", "https://host/path/more"));
+        assert!(glob(
+            "https://*/secrets/*/versions",
+            "https://host/secrets/a/versions"
+        ));
+        assert!(!glob(
+            "https://*/secrets/*/versions",
+            "https://host/keys/a/versions"
+        ));
+        assert!(glob("*", ""));
+    }
+
+    #[tokio::test]
+    async fn scripted_responses() {
+        let transport = MockTransport::new();
+        let put = transport.expect(
+            Expectation::new(Method::Put, "https://localhost/items/*")
+                .with_query("api-version", "1.0")
+                .with_header("content-type", "application/json")
+                .with_json_body(&json!({ "a": 1, "b": [true] }))
+                .respond(MockResponse::error(ErrorKind::Io, "connection reset"))
+                .respond(MockResponse::json(StatusCode::Created, &json!({ "id": 1 })))
+                .times(2),
+        );
+        let get = transport.expect(
+            Expectation::new(Method::Get, "https://localhost/items/1")
+                .respond(MockResponse::new(StatusCode::NotFound))
+                .respond(MockResponse::default().with_body("1")),
+        );
+
+        let mut req = request(Method::Put, "https://localhost/items/1?api-version=1.0");
+        req.insert_header("content-type", "application/json");
+        req.set_body(r#"{"b":[true],"a":1}"#);
+        let err = transport.execute_request(&req).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Io);
+        let response = transport.execute_request(&req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::Created);
+        assert_eq!(
+            response.into_body().collect_string().await.unwrap(),
+            r#"{"id":1}"#
+        );
+        assert_eq!(put.calls(), 2);
+
+        let req = request(Method::Get, "https://localhost/items/1");
+        for status in [StatusCode::NotFound, StatusCode::Ok, StatusCode::Ok] {
+            let response = transport.execute_request(&req).await.unwrap();
+            assert_eq!(response.status(), status);
+        }
+        assert_eq!(get.calls(), 3);
+
+        transport.verify();
+    }
+
+    #[tokio::test]
+    async fn unexpected_requests() {
+        let transport = MockTransport::new();
+        transport.expect(
+            Expectation::new(Method::Post, "https://localhost/items")
+                .with_json_body(&json!({ "a": 1 }))
+                .times(1),
+        );
+
+        // Requests that do not match the method, body, or after the expected number of times are unexpected.
+        let mut req = request(Method::Post, "https://localhost/items");
+        req.set_body(r#"{"a":2}"#);
+        let err = transport.execute_request(&req).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::MockFramework);
+
+        req.set_body(r#"{"a":1}"#);
+        transport.execute_request(&req).await.unwrap();
+        transport.execute_request(&req).await.unwrap_err();
+
+        let message = transport.lock().check().unwrap_err();
+        assert_eq!(
+            message,
+            "unexpected request POST https://localhost/items\n".repeat(2)
+        );
+        transport.verify_on_drop(false);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 request(s) matching GET https://localhost/* but got 0")]
+    fn verify_on_drop() {
+        let transport = MockTransport::new();
+        transport.expect(Expectation::new(Method::Get, "https://localhost/*").times(2));
+        let _client: Arc<dyn HttpClient> = Arc::new(transport.clone());
+        drop(transport);
+    }
+}
@@ -3,5 +3,7 @@
 
 //! HTTP testing utilities.
 mod clients;
+mod mock;
 
 pub use clients::*;
+pub use mock::{Expectation, ExpectationHandle, MockResponse, MockTransport};
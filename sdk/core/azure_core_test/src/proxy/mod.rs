@@ -7,6 +7,8 @@ mod bootstrap;
 pub(crate) mod client;
 pub(crate) mod matchers;
 pub(crate) mod models;
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub(crate) mod native;
 pub(crate) mod policy;
 pub(crate) mod sanitizers;
 
@@ -0,0 +1,1149 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Record and play back session records in-process without the [Test Proxy] service.
+//!
+//! Set `AZURE_TEST_PROXY=native` to use the [`NativeRecorder`] instead of starting `test-proxy`.
+//! Session records are read from and written to the same JSON format `test-proxy` uses under [`TestContext::test_data_dir()`](crate::TestContext::test_data_dir),
+//! and the same [`Sanitizer`]s and [`Matcher`]s are supported.
+//!
+//! [Test Proxy]: https://github.com/Azure/azure-sdk-tools/blob/main/tools/test-proxy/Azure.Sdk.Tools.TestProxy/README.md
+
+// cspell:ignore bodiless headerless
+use super::{
+    matchers::{CustomDefaultMatcher, Matcher},
+    policy::RecordingOptions,
+    sanitizers::{
+        RegexEntryValues, Sanitizer, DEFAULT_SANITIZED_VALUE, DEFAULT_SANITIZERS_TO_REMOVE,
+        SANITIZE_BODY_ETAG, SANITIZE_BODY_ID, SANITIZE_BODY_NAME,
+    },
+    ABSTRACTION_IDENTIFIER,
+};
+use crate::Skip;
+use async_trait::async_trait;
+use azure_core::{
+    base64,
+    error::{ErrorKind, ResultExt as _},
+    http::{
+        headers::{AsHeaders, Headers},
+        policies::{Policy, PolicyResult},
+        request::{Body, Request},
+        Context, RawResponse, StatusCode, Url,
+    },
+    test::TestMode,
+};
+use regex::{NoExpand, Regex};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fs,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+};
+use tracing::Instrument;
+
+/// The environment variable that, when set to `native`, selects the [`NativeRecorder`].
+const AZURE_TEST_PROXY: &str = "AZURE_TEST_PROXY";
+
+const DEFAULT_SUBSCRIPTION_ID: &str = "00000000-0000-0000-0000-000000000000";
+const OAUTH_TOKEN_PATTERN: &str = r"(?i)/oauth2(?:/v2\.0)?/token";
+const SUBSCRIPTION_ID_PATTERN: &str =
+    r"(?i)/subscriptions/([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})";
+
+/// Gets whether `AZURE_TEST_PROXY=native` selects the [`NativeRecorder`].
+pub(crate) fn is_enabled() -> bool {
+    env::var(AZURE_TEST_PROXY).is_ok_and(|v| v.eq_ignore_ascii_case("native"))
+}
+
+/// Records or plays back session records in-process.
+///
+/// When recording, requests are sent to the next policy and both requests and responses are sanitized and saved.
+/// When playing back, requests are sanitized and matched against saved entries, and the next policy is never called.
+#[derive(Debug)]
+pub struct NativeRecorder {
+    test_mode: TestMode,
+    path: PathBuf,
+    pub(crate) options: RwLock<RecordingOptions>,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    entries: Vec<RecordEntry>,
+    sanitizers: Vec<SanitizerRule>,
+    matcher: Matcher,
+}
+
+impl NativeRecorder {
+    /// Creates a `NativeRecorder` that reads or writes the session record at `path`.
+    pub(crate) fn new(test_mode: TestMode, path: impl Into<PathBuf>) -> Self {
+        let mut sanitizers = default_sanitizers();
+
+        // Match the defaults set by `Proxy::initialize`.
+        sanitizers.retain(|sanitizer| {
+            !sanitizer
+                .id
+                .as_deref()
+                .is_some_and(|id| DEFAULT_SANITIZERS_TO_REMOVE.contains(&id))
+        });
+
+        Self {
+            test_mode,
+            path: path.into(),
+            options: RwLock::default(),
+            state: Mutex::new(State {
+                entries: Vec::new(),
+                sanitizers,
+                matcher: CustomDefaultMatcher::default().into(),
+            }),
+        }
+    }
+
+    pub(crate) fn add_sanitizer<S>(&self, sanitizer: &S) -> azure_core::Result<()>
+    where
+        S: Sanitizer,
+        azure_core::Error: From<<S as AsHeaders>::Error>,
+    {
+        let name = sanitizer
+            .as_headers()?
+            .find(|(name, _)| *name == ABSTRACTION_IDENTIFIER)
+            .map(|(_, value)| value.as_str().to_string())
+            .ok_or_else(|| {
+                azure_core::Error::message(ErrorKind::Other, "sanitizer name not specified")
+            })?;
+        let rule = SanitizerRule::new(&name, serde_json::to_value(sanitizer)?)?;
+
+        self.state.lock().map_err(lock_error)?.sanitizers.push(rule);
+        Ok(())
+    }
+
+    pub(crate) fn remove_sanitizers(&self, sanitizers: &[&str]) -> azure_core::Result<()> {
+        self.state
+            .lock()
+            .map_err(lock_error)?
+            .sanitizers
+            .retain(|sanitizer| {
+                !sanitizer
+                    .id
+                    .as_deref()
+                    .is_some_and(|id| sanitizers.contains(&id))
+            });
+        Ok(())
+    }
+
+    pub(crate) fn set_matcher(&self, matcher: Matcher) -> azure_core::Result<()> {
+        self.state.lock().map_err(lock_error)?.matcher = matcher;
+        Ok(())
+    }
+
+    /// Loads the session record when playing back and returns any recorded variables.
+    pub(crate) fn start(&self) -> azure_core::Result<BTreeMap<String, String>> {
+        if self.test_mode != TestMode::Playback {
+            return Ok(BTreeMap::new());
+        }
+
+        let content = fs::read(&self.path).with_context(ErrorKind::Io, || {
+            format!("failed to read recording {}", self.path.display())
+        })?;
+        let session: RecordSession = serde_json::from_slice(&content)
+            .with_context(ErrorKind::DataConversion, || {
+                format!("failed to parse recording {}", self.path.display())
+            })?;
+
+        self.state.lock().map_err(lock_error)?.entries = session.entries;
+        Ok(session.variables)
+    }
+
+    /// Saves the session record with `variables` when recording.
+    pub(crate) fn stop(&self, variables: BTreeMap<String, String>) -> azure_core::Result<()> {
+        if self.test_mode != TestMode::Record {
+            return Ok(());
+        }
+
+        let session = RecordSession {
+            entries: std::mem::take(&mut self.state.lock().map_err(lock_error)?.entries),
+            variables,
+        };
+        let content = serde_json::to_vec_pretty(&session)?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).with_context(ErrorKind::Io, || {
+                format!("failed to create directory {}", dir.display())
+            })?;
+        }
+        fs::write(&self.path, content).with_context(ErrorKind::Io, || {
+            format!("failed to write recording {}", self.path.display())
+        })
+    }
+
+    async fn play(&self, request: &Request) -> PolicyResult {
+        let mut entry = RecordEntry::from_request(request).await?;
+
+        let mut state = self.state.lock().map_err(lock_error)?;
+        state.sanitize(&mut entry);
+
+        // Like test-proxy, each recorded entry is only played back once.
+        let index = state
+            .entries
+            .iter()
+            .position(|recorded| is_match(&state.matcher, recorded, &entry))
+            .ok_or_else(|| {
+                azure_core::Error::message(
+                    ErrorKind::MockFramework,
+                    format!(
+                        "no recorded entry matches {} {} in {}",
+                        entry.request_method,
+                        entry.request_uri,
+                        self.path.display()
+                    ),
+                )
+            })?;
+        let recorded = state.entries.remove(index);
+
+        let mut headers = Headers::new();
+        for (name, value) in &recorded.response_headers {
+            headers.insert(name.clone(), value.clone());
+        }
+        let body = decode_body(&recorded.response_headers, recorded.response_body.as_ref())?;
+
+        Ok(RawResponse::from_bytes(
+            StatusCode::from(recorded.status_code),
+            headers,
+            body,
+        ))
+    }
+
+    async fn record(
+        &self,
+        ctx: &Context<'_>,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let (skip_request_body, skip_all) = {
+            let options = self.options.read().map_err(lock_error)?;
+            (
+                matches!(options.skip, Some(Skip::RequestBody)),
+                matches!(options.skip, Some(Skip::RequestResponse)),
+            )
+        };
+        if skip_all {
+            return next[0].send(ctx, request, &next[1..]).await;
+        }
+
+        // Read the request before sending it since a stream body will be consumed.
+        let mut entry = RecordEntry::from_request(request).await?;
+        if skip_request_body {
+            entry.request_body = None;
+        }
+
+        let (status, headers, body) = next[0].send(ctx, request, &next[1..]).await?.deconstruct();
+        let body = body.collect().await?;
+
+        entry.status_code = status.into();
+        entry.response_headers = to_map(&headers);
+        entry.response_body = encode_body(&entry.response_headers, &body);
+
+        let mut state = self.state.lock().map_err(lock_error)?;
+        if state.sanitize(&mut entry) {
+            state.entries.push(entry);
+        }
+
+        Ok(RawResponse::from_bytes(status, headers, body))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Policy for NativeRecorder {
+    async fn send(
+        &self,
+        ctx: &Context,
+        request: &mut Request,
+        next: &[Arc<dyn Policy>],
+    ) -> PolicyResult {
+        let span = tracing::trace_span!("request", mode = ?self.test_mode);
+        async move {
+            match self.test_mode {
+                TestMode::Playback => self.play(request).await,
+                TestMode::Record => self.record(ctx, request, next).await,
+                TestMode::Live => next[0].send(ctx, request, &next[1..]).await,
+            }
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+impl State {
+    /// Applies all sanitizers to the `entry`, returning `false` if the entry should not be recorded.
+    fn sanitize(&self, entry: &mut RecordEntry) -> bool {
+        self.sanitizers
+            .iter()
+            .all(|sanitizer| sanitizer.apply(entry))
+    }
+}
+
+/// A session record in the same format as written by `test-proxy`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct RecordSession {
+    entries: Vec<RecordEntry>,
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct RecordEntry {
+    request_uri: String,
+    request_method: String,
+    #[serde(deserialize_with = "deserialize_headers")]
+    request_headers: BTreeMap<String, String>,
+    request_body: Option<Value>,
+    status_code: u16,
+    #[serde(deserialize_with = "deserialize_headers")]
+    response_headers: BTreeMap<String, String>,
+    response_body: Option<Value>,
+}
+
+impl RecordEntry {
+    async fn from_request(request: &Request) -> azure_core::Result<Self> {
+        let request_headers = to_map(request.headers());
+        let body = match request.body() {
+            Body::Bytes(bytes) => bytes.to_vec(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Body::SeekableStream(stream) => {
+                use futures::AsyncReadExt as _;
+
+                let mut stream = stream.clone();
+                stream.reset().await?;
+                let mut bytes = Vec::with_capacity(stream.len());
+                stream.read_to_end(&mut bytes).await?;
+                stream.reset().await?;
+                bytes
+            }
+        };
+
+        Ok(Self {
+            request_uri: request.url().to_string(),
+            request_method: request.method().to_string(),
+            request_body: encode_body(&request_headers, &body),
+            request_headers,
+            ..Default::default()
+        })
+    }
+}
+
+fn to_map(headers: &Headers) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().to_ascii_lowercase(),
+                value.as_str().to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Deserializes headers recorded as either a single string or an array of strings.
+fn deserialize_headers<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Values {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    let headers = HashMap::<String, Values>::deserialize(deserializer)?;
+    Ok(headers
+        .into_iter()
+        .map(|(name, values)| {
+            let value = match values {
+                Values::One(value) => value,
+                Values::Many(values) => values.join(", "),
+            };
+            (name.to_ascii_lowercase(), value)
+        })
+        .collect())
+}
+
+fn content_type(headers: &BTreeMap<String, String>) -> String {
+    headers
+        .get("content-type")
+        .map(|v| v.to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+fn is_text(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+        || content_type.contains("x-www-form-urlencoded")
+}
+
+/// Encodes a body like `test-proxy`: JSON documents are embedded, text as a string, and anything else as base64.
+fn encode_body(headers: &BTreeMap<String, String>, body: &[u8]) -> Option<Value> {
+    if body.is_empty() {
+        return None;
+    }
+
+    let content_type = content_type(headers);
+    if content_type.contains("json") {
+        if let Ok(value @ (Value::Object(_) | Value::Array(_))) = serde_json::from_slice(body) {
+            return Some(value);
+        }
+    }
+    if is_text(&content_type) {
+        if let Ok(text) = std::str::from_utf8(body) {
+            return Some(Value::String(text.to_string()));
+        }
+    }
+
+    Some(Value::String(base64::encode(body)))
+}
+
+fn decode_body(
+    headers: &BTreeMap<String, String>,
+    body: Option<&Value>,
+) -> azure_core::Result<Vec<u8>> {
+    match body {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(text)) if is_text(&content_type(headers)) => {
+            Ok(text.as_bytes().to_vec())
+        }
+        Some(Value::String(text)) => base64::decode(text),
+        Some(value) => Ok(serde_json::to_vec(value)?),
+    }
+}
+
+fn is_match(matcher: &Matcher, recorded: &RecordEntry, request: &RecordEntry) -> bool {
+    let default = CustomDefaultMatcher::default();
+    let (options, compare_headers, compare_bodies) = match matcher {
+        Matcher::BodilessMatcher => (&default, true, false),
+        Matcher::HeaderlessMatcher => (&default, false, true),
+        Matcher::CustomDefaultMatcher(options) => {
+            (options, true, options.compare_bodies.unwrap_or(true))
+        }
+    };
+
+    recorded
+        .request_method
+        .eq_ignore_ascii_case(&request.request_method)
+        && is_uri_match(options, &recorded.request_uri, &request.request_uri)
+        && (!compare_headers
+            || is_headers_match(options, &recorded.request_headers, &request.request_headers))
+        && (!compare_bodies || recorded.request_body == request.request_body)
+}
+
+fn is_uri_match(options: &CustomDefaultMatcher, recorded: &str, request: &str) -> bool {
+    let (Ok(mut recorded), Ok(mut request)) = (Url::parse(recorded), Url::parse(request)) else {
+        return recorded == request;
+    };
+
+    let query = |url: &Url| {
+        let mut pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(name, _)| !options.ignored_query_parameters.contains(&name.as_ref()))
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+        if options.ignore_query_ordering.unwrap_or_default() {
+            pairs.sort();
+        }
+        pairs
+    };
+    if query(&recorded) != query(&request) {
+        return false;
+    }
+
+    recorded.set_query(None);
+    request.set_query(None);
+    recorded == request
+}
+
+/// Compares request headers present when sent through the pipeline.
+///
+/// Headers only in the recording are allowed since the HTTP client may add headers after this policy.
+fn is_headers_match(
+    options: &CustomDefaultMatcher,
+    recorded: &BTreeMap<String, String>,
+    request: &BTreeMap<String, String>,
+) -> bool {
+    let contains =
+        |headers: &[&str], name: &str| headers.iter().any(|h| h.eq_ignore_ascii_case(name));
+    request.iter().all(|(name, value)| {
+        if contains(&options.excluded_headers, name) {
+            return true;
+        }
+        match recorded.get(name) {
+            Some(_) if contains(&options.ignored_headers, name) => true,
+            Some(recorded) => recorded == value,
+            None => false,
+        }
+    })
+}
+
+#[derive(Debug)]
+struct SanitizerRule {
+    id: Option<String>,
+    condition: Option<Regex>,
+    rule: Rule,
+}
+
+#[derive(Debug)]
+enum Rule {
+    Body(Replacement),
+    BodyKey(JsonPath, Replacement),
+    General(Replacement),
+    Header(String, Replacement),
+    RemoveEntries(RegexEntryValues, Regex),
+    RemoveHeaders(Vec<String>),
+    Uri(Replacement),
+}
+
+/// The serialized properties of all supported [`Sanitizer`]s.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SanitizerProperties {
+    json_path: String,
+    key: String,
+    target: String,
+    value: Option<String>,
+    regex: Option<String>,
+    group_for_replace: Option<String>,
+    condition: Option<Condition>,
+    #[serde(alias = "headers_for_removal")]
+    headers_for_removal: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Condition {
+    #[serde(rename = "UriRegex")]
+    uri_regex: String,
+}
+
+impl SanitizerRule {
+    fn new(name: &str, properties: Value) -> azure_core::Result<Self> {
+        let properties: SanitizerProperties = serde_json::from_value(properties)?;
+        let value = properties
+            .value
+            .as_deref()
+            .unwrap_or(DEFAULT_SANITIZED_VALUE);
+        let group = properties.group_for_replace.as_deref();
+        let pattern = properties.regex.as_deref();
+        let literal = regex::escape(&properties.target);
+
+        let rule = match name {
+            "BodyKeySanitizer" => Rule::BodyKey(
+                JsonPath::parse(&properties.json_path)?,
+                Replacement::new(pattern, value, group)?,
+            ),
+            "BodyRegexSanitizer" => Rule::Body(Replacement::new(pattern, value, group)?),
+            "BodyStringSanitizer" => Rule::Body(Replacement::new(Some(&literal), value, None)?),
+            "GeneralRegexSanitizer" => Rule::General(Replacement::new(pattern, value, group)?),
+            "GeneralStringSanitizer" => {
+                Rule::General(Replacement::new(Some(&literal), value, None)?)
+            }
+            "HeaderRegexSanitizer" => Rule::Header(
+                properties.key.to_ascii_lowercase(),
+                Replacement::new(pattern, value, group)?,
+            ),
+            "HeaderStringSanitizer" => Rule::Header(
+                properties.key.to_ascii_lowercase(),
+                Replacement::new(Some(&literal), value, None)?,
+            ),
+            "OAuthResponseSanitizer" => {
+                Rule::RemoveEntries(RegexEntryValues::Uri, new_regex(OAUTH_TOKEN_PATTERN)?)
+            }
+            "RegexEntrySanitizer" => {
+                let target = match properties.target.as_str() {
+                    "body" => RegexEntryValues::Body,
+                    "header" => RegexEntryValues::Header,
+                    "uri" => RegexEntryValues::Uri,
+                    target => {
+                        return Err(azure_core::Error::message(
+                            ErrorKind::DataConversion,
+                            format!("unsupported regex entry target {target}"),
+                        ))
+                    }
+                };
+                Rule::RemoveEntries(target, new_regex(pattern.unwrap_or_default())?)
+            }
+            "RemoveHeaderSanitizer" => Rule::RemoveHeaders(
+                properties
+                    .headers_for_removal
+                    .split(',')
+                    .map(|name| name.trim().to_ascii_lowercase())
+                    .filter(|name| !name.is_empty())
+                    .collect(),
+            ),
+            "UriRegexSanitizer" => Rule::Uri(Replacement::new(pattern, value, group)?),
+            "UriStringSanitizer" => Rule::Uri(Replacement::new(Some(&literal), value, None)?),
+            "UriSubscriptionIdSanitizer" => Rule::Uri(Replacement::new(
+                Some(SUBSCRIPTION_ID_PATTERN),
+                properties
+                    .value
+                    .as_deref()
+                    .unwrap_or(DEFAULT_SUBSCRIPTION_ID),
+                Some("1"),
+            )?),
+            name => {
+                return Err(azure_core::Error::message(
+                    ErrorKind::Other,
+                    format!("{name} is not supported by the native recorder"),
+                ))
+            }
+        };
+
+        Ok(Self {
+            id: None,
+            condition: properties
+                .condition
+                .map(|condition| new_regex(&condition.uri_regex))
+                .transpose()?,
+            rule,
+        })
+    }
+
+    /// Sanitizes the `entry`, returning `false` if the entry should be removed.
+    fn apply(&self, entry: &mut RecordEntry) -> bool {
+        if let Some(condition) = &self.condition {
+            if !condition.is_match(&entry.request_uri) {
+                return true;
+            }
+        }
+
+        match &self.rule {
+            Rule::Body(replacement) => {
+                sanitize_body(&mut entry.request_body, &entry.request_headers, replacement);
+                sanitize_body(
+                    &mut entry.response_body,
+                    &entry.response_headers,
+                    replacement,
+                );
+            }
+            Rule::BodyKey(path, replacement) => {
+                for body in [&mut entry.request_body, &mut entry.response_body]
+                    .into_iter()
+                    .flatten()
+                {
+                    path.visit(body, &mut |value| {
+                        if let Value::String(text) = value {
+                            *text = replacement.apply(text);
+                        }
+                    });
+                }
+            }
+            Rule::General(replacement) => {
+                entry.request_uri = replacement.apply(&entry.request_uri);
+                for headers in [&mut entry.request_headers, &mut entry.response_headers] {
+                    for value in headers.values_mut() {
+                        *value = replacement.apply(value);
+                    }
+                }
+                sanitize_body(&mut entry.request_body, &entry.request_headers, replacement);
+                sanitize_body(
+                    &mut entry.response_body,
+                    &entry.response_headers,
+                    replacement,
+                );
+            }
+            Rule::Header(name, replacement) => {
+                for headers in [&mut entry.request_headers, &mut entry.response_headers] {
+                    if let Some(value) = headers.get_mut(name) {
+                        *value = replacement.apply(value);
+                    }
+                }
+            }
+            Rule::RemoveEntries(target, regex) => {
+                return !match target {
+                    RegexEntryValues::Body => {
+                        entry.request_body.as_ref().is_some_and(|body| match body {
+                            Value::String(text) => regex.is_match(text),
+                            body => regex.is_match(&body.to_string()),
+                        })
+                    }
+                    RegexEntryValues::Header => {
+                        entry.request_headers.values().any(|v| regex.is_match(v))
+                    }
+                    RegexEntryValues::Uri => regex.is_match(&entry.request_uri),
+                };
+            }
+            Rule::RemoveHeaders(names) => {
+                for headers in [&mut entry.request_headers, &mut entry.response_headers] {
+                    headers.retain(|name, _| !names.contains(name));
+                }
+            }
+            Rule::Uri(replacement) => {
+                entry.request_uri = replacement.apply(&entry.request_uri);
+            }
+        }
+
+        true
+    }
+}
+
+fn default_sanitizers() -> Vec<SanitizerRule> {
+    let body_key = |id: &str, path: &str| SanitizerRule {
+        id: Some(id.to_string()),
+        condition: None,
+        rule: Rule::BodyKey(
+            JsonPath::parse(path).unwrap_or_else(|err| panic!("{err}")),
+            Replacement::whole(DEFAULT_SANITIZED_VALUE),
+        ),
+    };
+
+    vec![
+        SanitizerRule {
+            id: None,
+            condition: None,
+            rule: Rule::Header(
+                "authorization".into(),
+                Replacement::whole(DEFAULT_SANITIZED_VALUE),
+            ),
+        },
+        body_key(SANITIZE_BODY_ID, "$..id"),
+        body_key(SANITIZE_BODY_ETAG, "$..etag"),
+        body_key(SANITIZE_BODY_NAME, "$..name"),
+    ]
+}
+
+fn sanitize_body(
+    body: &mut Option<Value>,
+    headers: &BTreeMap<String, String>,
+    replacement: &Replacement,
+) {
+    match body {
+        Some(Value::String(text)) if is_text(&content_type(headers)) => {
+            *text = replacement.apply(text);
+        }
+        Some(value @ (Value::Object(_) | Value::Array(_))) => {
+            let text = value.to_string();
+            let sanitized = replacement.apply(&text);
+            if sanitized != text {
+                *value = serde_json::from_str(&sanitized).unwrap_or(Value::String(sanitized));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces regular expression matches, or an entire value if no regular expression is set.
+#[derive(Debug)]
+struct Replacement {
+    regex: Option<Regex>,
+    value: String,
+    group: Option<String>,
+}
+
+impl Replacement {
+    fn new(regex: Option<&str>, value: &str, group: Option<&str>) -> azure_core::Result<Self> {
+        Ok(Self {
+            regex: regex.map(new_regex).transpose()?,
+            value: value.to_string(),
+            group: group.map(String::from),
+        })
+    }
+
+    fn whole(value: &str) -> Self {
+        Self {
+            regex: None,
+            value: value.to_string(),
+            group: None,
+        }
+    }
+
+    fn apply(&self, input: &str) -> String {
+        let Some(regex) = &self.regex else {
+            return self.value.clone();
+        };
+        let Some(group) = &self.group else {
+            return regex.replace_all(input, NoExpand(&self.value)).into_owned();
+        };
+
+        let mut output = String::with_capacity(input.len());
+        let mut last = 0;
+        for captures in regex.captures_iter(input) {
+            let matched = match group.parse::<usize>() {
+                Ok(index) => captures.get(index),
+                Err(_) => captures.name(group),
+            };
+            if let Some(matched) = matched {
+                output.push_str(&input[last..matched.start()]);
+                output.push_str(&self.value);
+                last = matched.end();
+            }
+        }
+        output.push_str(&input[last..]);
+        output
+    }
+}
+
+fn new_regex(pattern: &str) -> azure_core::Result<Regex> {
+    Regex::new(pattern).with_context(ErrorKind::DataConversion, || {
+        format!("invalid regular expression {pattern}")
+    })
+}
+
+/// The subset of JSONPath used by sanitizers e.g., `$..name`, `$.values[*].id`, or `$['key']`.
+#[derive(Debug, PartialEq)]
+struct JsonPath(Vec<Segment>);
+
+#[derive(Debug, PartialEq)]
+struct Segment {
+    recursive: bool,
+    selector: Selector,
+}
+
+#[derive(Debug, PartialEq)]
+enum Selector {
+    Name(String),
+    Index(usize),
+    Wildcard,
+}
+
+impl JsonPath {
+    fn parse(path: &str) -> azure_core::Result<Self> {
+        let invalid = || {
+            azure_core::Error::message(
+                ErrorKind::DataConversion,
+                format!("unsupported JSONPath {path}"),
+            )
+        };
+
+        let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            let recursive = rest.starts_with("..");
+            if recursive {
+                rest = &rest[2..];
+            } else if let Some(r) = rest.strip_prefix('.') {
+                rest = r;
+            } else if !rest.starts_with('[') {
+                return Err(invalid());
+            }
+
+            let selector = if let Some(r) = rest.strip_prefix('[') {
+                let end = r.find(']').ok_or_else(invalid)?;
+                let inner = r[..end].trim();
+                rest = &r[end + 1..];
+
+                let quoted = inner
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+                    .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+                match quoted {
+                    Some(name) => Selector::Name(name.to_string()),
+                    None if inner == "*" => Selector::Wildcard,
+                    None => Selector::Index(inner.parse().map_err(|_| invalid())?),
+                }
+            } else {
+                let end = rest.find(['.', '[']).unwrap_or(rest.len());
+                let name = &rest[..end];
+                rest = &rest[end..];
+                match name {
+                    "" => return Err(invalid()),
+                    "*" => Selector::Wildcard,
+                    name => Selector::Name(name.to_string()),
+                }
+            };
+
+            segments.push(Segment {
+                recursive,
+                selector,
+            });
+        }
+
+        Ok(Self(segments))
+    }
+
+    fn visit(&self, value: &mut Value, f: &mut dyn FnMut(&mut Value)) {
+        visit(value, &self.0, f);
+    }
+}
+
+fn visit(value: &mut Value, segments: &[Segment], f: &mut dyn FnMut(&mut Value)) {
+    let Some((segment, rest)) = segments.split_first() else {
+        f(value);
+        return;
+    };
+
+    match (&segment.selector, &mut *value) {
+        (Selector::Name(name), Value::Object(map)) => {
+            if let Some(child) = map.get_mut(name) {
+                visit(child, rest, f);
+            }
+        }
+        (Selector::Index(index), Value::Array(items)) => {
+            if let Some(child) = items.get_mut(*index) {
+                visit(child, rest, f);
+            }
+        }
+        (Selector::Wildcard, Value::Object(map)) => {
+            for child in map.values_mut() {
+                visit(child, rest, f);
+            }
+        }
+        (Selector::Wildcard, Value::Array(items)) => {
+            for child in items {
+                visit(child, rest, f);
+            }
+        }
+        _ => {}
+    }
+
+    if segment.recursive {
+        match value {
+            Value::Object(map) => {
+                for child in map.values_mut() {
+                    visit(child, segments, f);
+                }
+            }
+            Value::Array(items) => {
+                for child in items {
+                    visit(child, segments, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn lock_error(_: impl std::error::Error) -> azure_core::Error {
+    azure_core::Error::message(ErrorKind::Other, "failed to lock native recorder")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BodyKeySanitizer, HeaderRegexSanitizer};
+    use azure_core::http::{headers::HeaderName, Method};
+    use serde_json::json;
+
+    #[derive(Debug)]
+    struct Upstream;
+
+    #[async_trait]
+    impl Policy for Upstream {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let mut headers = Headers::new();
+            headers.insert("content-type", "application/json");
+            headers.insert("x-ms-secret", "token=abc123;region=west");
+            let body = json!({
+                "name": "my-secret",
+                "value": "hunter2",
+                "path": request.url().path(),
+            });
+            Ok(RawResponse::from_bytes(
+                StatusCode::Ok,
+                headers,
+                serde_json::to_vec(&body).unwrap(),
+            ))
+        }
+    }
+
+    fn recording_path(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("azure_core_test-native-{}", std::process::id()))
+            .join(format!("{name}.json"))
+    }
+
+    fn request(url: &str) -> Request {
+        let mut request = Request::new(url.parse().unwrap(), Method::Get);
+        request.insert_header(HeaderName::from_static("authorization"), "Bearer secret");
+        request
+    }
+
+    #[tokio::test]
+    async fn record_then_play_back() {
+        let path = recording_path("record_then_play_back");
+        let ctx = Context::new();
+
+        let recorder = NativeRecorder::new(TestMode::Record, &path);
+        recorder
+            .add_sanitizer(&BodyKeySanitizer {
+                json_path: "$.value".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        recorder
+            .add_sanitizer(&HeaderRegexSanitizer {
+                key: "x-ms-secret".into(),
+                regex: Some("token=(?<token>[^;]+)".into()),
+                group_for_replace: Some("token".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        let upstream: Arc<dyn Policy> = Arc::new(Upstream);
+        let response = recorder
+            .send(
+                &ctx,
+                &mut request("https://example.com/secrets/a?b=1&c=2"),
+                &[upstream],
+            )
+            .await
+            .unwrap();
+        let body: Value =
+            serde_json::from_slice(&response.into_body().collect().await.unwrap()).unwrap();
+        assert_eq!(body["value"], "hunter2", "caller gets unsanitized response");
+        recorder
+            .stop(BTreeMap::from([("KEY".into(), "value".into())]))
+            .unwrap();
+
+        let session: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        let entry = &session["Entries"][0];
+        assert_eq!(entry["RequestMethod"], "GET");
+        assert_eq!(entry["RequestHeaders"]["authorization"], "Sanitized");
+        assert_eq!(
+            entry["ResponseHeaders"]["x-ms-secret"],
+            "token=Sanitized;region=west"
+        );
+        assert_eq!(entry["ResponseBody"]["name"], "Sanitized");
+        assert_eq!(entry["ResponseBody"]["value"], "Sanitized");
+        assert_eq!(session["Variables"]["KEY"], "value");
+
+        let player = NativeRecorder::new(TestMode::Playback, &path);
+        let variables = player.start().unwrap();
+        assert_eq!(variables.get("KEY").map(String::as_str), Some("value"));
+        player
+            .set_matcher(
+                CustomDefaultMatcher {
+                    ignore_query_ordering: Some(true),
+                    ..Default::default()
+                }
+                .into(),
+            )
+            .unwrap();
+
+        let response = player
+            .send(
+                &ctx,
+                &mut request("https://example.com/secrets/a?c=2&b=1"),
+                &[],
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::Ok);
+        let body: Value =
+            serde_json::from_slice(&response.into_body().collect().await.unwrap()).unwrap();
+        assert_eq!(body["path"], "/secrets/a");
+        assert_eq!(body["value"], "Sanitized");
+
+        let err = player
+            .send(
+                &ctx,
+                &mut request("https://example.com/secrets/a?c=2&b=1"),
+                &[],
+            )
+            .await
+            .expect_err("entry already played back");
+        assert_eq!(err.kind(), &ErrorKind::MockFramework);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn playback_mismatch() {
+        let path = recording_path("playback_mismatch");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            json!({
+                "Entries": [{
+                    "RequestUri": "https://example.com/secrets/a",
+                    "RequestMethod": "GET",
+                    "RequestHeaders": { "Authorization": "Sanitized", "x-ms-version": ["1.0"] },
+                    "RequestBody": null,
+                    "StatusCode": 204,
+                    "ResponseHeaders": {},
+                    "ResponseBody": null,
+                }],
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let player = NativeRecorder::new(TestMode::Playback, &path);
+        assert!(player.start().unwrap().is_empty());
+
+        let ctx = Context::new();
+        let mut mismatch = request("https://example.com/secrets/a");
+        mismatch.insert_header(HeaderName::from_static("x-ms-version"), "2.0");
+        player
+            .send(&ctx, &mut mismatch, &[])
+            .await
+            .expect_err("header mismatch");
+
+        let mut matched = request("https://example.com/secrets/a");
+        matched.insert_header(HeaderName::from_static("x-ms-version"), "1.0");
+        let response = player.send(&ctx, &mut matched, &[]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NoContent);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn json_path() {
+        assert_eq!(
+            JsonPath::parse("$..name").unwrap(),
+            JsonPath(vec![Segment {
+                recursive: true,
+                selector: Selector::Name("name".into()),
+            }])
+        );
+        assert!(JsonPath::parse("name").is_err());
+        assert!(JsonPath::parse("$.").is_err());
+
+        let mut value = json!({
+            "name": "a",
+            "values": [{ "name": "b", "id": "1" }, { "name": "c", "id": "2" }],
+            "properties": { "key": "d" },
+        });
+        let mut replace = |path: &str| {
+            JsonPath::parse(path).unwrap().visit(&mut value, &mut |v| {
+                *v = Value::String("x".into());
+            })
+        };
+        replace("$.values[*].id");
+        replace("$['properties'].key");
+        replace("$.values[1].name");
+        assert_eq!(
+            value,
+            json!({
+                "name": "a",
+                "values": [{ "name": "b", "id": "x" }, { "name": "x", "id": "x" }],
+                "properties": { "key": "x" },
+            })
+        );
+
+        JsonPath::parse("$..name")
+            .unwrap()
+            .visit(&mut value, &mut |v| *v = Value::String("y".into()));
+        assert_eq!(value["name"], "y");
+        assert_eq!(value["values"][0]["name"], "y");
+        assert_eq!(value["values"][1]["name"], "y");
+    }
+
+    #[test]
+    fn replacement() {
+        let r = Replacement::new(Some("/subscriptions/([^/]+)"), "0", Some("1")).unwrap();
+        assert_eq!(r.apply("/subscriptions/abc/rg"), "/subscriptions/0/rg");
+
+        let r = Replacement::new(Some("a(?<b>b)c"), "$x", Some("b")).unwrap();
+        assert_eq!(r.apply("abcabc"), "a$xca$xc");
+
+        let r = Replacement::new(Some("[0-9]+"), "$1", None).unwrap();
+        assert_eq!(r.apply("a1b22"), "a$1b$1");
+
+        assert_eq!(Replacement::whole("x").apply("anything"), "x");
+    }
+}
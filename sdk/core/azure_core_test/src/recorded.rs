@@ -3,7 +3,10 @@
 
 //! Live recording and playing back of client library tests.
 use crate::{
-    proxy::{Proxy, ProxyOptions},
+    proxy::{
+        native::{self, NativeRecorder},
+        Proxy, ProxyOptions,
+    },
     recording::Recording,
     TestContext,
 };
@@ -21,7 +24,8 @@ static TEST_PROXY: OnceCell<Result<Arc<Proxy>>> = OnceCell::const_new();
 
 /// Starts playback or recording of live recordings.
 ///
-/// The [Test Proxy](https://github.com/Azure/azure-sdk-tools/blob/main/tools/test-proxy/Azure.Sdk.Tools.TestProxy/README.md) service will be started as needed.
+/// The [Test Proxy](https://github.com/Azure/azure-sdk-tools/blob/main/tools/test-proxy/Azure.Sdk.Tools.TestProxy/README.md) service will be started as needed
+/// unless `AZURE_TEST_PROXY=native`, in which case session records are recorded and played back in-process.
 /// Every `#[recorded::test]` will call this automatically, but it can also be called manually by any other test e.g., those attributed with `#[tokio::test]`.
 #[tracing::instrument(level = "debug", err)]
 pub async fn start(
@@ -34,12 +38,20 @@ pub async fn start(
     let mut ctx = TestContext::new(crate_dir, module_dir, name)?;
 
     #[cfg(target_arch = "wasm32")]
-    let proxy: Option<Arc<Proxy>> = None;
+    let (proxy, native): (Option<Arc<Proxy>>, Option<Arc<NativeRecorder>>) = (None, None);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let native = (mode != TestMode::Live && native::is_enabled()).then(|| {
+        Arc::new(NativeRecorder::new(
+            mode,
+            ctx.repo_dir().join(ctx.test_recording_file()),
+        ))
+    });
 
     #[cfg(not(target_arch = "wasm32"))]
     let proxy = {
         match mode {
-            TestMode::Live => {
+            _ if mode == TestMode::Live || native.is_some() => {
                 ONLY_TRACE.get_or_init(init_tracing);
                 None
             }
@@ -64,6 +76,7 @@ pub async fn start(
         mode,
         span.entered(),
         proxy.clone(),
+        native,
         ctx.service_dir(),
         ctx.test_recording_file(),
         ctx.test_recording_assets_file(mode),
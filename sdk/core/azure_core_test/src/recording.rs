@@ -11,6 +11,7 @@ use crate::{
             ClientAddSanitizerOptions, ClientRemoveSanitizersOptions, ClientSetMatcherOptions,
         },
         models::{SanitizerList, StartPayload, VariablePayload},
+        native::NativeRecorder,
         policy::RecordingPolicy,
         Proxy, ProxyExt, RecordingId,
     },
@@ -34,13 +35,13 @@ use rand_chacha::ChaCha20Rng;
 use std::{
     borrow::Cow,
     cell::OnceCell,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     env,
     sync::{Arc, Mutex, OnceLock, RwLock},
 };
 use tracing::span::EnteredSpan;
 
-/// Represents a playback or recording session using the [`Proxy`].
+/// Represents a playback or recording session using the [`Proxy`] or, if `AZURE_TEST_PROXY=native`, an in-process recorder.
 #[derive(Debug)]
 pub struct Recording {
     test_mode: TestMode,
@@ -49,6 +50,7 @@ pub struct Recording {
     span: EnteredSpan,
     proxy: Option<Arc<Proxy>>,
     policy: OnceCell<Arc<RecordingPolicy>>,
+    native: Option<Arc<NativeRecorder>>,
     service_directory: String,
     recording_file: String,
     recording_assets_file: Option<String>,
@@ -64,6 +66,10 @@ impl Recording {
         S: Sanitizer,
         azure_core::Error: From<<S as AsHeaders>::Error>,
     {
+        if let Some(native) = &self.native {
+            return native.add_sanitizer(&sanitizer);
+        }
+
         let Some(client) = self.proxy.client() else {
             return Ok(());
         };
@@ -116,6 +122,11 @@ impl Recording {
     /// }
     /// ```
     pub fn instrument(&self, options: &mut ClientOptions) {
+        if let Some(native) = &self.native {
+            options.per_try_policies.push(native.clone());
+            return;
+        }
+
         let Some(client) = self.proxy.client() else {
             return;
         };
@@ -243,6 +254,10 @@ impl Recording {
     ///
     /// You can find a list of default sanitizers in [source code](https://github.com/Azure/azure-sdk-tools/blob/main/tools/test-proxy/Azure.Sdk.Tools.TestProxy/Common/SanitizerDictionary.cs).
     pub async fn remove_sanitizers(&self, sanitizers: &[&str]) -> azure_core::Result<()> {
+        if let Some(native) = &self.native {
+            return native.remove_sanitizers(sanitizers);
+        }
+
         let Some(client) = self.proxy.client() else {
             return Ok(());
         };
@@ -263,6 +278,10 @@ impl Recording {
 
     /// Sets a [`Matcher`] to compare requests and/or responses.
     pub async fn set_matcher(&self, matcher: Matcher) -> azure_core::Result<()> {
+        if let Some(native) = &self.native {
+            return native.set_matcher(matcher);
+        }
+
         let Some(client) = self.proxy.client() else {
             return Ok(());
         };
@@ -327,6 +346,7 @@ impl Recording {
         test_mode: TestMode,
         span: EnteredSpan,
         proxy: Option<Arc<Proxy>>,
+        native: Option<Arc<NativeRecorder>>,
         service_directory: &'static str,
         recording_file: String,
         recording_assets_file: Option<String>,
@@ -336,6 +356,7 @@ impl Recording {
             span,
             proxy,
             policy: OnceCell::new(),
+            native,
             service_directory: service_directory.into(),
             recording_file,
             recording_assets_file,
@@ -354,6 +375,7 @@ impl Recording {
             span: span.entered(),
             proxy: None,
             policy: OnceCell::new(),
+            native: None,
             service_directory: String::from("sdk/core"),
             recording_file: String::from("none"),
             recording_assets_file: None,
@@ -418,12 +440,13 @@ impl Recording {
     }
 
     fn set_skip(&self, skip: Option<Skip>) -> azure_core::Result<()> {
-        let Some(policy) = self.policy.get() else {
-            return Ok(());
+        let options = match (&self.native, self.policy.get()) {
+            (Some(native), _) => &native.options,
+            (None, Some(policy)) => &policy.options,
+            (None, None) => return Ok(()),
         };
 
-        let mut options = policy
-            .options
+        let mut options = options
             .write()
             .map_err(|err| azure_core::Error::message(ErrorKind::Other, err.to_string()))?;
         options.skip = skip;
@@ -435,6 +458,14 @@ impl Recording {
     ///
     /// If playing back a recording, environment variable that were recorded will be reloaded.
     pub(crate) async fn start(&mut self) -> azure_core::Result<()> {
+        if let Some(native) = &self.native {
+            let recorded = native.start()?;
+            let mut variables = self.variables.write().map_err(write_lock_error)?;
+            variables.extend(recorded.into_iter().map(|(k, v)| (k, v.into())));
+
+            return Ok(());
+        }
+
         let Some(client) = self.proxy.client() else {
             // Assumes running live test.
             return Ok(());
@@ -471,6 +502,13 @@ impl Recording {
     ///
     /// If recording, environment variables that were retrieved will be recorded.
     pub(crate) async fn stop(&self) -> azure_core::Result<()> {
+        if let Some(native) = &self.native {
+            let variables = self.variables.read().map_err(read_lock_error)?;
+            return native.stop(BTreeMap::from_iter(
+                variables.iter().map(|(k, v)| (k.clone(), v.into())),
+            ));
+        }
+
         let Some(client) = self.proxy.client() else {
             // Assumes running live test.
             return Ok(());
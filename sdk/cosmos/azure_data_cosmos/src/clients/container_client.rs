@@ -720,7 +720,7 @@ impl ContainerClient {
 
         let url = self.pipeline.url(&self.items_link);
         self.pipeline.send_query_request(
-            options.method_options.context,
+            options.method_options,
             query,
             url,
             self.items_link.clone(),
@@ -171,7 +171,7 @@ impl CosmosClient {
         let url = self.pipeline.url(&self.databases_link);
 
         self.pipeline.send_query_request(
-            options.method_options.context,
+            options.method_options,
             query.into(),
             url,
             self.databases_link.clone(),
@@ -116,7 +116,7 @@ impl DatabaseClient {
         let url = self.pipeline.url(&self.containers_link);
 
         self.pipeline.send_query_request(
-            options.method_options.context,
+            options.method_options,
             query.into(),
             url,
             self.containers_link.clone(),
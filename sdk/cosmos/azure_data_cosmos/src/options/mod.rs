@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use azure_core::http::{ClientMethodOptions, ClientOptions, Etag};
+use azure_core::http::{ClientMethodOptions, ClientOptions, Etag, PagerOptions};
 
 use crate::models::ThroughputProperties;
 
@@ -60,19 +60,19 @@ pub struct ItemOptions<'a> {
 /// Options to be passed to [`DatabaseClient::query_containers()`](crate::clients::DatabaseClient::query_containers())
 #[derive(Clone, Default)]
 pub struct QueryContainersOptions<'a> {
-    pub method_options: ClientMethodOptions<'a>,
+    pub method_options: PagerOptions<'a>,
 }
 
 /// Options to be passed to [`CosmosClient::query_databases()`](crate::CosmosClient::query_databases())
 #[derive(Clone, Default)]
 pub struct QueryDatabasesOptions<'a> {
-    pub method_options: ClientMethodOptions<'a>,
+    pub method_options: PagerOptions<'a>,
 }
 
 /// Options to be passed to [`ContainerClient::query_items()`](crate::clients::ContainerClient::query_items()).
 #[derive(Clone, Default)]
 pub struct QueryOptions<'a> {
-    pub method_options: ClientMethodOptions<'a>,
+    pub method_options: PagerOptions<'a>,
 
     /// An external query engine to use for executing the query.
     ///
@@ -85,9 +85,7 @@ pub struct QueryOptions<'a> {
 impl QueryOptions<'_> {
     pub fn into_owned(self) -> QueryOptions<'static> {
         QueryOptions {
-            method_options: ClientMethodOptions {
-                context: self.method_options.context.into_owned(),
-            },
+            method_options: self.method_options.into_owned(),
             #[cfg(feature = "preview_query_engine")]
             query_engine: self.query_engine,
         }
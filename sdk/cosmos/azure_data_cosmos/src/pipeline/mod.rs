@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 mod authorization_policy;
+mod should_retry;
 mod signature_target;
 
 use std::sync::Arc;
@@ -10,10 +11,11 @@ pub use authorization_policy::AuthorizationPolicy;
 use azure_core::http::{
     request::{options::ContentType, Request},
     response::Response,
-    ClientOptions, Context, Method, RawResponse,
+    ClientOptions, Context, Method, PagerOptions, RawResponse,
 };
 use futures::TryStreamExt;
 use serde::de::DeserializeOwned;
+use should_retry::CosmosShouldRetry;
 use url::Url;
 
 use crate::{
@@ -34,8 +36,15 @@ impl CosmosPipeline {
     pub fn new(
         endpoint: Url,
         auth_policy: AuthorizationPolicy,
-        client_options: ClientOptions,
+        mut client_options: ClientOptions,
     ) -> Self {
+        // Callers can still replace the Cosmos DB retry rules using `RetryOptions::with_should_retry`.
+        client_options.retry = Some(
+            client_options
+                .retry
+                .unwrap_or_default()
+                .with_default_should_retry(Arc::new(CosmosShouldRetry)),
+        );
         CosmosPipeline {
             endpoint,
             pipeline: azure_core::http::Pipeline::new(
@@ -80,7 +89,7 @@ impl CosmosPipeline {
 
     pub fn send_query_request<T: DeserializeOwned + Send>(
         &self,
-        ctx: Context<'_>,
+        options: PagerOptions<'_>,
         query: Query,
         url: Url,
         resource_link: ResourceLink,
@@ -92,25 +101,28 @@ impl CosmosPipeline {
         // We have to double-clone here.
         // First we clone the pipeline to pass it in to the closure
         let pipeline = self.pipeline.clone();
-        let ctx = ctx.with_value(resource_link).into_owned();
-        Ok(FeedPager::from_callback(move |continuation| {
-            // Then we have to clone it again to pass it in to the async block.
-            // This is because Pageable can't borrow any data, it has to own it all.
-            // That's probably good, because it means a Pageable can outlive the client that produced it, but it requires some extra cloning.
-            let pipeline = pipeline.clone();
-            let mut req = base_request.clone();
-            let ctx = ctx.clone();
-            async move {
-                if let Some(continuation) = continuation {
-                    req.insert_header(constants::CONTINUATION, continuation);
+        let ctx = options.context.with_value(resource_link).into_owned();
+        Ok(FeedPager::from_callback_with_continuation(
+            move |continuation| {
+                // Then we have to clone it again to pass it in to the async block.
+                // This is because Pageable can't borrow any data, it has to own it all.
+                // That's probably good, because it means a Pageable can outlive the client that produced it, but it requires some extra cloning.
+                let pipeline = pipeline.clone();
+                let mut req = base_request.clone();
+                let ctx = ctx.clone();
+                async move {
+                    if let Some(continuation) = continuation {
+                        req.insert_header(constants::CONTINUATION, continuation);
+                    }
+
+                    let resp = pipeline.send(&ctx, &mut req).await?;
+                    let page = FeedPage::<T>::from_response(resp).await?;
+
+                    Ok(page.into())
                 }
-
-                let resp = pipeline.send(&ctx, &mut req).await?;
-                let page = FeedPage::<T>::from_response(resp).await?;
-
-                Ok(page.into())
-            }
-        }))
+            },
+            options.continuation_token,
+        ))
     }
 
     /// Helper function to read a throughput offer given a resource ID.
@@ -133,7 +145,10 @@ impl CosmosPipeline {
             .with_parameter("@rid", resource_id)?;
         let offers_link = ResourceLink::root(ResourceType::Offers);
         let mut results = self.send_query_request::<ThroughputProperties>(
-            context.clone(),
+            PagerOptions {
+                context: context.clone(),
+                ..Default::default()
+            },
             query,
             self.url(&offers_link),
             offers_link.clone(),
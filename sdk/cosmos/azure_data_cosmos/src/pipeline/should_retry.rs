@@ -0,0 +1,138 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::http::{
+    policies::{AttemptOutcome, DefaultShouldRetry, ShouldRetry},
+    StatusCode,
+};
+
+use crate::constants;
+
+/// The `449 Retry With` status returned when a write conflicts with a concurrent operation.
+const RETRY_WITH: u16 = 449;
+
+/// Sub-statuses of `410 Gone` returned while a partition is being moved or split, which succeed once routing is refreshed.
+const RETRY_GONE_SUB_STATUSES: &[&str] = &[
+    "1000", // NameCacheIsStale
+    "1002", // PartitionKeyRangeGone
+    "1007", // CompletingSplit
+    "1008", // CompletingPartitionMigration
+];
+
+/// The [`ShouldRetry`] registered by Cosmos DB clients unless one is set in the client's [`RetryOptions`](azure_core::http::RetryOptions).
+///
+/// In addition to the [`DefaultShouldRetry`] rules, retries `449 Retry With` responses
+/// and `410 Gone` responses with a sub-status indicating the partition is moving.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct CosmosShouldRetry;
+
+impl ShouldRetry for CosmosShouldRetry {
+    fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool {
+        match outcome {
+            AttemptOutcome::Response { status, .. } if u16::from(*status) == RETRY_WITH => true,
+            AttemptOutcome::Response {
+                status: StatusCode::Gone,
+                headers,
+                ..
+            } => headers
+                .get_optional_str(&constants::SUB_STATUS)
+                .is_some_and(|sub_status| RETRY_GONE_SUB_STATUSES.contains(&sub_status)),
+            _ => DefaultShouldRetry.should_retry(outcome, attempt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use azure_core::{
+        http::{
+            headers::Headers, ClientOptions, Context, FixedRetryOptions, Method, Request,
+            RetryOptions, StatusCode, TransportOptions,
+        },
+        time::Duration,
+    };
+    use azure_core_test::{
+        credentials::MockCredential,
+        http::{Expectation, MockResponse, MockTransport},
+    };
+
+    use super::*;
+    use crate::{
+        pipeline::{AuthorizationPolicy, CosmosPipeline},
+        resource_context::{ResourceLink, ResourceType},
+    };
+
+    fn response(status: u16, sub_status: Option<&str>) -> (StatusCode, Headers) {
+        let mut headers = Headers::new();
+        if let Some(sub_status) = sub_status {
+            headers.insert(constants::SUB_STATUS, sub_status.to_string());
+        }
+        (StatusCode::from(status), headers)
+    }
+
+    fn should_retry(status: u16, sub_status: Option<&str>) -> bool {
+        let (status, headers) = response(status, sub_status);
+        CosmosShouldRetry.should_retry(
+            &AttemptOutcome::Response {
+                status,
+                headers: &headers,
+                error_code: None,
+            },
+            1,
+        )
+    }
+
+    #[test]
+    fn retries_retry_with() {
+        assert!(should_retry(449, None));
+    }
+
+    #[test]
+    fn retries_gone_with_partition_sub_status() {
+        assert!(should_retry(410, Some("1002")));
+        assert!(should_retry(410, Some("1007")));
+        assert!(!should_retry(410, Some("1001")));
+        assert!(!should_retry(410, None));
+    }
+
+    #[test]
+    fn retries_default_statuses() {
+        assert!(should_retry(429, None));
+        assert!(should_retry(503, None));
+        assert!(!should_retry(404, None));
+    }
+
+    #[tokio::test]
+    async fn pipeline_retries_retry_with() {
+        let transport = MockTransport::new();
+        let databases = transport.expect(
+            Expectation::new(Method::Get, "https://myaccount.documents.azure.com/dbs")
+                .respond(MockResponse::new(StatusCode::from(RETRY_WITH)))
+                .respond(MockResponse::new(StatusCode::Ok)),
+        );
+        let pipeline = CosmosPipeline::new(
+            "https://myaccount.documents.azure.com/".parse().unwrap(),
+            AuthorizationPolicy::from_token_credential(Arc::new(MockCredential)),
+            ClientOptions {
+                retry: Some(RetryOptions::fixed(FixedRetryOptions {
+                    delay: Duration::ZERO,
+                    ..Default::default()
+                })),
+                transport: Some(TransportOptions::new(Arc::new(transport.clone()))),
+                ..Default::default()
+            },
+        );
+
+        let link = ResourceLink::root(ResourceType::Databases);
+        let mut request = Request::new(pipeline.url(&link), Method::Get);
+        let response = pipeline
+            .send_raw(Context::new(), &mut request, link)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::Ok);
+        assert_eq!(databases.calls(), 2);
+        transport.verify();
+    }
+}
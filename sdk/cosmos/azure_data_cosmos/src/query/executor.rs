@@ -35,6 +35,13 @@ impl<T: DeserializeOwned + Send + 'static> QueryExecutor<T> {
         options: QueryOptions<'_>,
         query_engine: QueryEngineRef,
     ) -> azure_core::Result<Self> {
+        if options.method_options.continuation_token.is_some() {
+            return Err(azure_core::Error::message(
+                azure_core::error::ErrorKind::Other,
+                "continuation tokens are not supported for queries executed by a query engine",
+            ));
+        }
+
         let items_link = container_link.feed(ResourceType::Items);
         let context = options.method_options.context.into_owned();
         Ok(Self {
@@ -0,0 +1,355 @@
+// Copyright (c) Microsoft Corporation. All Rights reserved
+// Licensed under the MIT license.
+
+//! Distributed tracing and metrics support for Event Hubs clients.
+//!
+//! The trace context of the span that sent a message is propagated in the `Diagnostic-Id` and `traceparent`
+//! application properties of the message so that the spans that receive it can be linked to it.
+
+use azure_core::{
+    error::Result,
+    metrics::{Attribute, Counter, Histogram, MeterProvider},
+    tracing::{AttributeValue, Span, TraceContext, Tracer, TracerProvider},
+};
+use azure_core_amqp::{AmqpMessage, AmqpSimpleValue};
+use std::sync::Arc;
+
+/// The application property used by Event Hubs SDKs to propagate the `traceparent` of a message.
+pub(crate) const DIAGNOSTIC_ID: &str = "Diagnostic-Id";
+
+/// The W3C application property used to propagate the `traceparent` of a message.
+pub(crate) const TRACEPARENT: &str = "traceparent";
+
+/// The W3C application property used to propagate the `tracestate` of a message.
+pub(crate) const TRACESTATE: &str = "tracestate";
+
+/// The tracer and metrics used to instrument a client, if a tracer provider or meter provider was configured.
+#[derive(Clone, Default)]
+pub(crate) struct ClientInstrumentation {
+    pub tracer: Option<Arc<dyn Tracer + Send + Sync>>,
+    pub metrics: Option<Arc<EventHubsMetrics>>,
+}
+
+impl ClientInstrumentation {
+    pub(crate) fn new(
+        tracer_provider: Option<&(dyn TracerProvider + Send + Sync)>,
+        meter_provider: Option<&(dyn MeterProvider + Send + Sync)>,
+    ) -> Self {
+        Self {
+            tracer: tracer_provider.map(|tracer_provider| {
+                Arc::from(
+                    tracer_provider.get_tracer(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+                )
+            }),
+            metrics: meter_provider
+                .map(|meter_provider| Arc::new(EventHubsMetrics::new(meter_provider))),
+        }
+    }
+}
+
+/// Instruments recording Event Hubs client metrics using OpenTelemetry messaging semantic conventions.
+pub(crate) struct EventHubsMetrics {
+    sent: Arc<dyn Counter + Send + Sync>,
+    consumed: Arc<dyn Counter + Send + Sync>,
+    batch_size: Arc<dyn Histogram + Send + Sync>,
+}
+
+impl EventHubsMetrics {
+    fn new(meter_provider: &(dyn MeterProvider + Send + Sync)) -> Self {
+        let meter = meter_provider.get_meter(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        Self {
+            sent: meter.create_counter(
+                "messaging.client.sent.messages",
+                "Number of events sent to an Event Hub.",
+                "{message}",
+            ),
+            consumed: meter.create_counter(
+                "messaging.client.consumed.messages",
+                "Number of events received from an Event Hub.",
+                "{message}",
+            ),
+            batch_size: meter.create_histogram(
+                "messaging.batch.message_count",
+                "Number of events in each batch sent to an Event Hub.",
+                "{message}",
+            ),
+        }
+    }
+
+    /// Records the number of events sent in one operation, and the size of the batch if `batch` is true.
+    pub(crate) fn record_sent<T>(
+        &self,
+        count: u64,
+        batch: bool,
+        eventhub: &str,
+        partition_id: Option<&str>,
+        result: &Result<T>,
+    ) {
+        let mut attributes = messaging_attributes(eventhub, partition_id);
+        if let Err(err) = result {
+            attributes.push(("error.type", AttributeValue::String(err.kind().to_string())));
+        }
+        self.sent.add(count, &attributes);
+        if batch {
+            self.batch_size.record(count as f64, &attributes);
+        }
+    }
+
+    /// Records an event received from a partition.
+    pub(crate) fn record_consumed(&self, eventhub: &str, partition_id: &str) {
+        self.consumed
+            .add(1, &messaging_attributes(eventhub, Some(partition_id)));
+    }
+}
+
+fn messaging_attributes(eventhub: &str, partition_id: Option<&str>) -> Vec<Attribute> {
+    let mut attributes = vec![
+        (
+            "messaging.system",
+            AttributeValue::String("eventhubs".to_string()),
+        ),
+        (
+            "messaging.destination.name",
+            AttributeValue::String(eventhub.to_string()),
+        ),
+    ];
+    if let Some(partition_id) = partition_id {
+        attributes.push((
+            "messaging.destination.partition.id",
+            AttributeValue::String(partition_id.to_string()),
+        ));
+    }
+    attributes
+}
+
+/// Sets the messaging semantic convention attributes on a span.
+pub(crate) fn set_messaging_attributes(
+    span: &(dyn Span + Send + Sync),
+    operation: &'static str,
+    fully_qualified_namespace: Option<&str>,
+    eventhub: &str,
+) {
+    let _ = span.set_attribute(
+        "messaging.system",
+        AttributeValue::String("eventhubs".to_string()),
+    );
+    let _ = span.set_attribute(
+        "messaging.operation.name",
+        AttributeValue::String(operation.to_string()),
+    );
+    let _ = span.set_attribute(
+        "messaging.destination.name",
+        AttributeValue::String(eventhub.to_string()),
+    );
+    if let Some(fully_qualified_namespace) = fully_qualified_namespace {
+        let _ = span.set_attribute(
+            "server.address",
+            AttributeValue::String(fully_qualified_namespace.to_string()),
+        );
+    }
+}
+
+/// Records the error in `result`, if any, and ends the `span`.
+pub(crate) fn end_span<T>(span: Option<Arc<dyn Span + Send + Sync>>, result: &Result<T>) {
+    let Some(span) = span else {
+        return;
+    };
+    if let Err(err) = result {
+        let _ = span.set_attribute("error.type", AttributeValue::String(err.kind().to_string()));
+        let _ = span.record_error(err);
+    }
+    let _ = span.end();
+}
+
+/// Adds the trace context to the application properties of the `message`.
+///
+/// Messages that already have a `Diagnostic-Id` were instrumented by the application and are not modified.
+/// Returns the trace context propagated by the message, if any.
+pub(crate) fn inject(
+    message: &mut AmqpMessage,
+    trace_context: &TraceContext,
+) -> Option<TraceContext> {
+    let instrumented = message.application_properties().is_some_and(|properties| {
+        properties.0.get(DIAGNOSTIC_ID).is_some() || properties.0.get(TRACEPARENT).is_some()
+    });
+    if instrumented {
+        return extract(message);
+    }
+    if !trace_context.is_valid() {
+        return None;
+    }
+
+    let traceparent = trace_context.traceparent();
+    message.add_application_property(DIAGNOSTIC_ID.to_string(), traceparent.clone());
+    message.add_application_property(TRACEPARENT.to_string(), traceparent);
+    if let Some(trace_state) = &trace_context.trace_state {
+        message.add_application_property(TRACESTATE.to_string(), trace_state.clone());
+    }
+    Some(trace_context.clone())
+}
+
+/// Gets the trace context propagated in the application properties of the `message`, if any.
+///
+/// The `traceparent` property is preferred over the `Diagnostic-Id` property.
+pub(crate) fn extract(message: &AmqpMessage) -> Option<TraceContext> {
+    let properties = &message.application_properties()?.0;
+    let property = |name: &str| match properties.get(name) {
+        Some(AmqpSimpleValue::String(value)) => Some(value.as_str()),
+        _ => None,
+    };
+
+    let mut trace_context = property(TRACEPARENT)
+        .and_then(TraceContext::from_traceparent)
+        .or_else(|| property(DIAGNOSTIC_ID).and_then(TraceContext::from_traceparent))?;
+    trace_context.trace_state = property(TRACESTATE).map(ToString::to_string);
+    Some(trace_context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use azure_core::metrics::Meter;
+    use std::sync::Mutex;
+
+    const TRACEPARENT_VALUE: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    #[test]
+    fn inject_and_extract() {
+        let trace_context = TraceContext {
+            trace_state: Some("az=1".to_string()),
+            ..TraceContext::from_traceparent(TRACEPARENT_VALUE).unwrap()
+        };
+
+        let mut message = AmqpMessage::default();
+        assert_eq!(
+            inject(&mut message, &trace_context),
+            Some(trace_context.clone())
+        );
+
+        let properties = &message.application_properties().unwrap().0;
+        assert_eq!(
+            properties.get(DIAGNOSTIC_ID),
+            Some(&AmqpSimpleValue::String(TRACEPARENT_VALUE.to_string()))
+        );
+        assert_eq!(
+            properties.get(TRACEPARENT),
+            Some(&AmqpSimpleValue::String(TRACEPARENT_VALUE.to_string()))
+        );
+        assert_eq!(extract(&message), Some(trace_context));
+    }
+
+    #[test]
+    fn inject_preserves_diagnostic_id() {
+        let mut message = AmqpMessage::default();
+        message.add_application_property(DIAGNOSTIC_ID.to_string(), TRACEPARENT_VALUE);
+
+        let trace_context = TraceContext {
+            trace_id: [1; 16],
+            span_id: [1; 8],
+            ..Default::default()
+        };
+        let propagated = inject(&mut message, &trace_context).unwrap();
+        assert_eq!(propagated.traceparent(), TRACEPARENT_VALUE);
+        assert_eq!(message.application_properties().unwrap().0.len(), 1);
+    }
+
+    #[test]
+    fn inject_ignores_invalid_trace_context() {
+        let mut message = AmqpMessage::default();
+        assert_eq!(inject(&mut message, &TraceContext::default()), None);
+        assert!(message.application_properties().is_none());
+    }
+
+    #[test]
+    fn extract_invalid() {
+        let mut message = AmqpMessage::default();
+        assert_eq!(extract(&message), None);
+
+        message.add_application_property(TRACEPARENT.to_string(), "invalid");
+        assert_eq!(extract(&message), None);
+    }
+
+    type Recorded = Arc<Mutex<Vec<(&'static str, f64, usize)>>>;
+
+    #[derive(Default)]
+    struct MockMeter {
+        recorded: Recorded,
+    }
+
+    struct MockInstrument {
+        name: &'static str,
+        recorded: Recorded,
+    }
+
+    impl MeterProvider for MockMeter {
+        fn get_meter(&self, _: &'static str, _: &'static str) -> Box<dyn Meter + Send + Sync> {
+            Box::new(MockMeter {
+                recorded: self.recorded.clone(),
+            })
+        }
+    }
+
+    impl Meter for MockMeter {
+        fn create_histogram(
+            &self,
+            name: &'static str,
+            _: &'static str,
+            _: &'static str,
+        ) -> Arc<dyn Histogram + Send + Sync> {
+            Arc::new(MockInstrument {
+                name,
+                recorded: self.recorded.clone(),
+            })
+        }
+
+        fn create_counter(
+            &self,
+            name: &'static str,
+            _: &'static str,
+            _: &'static str,
+        ) -> Arc<dyn Counter + Send + Sync> {
+            Arc::new(MockInstrument {
+                name,
+                recorded: self.recorded.clone(),
+            })
+        }
+    }
+
+    impl Histogram for MockInstrument {
+        fn record(&self, value: f64, attributes: &[Attribute]) {
+            self.recorded
+                .lock()
+                .unwrap()
+                .push((self.name, value, attributes.len()));
+        }
+    }
+
+    impl Counter for MockInstrument {
+        fn add(&self, value: u64, attributes: &[Attribute]) {
+            self.recorded
+                .lock()
+                .unwrap()
+                .push((self.name, value as f64, attributes.len()));
+        }
+    }
+
+    #[test]
+    fn record_metrics() {
+        let meter_provider = MockMeter::default();
+        let instrumentation = ClientInstrumentation::new(None, Some(&meter_provider));
+        assert!(instrumentation.tracer.is_none());
+
+        let metrics = instrumentation.metrics.unwrap();
+        metrics.record_sent(3, true, "eventhub", None, &Ok(()));
+        metrics.record_consumed("eventhub", "0");
+
+        assert_eq!(
+            *meter_provider.recorded.lock().unwrap(),
+            vec![
+                ("messaging.client.sent.messages", 3.0, 2),
+                ("messaging.batch.message_count", 3.0, 2),
+                ("messaging.client.consumed.messages", 1.0, 3),
+            ]
+        );
+    }
+}
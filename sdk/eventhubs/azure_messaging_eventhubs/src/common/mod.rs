@@ -2,6 +2,7 @@
 // Licensed under the MIT license.
 
 pub(crate) mod authorizer;
+pub(crate) mod diagnostics;
 pub(crate) mod management;
 pub(crate) mod recoverable;
 pub mod retry;
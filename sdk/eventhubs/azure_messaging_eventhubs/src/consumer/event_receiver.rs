@@ -1,15 +1,24 @@
 // Copyright (c) Microsoft Corporation. All Rights reserved
 // Licensed under the MIT license.
 
-use crate::{common::recoverable::RecoverableConnection, models::ReceivedEventData};
+use crate::{
+    common::{
+        diagnostics::{self, ClientInstrumentation},
+        recoverable::RecoverableConnection,
+    },
+    models::ReceivedEventData,
+    OpenReceiverOptions,
+};
 use async_stream::try_stream;
-use azure_core::{error::Result, http::Url, time::Duration};
+use azure_core::{
+    cancellation::CancellationToken, error::Result, http::Url, time::Duration, tracing::SpanKind,
+};
 use azure_core_amqp::{
     AmqpDeliveryApis as _, AmqpReceiverApis as _, AmqpReceiverOptions, AmqpSource,
 };
 use futures::Stream;
 use std::sync::Arc;
-use tracing::trace;
+use tracing::{trace, warn};
 
 /// A message receiver that can be used to receive messages from an Event Hub.
 ///
@@ -57,6 +66,8 @@ pub struct EventReceiver {
     source_url: Url,
     partition_id: String,
     timeout: Option<Duration>,
+    cancellation_token: Option<CancellationToken>,
+    instrumentation: ClientInstrumentation,
 }
 
 impl EventReceiver {
@@ -66,15 +77,18 @@ impl EventReceiver {
         message_source: AmqpSource,
         source_url: Url,
         partition_id: String,
-        timeout: Option<Duration>,
+        options: &OpenReceiverOptions,
+        instrumentation: ClientInstrumentation,
     ) -> Self {
         Self {
+            instrumentation,
             source_url,
             connection,
             receiver_options,
             message_source,
             partition_id,
-            timeout,
+            timeout: options.receive_timeout,
+            cancellation_token: options.cancellation_token.clone(),
         }
     }
 
@@ -87,11 +101,17 @@ impl EventReceiver {
     /// This method returns a stream of [`ReceivedEventData`] that can be used to receive messages from the Event Hub.
     /// The stream will continue to yield messages as long as the receiver is not closed.
     /// The stream will yield an error if there is an issue receiving messages from the Event Hub.
+    /// If the [`OpenReceiverOptions::cancellation_token`] is cancelled, the stream finishes receiving any message already
+    /// in flight and then, before receiving the next one, yields an error with
+    /// [`ErrorKind::Cancelled`](azure_core::error::ErrorKind::Cancelled) and ends.
     ///
     /// # Returns
     ///
     /// A stream of [`ReceivedEventData`] that can be used to receive messages from the Event Hub.
     ///
+    /// If the consumer has a tracer provider, a span is created for each received event and linked to the span that sent it.
+    /// If the consumer has a meter provider, each received event is counted.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -121,6 +141,10 @@ impl EventReceiver {
         // Use async_stream to create a stream that yields messages from the receiver.
         Box::pin(try_stream! {
             loop {
+                // Only check for cancellation between deliveries so the link is never left with a receive in flight.
+                if let Some(token) = &self.cancellation_token {
+                    token.check()?;
+                }
                 let receiver = self.connection.get_receiver(&self.source_url,
                     self.message_source.clone(),
                     self.receiver_options.clone(),
@@ -134,11 +158,45 @@ impl EventReceiver {
                  let message = delivery.into_message();
                  let message = ReceivedEventData::from(message);
                  trace!("Received message: {:?}", message);
+                 self.instrument_received_event(&message);
                  yield message;
             }
         })
     }
 
+    /// Records a received event, and a span linked to the span that sent it.
+    fn instrument_received_event(&self, event: &ReceivedEventData) {
+        // The source URL is amqps://{namespace}/{eventhub}/ConsumerGroups/{consumer_group}/Partitions/{partition_id}.
+        let eventhub = self
+            .source_url
+            .path_segments()
+            .and_then(|mut segments| segments.next())
+            .unwrap_or_default();
+        if let Some(metrics) = &self.instrumentation.metrics {
+            metrics.record_consumed(eventhub, &self.partition_id);
+        }
+
+        let Some(tracer) = &self.instrumentation.tracer else {
+            return;
+        };
+        let links: Vec<_> = event.trace_context().into_iter().collect();
+        let span =
+            match tracer.start_span_with_links("EventHubs.receive", SpanKind::Consumer, &links) {
+                Ok(span) => span,
+                Err(err) => {
+                    warn!("failed to start span for received event: {err}");
+                    return;
+                }
+            };
+        diagnostics::set_messaging_attributes(
+            span.as_ref(),
+            "receive",
+            self.source_url.host_str(),
+            eventhub,
+        );
+        let _ = span.end();
+    }
+
     /// Closes the event receiver, detaching from the remote.
     pub async fn close(self) -> Result<()> {
         self.connection.close_receiver(&self.source_url).await
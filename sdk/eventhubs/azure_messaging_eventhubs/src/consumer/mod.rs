@@ -6,11 +6,14 @@
 pub(crate) mod event_receiver;
 
 use crate::{
-    common::{recoverable::RecoverableConnection, ManagementInstance},
+    common::{
+        diagnostics::ClientInstrumentation, recoverable::RecoverableConnection, ManagementInstance,
+    },
     models::{ConsumerClientDetails, EventHubPartitionProperties, EventHubProperties},
     RetryOptions,
 };
 use azure_core::{
+    cancellation::CancellationToken,
     credentials::TokenCredential,
     error::{Error, ErrorKind as AzureErrorKind, Result},
     http::Url,
@@ -38,6 +41,7 @@ pub struct ConsumerClient {
     endpoint: Url,
     // The instance ID to set.
     instance_id: Option<String>,
+    instrumentation: ClientInstrumentation,
 }
 
 // Clippy complains if a method has too many parameters, so we put some of the
@@ -47,6 +51,7 @@ struct ConsumerClientOptions {
     instance_id: Option<String>,
     retry_options: Option<RetryOptions>,
     custom_endpoint: Option<Url>,
+    instrumentation: ClientInstrumentation,
 }
 
 impl ConsumerClient {
@@ -106,6 +111,7 @@ impl ConsumerClient {
             eventhub: eventhub_name,
             endpoint: url,
             consumer_group,
+            instrumentation: options.instrumentation,
         })
     }
 
@@ -278,7 +284,8 @@ impl ConsumerClient {
             message_source,
             source_url,
             partition_id,
-            options.receive_timeout,
+            &options,
+            self.instrumentation.clone(),
         ))
     }
 
@@ -399,6 +406,13 @@ pub struct OpenReceiverOptions {
     /// Note: This is the timeout for individual messages, not the entire receive operation.
     /// As long as there are messages available, then they will be included in the stream events regardless of the timeout.
     pub receive_timeout: Option<Duration>,
+
+    /// Optional token to stop receiving messages.
+    ///
+    /// Cancellation is checked between messages: once cancelled, the stream returned by [`EventReceiver::stream_events()`]
+    /// returns an error with [`ErrorKind::Cancelled`](azure_core::error::ErrorKind::Cancelled) instead of receiving
+    /// the next message, and then ends.
+    pub cancellation_token: Option<CancellationToken>,
 }
 /// Represents the options for receiving events from an Event Hub.
 impl OpenReceiverOptions {}
@@ -531,7 +545,7 @@ impl StartPosition {
 
 pub mod builders {
     use super::*;
-    use azure_core::Result;
+    use azure_core::{metrics::MeterProvider, tracing::TracerProvider, Result};
     use std::sync::Arc;
 
     /// A builder for creating a [`ConsumerClient`].
@@ -559,6 +573,8 @@ pub mod builders {
         instance_id: Option<String>,
         retry_options: Option<RetryOptions>,
         custom_endpoint: Option<String>,
+        tracer_provider: Option<Arc<dyn TracerProvider + Send + Sync>>,
+        meter_provider: Option<Arc<dyn MeterProvider + Send + Sync>>,
     }
 
     impl ConsumerClientBuilder {
@@ -631,6 +647,29 @@ pub mod builders {
             self
         }
 
+        /// Specifies the tracer provider used to create spans for received events.
+        ///
+        /// Each span is linked to the span that sent the event, if its trace context was propagated
+        /// in the `traceparent` or `Diagnostic-Id` application properties of the event.
+        pub fn with_tracer_provider(
+            mut self,
+            tracer_provider: Arc<dyn TracerProvider + Send + Sync>,
+        ) -> Self {
+            self.tracer_provider = Some(tracer_provider);
+            self
+        }
+
+        /// Specifies the meter provider used to record metrics for received events.
+        ///
+        /// The number of events received is recorded to the `messaging.client.consumed.messages` counter.
+        pub fn with_meter_provider(
+            mut self,
+            meter_provider: Arc<dyn MeterProvider + Send + Sync>,
+        ) -> Self {
+            self.meter_provider = Some(meter_provider);
+            self
+        }
+
         /// Opens a connection to the Event Hub.
         ///
         /// This method establishes a connection to the Event Hubs instance associated
@@ -687,6 +726,10 @@ pub mod builders {
                     instance_id: self.instance_id,
                     retry_options: self.retry_options,
                     custom_endpoint,
+                    instrumentation: ClientInstrumentation::new(
+                        self.tracer_provider.as_deref(),
+                        self.meter_provider.as_deref(),
+                    ),
                 },
             )?;
             consumer.ensure_connection().await?;
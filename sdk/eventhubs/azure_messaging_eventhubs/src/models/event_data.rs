@@ -1,8 +1,11 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT license.
 
-use crate::models::{AmqpMessage, AmqpSimpleValue, AmqpValue, MessageId};
-use azure_core::fmt::SafeDebug;
+use crate::{
+    common::diagnostics,
+    models::{AmqpMessage, AmqpSimpleValue, AmqpValue, MessageId},
+};
+use azure_core::{fmt::SafeDebug, tracing::TraceContext};
 use azure_core_amqp::message::{AmqpAnnotationKey, AmqpMessageBody, AmqpMessageProperties};
 use std::{
     collections::HashMap,
@@ -187,6 +190,14 @@ impl ReceivedEventData {
         &self.message
     }
 
+    /// The W3C trace context of the span that sent the event, if it was propagated
+    /// in the `traceparent` or `Diagnostic-Id` application properties.
+    ///
+    /// Use this to link spans that process the event to the span that sent it.
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        diagnostics::extract(&self.message)
+    }
+
     /// The Event Data contained within the received event.
     ///
     /// Note that the conversion of AMQP message to EventData is deferred until it is needed.
@@ -5,8 +5,13 @@ use std::sync::Mutex;
 
 use super::ProducerClient;
 
-use crate::models::EventData;
-use azure_core::{error::Result, http::Url, Error, Uuid};
+use crate::{common::diagnostics, models::EventData};
+use azure_core::{
+    error::Result,
+    http::Url,
+    tracing::{SpanKind, TraceContext},
+    Error, Uuid,
+};
 use azure_core_amqp::{AmqpMessage, AmqpSenderApis, AmqpSymbol};
 use tracing::debug;
 
@@ -17,6 +22,7 @@ struct EventDataBatchState {
     serialized_messages: Vec<Vec<u8>>,
     size_in_bytes: u64,
     batch_envelope: Option<AmqpMessage>,
+    trace_contexts: Vec<TraceContext>,
 }
 
 /// Represents a collections of event data that can be sent to an Event Hubs instance in one operation.
@@ -67,6 +73,7 @@ impl<'a> EventDataBatch<'a> {
                 serialized_messages: Vec::new(),
                 size_in_bytes: 0,
                 batch_envelope: None,
+                trace_contexts: Vec::new(),
             }),
             max_size_in_bytes: options
                 .as_ref()
@@ -198,6 +205,8 @@ impl<'a> EventDataBatch<'a> {
     /// # Remarks
     /// If the message does not have a message ID, a new message ID is generated for the message.
     /// If the batch has a partition key, the message is assigned the partition key.
+    /// If the producer has a tracer provider and the message does not have a `Diagnostic-Id` application property,
+    /// the message is assigned the trace context of a new span, which the span that sends the batch is linked to.
     ///
     /// # Examples
     ///
@@ -233,6 +242,14 @@ impl<'a> EventDataBatch<'a> {
                 partition_key.clone(),
             );
         }
+        let trace_context = self
+            .producer
+            .start_span("EventHubs.message", "create", SpanKind::Producer, &[])
+            .and_then(|span| {
+                let trace_context = diagnostics::inject(&mut message, &span.trace_context());
+                let _ = span.end();
+                trace_context
+            });
 
         let mut batch_state = self.batch_state.lock().unwrap();
         let message_len = AmqpMessage::serialize(&message)?.len();
@@ -265,6 +282,7 @@ impl<'a> EventDataBatch<'a> {
         }
         batch_state.size_in_bytes += actual_message_size;
         batch_state.serialized_messages.push(serialized_message);
+        batch_state.trace_contexts.extend(trace_context);
 
         Ok(true)
     }
@@ -292,6 +310,15 @@ impl<'a> EventDataBatch<'a> {
         batch_envelope
     }
 
+    /// Takes the trace contexts of the messages in the batch, to which the span sending the batch is linked.
+    pub(crate) fn take_trace_contexts(&self) -> Vec<TraceContext> {
+        std::mem::take(&mut self.batch_state.lock().unwrap().trace_contexts)
+    }
+
+    pub(crate) fn partition_id(&self) -> Option<&str> {
+        self.partition_id.as_deref()
+    }
+
     pub(crate) fn get_batch_path(&self) -> Result<Url> {
         if let Some(partition_id) = self.partition_id.as_ref() {
             let batch_path = format!("{}/Partitions/{}", self.producer.base_url(), partition_id);
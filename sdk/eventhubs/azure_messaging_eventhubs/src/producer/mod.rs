@@ -3,19 +3,26 @@
 
 use crate::{
     common::{
+        diagnostics::{self, ClientInstrumentation},
         recoverable::{RecoverableConnection, RecoverableSender},
         ManagementInstance,
     },
     models::{AmqpMessage, EventData, EventHubPartitionProperties, EventHubProperties},
     RetryOptions,
 };
-use azure_core::{error::Result, http::Url, Uuid};
+use azure_core::{
+    cancellation::CancellationToken,
+    error::Result,
+    http::Url,
+    tracing::{Span, SpanKind, TraceContext},
+    Uuid,
+};
 use azure_core_amqp::{
     error::AmqpErrorKind, AmqpError, AmqpSendOptions, AmqpSendOutcome, AmqpSenderApis,
 };
 use batch::{EventDataBatch, EventDataBatchOptions};
 use std::{fmt::Debug, sync::Arc};
-use tracing::trace;
+use tracing::{trace, warn};
 
 /// Types used to collect messages into a "batch" before submitting them to an Event Hub.
 pub(crate) mod batch;
@@ -24,7 +31,14 @@ pub(crate) const DEFAULT_EVENTHUBS_APPLICATION: &str = "DefaultApplicationName";
 
 #[derive(Default, Debug, Clone)]
 /// Represents the options that can be set when submitting a batch of event data.
-pub struct SendBatchOptions {}
+pub struct SendBatchOptions {
+    /// Optional token to cancel sending the batch.
+    ///
+    /// The token is checked before the batch is transferred, in which case [`ProducerClient::send_batch()`] returns
+    /// an error with [`ErrorKind::Cancelled`](azure_core::error::ErrorKind::Cancelled). Once the transfer has started,
+    /// it is not cancelled so that whether the batch was accepted is known.
+    pub cancellation_token: Option<CancellationToken>,
+}
 
 /// A client that can be used to send events to an Event Hubs instance.
 ///
@@ -56,6 +70,7 @@ pub struct ProducerClient {
     connection: Arc<RecoverableConnection>,
     eventhub: String,
     endpoint: Url,
+    instrumentation: ClientInstrumentation,
 }
 
 /// Options used when sending an event to an Event Hub.
@@ -94,6 +109,7 @@ impl ProducerClient {
         application_id: Option<String>,
         retry_options: RetryOptions,
         custom_endpoint: Option<Url>,
+        instrumentation: ClientInstrumentation,
     ) -> Self {
         Self {
             connection: RecoverableConnection::new(
@@ -105,6 +121,7 @@ impl ProducerClient {
             ),
             eventhub,
             endpoint,
+            instrumentation,
         }
     }
 
@@ -172,7 +189,9 @@ impl ProducerClient {
     /// A `Result` indicating success or failure.
     ///
     /// Note:
-    /// - The message is sent to the service unmodified.
+    /// - The message is sent to the service unmodified, except that if a tracer provider was configured
+    ///   and the message does not already have a `Diagnostic-Id` application property,
+    ///   the `Diagnostic-Id` and `traceparent` application properties are set to the trace context of the send span.
     ///
     pub async fn send_message<M>(
         &self,
@@ -182,7 +201,27 @@ impl ProducerClient {
     where
         M: Into<AmqpMessage> + Debug + Send,
     {
+        let mut message = message.into();
+        let span = self.start_span("EventHubs.send", "send", SpanKind::Producer, &[]);
+        if let Some(span) = &span {
+            diagnostics::inject(&mut message, &span.trace_context());
+        }
+
         let options = options.unwrap_or_default();
+        let partition_id = options.partition_id.clone();
+        let result = self.send_amqp_message(message, options).await;
+        diagnostics::end_span(span, &result);
+        if let Some(metrics) = &self.instrumentation.metrics {
+            metrics.record_sent(1, false, &self.eventhub, partition_id.as_deref(), &result);
+        }
+        result
+    }
+
+    async fn send_amqp_message(
+        &self,
+        message: AmqpMessage,
+        options: SendMessageOptions,
+    ) -> Result<()> {
         let mut target = self.endpoint.clone();
         if let Some(partition_id) = options.partition_id {
             let target_url = format!("{}/Partitions/{}", self.base_url(), partition_id);
@@ -302,11 +341,37 @@ impl ProducerClient {
     pub async fn send_batch(
         &self,
         batch: &EventDataBatch<'_>,
-        #[allow(unused_variables)] options: Option<SendBatchOptions>,
+        options: Option<SendBatchOptions>,
+    ) -> Result<()> {
+        let options = options.unwrap_or_default();
+        let span = self.start_span(
+            "EventHubs.send",
+            "send",
+            SpanKind::Client,
+            &batch.take_trace_contexts(),
+        );
+        let count = batch.len() as u64;
+        let result = self
+            .send_batch_messages(batch, options.cancellation_token.as_ref())
+            .await;
+        diagnostics::end_span(span, &result);
+        if let Some(metrics) = &self.instrumentation.metrics {
+            metrics.record_sent(count, true, &self.eventhub, batch.partition_id(), &result);
+        }
+        result
+    }
+
+    async fn send_batch_messages(
+        &self,
+        batch: &EventDataBatch<'_>,
+        cancellation_token: Option<&CancellationToken>,
     ) -> Result<()> {
+        let check_cancelled = || cancellation_token.map_or(Ok(()), CancellationToken::check);
+        check_cancelled()?;
         let sender = self.connection.get_sender(batch.get_batch_path()?).await?;
 
         let messages = batch.get_messages();
+        check_cancelled()?;
         let outcome = sender
             .send(
                 messages,
@@ -410,6 +475,31 @@ impl ProducerClient {
             .await
     }
 
+    /// Starts a span for an operation if a tracer provider was configured.
+    pub(crate) fn start_span(
+        &self,
+        name: &'static str,
+        operation: &'static str,
+        kind: SpanKind,
+        links: &[TraceContext],
+    ) -> Option<Arc<dyn Span + Send + Sync>> {
+        let tracer = self.instrumentation.tracer.as_ref()?;
+        let span = match tracer.start_span_with_links(name, kind, links) {
+            Ok(span) => span,
+            Err(err) => {
+                warn!("failed to start span for {name}: {err}");
+                return None;
+            }
+        };
+        diagnostics::set_messaging_attributes(
+            span.as_ref(),
+            operation,
+            self.endpoint.host_str(),
+            &self.eventhub,
+        );
+        Some(span)
+    }
+
     pub(crate) fn base_url(&self) -> &Url {
         &self.endpoint
     }
@@ -426,8 +516,8 @@ impl ProducerClient {
 
 pub mod builders {
     use super::ProducerClient;
-    use crate::RetryOptions;
-    use azure_core::{http::Url, Error};
+    use crate::{common::diagnostics::ClientInstrumentation, RetryOptions};
+    use azure_core::{http::Url, metrics::MeterProvider, tracing::TracerProvider, Error};
     use std::sync::Arc;
 
     /// A builder for creating a [`ProducerClient`].
@@ -457,6 +547,12 @@ pub mod builders {
 
         /// The custom endpoint for the Event Hub.
         custom_endpoint: Option<String>,
+
+        /// The tracer provider used to create spans for sent messages.
+        tracer_provider: Option<Arc<dyn TracerProvider + Send + Sync>>,
+
+        /// The meter provider used to record metrics for sent messages.
+        meter_provider: Option<Arc<dyn MeterProvider + Send + Sync>>,
     }
 
     impl ProducerClientBuilder {
@@ -512,6 +608,44 @@ pub mod builders {
             self
         }
 
+        /// Sets the tracer provider used to create spans for sent messages.
+        ///
+        /// # Arguments
+        /// * `tracer_provider` - The tracer provider e.g., from `azure_core_opentelemetry`.
+        ///
+        /// # Returns
+        /// The updated [`ProducerClientBuilder`].
+        ///
+        /// Note: The trace context of each span is propagated to consumers in the
+        /// `Diagnostic-Id` and `traceparent` application properties of the sent messages.
+        ///
+        pub fn with_tracer_provider(
+            mut self,
+            tracer_provider: Arc<dyn TracerProvider + Send + Sync>,
+        ) -> Self {
+            self.tracer_provider = Some(tracer_provider);
+            self
+        }
+
+        /// Sets the meter provider used to record metrics for sent messages.
+        ///
+        /// # Arguments
+        /// * `meter_provider` - The meter provider e.g., from `azure_core_opentelemetry`.
+        ///
+        /// # Returns
+        /// The updated [`ProducerClientBuilder`].
+        ///
+        /// Note: The number of events sent is recorded to the `messaging.client.sent.messages` counter,
+        /// and the number of events in each batch to the `messaging.batch.message_count` histogram.
+        ///
+        pub fn with_meter_provider(
+            mut self,
+            meter_provider: Arc<dyn MeterProvider + Send + Sync>,
+        ) -> Self {
+            self.meter_provider = Some(meter_provider);
+            self
+        }
+
         /// Opens the connection to the Event Hub.
         ///
         /// # Arguments
@@ -543,6 +677,10 @@ pub mod builders {
                 self.application_id,
                 self.retry_options.unwrap_or_default(),
                 custom_endpoint,
+                ClientInstrumentation::new(
+                    self.tracer_provider.as_deref(),
+                    self.meter_provider.as_deref(),
+                ),
             );
 
             // Open a connection to the Event Hub to ensure that the client is ready to send messages.
@@ -0,0 +1,362 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{claims_parameter, user_credential::UserCredential, TokenCredentialOptions};
+use async_lock::Mutex;
+use azure_core::{
+    credentials::{AccessToken, Secret, TokenCredential, TokenRequestOptions},
+    error::{Error, ErrorKind},
+    http::Url,
+};
+use std::sync::Arc;
+
+const AUTHORIZATION_CODE_CREDENTIAL: &str = "AuthorizationCodeCredential";
+
+/// Options for constructing a new [`AuthorizationCodeCredential`].
+#[derive(Debug, Default)]
+pub struct AuthorizationCodeCredentialOptions {
+    /// The client secret of a confidential client application. Public client applications don't have a secret.
+    pub client_secret: Option<Secret>,
+
+    /// Additional tenants for which the credential may acquire tokens.
+    ///
+    /// Add the wildcard value "*" to allow the credential to acquire tokens for any tenant in which the application is registered.
+    pub additionally_allowed_tenants: Vec<String>,
+
+    /// Options for constructing credentials.
+    pub credential_options: TokenCredentialOptions,
+}
+
+/// Authenticates a user by redeeming an authorization code from the
+/// [authorization code flow](https://learn.microsoft.com/entra/identity-platform/v2-oauth2-auth-code-flow).
+///
+/// The application directs the user to sign in with a [PKCE](https://datatracker.ietf.org/doc/html/rfc7636) code challenge,
+/// then creates this credential with the authorization code Microsoft Entra ID sends to the redirect URI and the matching code verifier.
+/// The code can be redeemed only once, so the refresh token from Microsoft Entra ID is cached to get tokens for other scopes.
+#[derive(Debug)]
+pub struct AuthorizationCodeCredential {
+    client: UserCredential,
+    redirect_uri: Url,
+    /// The authorization code and code verifier until Microsoft Entra ID answers a request to redeem the code.
+    authorization_code: Mutex<Option<(Secret, Secret)>>,
+}
+
+impl AuthorizationCodeCredential {
+    /// Create a new `AuthorizationCodeCredential`.
+    pub fn new(
+        tenant_id: &str,
+        client_id: String,
+        authorization_code: Secret,
+        code_verifier: Secret,
+        redirect_uri: Url,
+        options: Option<AuthorizationCodeCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        crate::validate_not_empty(
+            authorization_code.secret(),
+            "no authorization code specified",
+        )?;
+        crate::validate_not_empty(code_verifier.secret(), "no code verifier specified")?;
+
+        let options = options.unwrap_or_default();
+        Ok(Arc::new(Self {
+            client: UserCredential::new(
+                AUTHORIZATION_CODE_CREDENTIAL,
+                tenant_id,
+                client_id,
+                options.client_secret,
+                options.additionally_allowed_tenants,
+                options.credential_options,
+            )?,
+            redirect_uri,
+            authorization_code: Mutex::new(Some((authorization_code, code_verifier))),
+        }))
+    }
+
+    async fn get_token_impl(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        let client = &self.client;
+        let tenant_id = client.tenant_id(options.as_ref())?;
+        let claims = claims_parameter(options.as_ref())?;
+        if let Some(token) = client.refresh(tenant_id, scopes, claims.as_deref()).await? {
+            return Ok(token);
+        }
+
+        // Hold the lock while redeeming the code so concurrent requests don't redeem it twice.
+        let mut authorization_code = self.authorization_code.lock().await;
+        let Some((code, code_verifier)) = authorization_code.as_ref() else {
+            // Another request may have redeemed the code while this one waited.
+            if let Some(token) = client.refresh(tenant_id, scopes, claims.as_deref()).await? {
+                return Ok(token);
+            }
+            return Err(Error::message(
+                ErrorKind::Credential,
+                format!(
+                    "{AUTHORIZATION_CODE_CREDENTIAL} authentication failed. The authorization code was already redeemed and there's no valid refresh token for tenant {tenant_id}. The user must sign in again."
+                ),
+            ));
+        };
+
+        let req = client.token_request(
+            tenant_id,
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", code.secret()),
+                ("code_verifier", code_verifier.secret()),
+                ("redirect_uri", self.redirect_uri.as_str()),
+            ],
+            scopes,
+            claims.as_deref(),
+        )?;
+        let res = client.send(&req).await?;
+
+        // Microsoft Entra ID has answered, so the code can't be redeemed again even if it was rejected.
+        *authorization_code = None;
+        drop(authorization_code);
+        client.token_from_response(tenant_id, res).await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for AuthorizationCodeCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        if scopes.is_empty() {
+            return Err(Error::message(ErrorKind::Credential, "no scopes specified"));
+        }
+        self.client
+            .cache
+            .get_token(scopes, options, |s, o| self.get_token_impl(s, o))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use azure_core::http::{HttpClient, RawResponse, StatusCode};
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt as _;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const FAKE_CODE: &str = "fake code";
+    const FAKE_CODE_VERIFIER: &str = "fake code verifier";
+    const FAKE_REFRESH_TOKEN: &str = "fake refresh token";
+    const REDIRECT_URI: &str = "http://localhost:8400/";
+
+    fn token_response() -> RawResponse {
+        response(
+            StatusCode::Ok,
+            format!(
+                r#"{{"access_token":"{FAKE_TOKEN}","refresh_token":"{FAKE_REFRESH_TOKEN}","expires_in":3600,"token_type":"Bearer"}}"#
+            ),
+        )
+    }
+
+    fn error_response(status: StatusCode, error: &str) -> RawResponse {
+        response(
+            status,
+            format!(r#"{{"error":"{error}","error_description":"{error} description"}}"#),
+        )
+    }
+
+    fn credential(
+        client: impl HttpClient + 'static,
+        client_secret: Option<Secret>,
+    ) -> Arc<AuthorizationCodeCredential> {
+        AuthorizationCodeCredential::new(
+            FAKE_TENANT_ID,
+            FAKE_CLIENT_ID.to_string(),
+            FAKE_CODE.into(),
+            FAKE_CODE_VERIFIER.into(),
+            REDIRECT_URI.parse().unwrap(),
+            Some(AuthorizationCodeCredentialOptions {
+                client_secret,
+                credential_options: TokenCredentialOptions {
+                    http_client: Arc::new(client),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        )
+        .expect("valid credential")
+    }
+
+    #[tokio::test]
+    async fn redeems_code_then_refresh_token() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let requests_clone = requests.clone();
+        let client = MockHttpClient::new(move |req| {
+            let n = requests_clone.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let form = form(req);
+                assert_eq!(form["client_id"], FAKE_CLIENT_ID);
+                assert!(!form.contains_key("client_secret"));
+                match n {
+                    0 => {
+                        assert_eq!(form["grant_type"], "authorization_code");
+                        assert_eq!(form["code"], FAKE_CODE);
+                        assert_eq!(form["code_verifier"], FAKE_CODE_VERIFIER);
+                        assert_eq!(form["redirect_uri"], REDIRECT_URI);
+                        assert_eq!(
+                            form["scope"],
+                            format!("{} offline_access", LIVE_TEST_SCOPES.join(" "))
+                        );
+                    }
+                    1 => {
+                        assert_eq!(form["grant_type"], "refresh_token");
+                        assert_eq!(form["refresh_token"], FAKE_REFRESH_TOKEN);
+                        assert_eq!(form["scope"], "other/.default offline_access");
+                        assert!(!form.contains_key("code"));
+                    }
+                    _ => panic!("unexpected request"),
+                }
+                Ok(token_response())
+            }
+            .boxed()
+        });
+        let credential = credential(client, None);
+
+        for scopes in [LIVE_TEST_SCOPES, LIVE_TEST_SCOPES, &["other/.default"]] {
+            let token = credential.get_token(scopes, None).await.expect("token");
+            assert_eq!(token.token.secret(), FAKE_TOKEN);
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn client_secret() {
+        let client = MockHttpClient::new(|req| {
+            async move {
+                assert_eq!(form(req)["client_secret"], "fake secret");
+                Ok(token_response())
+            }
+            .boxed()
+        });
+        credential(client, Some("fake secret".into()))
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+    }
+
+    #[tokio::test]
+    async fn code_redeemed_once() {
+        let client = MockHttpClient::new(|_| {
+            async move {
+                Ok(response(
+                    StatusCode::BadRequest,
+                    r#"{"error":"invalid_grant","error_description":"AADSTS70008: The provided authorization code has expired."}"#.to_string(),
+                ))
+            }
+            .boxed()
+        });
+        let credential = credential(client, None);
+
+        let err = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect_err("expired code");
+        assert_eq!(
+            err.to_string(),
+            "AuthorizationCodeCredential authentication failed. AADSTS70008: The provided authorization code has expired."
+        );
+
+        let err = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect_err("code already redeemed");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        assert!(err.to_string().contains("already redeemed"));
+    }
+
+    #[tokio::test]
+    async fn code_kept_after_network_error() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let requests_clone = requests.clone();
+        let client = MockHttpClient::new(move |req| {
+            let n = requests_clone.fetch_add(1, Ordering::SeqCst);
+            async move {
+                assert_eq!(form(req)["code"], FAKE_CODE);
+                match n {
+                    0 => Err(azure_core::Error::message(
+                        ErrorKind::Io,
+                        "connection reset",
+                    )),
+                    _ => Ok(token_response()),
+                }
+            }
+            .boxed()
+        });
+        let credential = credential(client, None);
+
+        let err = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect_err("network error");
+        assert_eq!(err.kind(), &ErrorKind::Io);
+
+        // Microsoft Entra ID didn't answer, so the code can still be redeemed.
+        credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn refresh_token_kept_after_transient_error() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let requests_clone = requests.clone();
+        let client = MockHttpClient::new(move |req| {
+            let n = requests_clone.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let form = form(req);
+                match n {
+                    0 => Ok(token_response()),
+                    1 => {
+                        assert_eq!(form["refresh_token"], FAKE_REFRESH_TOKEN);
+                        Ok(error_response(
+                            StatusCode::ServiceUnavailable,
+                            "temporarily_unavailable",
+                        ))
+                    }
+                    2 => {
+                        assert_eq!(form["refresh_token"], FAKE_REFRESH_TOKEN);
+                        Ok(error_response(StatusCode::BadRequest, "invalid_grant"))
+                    }
+                    _ => panic!("unexpected request"),
+                }
+            }
+            .boxed()
+        });
+        let credential = credential(client, None);
+        credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+
+        let err = credential
+            .get_token(&["other/.default"], None)
+            .await
+            .expect_err("service unavailable");
+        assert_eq!(
+            err.to_string(),
+            "AuthorizationCodeCredential authentication failed. temporarily_unavailable description"
+        );
+
+        // The refresh token is redeemed again, and removed only once it's rejected.
+        let err = credential
+            .get_token(&["other/.default"], None)
+            .await
+            .expect_err("invalid grant");
+        assert!(err.to_string().contains("already redeemed"));
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+}
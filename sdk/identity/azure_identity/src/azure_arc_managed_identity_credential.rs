@@ -0,0 +1,360 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    env::Env, scopes_to_resource, token_from_response, ImdsId, TokenCache, TokenCredentialOptions,
+};
+use azure_core::{
+    credentials::{AccessToken, TokenCredential, TokenRequestOptions},
+    error::{http_response_from_body, Error, ErrorKind, ResultExt},
+    http::{
+        headers::{self, HeaderName},
+        HttpClient, Method, Request, StatusCode, Url,
+    },
+};
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+const ENDPOINT_ENV: &str = "IDENTITY_ENDPOINT";
+const API_VERSION: &str = "2020-06-01";
+const WWW_AUTHENTICATE: HeaderName = HeaderName::from_static("www-authenticate");
+
+/// The largest challenge file the Azure Connected Machine agent writes.
+const MAX_KEY_FILE_SIZE: u64 = 4096;
+
+/// Authenticates the managed identity of an Azure Arc-enabled server.
+///
+/// The Azure Connected Machine agent challenges the first request with the path of a file only readable
+/// by privileged users, and the contents of that file authenticate a second request.
+/// See <https://learn.microsoft.com/azure/azure-arc/servers/managed-identity-authentication>.
+#[derive(Debug)]
+pub(crate) struct AzureArcManagedIdentityCredential {
+    http_client: Arc<dyn HttpClient>,
+    endpoint: Url,
+    key_directory: PathBuf,
+    cache: TokenCache,
+}
+
+impl AzureArcManagedIdentityCredential {
+    pub fn new(
+        id: ImdsId,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        let options = options.into();
+        let key_directory = key_directory(options.env())?;
+        Self::with_key_directory(id, options, key_directory)
+    }
+
+    fn with_key_directory(
+        id: ImdsId,
+        options: TokenCredentialOptions,
+        key_directory: PathBuf,
+    ) -> azure_core::Result<Arc<Self>> {
+        if !matches!(id, ImdsId::SystemAssigned) {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "User-assigned identities aren't supported for Azure Arc. Use the system-assigned identity instead.",
+            ));
+        }
+        let endpoint = options
+            .env()
+            .var(ENDPOINT_ENV)
+            .with_context(ErrorKind::Credential, || {
+                format!("Azure Arc credential requires {ENDPOINT_ENV} environment variable")
+            })?;
+        let endpoint = Url::parse(&endpoint).with_context(ErrorKind::Credential, || {
+            format!(
+                "Azure Arc credential {ENDPOINT_ENV} environment variable must be a valid URL, but is '{endpoint}'"
+            )
+        })?;
+        Ok(Arc::new(Self {
+            http_client: options.http_client(),
+            endpoint,
+            key_directory,
+            cache: TokenCache::new(),
+        }))
+    }
+
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        _: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        let resource = scopes_to_resource(scopes)?;
+
+        let mut url = self.endpoint.clone();
+        url.query_pairs_mut()
+            .append_pair("api-version", API_VERSION)
+            .append_pair("resource", resource);
+        let mut req = Request::new(url, Method::Get);
+        req.insert_header("metadata", "true");
+
+        let rsp = self.http_client.execute_request(&req).await?;
+        if rsp.status() != StatusCode::Unauthorized {
+            let (status, _, body) = rsp.deconstruct();
+            let body = body.collect().await?;
+            return Err(Error::full(
+                ErrorKind::Credential,
+                http_response_from_body(status, &body).into_error(),
+                "Azure Arc didn't respond with the expected authentication challenge",
+            ));
+        }
+        let challenge = rsp
+            .headers()
+            .get_optional_str(&WWW_AUTHENTICATE)
+            .ok_or_else(|| {
+                Error::message(
+                    ErrorKind::Credential,
+                    "Azure Arc challenge is missing the WWW-Authenticate header",
+                )
+            })?;
+        let key = self.read_key(challenge)?;
+
+        req.insert_header(headers::AUTHORIZATION, format!("Basic {key}"));
+        let rsp = self.http_client.execute_request(&req).await?;
+        token_from_response(rsp).await
+    }
+
+    /// Reads the key from the file named by a `Basic realm=<path>` challenge after validating the file.
+    fn read_key(&self, challenge: &str) -> azure_core::Result<String> {
+        let path = challenge
+            .split_once('=')
+            .filter(|(scheme, _)| scheme.trim().eq_ignore_ascii_case("Basic realm"))
+            .map(|(_, path)| Path::new(path.trim()))
+            .ok_or_else(|| {
+                Error::with_message(ErrorKind::Credential, || {
+                    format!("unexpected Azure Arc challenge '{challenge}'")
+                })
+            })?;
+
+        if path.parent() != Some(self.key_directory.as_path()) {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "Azure Arc challenge file {} isn't in the expected directory {}",
+                    path.display(),
+                    self.key_directory.display()
+                )
+            }));
+        }
+        if path.extension() != Some(OsStr::new("key")) {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "Azure Arc challenge file {} doesn't have the .key extension",
+                    path.display()
+                )
+            }));
+        }
+        let metadata = fs::metadata(path).with_context(ErrorKind::Credential, || {
+            format!("failed to read Azure Arc challenge file {}", path.display())
+        })?;
+        if metadata.len() > MAX_KEY_FILE_SIZE {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "Azure Arc challenge file {} is larger than {MAX_KEY_FILE_SIZE} bytes",
+                    path.display()
+                )
+            }));
+        }
+        fs::read_to_string(path).with_context(ErrorKind::Credential, || {
+            format!("failed to read Azure Arc challenge file {}", path.display())
+        })
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for AzureArcManagedIdentityCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        self.cache
+            .get_token(scopes, options, |s, o| self.get_token(s, o))
+            .await
+    }
+}
+
+/// Gets the directory in which the Azure Connected Machine agent writes challenge files.
+fn key_directory(env: &Env) -> azure_core::Result<PathBuf> {
+    if cfg!(target_os = "linux") {
+        Ok(PathBuf::from("/var/opt/azcmagent/tokens"))
+    } else if cfg!(windows) {
+        let program_data = env.var("ProgramData").context(
+            ErrorKind::Credential,
+            "Azure Arc credential requires ProgramData environment variable",
+        )?;
+        Ok(PathBuf::from(program_data)
+            .join("AzureConnectedMachineAgent")
+            .join("Tokens"))
+    } else {
+        Err(Error::message(
+            ErrorKind::Credential,
+            "Azure Arc managed identity is supported only on Linux and Windows",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{env::Env, tests::*};
+    use azure_core::{
+        http::{headers::Headers, RawResponse},
+        Bytes,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt as _;
+    use std::{
+        env,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    const ENDPOINT: &str = "http://localhost:40342/metadata/identity/oauth2/token";
+    const KEY: &str = "challenge key";
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = env::temp_dir().join(format!("azure_identity_arc_{name}"));
+            fs::create_dir_all(&path).expect("create temp dir");
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn credential(
+        key_directory: &Path,
+        challenge: String,
+    ) -> (Arc<AzureArcManagedIdentityCredential>, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let requests_clone = requests.clone();
+        let client = MockHttpClient::new(move |req| {
+            let n = requests_clone.fetch_add(1, Ordering::SeqCst);
+            let challenge = challenge.clone();
+            async move {
+                assert_eq!(req.headers().get_str(&"metadata".into()).unwrap(), "true");
+                let query: Vec<_> = req.url().query_pairs().into_owned().collect();
+                assert!(query.contains(&("api-version".to_string(), API_VERSION.to_string())));
+                assert!(query.contains(&("resource".to_string(), LIVE_TEST_RESOURCE.to_string())));
+
+                let authorization = req.headers().get_optional_str(&headers::AUTHORIZATION);
+                if n == 0 {
+                    assert!(authorization.is_none());
+                    let mut headers = Headers::new();
+                    headers.insert(WWW_AUTHENTICATE, challenge);
+                    return Ok(RawResponse::from_bytes(
+                        StatusCode::Unauthorized,
+                        headers,
+                        Bytes::new(),
+                    ));
+                }
+                assert_eq!(authorization, Some(format!("Basic {KEY}").as_str()));
+                Ok(RawResponse::from_bytes(
+                    StatusCode::Ok,
+                    Headers::default(),
+                    Bytes::from(format!(
+                        r#"{{"access_token":"{FAKE_TOKEN}","expires_in":"3600","expires_on":"4102444800","resource":"{LIVE_TEST_RESOURCE}","token_type":"Bearer"}}"#
+                    )),
+                ))
+            }
+            .boxed()
+        });
+        let credential = AzureArcManagedIdentityCredential::with_key_directory(
+            ImdsId::SystemAssigned,
+            TokenCredentialOptions {
+                env: Env::from(&[(ENDPOINT_ENV, ENDPOINT)][..]),
+                http_client: Arc::new(client),
+                ..Default::default()
+            },
+            key_directory.to_path_buf(),
+        )
+        .expect("valid credential");
+        (credential, requests)
+    }
+
+    #[tokio::test]
+    async fn challenge() {
+        let dir = TempDir::new("challenge");
+        let key_file = dir.0.join("challenge.key");
+        fs::write(&key_file, KEY).expect("write key file");
+
+        let (credential, requests) =
+            credential(&dir.0, format!("Basic realm={}", key_file.display()));
+        let credential: Arc<dyn TokenCredential> = credential;
+        for _ in 0..2 {
+            let token = credential
+                .get_token(LIVE_TEST_SCOPES, None)
+                .await
+                .expect("token");
+            assert_eq!(token.token.secret(), FAKE_TOKEN);
+            assert_eq!(token.expires_on.unix_timestamp(), 4102444800);
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn invalid_challenge_file() {
+        let dir = TempDir::new("invalid");
+        let other_dir = TempDir::new("invalid_other");
+
+        let wrong_directory = other_dir.0.join("challenge.key");
+        fs::write(&wrong_directory, KEY).expect("write key file");
+        let wrong_extension = dir.0.join("challenge.txt");
+        fs::write(&wrong_extension, KEY).expect("write key file");
+        let too_large = dir.0.join("large.key");
+        fs::write(&too_large, vec![b'k'; MAX_KEY_FILE_SIZE as usize + 1]).expect("write key file");
+
+        for (challenge, expected) in [
+            (
+                format!("Basic realm={}", wrong_directory.display()),
+                "isn't in the expected directory",
+            ),
+            (
+                format!("Basic realm={}", wrong_extension.display()),
+                "doesn't have the .key extension",
+            ),
+            (
+                format!("Basic realm={}", too_large.display()),
+                "is larger than 4096 bytes",
+            ),
+            (
+                format!("Bearer {}", dir.0.join("challenge.key").display()),
+                "unexpected Azure Arc challenge",
+            ),
+        ] {
+            let (credential, requests) = credential(&dir.0, challenge);
+            let err = credential
+                .get_token(LIVE_TEST_SCOPES, None)
+                .await
+                .expect_err("invalid challenge");
+            assert_eq!(err.kind(), &ErrorKind::Credential);
+            assert!(err.to_string().contains(expected), "{err}");
+            assert_eq!(requests.load(Ordering::SeqCst), 1);
+        }
+    }
+
+    #[test]
+    fn user_assigned_id() {
+        let err = AzureArcManagedIdentityCredential::with_key_directory(
+            ImdsId::ClientId("client".to_string()),
+            TokenCredentialOptions {
+                env: Env::from(&[(ENDPOINT_ENV, ENDPOINT)][..]),
+                ..Default::default()
+            },
+            PathBuf::new(),
+        )
+        .expect_err("user-assigned identity");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+    }
+}
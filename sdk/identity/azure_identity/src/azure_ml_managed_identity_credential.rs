@@ -0,0 +1,108 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    env::Env, scopes_to_resource, token_from_response, ImdsId, TokenCache, TokenCredentialOptions,
+};
+use azure_core::{
+    credentials::{AccessToken, Secret, TokenCredential, TokenRequestOptions},
+    error::{Error, ErrorKind, ResultExt},
+    http::{HttpClient, Method, Request, Url},
+};
+use std::sync::Arc;
+
+const ENDPOINT_ENV: &str = "MSI_ENDPOINT";
+const SECRET_ENV: &str = "MSI_SECRET";
+const API_VERSION: &str = "2017-09-01";
+
+/// Authenticates the managed identity of an Azure Machine Learning compute resource.
+#[derive(Debug)]
+pub(crate) struct AzureMLManagedIdentityCredential {
+    http_client: Arc<dyn HttpClient>,
+    endpoint: Url,
+    secret: Secret,
+    client_id: Option<String>,
+    cache: TokenCache,
+}
+
+impl AzureMLManagedIdentityCredential {
+    pub fn new(
+        id: ImdsId,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        let client_id = match id {
+            ImdsId::SystemAssigned => None,
+            ImdsId::ClientId(client_id) => Some(client_id),
+            ImdsId::ObjectId(_) | ImdsId::MsiResId(_) => {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "Azure ML supports only client IDs for user-assigned identities.",
+                ))
+            }
+        };
+        let options = options.into();
+        let env = options.env();
+        let endpoint = endpoint(env)?;
+        let secret = env
+            .var(SECRET_ENV)
+            .with_context(ErrorKind::Credential, || {
+                format!("Azure ML credential requires {SECRET_ENV} environment variable")
+            })?;
+        Ok(Arc::new(Self {
+            http_client: options.http_client(),
+            endpoint,
+            secret: secret.into(),
+            client_id,
+            cache: TokenCache::new(),
+        }))
+    }
+
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        _: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        let resource = scopes_to_resource(scopes)?;
+
+        let mut url = self.endpoint.clone();
+        url.query_pairs_mut()
+            .append_pair("api-version", API_VERSION)
+            .append_pair("resource", resource);
+        if let Some(client_id) = &self.client_id {
+            url.query_pairs_mut().append_pair("clientid", client_id);
+        }
+
+        let mut req = Request::new(url, Method::Get);
+        req.insert_header("secret", self.secret.secret().to_string());
+
+        let rsp = self.http_client.execute_request(&req).await?;
+        token_from_response(rsp).await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for AzureMLManagedIdentityCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        self.cache
+            .get_token(scopes, options, |s, o| self.get_token(s, o))
+            .await
+    }
+}
+
+fn endpoint(env: &Env) -> azure_core::Result<Url> {
+    let endpoint = env
+        .var(ENDPOINT_ENV)
+        .with_context(ErrorKind::Credential, || {
+            format!("Azure ML credential requires {ENDPOINT_ENV} environment variable")
+        })?;
+    Url::parse(&endpoint).with_context(ErrorKind::Credential, || {
+        format!(
+            "Azure ML credential {ENDPOINT_ENV} environment variable must be a valid URL, but is '{endpoint}'"
+        )
+    })
+}
@@ -2,14 +2,26 @@
 // Licensed under the MIT License.
 
 use async_lock::RwLock;
-use azure_core::credentials::{AccessToken, TokenRequestOptions};
+use azure_core::credentials::{AccessToken, Secret, TokenRequestOptions};
 use azure_core::time::{Duration, OffsetDateTime};
 use std::collections::HashMap;
 use std::future::Future;
 use tracing::trace;
 
 #[derive(Debug)]
-pub(crate) struct TokenCache(RwLock<HashMap<Vec<String>, AccessToken>>);
+pub(crate) struct TokenCache {
+    tokens: RwLock<HashMap<CacheKey, AccessToken>>,
+    /// Refresh tokens for credentials that authenticate users, per tenant.
+    refresh_tokens: RwLock<HashMap<String, Secret>>,
+}
+
+/// Tokens are cached per scopes, tenant, and whether CAE is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    scopes: Vec<String>,
+    tenant_id: Option<String>,
+    enable_cae: bool,
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) trait MaybeSend: Send {}
@@ -23,7 +35,10 @@ impl<T> MaybeSend for T {}
 
 impl TokenCache {
     pub(crate) fn new() -> Self {
-        Self(RwLock::new(HashMap::new()))
+        Self {
+            tokens: RwLock::new(HashMap::new()),
+            refresh_tokens: RwLock::new(HashMap::new()),
+        }
     }
 
     pub(crate) async fn get_token<'a, C, F>(
@@ -36,9 +51,11 @@ impl TokenCache {
         C: FnOnce(&'a [&'a str], Option<TokenRequestOptions>) -> F + MaybeSend,
         F: Future<Output = azure_core::Result<AccessToken>> + MaybeSend,
     {
-        let token_cache = self.0.read().await;
-        let scopes_owned = scopes.iter().map(ToString::to_string).collect::<Vec<_>>();
-        if let Some(token) = token_cache.get(&scopes_owned) {
+        let key = CacheKey::new(scopes, options.as_ref());
+        let has_claims = has_claims(options.as_ref());
+
+        let token_cache = self.tokens.read().await;
+        if let Some(token) = token_cache.get(&key).filter(|_| !has_claims) {
             if !should_refresh(token) {
                 trace!("returning cached token");
                 return Ok(token.clone());
@@ -47,11 +64,11 @@ impl TokenCache {
 
         // otherwise, drop the read lock and get a write lock to refresh the token
         drop(token_cache);
-        let mut token_cache = self.0.write().await;
+        let mut token_cache = self.tokens.write().await;
 
         // check again in case another thread refreshed the token while we were
         // waiting on the write lock
-        if let Some(token) = token_cache.get(&scopes_owned) {
+        if let Some(token) = token_cache.get(&key).filter(|_| !has_claims) {
             if !should_refresh(token) {
                 trace!("returning token that was updated while waiting on write lock");
                 return Ok(token.clone());
@@ -60,9 +77,69 @@ impl TokenCache {
 
         trace!("token cache miss");
         let token = callback(scopes, options).await?;
-        token_cache.insert(scopes_owned, token.clone());
+        token_cache.insert(key, token.clone());
         Ok(token)
     }
+
+    /// Gets a cached token that doesn't need to be refreshed, if any.
+    ///
+    /// Unlike [`TokenCache::get_token`], this doesn't hold the cache's write lock while a new token is acquired,
+    /// so a credential that waits for user interaction doesn't block requests for other cached tokens.
+    /// Add the new token with [`TokenCache::insert_token`].
+    pub(crate) async fn cached_token(
+        &self,
+        scopes: &[&str],
+        options: Option<&TokenRequestOptions>,
+    ) -> Option<AccessToken> {
+        if has_claims(options) {
+            return None;
+        }
+        let key = CacheKey::new(scopes, options);
+        self.tokens
+            .read()
+            .await
+            .get(&key)
+            .filter(|token| !should_refresh(token))
+            .cloned()
+    }
+
+    /// Caches a token acquired after [`TokenCache::cached_token`] returned `None`.
+    pub(crate) async fn insert_token(
+        &self,
+        scopes: &[&str],
+        options: Option<&TokenRequestOptions>,
+        token: AccessToken,
+    ) {
+        let key = CacheKey::new(scopes, options);
+        self.tokens.write().await.insert(key, token);
+    }
+
+    /// Gets the refresh token cached for a tenant, if any.
+    pub(crate) async fn refresh_token(&self, tenant_id: &str) -> Option<Secret> {
+        self.refresh_tokens.read().await.get(tenant_id).cloned()
+    }
+
+    /// Caches a refresh token for a tenant, replacing any previous refresh token.
+    pub(crate) async fn set_refresh_token(&self, tenant_id: &str, refresh_token: Secret) {
+        self.refresh_tokens
+            .write()
+            .await
+            .insert(tenant_id.to_string(), refresh_token);
+    }
+
+    /// Returns `true` if no cached access token is valid at `now`, so the cache can be discarded.
+    ///
+    /// A cache in use by another task is never expired.
+    pub(crate) fn is_expired(&self, now: OffsetDateTime) -> bool {
+        self.tokens
+            .try_read()
+            .is_some_and(|tokens| tokens.values().all(|token| token.expires_on <= now))
+    }
+
+    /// Removes the refresh token cached for a tenant e.g., because it was revoked.
+    pub(crate) async fn remove_refresh_token(&self, tenant_id: &str) {
+        self.refresh_tokens.write().await.remove(tenant_id);
+    }
 }
 
 impl Default for TokenCache {
@@ -71,6 +148,21 @@ impl Default for TokenCache {
     }
 }
 
+impl CacheKey {
+    fn new(scopes: &[&str], options: Option<&TokenRequestOptions>) -> Self {
+        Self {
+            scopes: scopes.iter().map(ToString::to_string).collect(),
+            tenant_id: options.and_then(|o| o.tenant_id.clone()),
+            enable_cae: options.is_some_and(|o| o.enable_cae),
+        }
+    }
+}
+
+/// Whether a claims challenge requires a new token, which replaces any cached token.
+fn has_claims(options: Option<&TokenRequestOptions>) -> bool {
+    options.is_some_and(|o| o.claims.as_ref().is_some_and(|c| !c.is_empty()))
+}
+
 fn should_refresh(token: &AccessToken) -> bool {
     token.expires_on <= OffsetDateTime::now_utc() + Duration::seconds(300)
 }
@@ -101,15 +193,19 @@ mod tests {
         async fn get_token(
             &self,
             scopes: &[&str],
-            _: Option<TokenRequestOptions>,
+            options: Option<TokenRequestOptions>,
         ) -> azure_core::Result<AccessToken> {
+            let tenant_id = options
+                .and_then(|o| o.tenant_id)
+                .unwrap_or_else(|| "default".to_string());
             // Include an incrementing counter in the token to track how many times the token has been refreshed
             let mut call_count = self.get_token_call_count.lock().await;
             *call_count += 1;
             Ok(AccessToken {
                 token: Secret::new(format!(
-                    "{}-{}:{}",
+                    "{}-{}-{}:{}",
                     scopes.join(" "),
+                    tenant_id,
                     self.token.token.secret(),
                     *call_count
                 )),
@@ -141,7 +237,7 @@ mod tests {
             .get_token(resource1, None, |s, o| mock_credential.get_token(s, o))
             .await?;
 
-        let expected_token = format!("{}-{}:1", resource1.join(" "), secret_string);
+        let expected_token = format!("{}-default-{}:1", resource1.join(" "), secret_string);
         assert_eq!(token1.token.secret(), expected_token);
         assert_eq!(token2.token.secret(), expected_token);
 
@@ -153,7 +249,7 @@ mod tests {
         let token4 = cache
             .get_token(resource2, None, |s, o| mock_credential.get_token(s, o))
             .await?;
-        let expected_token = format!("{}-{}:2", resource2.join(" "), secret_string);
+        let expected_token = format!("{}-default-{}:2", resource2.join(" "), secret_string);
         assert_eq!(token3.token.secret(), expected_token);
         assert_eq!(token4.token.secret(), expected_token);
 
@@ -178,10 +274,75 @@ mod tests {
                 .await?;
             assert_eq!(
                 token.token.secret(),
-                format!("{}-{}:{}", resource.join(" "), access_token, i)
+                format!("{}-default-{}:{}", resource.join(" "), access_token, i)
             );
         }
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_get_token_different_options() -> azure_core::Result<()> {
+        let resource = &[STORAGE_TOKEN_SCOPE];
+        let expires_on = OffsetDateTime::now_utc() + Duration::seconds(3600);
+        let mock_credential = MockCredential::new(AccessToken::new("test-token", expires_on));
+        let cache = TokenCache::new();
+
+        let tenant = || {
+            Some(TokenRequestOptions {
+                tenant_id: Some("tenant".into()),
+                ..Default::default()
+            })
+        };
+        let cae = || {
+            Some(TokenRequestOptions {
+                enable_cae: true,
+                ..Default::default()
+            })
+        };
+        let claims = || {
+            Some(TokenRequestOptions {
+                claims: Some("{}".into()),
+                enable_cae: true,
+                ..Default::default()
+            })
+        };
+
+        let mut tokens = Vec::new();
+        for options in [None, tenant(), cae(), None, tenant(), cae()] {
+            let token = cache
+                .get_token(resource, options, |s, o| mock_credential.get_token(s, o))
+                .await?;
+            tokens.push(token.token.secret().to_string());
+        }
+        assert_eq!(
+            tokens,
+            [
+                format!("{STORAGE_TOKEN_SCOPE}-default-test-token:1"),
+                format!("{STORAGE_TOKEN_SCOPE}-tenant-test-token:2"),
+                format!("{STORAGE_TOKEN_SCOPE}-default-test-token:3"),
+                format!("{STORAGE_TOKEN_SCOPE}-default-test-token:1"),
+                format!("{STORAGE_TOKEN_SCOPE}-tenant-test-token:2"),
+                format!("{STORAGE_TOKEN_SCOPE}-default-test-token:3"),
+            ]
+        );
+
+        // claims bypass the cache and replace the cached token
+        let token = cache
+            .get_token(resource, claims(), |s, o| mock_credential.get_token(s, o))
+            .await?;
+        assert_eq!(
+            token.token.secret(),
+            format!("{STORAGE_TOKEN_SCOPE}-default-test-token:4")
+        );
+        let token = cache
+            .get_token(resource, cae(), |s, o| mock_credential.get_token(s, o))
+            .await?;
+        assert_eq!(
+            token.token.secret(),
+            format!("{STORAGE_TOKEN_SCOPE}-default-test-token:4")
+        );
+
+        Ok(())
+    }
 }
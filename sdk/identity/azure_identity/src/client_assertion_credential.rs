@@ -2,12 +2,13 @@
 // Licensed under the MIT License.
 
 use crate::{
-    deserialize, validate_not_empty, validate_tenant_id, EntraIdErrorResponse,
-    EntraIdTokenResponse, TokenCache, TokenCredentialOptions,
+    claims_parameter, deserialize, resolve_tenant_id, token_endpoint, validate_not_empty,
+    validate_tenant_id, EntraIdErrorResponse, EntraIdTokenResponse, TokenCache,
+    TokenCredentialOptions,
 };
 use azure_core::{
     credentials::{AccessToken, TokenCredential, TokenRequestOptions},
-    error::{ErrorKind, ResultExt},
+    error::ErrorKind,
     http::{
         headers::{self, content_type},
         Method, Request, StatusCode, Url,
@@ -24,8 +25,10 @@ const CLIENT_ASSERTION_CREDENTIAL: &str = "ClientAssertionCredential";
 /// Enables authentication of a Microsoft Entra service principal using a signed client assertion.
 #[derive(Debug)]
 pub struct ClientAssertionCredential<C> {
+    tenant_id: String,
     client_id: String,
-    endpoint: Url,
+    authority_host: Url,
+    additionally_allowed_tenants: Vec<String>,
     assertion: C,
     cache: TokenCache,
     options: TokenCredentialOptions,
@@ -82,40 +85,52 @@ impl<C: ClientAssertion> ClientAssertionCredential<C> {
     ) -> azure_core::Result<Self> {
         validate_tenant_id(&tenant_id)?;
         validate_not_empty(&client_id, "no client ID specified")?;
-        let options = options.unwrap_or_default().credential_options;
-        let endpoint = options
-            .authority_host()?
-            .join(&format!("/{tenant_id}/oauth2/v2.0/token"))
-            .with_context(ErrorKind::DataConversion, || {
-                format!("tenant_id {tenant_id} could not be URL encoded")
-            })?;
+        let options = options.unwrap_or_default();
+        let authority_host = options.credential_options.authority_host()?;
+        token_endpoint(&authority_host, &tenant_id)?;
         Ok(Self {
+            tenant_id,
             client_id,
+            authority_host,
+            additionally_allowed_tenants: options.additionally_allowed_tenants,
             assertion,
-            endpoint,
             cache: TokenCache::new(),
-            options,
+            options: options.credential_options,
         })
     }
 
     async fn get_token_impl(
         &self,
         scopes: &[&str],
-        _: Option<TokenRequestOptions>,
+        options: Option<TokenRequestOptions>,
     ) -> azure_core::Result<AccessToken> {
-        let mut req = Request::new(self.endpoint.clone(), Method::Post);
+        let tenant_id = resolve_tenant_id(
+            &self.tenant_id,
+            options.as_ref(),
+            &self.additionally_allowed_tenants,
+        )?;
+        let mut req = Request::new(
+            token_endpoint(&self.authority_host, tenant_id)?,
+            Method::Post,
+        );
         req.insert_header(
             headers::CONTENT_TYPE,
             content_type::APPLICATION_X_WWW_FORM_URLENCODED,
         );
         let assertion = self.assertion.secret().await?;
-        let encoded: String = form_urlencoded::Serializer::new(String::new())
-            .append_pair("client_assertion", assertion.as_str())
-            .append_pair("client_assertion_type", ASSERTION_TYPE)
-            .append_pair("client_id", self.client_id.as_str())
-            .append_pair("grant_type", "client_credentials")
-            .append_pair("scope", &scopes.join(" "))
-            .finish();
+        let encoded = {
+            let mut encoded = form_urlencoded::Serializer::new(String::new());
+            encoded
+                .append_pair("client_assertion", assertion.as_str())
+                .append_pair("client_assertion_type", ASSERTION_TYPE)
+                .append_pair("client_id", self.client_id.as_str())
+                .append_pair("grant_type", "client_credentials")
+                .append_pair("scope", &scopes.join(" "));
+            if let Some(claims) = claims_parameter(options.as_ref())? {
+                encoded.append_pair("claims", &claims);
+            }
+            encoded.finish()
+        };
         req.set_body(encoded);
 
         let res = self.options.http_client.execute_request(&req).await?;
@@ -39,6 +39,7 @@ const AZURE_CLIENT_SEND_CERTIFICATE_CHAIN_ENV_KEY: &str = "AZURE_CLIENT_SEND_CER
 pub struct ClientCertificateCredentialOptions {
     options: TokenCredentialOptions,
     send_certificate_chain: bool,
+    additionally_allowed_tenants: Vec<String>,
 }
 
 impl From<TokenCredentialOptions> for ClientCertificateCredentialOptions {
@@ -51,6 +52,7 @@ impl From<TokenCredentialOptions> for ClientCertificateCredentialOptions {
         Self {
             options,
             send_certificate_chain,
+            additionally_allowed_tenants: Vec::new(),
         }
     }
 }
@@ -61,6 +63,7 @@ impl ClientCertificateCredentialOptions {
         Self {
             options: options.into(),
             send_certificate_chain,
+            additionally_allowed_tenants: Vec::new(),
         }
     }
 
@@ -82,6 +85,18 @@ impl ClientCertificateCredentialOptions {
     pub fn send_certificate_chain(&self) -> bool {
         self.send_certificate_chain
     }
+
+    /// Set additional tenants for which the credential may acquire tokens.
+    ///
+    /// Add the wildcard value "*" to allow the credential to acquire tokens for any tenant in which the application is registered.
+    pub fn set_additionally_allowed_tenants(&mut self, additionally_allowed_tenants: Vec<String>) {
+        self.additionally_allowed_tenants = additionally_allowed_tenants;
+    }
+
+    /// Additional tenants for which the credential may acquire tokens.
+    pub fn additionally_allowed_tenants(&self) -> &[String] {
+        &self.additionally_allowed_tenants
+    }
 }
 
 /// Enables authentication to Azure Active Directory using a client certificate that
@@ -98,6 +113,7 @@ pub struct ClientCertificateCredential {
     http_client: Arc<dyn HttpClient>,
     authority_host: Url,
     send_certificate_chain: bool,
+    additionally_allowed_tenants: Vec<String>,
     cache: TokenCache,
 }
 
@@ -123,6 +139,7 @@ impl ClientCertificateCredential {
             http_client: options.options().http_client().clone(),
             authority_host: options.options().authority_host()?.clone(),
             send_certificate_chain: options.send_certificate_chain(),
+            additionally_allowed_tenants: options.additionally_allowed_tenants,
             cache: TokenCache::new(),
         }))
     }
@@ -146,7 +163,7 @@ impl ClientCertificateCredential {
     async fn get_token(
         &self,
         scopes: &[&str],
-        _: Option<TokenRequestOptions>,
+        options: Option<TokenRequestOptions>,
     ) -> azure_core::Result<AccessToken> {
         if scopes.len() != 1 {
             return Err(Error::message(
@@ -162,73 +179,20 @@ impl ClientCertificateCredential {
             ));
         };
 
-        let url = self
-            .authority_host
-            .join(&format!("{}/oauth2/v2.0/token", self.tenant_id))?;
-
-        let certificate = base64::decode(self.client_certificate.secret())
-            .map_err(|_| Error::message(ErrorKind::Credential, "Base64 decode failed"))?;
-
-        let pkcs12_certificate = Pkcs12::from_der(&certificate)
-            .map_err(openssl_error)?
-            .parse2(self.client_certificate_pass.secret())
-            .map_err(openssl_error)?;
-
-        let Some(cert) = pkcs12_certificate.cert.as_ref() else {
-            return Err(Error::message(
-                ErrorKind::Credential,
-                "Certificate not found",
-            ));
-        };
-
-        let Some(pkey) = pkcs12_certificate.pkey.as_ref() else {
-            return Err(Error::message(
-                ErrorKind::Credential,
-                "Private key not found",
-            ));
-        };
-
-        let thumbprint =
-            ClientCertificateCredential::get_thumbprint(cert).map_err(openssl_error)?;
-
-        let uuid = Uuid::new_v4();
-        let current_time = OffsetDateTime::now_utc().unix_timestamp();
-        let expiry_time = current_time + DEFAULT_REFRESH_TIME;
-        let x5t = base64::encode(thumbprint);
-
-        let header = match self.send_certificate_chain {
-            true => {
-                let base_signature = get_encoded_cert(cert)?;
-                let x5c = match pkcs12_certificate.ca {
-                    Some(chain) => {
-                        let chain = chain
-                            .into_iter()
-                            .map(|x| get_encoded_cert(&x))
-                            .collect::<azure_core::Result<Vec<String>>>()?
-                            .join(",");
-                        format! {"{},{}", base_signature, chain}
-                    }
-                    None => base_signature,
-                };
-                format!(
-                    r#"{{"alg":"RS256","typ":"JWT", "x5t":"{}", "x5c":[{}]}}"#,
-                    x5t, x5c
-                )
-            }
-            false => format!(r#"{{"alg":"RS256","typ":"JWT", "x5t":"{}"}}"#, x5t),
-        };
-        let header = ClientCertificateCredential::as_jwt_part(header.as_bytes());
-
-        let payload = format!(
-            r#"{{"aud":"{}","exp":{},"iss": "{}", "jti": "{}", "nbf": {}, "sub": "{}"}}"#,
-            url, expiry_time, self.client_id, uuid, current_time, self.client_id
-        );
-        let payload = ClientCertificateCredential::as_jwt_part(payload.as_bytes());
-
-        let jwt = format!("{}.{}", header, payload);
-        let signature = ClientCertificateCredential::sign(&jwt, pkey).map_err(openssl_error)?;
-        let sig = ClientCertificateCredential::as_jwt_part(&signature);
-        let client_assertion = format!("{}.{}", jwt, sig);
+        let tenant_id = crate::resolve_tenant_id(
+            &self.tenant_id,
+            options.as_ref(),
+            &self.additionally_allowed_tenants,
+        )?;
+        let url = crate::token_endpoint(&self.authority_host, tenant_id)?;
+
+        let client_assertion = client_assertion(
+            &self.client_id,
+            &url,
+            &self.client_certificate,
+            &self.client_certificate_pass,
+            self.send_certificate_chain,
+        )?;
 
         let encoded = {
             let mut encoded = &mut form_urlencoded::Serializer::new(String::new());
@@ -241,6 +205,9 @@ impl ClientCertificateCredential {
                 )
                 .append_pair("client_assertion", client_assertion.as_str())
                 .append_pair("grant_type", "client_credentials");
+            if let Some(claims) = crate::claims_parameter(options.as_ref())? {
+                encoded = encoded.append_pair("claims", &claims);
+            }
             encoded.finish()
         };
 
@@ -267,6 +234,78 @@ impl ClientCertificateCredential {
     }
 }
 
+/// Creates a client assertion for `client_id` signed with a base64-encoded PKCS12 certificate, for the token endpoint `audience`.
+pub(crate) fn client_assertion(
+    client_id: &str,
+    audience: &Url,
+    certificate: &Secret,
+    password: &Secret,
+    send_certificate_chain: bool,
+) -> azure_core::Result<String> {
+    let certificate = base64::decode(certificate.secret())
+        .map_err(|_| Error::message(ErrorKind::Credential, "Base64 decode failed"))?;
+
+    let pkcs12_certificate = Pkcs12::from_der(&certificate)
+        .map_err(openssl_error)?
+        .parse2(password.secret())
+        .map_err(openssl_error)?;
+
+    let Some(cert) = pkcs12_certificate.cert.as_ref() else {
+        return Err(Error::message(
+            ErrorKind::Credential,
+            "Certificate not found",
+        ));
+    };
+
+    let Some(pkey) = pkcs12_certificate.pkey.as_ref() else {
+        return Err(Error::message(
+            ErrorKind::Credential,
+            "Private key not found",
+        ));
+    };
+
+    let thumbprint = ClientCertificateCredential::get_thumbprint(cert).map_err(openssl_error)?;
+
+    let uuid = Uuid::new_v4();
+    let current_time = OffsetDateTime::now_utc().unix_timestamp();
+    let expiry_time = current_time + DEFAULT_REFRESH_TIME;
+    let x5t = base64::encode(thumbprint);
+
+    let header = match send_certificate_chain {
+        true => {
+            let base_signature = get_encoded_cert(cert)?;
+            let x5c = match pkcs12_certificate.ca {
+                Some(chain) => {
+                    let chain = chain
+                        .into_iter()
+                        .map(|x| get_encoded_cert(&x))
+                        .collect::<azure_core::Result<Vec<String>>>()?
+                        .join(",");
+                    format! {"{},{}", base_signature, chain}
+                }
+                None => base_signature,
+            };
+            format!(
+                r#"{{"alg":"RS256","typ":"JWT", "x5t":"{}", "x5c":[{}]}}"#,
+                x5t, x5c
+            )
+        }
+        false => format!(r#"{{"alg":"RS256","typ":"JWT", "x5t":"{}"}}"#, x5t),
+    };
+    let header = ClientCertificateCredential::as_jwt_part(header.as_bytes());
+
+    let payload = format!(
+        r#"{{"aud":"{}","exp":{},"iss": "{}", "jti": "{}", "nbf": {}, "sub": "{}"}}"#,
+        audience, expiry_time, client_id, uuid, current_time, client_id
+    );
+    let payload = ClientCertificateCredential::as_jwt_part(payload.as_bytes());
+
+    let jwt = format!("{}.{}", header, payload);
+    let signature = ClientCertificateCredential::sign(&jwt, pkey).map_err(openssl_error)?;
+    let sig = ClientCertificateCredential::as_jwt_part(&signature);
+    Ok(format!("{}.{}", jwt, sig))
+}
+
 fn get_encoded_cert(cert: &X509) -> azure_core::Result<String> {
     Ok(format!(
         "\"{}\"",
@@ -9,7 +9,7 @@ use azure_core::http::StatusCode;
 use azure_core::Result;
 use azure_core::{
     credentials::{AccessToken, Secret, TokenCredential},
-    error::{ErrorKind, ResultExt},
+    error::ErrorKind,
     http::{
         headers::{self, content_type},
         Method, Request, Url,
@@ -25,6 +25,11 @@ const CLIENT_SECRET_CREDENTIAL: &str = "ClientSecretCredential";
 /// Options for constructing a new [`ClientSecretCredential`].
 #[derive(Debug, Default)]
 pub struct ClientSecretCredentialOptions {
+    /// Additional tenants for which the credential may acquire tokens.
+    ///
+    /// Add the wildcard value "*" to allow the credential to acquire tokens for any tenant in which the application is registered.
+    pub additionally_allowed_tenants: Vec<String>,
+
     /// Options for constructing credentials.
     pub credential_options: TokenCredentialOptions,
 }
@@ -32,11 +37,13 @@ pub struct ClientSecretCredentialOptions {
 /// Authenticates an application with a client secret.
 #[derive(Debug)]
 pub struct ClientSecretCredential {
+    additionally_allowed_tenants: Vec<String>,
+    authority_host: Url,
     cache: TokenCache,
     client_id: String,
-    endpoint: Url,
     options: TokenCredentialOptions,
     secret: Secret,
+    tenant_id: String,
 }
 
 impl ClientSecretCredential {
@@ -51,39 +58,47 @@ impl ClientSecretCredential {
         crate::validate_not_empty(secret.secret(), "no secret specified")?;
 
         let options = options.unwrap_or_default();
-        let endpoint = options
-            .credential_options
-            .authority_host()?
-            .join(&format!("/{tenant_id}/oauth2/v2.0/token"))
-            .with_context(ErrorKind::DataConversion, || {
-                format!("tenant_id '{tenant_id}' could not be URL encoded")
-            })?;
+        let authority_host = options.credential_options.authority_host()?;
+        crate::token_endpoint(&authority_host, tenant_id)?;
 
         Ok(Arc::new(Self {
+            additionally_allowed_tenants: options.additionally_allowed_tenants,
+            authority_host,
             cache: TokenCache::new(),
             client_id,
-            endpoint,
             options: options.credential_options,
             secret,
+            tenant_id: tenant_id.to_string(),
         }))
     }
 
     async fn get_token_impl(
         &self,
         scopes: &[&str],
-        _: Option<TokenRequestOptions>,
+        options: Option<TokenRequestOptions>,
     ) -> Result<AccessToken> {
-        let mut req = Request::new(self.endpoint.clone(), Method::Post);
+        let tenant_id = crate::resolve_tenant_id(
+            &self.tenant_id,
+            options.as_ref(),
+            &self.additionally_allowed_tenants,
+        )?;
+        let endpoint = crate::token_endpoint(&self.authority_host, tenant_id)?;
+        let mut req = Request::new(endpoint, Method::Post);
         req.insert_header(
             headers::CONTENT_TYPE,
             content_type::APPLICATION_X_WWW_FORM_URLENCODED,
         );
-        let body = form_urlencoded::Serializer::new(String::new())
-            .append_pair("client_id", &self.client_id)
-            .append_pair("client_secret", self.secret.secret())
-            .append_pair("grant_type", "client_credentials")
-            .append_pair("scope", &scopes.join(" "))
-            .finish();
+        let body = {
+            let mut body = form_urlencoded::Serializer::new(String::new());
+            body.append_pair("client_id", &self.client_id)
+                .append_pair("client_secret", self.secret.secret())
+                .append_pair("grant_type", "client_credentials")
+                .append_pair("scope", &scopes.join(" "));
+            if let Some(claims) = crate::claims_parameter(options.as_ref())? {
+                body.append_pair("claims", &claims);
+            }
+            body.finish()
+        };
         req.set_body(body);
 
         let res = self.options.http_client().execute_request(&req).await?;
@@ -183,6 +198,7 @@ mod tests {
                     http_client: Arc::new(sts),
                     ..Default::default()
                 },
+                ..Default::default()
             }),
         )
         .expect("valid credential");
@@ -225,6 +241,7 @@ mod tests {
                     http_client: Arc::new(sts),
                     ..Default::default()
                 },
+                ..Default::default()
             }),
         )
         .expect("valid credential");
@@ -277,4 +294,79 @@ mod tests {
         .await
         .expect_err("no scopes specified");
     }
+
+    #[tokio::test]
+    async fn get_token_with_tenant_and_claims() {
+        let other_tenant = "other-tenant";
+        let expected_url = format!(
+            "{}{}/oauth2/v2.0/token",
+            AZURE_PUBLIC_CLOUD.as_str(),
+            other_tenant
+        );
+        let sts = MockSts::new(
+            vec![RawResponse::from_bytes(
+                StatusCode::Ok,
+                Headers::default(),
+                Bytes::from(format!(
+                    r#"{{"access_token":"{}","expires_in":3600,"token_type":"Bearer"}}"#,
+                    FAKE_TOKEN
+                )),
+            )],
+            Some(Arc::new(move |req: &Request| {
+                assert_eq!(expected_url, req.url().to_string());
+                let body = match req.body() {
+                    azure_core::http::Body::Bytes(bytes) => str::from_utf8(bytes).unwrap(),
+                    _ => panic!("unexpected body type"),
+                };
+                let claims = form_urlencoded::parse(body.as_bytes())
+                    .find(|(k, _)| k == "claims")
+                    .map(|(_, v)| v.to_string());
+                assert_eq!(
+                    claims.as_deref(),
+                    Some(
+                        r#"{"access_token":{"nbf":{"essential":true},"xms_cc":{"values":["CP1"]}}}"#
+                    )
+                );
+                Ok(())
+            })),
+        );
+        let cred = ClientSecretCredential::new(
+            FAKE_TENANT_ID,
+            FAKE_CLIENT_ID.to_string(),
+            FAKE_SECRET.into(),
+            Some(ClientSecretCredentialOptions {
+                additionally_allowed_tenants: vec![other_tenant.to_string()],
+                credential_options: TokenCredentialOptions {
+                    http_client: Arc::new(sts),
+                    ..Default::default()
+                },
+            }),
+        )
+        .expect("valid credential");
+
+        let token = cred
+            .get_token(
+                LIVE_TEST_SCOPES,
+                Some(TokenRequestOptions {
+                    claims: Some(r#"{"access_token":{"nbf":{"essential":true}}}"#.into()),
+                    enable_cae: true,
+                    tenant_id: Some(other_tenant.to_string()),
+                }),
+            )
+            .await
+            .expect("token");
+        assert_eq!(FAKE_TOKEN, token.token.secret());
+
+        let err = cred
+            .get_token(
+                LIVE_TEST_SCOPES,
+                Some(TokenRequestOptions {
+                    tenant_id: Some("disallowed-tenant".to_string()),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .expect_err("disallowed tenant");
+        assert!(matches!(err.kind(), ErrorKind::Credential));
+    }
 }
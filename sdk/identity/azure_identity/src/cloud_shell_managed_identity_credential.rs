@@ -0,0 +1,96 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    env::Env, scopes_to_resource, token_from_response, ImdsId, TokenCache, TokenCredentialOptions,
+};
+use azure_core::{
+    credentials::{AccessToken, TokenCredential, TokenRequestOptions},
+    error::{Error, ErrorKind, ResultExt},
+    http::{
+        headers::{self, content_type},
+        HttpClient, Method, Request, Url,
+    },
+};
+use std::sync::Arc;
+use url::form_urlencoded;
+
+const ENDPOINT_ENV: &str = "MSI_ENDPOINT";
+
+/// Authenticates the managed identity of the user signed in to Azure Cloud Shell.
+#[derive(Debug)]
+pub(crate) struct CloudShellManagedIdentityCredential {
+    http_client: Arc<dyn HttpClient>,
+    endpoint: Url,
+    cache: TokenCache,
+}
+
+impl CloudShellManagedIdentityCredential {
+    pub fn new(
+        id: ImdsId,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        if !matches!(id, ImdsId::SystemAssigned) {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "User-assigned identities aren't supported for Cloud Shell.",
+            ));
+        }
+        let options = options.into();
+        let endpoint = endpoint(options.env())?;
+        Ok(Arc::new(Self {
+            http_client: options.http_client(),
+            endpoint,
+            cache: TokenCache::new(),
+        }))
+    }
+
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        _: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        let resource = scopes_to_resource(scopes)?;
+
+        let mut req = Request::new(self.endpoint.clone(), Method::Post);
+        req.insert_header("metadata", "true");
+        req.insert_header(
+            headers::CONTENT_TYPE,
+            content_type::APPLICATION_X_WWW_FORM_URLENCODED,
+        );
+        let body = form_urlencoded::Serializer::new(String::new())
+            .append_pair("resource", resource)
+            .finish();
+        req.set_body(body);
+
+        let rsp = self.http_client.execute_request(&req).await?;
+        token_from_response(rsp).await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for CloudShellManagedIdentityCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        self.cache
+            .get_token(scopes, options, |s, o| self.get_token(s, o))
+            .await
+    }
+}
+
+fn endpoint(env: &Env) -> azure_core::Result<Url> {
+    let endpoint = env
+        .var(ENDPOINT_ENV)
+        .with_context(ErrorKind::Credential, || {
+            format!("Cloud Shell credential requires {ENDPOINT_ENV} environment variable")
+        })?;
+    Url::parse(&endpoint).with_context(ErrorKind::Credential, || {
+        format!(
+            "Cloud Shell credential {ENDPOINT_ENV} environment variable must be a valid URL, but is '{endpoint}'"
+        )
+    })
+}
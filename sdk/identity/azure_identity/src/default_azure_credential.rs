@@ -1,31 +1,47 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+use crate::{
+    managed_identity_credential, virtual_machine_managed_identity_credential,
+    EnvironmentCredential, ManagedIdentityCredential, ManagedIdentityCredentialOptions, TokenCache,
+    TokenCredentialOptions, UserAssignedId, WorkloadIdentityCredential,
+};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::{AzureCliCredential, AzureDeveloperCliCredential};
-use crate::{TokenCache, TokenCredentialOptions};
-#[cfg(not(target_arch = "wasm32"))]
-use azure_core::error::ResultExt;
+use async_lock::OnceCell;
 use azure_core::{
     credentials::{AccessToken, TokenCredential, TokenRequestOptions},
-    error::{Error, ErrorKind},
+    error::{Error, ErrorKind, ResultExt},
+    http::{HttpClient, Method, Request, Url},
+    time::Duration,
 };
+use futures::future::{self, Either};
 use std::sync::Arc;
 
+const AZURE_CLIENT_ID: &str = "AZURE_CLIENT_ID";
+
+/// How long to wait for any response from IMDS before deciding managed identity isn't available.
+const IMDS_PROBE_TIMEOUT: Duration = Duration::seconds(1);
+
 /// Provides a mechanism of selectively disabling credentials used for a `DefaultAzureCredential` instance
 pub struct DefaultAzureCredentialBuilder {
     options: TokenCredentialOptions,
+    include_environment_credential: bool,
+    include_workload_identity_credential: bool,
+    include_managed_identity_credential: bool,
     #[cfg(not(target_arch = "wasm32"))]
     include_azure_cli_credential: bool,
     #[cfg(not(target_arch = "wasm32"))]
     include_azure_developer_cli_credential: bool,
 }
 
-#[cfg_attr(target_arch = "wasm32", allow(clippy::derivable_impls))]
 impl Default for DefaultAzureCredentialBuilder {
     fn default() -> Self {
         Self {
             options: TokenCredentialOptions::default(),
+            include_environment_credential: true,
+            include_workload_identity_credential: true,
+            include_managed_identity_credential: true,
             #[cfg(not(target_arch = "wasm32"))]
             include_azure_cli_credential: true,
             #[cfg(not(target_arch = "wasm32"))]
@@ -45,6 +61,24 @@ impl DefaultAzureCredentialBuilder {
         self
     }
 
+    /// Exclude authenticating using a service principal configured by environment variables.
+    pub fn exclude_environment_credential(&mut self) -> &mut Self {
+        self.include_environment_credential = false;
+        self
+    }
+
+    /// Exclude authenticating using Microsoft Entra Workload ID on Kubernetes.
+    pub fn exclude_workload_identity_credential(&mut self) -> &mut Self {
+        self.include_workload_identity_credential = false;
+        self
+    }
+
+    /// Exclude authenticating using the managed identity of an Azure resource.
+    pub fn exclude_managed_identity_credential(&mut self) -> &mut Self {
+        self.include_managed_identity_credential = false;
+        self
+    }
+
     /// Exclude authenticating using the Azure CLI (az).
     #[cfg(not(target_arch = "wasm32"))]
     pub fn exclude_azure_cli_credential(&mut self) -> &mut Self {
@@ -61,8 +95,16 @@ impl DefaultAzureCredentialBuilder {
 
     /// Get a list of the credential types to include.
     fn included(&self) -> Vec<DefaultAzureCredentialType> {
-        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
         let mut sources = Vec::new();
+        if self.include_environment_credential {
+            sources.push(DefaultAzureCredentialType::Environment);
+        }
+        if self.include_workload_identity_credential {
+            sources.push(DefaultAzureCredentialType::WorkloadIdentity);
+        }
+        if self.include_managed_identity_credential {
+            sources.push(DefaultAzureCredentialType::ManagedIdentity);
+        }
         #[cfg(not(target_arch = "wasm32"))]
         if self.include_azure_cli_credential {
             sources.push(DefaultAzureCredentialType::AzureCli);
@@ -75,40 +117,48 @@ impl DefaultAzureCredentialBuilder {
     }
 
     /// Creates a list of `TokenCredential` instances from the included credential types.
-    /// The credentials created successfully are used as sources for getting a token.
+    /// The credentials created successfully are used as sources for getting a token,
+    /// and errors for those that could not be created are returned for reporting.
     fn create_sources(
         &self,
         included: &Vec<DefaultAzureCredentialType>,
-    ) -> azure_core::Result<Vec<DefaultAzureCredentialKind>> {
-        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+    ) -> azure_core::Result<(Vec<DefaultAzureCredentialKind>, Vec<Error>)> {
         let mut sources = Vec::<DefaultAzureCredentialKind>::with_capacity(included.len());
-        let errors = Vec::new();
+        let mut errors = Vec::new();
 
-        #[cfg_attr(target_arch = "wasm32", allow(clippy::never_loop))]
         for source in included {
-            match source {
+            let credential = match source {
+                DefaultAzureCredentialType::Environment => {
+                    EnvironmentCredential::new(Some(self.options.clone().into()))
+                        .map(DefaultAzureCredentialKind::Environment)
+                }
+                DefaultAzureCredentialType::WorkloadIdentity => {
+                    WorkloadIdentityCredential::new(Some(self.options.clone().into()))
+                        .map(DefaultAzureCredentialKind::WorkloadIdentity)
+                }
+                DefaultAzureCredentialType::ManagedIdentity => {
+                    managed_identity_credential(&self.options).map(|(credential, probe)| {
+                        DefaultAzureCredentialKind::ManagedIdentity(credential, probe)
+                    })
+                }
                 #[cfg(not(target_arch = "wasm32"))]
                 DefaultAzureCredentialType::AzureCli => {
-                    if let Ok(credential) =
-                        AzureCliCredential::new(Some(self.options.clone().into()))
-                    {
-                        sources.push(DefaultAzureCredentialKind::AzureCli(credential));
-                    }
+                    AzureCliCredential::new(Some(self.options.clone().into()))
+                        .map(DefaultAzureCredentialKind::AzureCli)
                 }
                 #[cfg(not(target_arch = "wasm32"))]
                 DefaultAzureCredentialType::AzureDeveloperCli => {
-                    if let Ok(credential) =
-                        AzureDeveloperCliCredential::new(Some(self.options.clone().into()))
-                    {
-                        sources.push(DefaultAzureCredentialKind::AzureDeveloperCli(credential));
-                    }
-                }
-                #[cfg(target_arch = "wasm32")]
-                _ => {
-                    return Err(Error::with_message(ErrorKind::Credential, || {
-                        "No credential providers available"
-                    }));
+                    AzureDeveloperCliCredential::new(Some(self.options.clone().into()))
+                        .map(DefaultAzureCredentialKind::AzureDeveloperCli)
                 }
+            };
+            match credential {
+                Ok(credential) => sources.push(credential),
+                Err(err) => errors.push(Error::full(
+                    ErrorKind::Credential,
+                    err,
+                    format!("{} is unavailable", source.name()),
+                )),
             }
         }
         if sources.is_empty() {
@@ -119,29 +169,68 @@ impl DefaultAzureCredentialBuilder {
                 )
             }));
         }
-        Ok(sources)
+        Ok((sources, errors))
     }
 
     /// Create a `DefaultAzureCredential` from this builder.
     pub fn build(&self) -> azure_core::Result<Arc<DefaultAzureCredential>> {
         let included = self.included();
-        let sources = self.create_sources(&included)?;
-        DefaultAzureCredential::with_sources(sources)
+        let (sources, unavailable) = self.create_sources(&included)?;
+        DefaultAzureCredential::with_sources(sources, unavailable)
     }
 }
 
+/// Creates a [`ManagedIdentityCredential`] for the user-assigned `AZURE_CLIENT_ID`, if set,
+/// and an [`ImdsProbe`] if the managed identity would be provided by IMDS.
+fn managed_identity_credential(
+    options: &TokenCredentialOptions,
+) -> azure_core::Result<(Arc<ManagedIdentityCredential>, Option<ImdsProbe>)> {
+    let env = options.env();
+    let credential = ManagedIdentityCredential::new(Some(ManagedIdentityCredentialOptions {
+        credential_options: options.clone(),
+        user_assigned_id: env.var(AZURE_CLIENT_ID).ok().map(UserAssignedId::ClientId),
+    }))?;
+    let probe = managed_identity_credential::is_imds(env)
+        .then(|| ImdsProbe::new(options.http_client(), IMDS_PROBE_TIMEOUT));
+
+    Ok((credential, probe))
+}
+
 /// Types that may be enabled for use by `DefaultAzureCredential`.
 #[derive(Debug, PartialEq)]
 enum DefaultAzureCredentialType {
+    Environment,
+    WorkloadIdentity,
+    ManagedIdentity,
     #[cfg(not(target_arch = "wasm32"))]
     AzureCli,
     #[cfg(not(target_arch = "wasm32"))]
     AzureDeveloperCli,
 }
 
+impl DefaultAzureCredentialType {
+    fn name(&self) -> &'static str {
+        match self {
+            DefaultAzureCredentialType::Environment => "EnvironmentCredential",
+            DefaultAzureCredentialType::WorkloadIdentity => "WorkloadIdentityCredential",
+            DefaultAzureCredentialType::ManagedIdentity => "ManagedIdentityCredential",
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialType::AzureCli => "AzureCliCredential",
+            #[cfg(not(target_arch = "wasm32"))]
+            DefaultAzureCredentialType::AzureDeveloperCli => "AzureDeveloperCliCredential",
+        }
+    }
+}
+
 /// Types of `TokenCredential` supported by `DefaultAzureCredential`
 #[derive(Debug)]
 pub(crate) enum DefaultAzureCredentialKind {
+    /// `TokenCredential` from environment variables.
+    Environment(Arc<EnvironmentCredential>),
+    /// `TokenCredential` from Microsoft Entra Workload ID.
+    WorkloadIdentity(Arc<WorkloadIdentityCredential>),
+    /// `TokenCredential` from managed identity, and a probe if the managed identity is provided by IMDS.
+    ManagedIdentity(Arc<ManagedIdentityCredential>, Option<ImdsProbe>),
     #[cfg(not(target_arch = "wasm32"))]
     /// `TokenCredential` from Azure CLI (az).
     AzureCli(Arc<AzureCliCredential>),
@@ -150,50 +239,160 @@ pub(crate) enum DefaultAzureCredentialKind {
     AzureDeveloperCli(Arc<AzureDeveloperCliCredential>),
 }
 
-#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send), allow(unused_variables))]
+impl DefaultAzureCredentialKind {
+    /// Whether the credential is used in Azure hosting environments.
+    ///
+    /// If these credentials attempt but fail to get a token, `DefaultAzureCredential` stops trying other credentials.
+    fn is_deployed(&self) -> bool {
+        matches!(
+            self,
+            DefaultAzureCredentialKind::Environment(_)
+                | DefaultAzureCredentialKind::WorkloadIdentity(_)
+                | DefaultAzureCredentialKind::ManagedIdentity(..)
+        )
+    }
+
+    /// Gets a token, or an error and whether the credential was unavailable to attempt getting a token.
+    async fn try_get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> Result<AccessToken, (Error, bool)> {
+        if let DefaultAzureCredentialKind::ManagedIdentity(_, Some(probe)) = self {
+            probe.check().await.map_err(|err| {
+                (
+                    Error::full(
+                        ErrorKind::Credential,
+                        err,
+                        "ManagedIdentityCredential is unavailable",
+                    ),
+                    true,
+                )
+            })?;
+        }
+        self.get_token(scopes, options)
+            .await
+            .map_err(|err| (err, false))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl TokenCredential for DefaultAzureCredentialKind {
     async fn get_token(
         &self,
         scopes: &[&str],
-        _: Option<TokenRequestOptions>,
+        options: Option<TokenRequestOptions>,
     ) -> azure_core::Result<AccessToken> {
         match self {
+            DefaultAzureCredentialKind::Environment(credential) => {
+                credential.get_token(scopes, options).await.context(
+                    ErrorKind::Credential,
+                    "error getting token credential from environment variables",
+                )
+            }
+            DefaultAzureCredentialKind::WorkloadIdentity(credential) => {
+                credential.get_token(scopes, options).await.context(
+                    ErrorKind::Credential,
+                    "error getting token credential from workload identity",
+                )
+            }
+            DefaultAzureCredentialKind::ManagedIdentity(credential, _) => {
+                credential.get_token(scopes, options).await.context(
+                    ErrorKind::Credential,
+                    "error getting token credential from managed identity",
+                )
+            }
             #[cfg(not(target_arch = "wasm32"))]
             DefaultAzureCredentialKind::AzureCli(credential) => {
-                credential.get_token(scopes, None).await.context(
+                credential.get_token(scopes, options).await.context(
                     ErrorKind::Credential,
                     "error getting token credential from Azure CLI",
                 )
             }
             #[cfg(not(target_arch = "wasm32"))]
             DefaultAzureCredentialKind::AzureDeveloperCli(credential) => {
-                credential.get_token(scopes, None).await.context(
+                credential.get_token(scopes, options).await.context(
                     ErrorKind::Credential,
                     "error getting token credential from Azure Developer CLI",
                 )
             }
-            #[cfg(target_arch = "wasm32")]
-            _ => {
-                return Err(Error::with_message(ErrorKind::Credential, || {
-                    "No credential providers available"
-                }));
-            }
         }
     }
 }
 
+/// Probes IMDS before the first managed identity token request so `DefaultAzureCredential` fails fast when not running in Azure.
+#[derive(Debug)]
+pub(crate) struct ImdsProbe {
+    http_client: Arc<dyn HttpClient>,
+    timeout: Duration,
+    available: OnceCell<bool>,
+}
+
+impl ImdsProbe {
+    fn new(http_client: Arc<dyn HttpClient>, timeout: Duration) -> Self {
+        Self {
+            http_client,
+            timeout,
+            available: OnceCell::new(),
+        }
+    }
+
+    async fn check(&self) -> azure_core::Result<()> {
+        let available = *self.available.get_or_init(|| self.probe()).await;
+        if !available {
+            return Err(Error::with_message(ErrorKind::Credential, || {
+                format!(
+                    "no response from the IMDS endpoint within {} seconds",
+                    self.timeout.as_seconds_f32()
+                )
+            }));
+        }
+        Ok(())
+    }
+
+    async fn probe(&self) -> bool {
+        // IMDS responds to a request without the "metadata" header with an error, which is enough to know it's available.
+        let url = Url::parse(virtual_machine_managed_identity_credential::ENDPOINT)
+            .expect("valid url constant");
+        let request = Request::new(url, Method::Get);
+        let response = self.http_client.execute_request(&request);
+        let timeout = Box::pin(azure_core::sleep::sleep(self.timeout));
+
+        let available = match future::select(response, timeout).await {
+            Either::Left((Ok(_), _)) => true,
+            Either::Left((Err(err), _)) => {
+                tracing::debug!("IMDS probe failed: {err}");
+                false
+            }
+            Either::Right(_) => {
+                tracing::debug!("IMDS probe timed out");
+                false
+            }
+        };
+        available
+    }
+}
+
 /// Provides a default `TokenCredential` authentication flow for applications that will be deployed to Azure.
 ///
 /// The following credential types if enabled will be tried, in order:
 ///
-/// * `ManagedIdentityCredential`
-/// * `AzureCliCredential`
+/// * [`EnvironmentCredential`]
+/// * [`WorkloadIdentityCredential`]
+/// * [`ManagedIdentityCredential`] using the user-assigned `AZURE_CLIENT_ID`, if set
+/// * [`AzureCliCredential`](crate::AzureCliCredential)
+/// * [`AzureDeveloperCliCredential`](crate::AzureDeveloperCliCredential)
+///
+/// Credentials that are not configured, or a managed identity from IMDS that doesn't respond within one second, are skipped.
+/// If a credential used in Azure hosting environments attempts but fails to get a token, no other credentials are tried.
+/// The error returned lists why each credential failed.
 ///
 /// Consult the documentation of these credential types for more information on how they attempt authentication.
 #[derive(Debug)]
 pub struct DefaultAzureCredential {
     sources: Vec<DefaultAzureCredentialKind>,
+    unavailable: Vec<String>,
     cache: TokenCache,
 }
 
@@ -217,10 +416,14 @@ impl DefaultAzureCredential {
             .build()
     }
 
-    /// Creates a `DefaultAzureCredential` with specified sources.
-    fn with_sources(sources: Vec<DefaultAzureCredentialKind>) -> azure_core::Result<Arc<Self>> {
+    /// Creates a `DefaultAzureCredential` with specified sources and errors for sources that were unavailable.
+    fn with_sources(
+        sources: Vec<DefaultAzureCredentialKind>,
+        unavailable: Vec<Error>,
+    ) -> azure_core::Result<Arc<Self>> {
         Ok(Arc::new(DefaultAzureCredential {
             sources,
+            unavailable: unavailable.iter().map(format_error).collect(),
             cache: TokenCache::new(),
         }))
     }
@@ -231,19 +434,22 @@ impl DefaultAzureCredential {
         scopes: &[&str],
         options: Option<TokenRequestOptions>,
     ) -> azure_core::Result<AccessToken> {
-        let mut errors = Vec::new();
+        let mut errors = self.unavailable.clone();
         for source in &self.sources {
-            let token_res = source.get_token(scopes, options.clone()).await;
-
-            match token_res {
+            match source.try_get_token(scopes, options.clone()).await {
                 Ok(token) => return Ok(token),
-                Err(error) => errors.push(error),
+                Err((error, unavailable)) => {
+                    errors.push(format_error(&error));
+                    if source.is_deployed() && !unavailable {
+                        break;
+                    }
+                }
             }
         }
         Err(Error::with_message(ErrorKind::Credential, || {
             format!(
                 "Multiple errors were encountered while attempting to authenticate:\n{}",
-                format_aggregate_error(&errors)
+                errors.join("\n")
             )
         }))
     }
@@ -264,34 +470,61 @@ impl TokenCredential for DefaultAzureCredential {
 }
 
 fn format_aggregate_error(errors: &[Error]) -> String {
-    use std::error::Error;
     errors
         .iter()
-        .map(|e| {
-            let mut current: Option<&dyn Error> = Some(e);
-            let mut stack = vec![];
-            while let Some(err) = current.take() {
-                stack.push(err.to_string());
-                current = err.source();
-            }
-            stack.join(" - ")
-        })
+        .map(format_error)
         .collect::<Vec<String>>()
         .join("\n")
 }
 
+fn format_error(error: &Error) -> String {
+    use std::error::Error;
+    let mut current: Option<&dyn Error> = Some(error);
+    let mut stack = vec![];
+    while let Some(err) = current.take() {
+        stack.push(err.to_string());
+        current = err.source();
+    }
+    stack.join(" - ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{env::Env, tests::*};
+    use azure_core::{
+        http::{headers::Headers, RawResponse, StatusCode},
+        Bytes,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt as _;
+
+    const AZURE_CLIENT_SECRET: &str = "AZURE_CLIENT_SECRET";
+    const AZURE_TENANT_ID: &str = "AZURE_TENANT_ID";
 
     #[test]
     fn test_builder_included_credential_flags() {
         let builder = DefaultAzureCredentialBuilder::new();
+        assert!(builder.include_environment_credential);
+        assert!(builder.include_workload_identity_credential);
+        assert!(builder.include_managed_identity_credential);
         #[cfg(not(target_arch = "wasm32"))]
         assert!(builder.include_azure_cli_credential);
         #[cfg(not(target_arch = "wasm32"))]
         assert!(builder.include_azure_developer_cli_credential);
 
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder.exclude_environment_credential();
+        assert!(!builder.include_environment_credential);
+
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder.exclude_workload_identity_credential();
+        assert!(!builder.include_workload_identity_credential);
+
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder.exclude_managed_identity_credential();
+        assert!(!builder.include_managed_identity_credential);
+
         #[cfg(not(target_arch = "wasm32"))]
         {
             let mut builder = DefaultAzureCredentialBuilder::new();
@@ -305,12 +538,6 @@ mod tests {
             builder.exclude_azure_developer_cli_credential();
             assert!(!builder.include_azure_developer_cli_credential);
         }
-
-        let builder = DefaultAzureCredentialBuilder::new();
-        #[cfg(not(target_arch = "wasm32"))]
-        assert!(builder.include_azure_cli_credential);
-        #[cfg(not(target_arch = "wasm32"))]
-        assert!(builder.include_azure_developer_cli_credential);
     }
 
     #[test]
@@ -320,18 +547,161 @@ mod tests {
         assert_eq!(
             builder.included(),
             vec![
+                DefaultAzureCredentialType::Environment,
+                DefaultAzureCredentialType::WorkloadIdentity,
+                DefaultAzureCredentialType::ManagedIdentity,
                 DefaultAzureCredentialType::AzureCli,
-                DefaultAzureCredentialType::AzureDeveloperCli
+                DefaultAzureCredentialType::AzureDeveloperCli,
             ]
         );
     }
 
-    /// test excluding azure cli credential
+    /// test excluding all credentials
     #[test]
-    fn test_exclude_azure_cli_credential() {
+    fn test_exclude_all_credentials() {
         let mut builder = DefaultAzureCredentialBuilder::new();
-        builder.exclude_azure_cli_credential();
-        builder.exclude_azure_developer_cli_credential();
+        builder
+            .exclude_environment_credential()
+            .exclude_workload_identity_credential()
+            .exclude_managed_identity_credential()
+            .exclude_azure_cli_credential()
+            .exclude_azure_developer_cli_credential();
         assert!(builder.included().is_empty());
     }
+
+    #[test]
+    fn unavailable_sources_are_reported() {
+        let mut builder = DefaultAzureCredentialBuilder::new();
+        builder
+            .with_options(TokenCredentialOptions {
+                env: Env::from(&[(AZURE_TENANT_ID, FAKE_TENANT_ID)][..]),
+                ..Default::default()
+            })
+            .exclude_managed_identity_credential()
+            .exclude_azure_cli_credential()
+            .exclude_azure_developer_cli_credential();
+        let err = builder.build().expect_err("no sources available");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+
+        let message = format_error(&err);
+        assert!(
+            message.contains("EnvironmentCredential is unavailable - missing environment variables: AZURE_CLIENT_ID, AZURE_CLIENT_SECRET or AZURE_CLIENT_CERTIFICATE_PATH"),
+            "{message}"
+        );
+        assert!(
+            message.contains("WorkloadIdentityCredential is unavailable"),
+            "{message}"
+        );
+    }
+
+    #[tokio::test]
+    async fn environment_credential_used_first() {
+        let sts = MockSts::new(
+            vec![RawResponse::from_bytes(
+                StatusCode::Ok,
+                Headers::default(),
+                Bytes::from(format!(
+                    r#"{{"access_token":"{FAKE_TOKEN}","expires_in":3600,"token_type":"Bearer"}}"#
+                )),
+            )],
+            None,
+        );
+        let credential = DefaultAzureCredential::with_options(TokenCredentialOptions {
+            env: Env::from(
+                &[
+                    (AZURE_TENANT_ID, FAKE_TENANT_ID),
+                    (AZURE_CLIENT_ID, FAKE_CLIENT_ID),
+                    (AZURE_CLIENT_SECRET, "fake secret"),
+                ][..],
+            ),
+            http_client: Arc::new(sts),
+            ..Default::default()
+        })
+        .expect("valid credential");
+        assert!(matches!(
+            credential.sources.first(),
+            Some(DefaultAzureCredentialKind::Environment(_))
+        ));
+
+        let token = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        assert_eq!(token.token.secret(), FAKE_TOKEN);
+    }
+
+    #[tokio::test]
+    async fn deployed_credential_error_stops_chain() {
+        let sts = MockSts::new(
+            vec![RawResponse::from_bytes(
+                StatusCode::BadRequest,
+                Headers::default(),
+                Bytes::from_static(
+                    br#"{"error":"invalid_client","error_description":"invalid secret"}"#,
+                ),
+            )],
+            None,
+        );
+        let options = TokenCredentialOptions {
+            env: Env::from(
+                &[
+                    (AZURE_TENANT_ID, FAKE_TENANT_ID),
+                    (AZURE_CLIENT_ID, FAKE_CLIENT_ID),
+                    (AZURE_CLIENT_SECRET, "fake secret"),
+                ][..],
+            ),
+            http_client: Arc::new(sts),
+            ..Default::default()
+        };
+        let managed_identity = managed_identity_credential(&options).expect("credential");
+        let credential = DefaultAzureCredential::with_sources(
+            vec![
+                DefaultAzureCredentialKind::Environment(
+                    EnvironmentCredential::new(Some(options.clone().into())).expect("credential"),
+                ),
+                DefaultAzureCredentialKind::ManagedIdentity(managed_identity.0, managed_identity.1),
+            ],
+            Vec::new(),
+        )
+        .unwrap();
+
+        let err = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect_err("environment credential failed");
+        let message = err.to_string();
+        assert!(message.contains("environment variables"), "{message}");
+        assert!(!message.contains("managed identity"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn imds_probe_times_out() {
+        let client = MockHttpClient::new(|_| future::pending().boxed());
+        let probe = ImdsProbe::new(Arc::new(client), Duration::milliseconds(50));
+        let err = probe.check().await.expect_err("probe timed out");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+
+        // The result is cached so subsequent checks fail fast.
+        probe.check().await.expect_err("probe timed out");
+    }
+
+    #[tokio::test]
+    async fn imds_probe_accepts_any_response() {
+        let client = MockHttpClient::new(|request| {
+            assert!(request
+                .headers()
+                .get_optional_str(&"metadata".into())
+                .is_none());
+            async {
+                Ok(RawResponse::from_bytes(
+                    StatusCode::BadRequest,
+                    Headers::default(),
+                    Bytes::new(),
+                ))
+            }
+            .boxed()
+        });
+        let probe = ImdsProbe::new(Arc::new(client), IMDS_PROBE_TIMEOUT);
+        probe.check().await.expect("IMDS available");
+    }
 }
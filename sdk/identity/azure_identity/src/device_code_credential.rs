@@ -0,0 +1,489 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    claims_parameter, deserialize, user_credential::UserCredential, EntraIdErrorResponse,
+    TokenCredentialOptions,
+};
+use async_lock::Mutex;
+use azure_core::{
+    credentials::{AccessToken, TokenCredential, TokenRequestOptions},
+    error::{Error, ErrorKind},
+    http::{
+        headers::{self, content_type},
+        Method, Request, StatusCode,
+    },
+    sleep::sleep,
+    time::{Duration, OffsetDateTime},
+};
+use serde::Deserialize;
+use std::{fmt, sync::Arc};
+use url::form_urlencoded;
+
+const DEVICE_CODE_CREDENTIAL: &str = "DeviceCodeCredential";
+const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Seconds to wait between polling requests when Microsoft Entra ID doesn't specify an interval.
+const DEFAULT_INTERVAL: i64 = 5;
+
+/// Seconds to add to the polling interval when Microsoft Entra ID asks the client to slow down.
+const SLOW_DOWN_INTERVAL: i64 = 5;
+
+/// What the user needs to authenticate with a [`DeviceCodeCredential`] on another device.
+#[derive(Debug, Clone)]
+pub struct DeviceCode {
+    /// The code the user enters at [`DeviceCode::verification_uri`].
+    pub user_code: String,
+
+    /// The URI where the user enters [`DeviceCode::user_code`] and signs in.
+    pub verification_uri: String,
+
+    /// Instructions for the user from Microsoft Entra ID, including the user code and verification URI.
+    pub message: String,
+
+    /// When the user code expires.
+    pub expires_on: OffsetDateTime,
+}
+
+/// Options for constructing a new [`DeviceCodeCredential`].
+#[derive(Debug, Default)]
+pub struct DeviceCodeCredentialOptions {
+    /// Additional tenants for which the credential may acquire tokens.
+    ///
+    /// Add the wildcard value "*" to allow the credential to acquire tokens for any tenant in which the application is registered.
+    pub additionally_allowed_tenants: Vec<String>,
+
+    /// Options for constructing credentials.
+    pub credential_options: TokenCredentialOptions,
+}
+
+type Prompt = dyn Fn(&DeviceCode) -> azure_core::Result<()> + Send + Sync;
+
+/// Authenticates a user with the [device code flow](https://learn.microsoft.com/entra/identity-platform/v2-oauth2-device-code).
+///
+/// The credential calls a prompt with a [`DeviceCode`] the user enters in a browser on any device,
+/// then polls Microsoft Entra ID until the user signs in. This is useful on devices without a browser.
+/// The refresh token from Microsoft Entra ID is cached, so the user isn't prompted again for other scopes.
+pub struct DeviceCodeCredential {
+    client: UserCredential,
+    prompt: Box<Prompt>,
+    /// Held while the user authenticates, so the user is prompted once for concurrent requests.
+    authenticating: Mutex<()>,
+}
+
+impl DeviceCodeCredential {
+    /// Create a new `DeviceCodeCredential`.
+    ///
+    /// `prompt` shows the user the [`DeviceCode`] e.g., by printing [`DeviceCode::message`].
+    /// Returning an error stops authentication.
+    pub fn new<F>(
+        tenant_id: &str,
+        client_id: String,
+        prompt: F,
+        options: Option<DeviceCodeCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>>
+    where
+        F: Fn(&DeviceCode) -> azure_core::Result<()> + Send + Sync + 'static,
+    {
+        let options = options.unwrap_or_default();
+        Ok(Arc::new(Self {
+            client: UserCredential::new(
+                DEVICE_CODE_CREDENTIAL,
+                tenant_id,
+                client_id,
+                None,
+                options.additionally_allowed_tenants,
+                options.credential_options,
+            )?,
+            prompt: Box::new(prompt),
+            authenticating: Mutex::new(()),
+        }))
+    }
+
+    async fn get_token_impl(
+        &self,
+        scopes: &[&str],
+        options: Option<&TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        let client = &self.client;
+        let tenant_id = client.tenant_id(options)?;
+        let claims = claims_parameter(options)?;
+        if let Some(token) = client.refresh(tenant_id, scopes, claims.as_deref()).await? {
+            return Ok(token);
+        }
+
+        let device_code = self
+            .device_code(tenant_id, scopes, claims.as_deref())
+            .await?;
+        let expires_on = OffsetDateTime::now_utc() + Duration::seconds(device_code.expires_in);
+        (self.prompt)(&DeviceCode {
+            user_code: device_code.user_code,
+            verification_uri: device_code.verification_uri,
+            message: device_code.message,
+            expires_on,
+        })?;
+
+        let req = client.token_request(
+            tenant_id,
+            &[
+                ("grant_type", GRANT_TYPE),
+                ("device_code", &device_code.device_code),
+            ],
+            scopes,
+            claims.as_deref(),
+        )?;
+        let mut interval = Duration::seconds(device_code.interval);
+        loop {
+            sleep(interval).await;
+            if OffsetDateTime::now_utc() >= expires_on {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    format!(
+                        "{DEVICE_CODE_CREDENTIAL} authentication failed. The device code expired before the user signed in."
+                    ),
+                ));
+            }
+
+            let res = client.send(&req).await?;
+            if res.status() == StatusCode::Ok {
+                return client.token_from_response(tenant_id, res).await;
+            }
+            let error: EntraIdErrorResponse = deserialize(DEVICE_CODE_CREDENTIAL, res).await?;
+            match error.error.as_str() {
+                "authorization_pending" => {}
+                "slow_down" => interval += Duration::seconds(SLOW_DOWN_INTERVAL),
+                _ => return Err(client.error(&error)),
+            }
+        }
+    }
+
+    async fn device_code(
+        &self,
+        tenant_id: &str,
+        scopes: &[&str],
+        claims: Option<&str>,
+    ) -> azure_core::Result<DeviceCodeResponse> {
+        let client = &self.client;
+        let url = client
+            .authority_host()
+            .join(&format!("/{tenant_id}/oauth2/v2.0/devicecode"))
+            .map_err(|e| {
+                Error::full(
+                    ErrorKind::DataConversion,
+                    e,
+                    format!("tenant_id {tenant_id} could not be URL encoded"),
+                )
+            })?;
+
+        let body = {
+            let mut body = form_urlencoded::Serializer::new(String::new());
+            body.append_pair("client_id", client.client_id())
+                .append_pair("scope", &UserCredential::scope(scopes));
+            if let Some(claims) = claims {
+                body.append_pair("claims", claims);
+            }
+            body.finish()
+        };
+
+        let mut req = Request::new(url, Method::Post);
+        req.insert_header(
+            headers::CONTENT_TYPE,
+            content_type::APPLICATION_X_WWW_FORM_URLENCODED,
+        );
+        req.set_body(body);
+
+        let res = client.send(&req).await?;
+        if res.status() != StatusCode::Ok {
+            let error: EntraIdErrorResponse = deserialize(DEVICE_CODE_CREDENTIAL, res).await?;
+            return Err(client.error(&error));
+        }
+        deserialize(DEVICE_CODE_CREDENTIAL, res).await
+    }
+}
+
+impl fmt::Debug for DeviceCodeCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeviceCodeCredential")
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for DeviceCodeCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        if scopes.is_empty() {
+            return Err(Error::message(ErrorKind::Credential, "no scopes specified"));
+        }
+        // The user may take minutes to sign in, so don't hold the cache's lock while authenticating.
+        let cache = &self.client.cache;
+        if let Some(token) = cache.cached_token(scopes, options.as_ref()).await {
+            return Ok(token);
+        }
+        let _authenticating = self.authenticating.lock().await;
+
+        // check again in case another request authenticated the user while this one waited
+        if let Some(token) = cache.cached_token(scopes, options.as_ref()).await {
+            return Ok(token);
+        }
+        let token = self.get_token_impl(scopes, options.as_ref()).await?;
+        cache
+            .insert_token(scopes, options.as_ref(), token.clone())
+            .await;
+        Ok(token)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    expires_in: i64,
+    #[serde(default = "default_interval")]
+    interval: i64,
+    message: String,
+}
+
+fn default_interval() -> i64 {
+    DEFAULT_INTERVAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use azure_core::{
+        authority_hosts::AZURE_PUBLIC_CLOUD,
+        http::{HttpClient, RawResponse},
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt as _;
+    use std::sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Mutex,
+    };
+
+    const FAKE_DEVICE_CODE: &str = "fake device code";
+    const FAKE_REFRESH_TOKEN: &str = "fake refresh token";
+    const FAKE_USER_CODE: &str = "FAKECODE";
+
+    fn device_code_response() -> RawResponse {
+        response(
+            StatusCode::Ok,
+            format!(
+                r#"{{"device_code":"{FAKE_DEVICE_CODE}","user_code":"{FAKE_USER_CODE}","verification_uri":"https://microsoft.com/devicelogin","expires_in":900,"interval":0,"message":"To sign in, enter the code {FAKE_USER_CODE}"}}"#
+            ),
+        )
+    }
+
+    fn token_response() -> RawResponse {
+        response(
+            StatusCode::Ok,
+            format!(
+                r#"{{"access_token":"{FAKE_TOKEN}","refresh_token":"{FAKE_REFRESH_TOKEN}","expires_in":3600,"token_type":"Bearer"}}"#
+            ),
+        )
+    }
+
+    fn error_response(error: &str) -> RawResponse {
+        response(
+            StatusCode::BadRequest,
+            format!(r#"{{"error":"{error}","error_description":"{error} description"}}"#),
+        )
+    }
+
+    fn credential(
+        sts: impl HttpClient + 'static,
+        prompt: impl Fn(&DeviceCode) -> azure_core::Result<()> + Send + Sync + 'static,
+    ) -> Arc<DeviceCodeCredential> {
+        DeviceCodeCredential::new(
+            FAKE_TENANT_ID,
+            FAKE_CLIENT_ID.to_string(),
+            prompt,
+            Some(DeviceCodeCredentialOptions {
+                credential_options: TokenCredentialOptions {
+                    http_client: Arc::new(sts),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        )
+        .expect("valid credential")
+    }
+
+    #[tokio::test]
+    async fn polls_until_user_authenticates() {
+        let sts = MockSts::new(
+            vec![
+                device_code_response(),
+                error_response("authorization_pending"),
+                token_response(),
+            ],
+            Some(Arc::new(|request: &Request| {
+                let form = form(request);
+                assert_eq!(form["client_id"], FAKE_CLIENT_ID);
+                let scope = format!("{} offline_access", LIVE_TEST_SCOPES.join(" "));
+                assert_eq!(form["scope"], scope);
+                if request.url().path().ends_with("/devicecode") {
+                    assert_eq!(
+                        request.url().as_str(),
+                        format!(
+                            "{}{}/oauth2/v2.0/devicecode",
+                            AZURE_PUBLIC_CLOUD.as_str(),
+                            FAKE_TENANT_ID
+                        )
+                    );
+                } else {
+                    assert_eq!(form["grant_type"], GRANT_TYPE);
+                    assert_eq!(form["device_code"], FAKE_DEVICE_CODE);
+                }
+                Ok(())
+            })),
+        );
+        let prompts = Arc::new(Mutex::new(Vec::new()));
+        let prompts_clone = prompts.clone();
+        let credential = credential(sts, move |device_code| {
+            prompts_clone
+                .lock()
+                .unwrap()
+                .push(device_code.user_code.clone());
+            Ok(())
+        });
+
+        let token = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        assert_eq!(token.token.secret(), FAKE_TOKEN);
+        assert_eq!(*prompts.lock().unwrap(), [FAKE_USER_CODE]);
+
+        // the token is cached; the mock has no more responses
+        credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("cached token");
+    }
+
+    #[tokio::test]
+    async fn refresh_token_for_other_scopes() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let requests_clone = requests.clone();
+        let sts = MockHttpClient::new(move |req| {
+            let n = requests_clone.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let form = form(req);
+                match n {
+                    0 => Ok(device_code_response()),
+                    1 => {
+                        assert_eq!(form["device_code"], FAKE_DEVICE_CODE);
+                        Ok(token_response())
+                    }
+                    2 => {
+                        assert_eq!(form["grant_type"], "refresh_token");
+                        assert_eq!(form["refresh_token"], FAKE_REFRESH_TOKEN);
+                        assert_eq!(form["scope"], "other/.default offline_access");
+                        Ok(token_response())
+                    }
+                    _ => panic!("unexpected request"),
+                }
+            }
+            .boxed()
+        });
+        let prompts = Arc::new(AtomicUsize::new(0));
+        let prompts_clone = prompts.clone();
+        let credential = credential(sts, move |_| {
+            prompts_clone.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        });
+
+        credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        credential
+            .get_token(&["other/.default"], None)
+            .await
+            .expect("token from refresh token");
+        assert_eq!(prompts.load(Ordering::SeqCst), 1);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn cached_tokens_returned_while_user_authenticates() {
+        let pending = Arc::new(AtomicBool::new(false));
+        let pending_clone = pending.clone();
+        let sts = MockHttpClient::new(move |req| {
+            let pending = pending_clone.clone();
+            async move {
+                if req.url().path().ends_with("/devicecode") {
+                    return Ok(device_code_response());
+                }
+                match form(req)["grant_type"].as_str() {
+                    "refresh_token" => Ok(error_response("invalid_grant")),
+                    _ if pending.load(Ordering::SeqCst) => {
+                        Ok(error_response("authorization_pending"))
+                    }
+                    _ => Ok(token_response()),
+                }
+            }
+            .boxed()
+        });
+        let credential = credential(sts, |_| Ok(()));
+        credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+
+        // The refresh token is rejected, so the user must authenticate for other scopes.
+        pending.store(true, Ordering::SeqCst);
+        let authenticate = credential.get_token(&["other/.default"], None);
+        let cached = async {
+            let token = credential.get_token(LIVE_TEST_SCOPES, None).await;
+            pending.store(false, Ordering::SeqCst);
+            token
+        };
+        let (token, cached) = futures::join!(authenticate, cached);
+        token.expect("token after user authenticates");
+        cached.expect("cached token while user authenticates");
+    }
+
+    #[tokio::test]
+    async fn declined() {
+        let sts = MockSts::new(
+            vec![
+                device_code_response(),
+                error_response("authorization_declined"),
+            ],
+            None,
+        );
+        let credential = credential(sts, |_| Ok(()));
+        let err = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect_err("declined");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        assert_eq!(
+            err.to_string(),
+            "DeviceCodeCredential authentication failed. authorization_declined description"
+        );
+    }
+
+    #[tokio::test]
+    async fn prompt_error() {
+        let sts = MockSts::new(vec![device_code_response()], None);
+        let credential = credential(sts, |_| {
+            Err(Error::message(ErrorKind::Other, "user canceled"))
+        });
+        let err = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect_err("prompt error");
+        assert_eq!(err.to_string(), "user canceled");
+    }
+}
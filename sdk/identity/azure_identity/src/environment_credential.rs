@@ -0,0 +1,287 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+#[cfg(feature = "client_certificate")]
+use crate::{ClientCertificateCredential, ClientCertificateCredentialOptions};
+use crate::{ClientSecretCredential, ClientSecretCredentialOptions, TokenCredentialOptions};
+use azure_core::{
+    credentials::{AccessToken, TokenCredential, TokenRequestOptions},
+    error::{Error, ErrorKind},
+};
+use std::sync::Arc;
+use tracing::info;
+
+const AZURE_TENANT_ID: &str = "AZURE_TENANT_ID";
+const AZURE_CLIENT_ID: &str = "AZURE_CLIENT_ID";
+const AZURE_CLIENT_SECRET: &str = "AZURE_CLIENT_SECRET";
+const AZURE_CLIENT_CERTIFICATE_PATH: &str = "AZURE_CLIENT_CERTIFICATE_PATH";
+#[cfg(feature = "client_certificate")]
+const AZURE_CLIENT_CERTIFICATE_PASSWORD: &str = "AZURE_CLIENT_CERTIFICATE_PASSWORD";
+
+/// Authenticates a service principal configured by environment variables.
+///
+/// `AZURE_TENANT_ID` and `AZURE_CLIENT_ID` are always required, along with one of the following:
+///
+/// * `AZURE_CLIENT_SECRET` to authenticate with a [`ClientSecretCredential`].
+/// * `AZURE_CLIENT_CERTIFICATE_PATH` to authenticate with a `ClientCertificateCredential`, which requires the `client_certificate` feature.
+///   The file must contain a PKCS12 certificate and private key, and may be protected by `AZURE_CLIENT_CERTIFICATE_PASSWORD`.
+///   Set `AZURE_CLIENT_SEND_CERTIFICATE_CHAIN` to `1` or `true` to send the certificate chain for subject name/issuer authentication.
+///
+/// When both a secret and certificate are configured, the secret is used.
+#[derive(Debug)]
+pub struct EnvironmentCredential {
+    credential: Arc<dyn TokenCredential>,
+}
+
+/// Options for constructing a new [`EnvironmentCredential`].
+#[derive(Clone, Debug, Default)]
+pub struct EnvironmentCredentialOptions {
+    /// The [`TokenCredentialOptions`] to use for the credential.
+    pub credential_options: TokenCredentialOptions,
+
+    /// Additional tenants for which the credential may acquire tokens.
+    ///
+    /// Add the wildcard value "*" to allow the credential to acquire tokens for any tenant in which the application is registered.
+    pub additionally_allowed_tenants: Vec<String>,
+}
+
+impl From<TokenCredentialOptions> for EnvironmentCredentialOptions {
+    fn from(credential_options: TokenCredentialOptions) -> Self {
+        Self {
+            credential_options,
+            ..Default::default()
+        }
+    }
+}
+
+impl EnvironmentCredential {
+    /// Create a new `EnvironmentCredential` from the environment variables.
+    ///
+    /// Returns an error listing the missing variables if the environment doesn't configure a service principal.
+    pub fn new(options: Option<EnvironmentCredentialOptions>) -> azure_core::Result<Arc<Self>> {
+        let options = options.unwrap_or_default();
+        let env = options.credential_options.env();
+
+        let mut missing = Vec::new();
+        let tenant_id = env.var(AZURE_TENANT_ID).ok();
+        if tenant_id.is_none() {
+            missing.push(AZURE_TENANT_ID);
+        }
+        let client_id = env.var(AZURE_CLIENT_ID).ok();
+        if client_id.is_none() {
+            missing.push(AZURE_CLIENT_ID);
+        }
+        let secret = env.var(AZURE_CLIENT_SECRET).ok();
+        let certificate_path = env.var(AZURE_CLIENT_CERTIFICATE_PATH).ok();
+        if secret.is_none() && certificate_path.is_none() {
+            missing.push("AZURE_CLIENT_SECRET or AZURE_CLIENT_CERTIFICATE_PATH");
+        }
+
+        let (Some(tenant_id), Some(client_id)) = (tenant_id, client_id) else {
+            return Err(missing_vars(&missing));
+        };
+
+        let credential: Arc<dyn TokenCredential> = match (secret, certificate_path) {
+            (Some(secret), _) => {
+                info!("EnvironmentCredential will authenticate with a client secret");
+                ClientSecretCredential::new(
+                    &tenant_id,
+                    client_id,
+                    secret.into(),
+                    Some(ClientSecretCredentialOptions {
+                        additionally_allowed_tenants: options.additionally_allowed_tenants,
+                        credential_options: options.credential_options,
+                    }),
+                )?
+            }
+            #[cfg(feature = "client_certificate")]
+            (None, Some(path)) => {
+                info!("EnvironmentCredential will authenticate with a client certificate");
+                let certificate = std::fs::read(&path).map_err(|err| {
+                    Error::full(
+                        ErrorKind::Credential,
+                        err,
+                        format!("failed to read certificate file {path} from {AZURE_CLIENT_CERTIFICATE_PATH}"),
+                    )
+                })?;
+                let password = env
+                    .var(AZURE_CLIENT_CERTIFICATE_PASSWORD)
+                    .unwrap_or_default();
+
+                // Reads AZURE_CLIENT_SEND_CERTIFICATE_CHAIN from the environment.
+                let mut certificate_options =
+                    ClientCertificateCredentialOptions::from(options.credential_options);
+                certificate_options
+                    .set_additionally_allowed_tenants(options.additionally_allowed_tenants);
+
+                ClientCertificateCredential::new(
+                    tenant_id,
+                    client_id,
+                    azure_core::base64::encode(certificate),
+                    password,
+                    certificate_options,
+                )?
+            }
+            #[cfg(not(feature = "client_certificate"))]
+            (None, Some(_)) => {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "AZURE_CLIENT_CERTIFICATE_PATH is set but certificate authentication requires the client_certificate feature",
+                ));
+            }
+            (None, None) => return Err(missing_vars(&missing)),
+        };
+
+        Ok(Arc::new(Self { credential }))
+    }
+}
+
+fn missing_vars(missing: &[&str]) -> Error {
+    let missing = missing.join(", ");
+    Error::with_message(ErrorKind::Credential, || {
+        format!("missing environment variables: {missing}")
+    })
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for EnvironmentCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        self.credential.get_token(scopes, options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{env::Env, tests::*};
+    use azure_core::{
+        http::{headers::Headers, RawResponse, Request, StatusCode},
+        Bytes,
+    };
+    use std::str;
+
+    fn options(
+        vars: &[(&str, &str)],
+        http_client: Option<MockSts>,
+    ) -> EnvironmentCredentialOptions {
+        let mut credential_options = TokenCredentialOptions {
+            env: Env::from(vars),
+            ..Default::default()
+        };
+        if let Some(http_client) = http_client {
+            credential_options.http_client = Arc::new(http_client);
+        }
+        credential_options.into()
+    }
+
+    #[test]
+    fn reports_missing_variables() {
+        let err =
+            EnvironmentCredential::new(Some(options(&[], None))).expect_err("missing variables");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        assert_eq!(
+            err.to_string(),
+            "missing environment variables: AZURE_TENANT_ID, AZURE_CLIENT_ID, AZURE_CLIENT_SECRET or AZURE_CLIENT_CERTIFICATE_PATH"
+        );
+
+        let err = EnvironmentCredential::new(Some(options(
+            &[
+                (AZURE_TENANT_ID, FAKE_TENANT_ID),
+                (AZURE_CLIENT_SECRET, "fake secret"),
+            ],
+            None,
+        )))
+        .expect_err("missing client ID");
+        assert_eq!(
+            err.to_string(),
+            "missing environment variables: AZURE_CLIENT_ID"
+        );
+
+        let err = EnvironmentCredential::new(Some(options(
+            &[
+                (AZURE_TENANT_ID, FAKE_TENANT_ID),
+                (AZURE_CLIENT_ID, FAKE_CLIENT_ID),
+            ],
+            None,
+        )))
+        .expect_err("missing secret or certificate");
+        assert_eq!(
+            err.to_string(),
+            "missing environment variables: AZURE_CLIENT_SECRET or AZURE_CLIENT_CERTIFICATE_PATH"
+        );
+    }
+
+    #[tokio::test]
+    async fn client_secret() {
+        let sts = MockSts::new(
+            vec![RawResponse::from_bytes(
+                StatusCode::Ok,
+                Headers::default(),
+                Bytes::from(format!(
+                    r#"{{"access_token":"{FAKE_TOKEN}","expires_in":3600,"token_type":"Bearer"}}"#
+                )),
+            )],
+            Some(Arc::new(|request: &Request| {
+                assert!(request
+                    .url()
+                    .path()
+                    .starts_with(&format!("/{FAKE_TENANT_ID}/")));
+                let body = match request.body() {
+                    azure_core::http::Body::Bytes(bytes) => str::from_utf8(bytes).unwrap(),
+                    _ => panic!("unexpected body type"),
+                };
+                assert!(body.contains("client_secret=fake+secret"), "{body}");
+                Ok(())
+            })),
+        );
+        // The secret is preferred when a certificate path is also set.
+        let credential = EnvironmentCredential::new(Some(options(
+            &[
+                (AZURE_TENANT_ID, FAKE_TENANT_ID),
+                (AZURE_CLIENT_ID, FAKE_CLIENT_ID),
+                (AZURE_CLIENT_SECRET, "fake secret"),
+                (AZURE_CLIENT_CERTIFICATE_PATH, "/does/not/exist.pfx"),
+            ],
+            Some(sts),
+        )))
+        .expect("valid credential");
+
+        let token = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        assert_eq!(token.token.secret(), FAKE_TOKEN);
+    }
+
+    #[test]
+    fn client_certificate() {
+        let result = EnvironmentCredential::new(Some(options(
+            &[
+                (AZURE_TENANT_ID, FAKE_TENANT_ID),
+                (AZURE_CLIENT_ID, FAKE_CLIENT_ID),
+                (AZURE_CLIENT_CERTIFICATE_PATH, "/does/not/exist.pfx"),
+            ],
+            None,
+        )));
+        let err = result.expect_err("certificate can't be used");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+
+        #[cfg(feature = "client_certificate")]
+        assert!(
+            err.to_string().starts_with(
+                "failed to read certificate file /does/not/exist.pfx from AZURE_CLIENT_CERTIFICATE_PATH"
+            ),
+            "{err}"
+        );
+        #[cfg(not(feature = "client_certificate"))]
+        assert!(
+            err.to_string().contains("client_certificate feature"),
+            "{err}"
+        );
+    }
+}
@@ -5,7 +5,9 @@ use crate::{env::Env, TokenCache, TokenCredentialOptions, UserAssignedId};
 use azure_core::{
     credentials::{AccessToken, Secret, TokenCredential, TokenRequestOptions},
     error::{http_response_from_body, Error, ErrorKind},
-    http::{headers::HeaderName, request::Request, HttpClient, Method, StatusCode, Url},
+    http::{
+        headers::HeaderName, request::Request, HttpClient, Method, RawResponse, StatusCode, Url,
+    },
     json::from_json,
     time::OffsetDateTime,
 };
@@ -112,35 +114,7 @@ impl ImdsManagedIdentityCredential {
         };
 
         let rsp = self.http_client.execute_request(&req).await?;
-
-        let (rsp_status, _, rsp_body) = rsp.deconstruct();
-        let rsp_body = rsp_body.collect().await?;
-
-        if !rsp_status.is_success() {
-            match rsp_status {
-                StatusCode::BadRequest => {
-                    return Err(Error::message(
-                        ErrorKind::Credential,
-                        "the requested identity has not been assigned to this resource",
-                    ))
-                }
-                StatusCode::BadGateway | StatusCode::GatewayTimeout => {
-                    return Err(Error::message(
-                        ErrorKind::Credential,
-                        "the request failed due to a gateway error",
-                    ))
-                }
-                rsp_status => {
-                    return Err(http_response_from_body(rsp_status, &rsp_body).into_error())
-                }
-            }
-        }
-
-        let token_response: MsiTokenResponse = from_json(&rsp_body)?;
-        Ok(AccessToken::new(
-            token_response.access_token,
-            token_response.expires_on,
-        ))
+        token_from_response(rsp).await
     }
 }
 
@@ -158,12 +132,52 @@ impl TokenCredential for ImdsManagedIdentityCredential {
     }
 }
 
+/// Deserializes a managed identity token response, or returns an error for an unsuccessful response.
+pub(crate) async fn token_from_response(rsp: RawResponse) -> azure_core::Result<AccessToken> {
+    let (rsp_status, _, rsp_body) = rsp.deconstruct();
+    let rsp_body = rsp_body.collect().await?;
+
+    if !rsp_status.is_success() {
+        match rsp_status {
+            StatusCode::BadRequest => {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "the requested identity has not been assigned to this resource",
+                ))
+            }
+            StatusCode::BadGateway | StatusCode::GatewayTimeout => {
+                return Err(Error::message(
+                    ErrorKind::Credential,
+                    "the request failed due to a gateway error",
+                ))
+            }
+            rsp_status => return Err(http_response_from_body(rsp_status, &rsp_body).into_error()),
+        }
+    }
+
+    let token_response: MsiTokenResponse = from_json(&rsp_body)?;
+    Ok(AccessToken::new(
+        token_response.access_token,
+        token_response.expires_on,
+    ))
+}
+
+/// Some managed identity sources return `expires_on` as a string and others as an integer.
 fn expires_on_string<'de, D>(deserializer: D) -> std::result::Result<OffsetDateTime, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let v = String::deserialize(deserializer)?;
-    let as_i64 = v.parse::<i64>().map_err(de::Error::custom)?;
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ExpiresOn {
+        String(String),
+        Integer(i64),
+    }
+
+    let as_i64 = match ExpiresOn::deserialize(deserializer)? {
+        ExpiresOn::String(v) => v.parse::<i64>().map_err(de::Error::custom)?,
+        ExpiresOn::Integer(v) => v,
+    };
     OffsetDateTime::from_unix_timestamp(as_i64).map_err(de::Error::custom)
 }
 
@@ -171,7 +185,7 @@ where
 ///
 /// Directly based on the `azure-sdk-for-python` implementation:
 /// ref: <https://github.com/Azure/azure-sdk-for-python/blob/d6aeefef46c94b056419613f1a5cc9eaa3af0d22/sdk/identity/azure-identity/azure/identity/_internal/__init__.py#L22>
-fn scopes_to_resource<'a>(scopes: &'a [&'a str]) -> azure_core::Result<&'a str> {
+pub(crate) fn scopes_to_resource<'a>(scopes: &'a [&'a str]) -> azure_core::Result<&'a str> {
     if scopes.len() != 1 {
         return Err(Error::message(
             ErrorKind::Credential,
@@ -218,6 +232,10 @@ mod tests {
         let expected = datetime!(2020-4-15 21:5:35 UTC);
         let parsed: TestExpires = from_json(as_string)?;
         assert_eq!(expected, parsed.date);
+
+        let as_integer = r#"{"date": 1586984735}"#;
+        let parsed: TestExpires = from_json(as_integer)?;
+        assert_eq!(expected, parsed.date);
         Ok(())
     }
 }
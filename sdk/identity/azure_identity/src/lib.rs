@@ -5,25 +5,35 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
 mod app_service_managed_identity_credential;
+mod authorization_code_credential;
+mod azure_arc_managed_identity_credential;
 #[cfg(not(target_arch = "wasm32"))]
 mod azure_cli_credential;
 mod azure_developer_cli_credential;
+mod azure_ml_managed_identity_credential;
 mod azure_pipelines_credential;
 mod cache;
 mod client_assertion_credential;
 #[cfg(feature = "client_certificate")]
 mod client_certificate_credential;
 mod client_secret_credential;
+mod cloud_shell_managed_identity_credential;
 mod default_azure_credential;
+mod device_code_credential;
 mod env;
+mod environment_credential;
 mod imds_managed_identity_credential;
 mod managed_identity_credential;
+mod on_behalf_of_credential;
 mod options;
 mod process;
+mod service_fabric_managed_identity_credential;
+mod user_credential;
 mod virtual_machine_managed_identity_credential;
 mod workload_identity_credential;
 
 #[cfg(not(target_arch = "wasm32"))]
+pub use authorization_code_credential::*;
 pub use azure_cli_credential::*;
 pub use azure_developer_cli_credential::*;
 pub use azure_pipelines_credential::*;
@@ -32,19 +42,27 @@ pub use client_assertion_credential::*;
 pub use client_certificate_credential::*;
 pub use client_secret_credential::*;
 pub use default_azure_credential::*;
+pub use device_code_credential::*;
+pub use environment_credential::*;
 pub use managed_identity_credential::*;
+pub use on_behalf_of_credential::*;
 pub use options::TokenCredentialOptions;
 pub use process::{new_executor, Executor};
 pub use workload_identity_credential::*;
 
 pub(crate) use app_service_managed_identity_credential::*;
+pub(crate) use azure_arc_managed_identity_credential::*;
+pub(crate) use azure_ml_managed_identity_credential::*;
 pub(crate) use cache::TokenCache;
+pub(crate) use cloud_shell_managed_identity_credential::*;
 pub(crate) use imds_managed_identity_credential::*;
+pub(crate) use service_fabric_managed_identity_credential::*;
 pub(crate) use virtual_machine_managed_identity_credential::*;
 
 use azure_core::{
+    credentials::TokenRequestOptions,
     error::{ErrorKind, ResultExt},
-    http::RawResponse,
+    http::{RawResponse, Url},
     Error, Result,
 };
 use serde::Deserialize;
@@ -53,6 +71,7 @@ use std::borrow::Cow;
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 struct EntraIdErrorResponse {
+    error: String,
     error_description: String,
 }
 
@@ -65,6 +84,7 @@ struct EntraIdTokenResponse {
     expires_in: i64,
     ext_expires_in: i64,
     access_token: String,
+    refresh_token: Option<String>,
 }
 
 async fn deserialize<T>(credential_name: &str, res: RawResponse) -> Result<T>
@@ -173,20 +193,151 @@ fn test_validate_tenant_id() {
     assert!(validate_tenant_id("7b795fb9-09d3-42f4-a494-38864f99ba3c").is_ok());
 }
 
+/// Gets the tenant to request a token from.
+///
+/// A tenant other than `tenant_id` may be requested in `options` only if it is in `additionally_allowed_tenants`,
+/// or if `additionally_allowed_tenants` contains the wildcard "*".
+fn resolve_tenant_id<'a>(
+    tenant_id: &'a str,
+    options: Option<&'a TokenRequestOptions>,
+    additionally_allowed_tenants: &[String],
+) -> Result<&'a str> {
+    let Some(requested) = options.and_then(|o| o.tenant_id.as_deref()) else {
+        return Ok(tenant_id);
+    };
+    if requested == tenant_id
+        || additionally_allowed_tenants
+            .iter()
+            .any(|t| t == "*" || t == requested)
+    {
+        validate_tenant_id(requested)?;
+        return Ok(requested);
+    }
+
+    Err(Error::message(
+        ErrorKind::Credential,
+        format!("the credential is not configured to acquire tokens for tenant {requested}. Add it to additionally_allowed_tenants, or add \"*\" to allow acquiring tokens for any tenant"),
+    ))
+}
+
+#[test]
+fn test_resolve_tenant_id() {
+    let options = TokenRequestOptions {
+        tenant_id: Some("other".into()),
+        ..Default::default()
+    };
+    assert_eq!(resolve_tenant_id("tenant", None, &[]).unwrap(), "tenant");
+    assert_eq!(
+        resolve_tenant_id("tenant", Some(&TokenRequestOptions::default()), &[]).unwrap(),
+        "tenant"
+    );
+    assert!(resolve_tenant_id("tenant", Some(&options), &[]).is_err());
+    assert!(resolve_tenant_id("tenant", Some(&options), &["another".into()]).is_err());
+    assert_eq!(
+        resolve_tenant_id("tenant", Some(&options), &["other".into()]).unwrap(),
+        "other"
+    );
+    assert_eq!(
+        resolve_tenant_id("tenant", Some(&options), &["*".into()]).unwrap(),
+        "other"
+    );
+}
+
+/// Gets the Microsoft Entra ID token endpoint for a tenant.
+fn token_endpoint(authority_host: &Url, tenant_id: &str) -> Result<Url> {
+    authority_host
+        .join(&format!("/{tenant_id}/oauth2/v2.0/token"))
+        .with_context(ErrorKind::DataConversion, || {
+            format!("tenant_id {tenant_id} could not be URL encoded")
+        })
+}
+
+/// Gets the `claims` parameter to send to Microsoft Entra ID, if any.
+///
+/// When CAE is enabled, the "CP1" client capability is merged into the requested claims
+/// so Microsoft Entra ID issues a token that may be revoked by services supporting CAE.
+fn claims_parameter(options: Option<&TokenRequestOptions>) -> Result<Option<String>> {
+    let Some(options) = options else {
+        return Ok(None);
+    };
+    let claims = options.claims.as_deref().filter(|c| !c.is_empty());
+    if !options.enable_cae {
+        return Ok(claims.map(String::from));
+    }
+
+    let mut claims: serde_json::Map<String, serde_json::Value> = match claims {
+        Some(claims) => serde_json::from_str(claims)
+            .with_context(ErrorKind::Credential, || format!("invalid claims {claims}"))?,
+        None => serde_json::Map::new(),
+    };
+    let access_token = claims
+        .entry("access_token")
+        .or_insert_with(|| serde_json::Value::Object(Default::default()));
+    if let serde_json::Value::Object(access_token) = access_token {
+        access_token.insert("xms_cc".into(), serde_json::json!({ "values": ["CP1"] }));
+    }
+    Ok(Some(serde_json::Value::Object(claims).to_string()))
+}
+
+#[test]
+fn test_claims_parameter() {
+    assert_eq!(claims_parameter(None).unwrap(), None);
+    assert_eq!(
+        claims_parameter(Some(&TokenRequestOptions::default())).unwrap(),
+        None
+    );
+    assert_eq!(
+        claims_parameter(Some(&TokenRequestOptions {
+            claims: Some(r#"{"access_token":{"nbf":{"essential":true}}}"#.into()),
+            ..Default::default()
+        }))
+        .unwrap()
+        .as_deref(),
+        Some(r#"{"access_token":{"nbf":{"essential":true}}}"#)
+    );
+    assert_eq!(
+        claims_parameter(Some(&TokenRequestOptions {
+            enable_cae: true,
+            ..Default::default()
+        }))
+        .unwrap()
+        .as_deref(),
+        Some(r#"{"access_token":{"xms_cc":{"values":["CP1"]}}}"#)
+    );
+    assert_eq!(
+        claims_parameter(Some(&TokenRequestOptions {
+            claims: Some(r#"{"access_token":{"nbf":{"essential":true}}}"#.into()),
+            enable_cae: true,
+            ..Default::default()
+        }))
+        .unwrap()
+        .as_deref(),
+        Some(r#"{"access_token":{"nbf":{"essential":true},"xms_cc":{"values":["CP1"]}}}"#)
+    );
+    assert!(claims_parameter(Some(&TokenRequestOptions {
+        claims: Some("not json".into()),
+        enable_cae: true,
+        ..Default::default()
+    }))
+    .is_err());
+}
+
 #[cfg(test)]
 mod tests {
     use crate::process::Executor;
     use async_trait::async_trait;
     use azure_core::{
         error::ErrorKind,
-        http::{RawResponse, Request},
-        Error, Result,
+        http::{headers::Headers, Body, RawResponse, Request, StatusCode},
+        Bytes, Error, Result,
     };
     use std::{
+        collections::HashMap,
         ffi::OsStr,
         process::Output,
         sync::{Arc, Mutex},
     };
+    use url::form_urlencoded;
 
     pub const FAKE_CLIENT_ID: &str = "fake-client";
     pub const FAKE_TENANT_ID: &str = "fake-tenant";
@@ -295,4 +446,16 @@ mod tests {
             }
         }
     }
+
+    /// Parses the form-encoded body of a request to a token endpoint.
+    pub fn form(request: &Request) -> HashMap<String, String> {
+        let Body::Bytes(bytes) = request.body() else {
+            panic!("unexpected body type");
+        };
+        form_urlencoded::parse(bytes).into_owned().collect()
+    }
+
+    pub fn response(status: StatusCode, body: String) -> RawResponse {
+        RawResponse::from_bytes(status, Headers::default(), Bytes::from(body))
+    }
 }
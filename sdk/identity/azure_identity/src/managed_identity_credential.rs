@@ -2,7 +2,9 @@
 // Licensed under the MIT License.
 
 use crate::{
-    env::Env, AppServiceManagedIdentityCredential, ImdsId, TokenCredentialOptions,
+    env::Env, AppServiceManagedIdentityCredential, AzureArcManagedIdentityCredential,
+    AzureMLManagedIdentityCredential, CloudShellManagedIdentityCredential, ImdsId,
+    ServiceFabricManagedIdentityCredential, TokenCredentialOptions,
     VirtualMachineManagedIdentityCredential,
 };
 use azure_core::credentials::{AccessToken, TokenCredential, TokenRequestOptions};
@@ -20,7 +22,11 @@ pub enum UserAssignedId {
     ResourceId(String),
 }
 
-/// Authenticates a managed identity from Azure App Service or an Azure Virtual Machine.
+/// Authenticates a managed identity from Azure App Service, Azure Arc, Azure Cloud Shell, Azure Machine Learning,
+/// Service Fabric, or an Azure Virtual Machine.
+///
+/// The source is detected from environment variables. Azure Arc, Cloud Shell, and Service Fabric support only
+/// system-assigned identities, and Azure Machine Learning supports only user-assigned client IDs.
 #[derive(Debug)]
 pub struct ManagedIdentityCredential {
     credential: Arc<dyn TokenCredential>,
@@ -62,14 +68,20 @@ impl ManagedIdentityCredential {
                 }
                 AppServiceManagedIdentityCredential::new(id, options.credential_options)?
             }
+            ManagedIdentitySource::AzureArc => {
+                AzureArcManagedIdentityCredential::new(id, options.credential_options)?
+            }
+            ManagedIdentitySource::AzureML => {
+                AzureMLManagedIdentityCredential::new(id, options.credential_options)?
+            }
+            ManagedIdentitySource::CloudShell => {
+                CloudShellManagedIdentityCredential::new(id, options.credential_options)?
+            }
             ManagedIdentitySource::Imds => {
                 VirtualMachineManagedIdentityCredential::new(id, options.credential_options)?
             }
-            _ => {
-                return Err(azure_core::Error::with_message(
-                    azure_core::error::ErrorKind::Credential,
-                    || format!("{} managed identity isn't supported", source.as_str()),
-                ));
+            ManagedIdentitySource::ServiceFabric => {
+                ServiceFabricManagedIdentityCredential::new(id, options.credential_options)?
             }
         };
 
@@ -120,6 +132,11 @@ impl ManagedIdentitySource {
     }
 }
 
+/// Whether [`ManagedIdentityCredential`] would authenticate using IMDS in the given environment.
+pub(crate) fn is_imds(env: &Env) -> bool {
+    matches!(get_source(env), ManagedIdentitySource::Imds)
+}
+
 const IDENTITY_ENDPOINT: &str = "IDENTITY_ENDPOINT";
 const IDENTITY_HEADER: &str = "IDENTITY_HEADER";
 const IDENTITY_SERVER_THUMBPRINT: &str = "IDENTITY_SERVER_THUMBPRINT";
@@ -237,7 +254,11 @@ mod tests {
         }
     }
 
-    fn run_unsupported_source_test(env: Env, expected_source: ManagedIdentitySource) {
+    fn run_unsupported_id_test(
+        env: Env,
+        expected_source: ManagedIdentitySource,
+        id: UserAssignedId,
+    ) {
         let actual_source = get_source(&env);
         assert_eq!(
             std::mem::discriminant(&actual_source),
@@ -248,7 +269,7 @@ mod tests {
                 env,
                 ..Default::default()
             },
-            ..Default::default()
+            user_assigned_id: Some(id),
         }));
         assert!(
             matches!(result, Err(ref e) if *e.kind() == azure_core::error::ErrorKind::Credential),
@@ -340,32 +361,118 @@ mod tests {
         );
     }
 
+    fn arc_env() -> Env {
+        Env::from(
+            &[
+                (
+                    IDENTITY_ENDPOINT,
+                    "http://localhost:40342/metadata/identity/oauth2/token",
+                ),
+                (IMDS_ENDPOINT, "http://localhost:40342"),
+            ][..],
+        )
+    }
+
+    #[cfg(any(target_os = "linux", windows))]
     #[test]
     fn arc() {
-        run_unsupported_source_test(
-            Env::from(
-                &[
-                    (IDENTITY_ENDPOINT, "http://localhost"),
-                    (IMDS_ENDPOINT, "..."),
-                ][..],
-            ),
+        let env = arc_env();
+        assert!(matches!(get_source(&env), ManagedIdentitySource::AzureArc));
+        ManagedIdentityCredential::new(Some(ManagedIdentityCredentialOptions {
+            credential_options: TokenCredentialOptions {
+                env,
+                ..Default::default()
+            },
+            ..Default::default()
+        }))
+        .expect("valid credential");
+    }
+
+    #[test]
+    fn arc_user_assigned_id() {
+        run_unsupported_id_test(
+            arc_env(),
             ManagedIdentitySource::AzureArc,
+            UserAssignedId::ClientId("expected client ID".to_string()),
         );
     }
 
+    async fn run_azure_ml_test(options: Option<ManagedIdentityCredentialOptions>) {
+        let endpoint = "http://localhost:46808/MSI/auth";
+        let secret = "secret";
+        let mut model = Request::new(endpoint.parse().unwrap(), Method::Get);
+        model.insert_header("secret", secret);
+        let mut params = Vec::from([
+            ("api-version", "2017-09-01"),
+            ("resource", LIVE_TEST_RESOURCE),
+        ]);
+        if let Some(UserAssignedId::ClientId(client_id)) = options
+            .as_ref()
+            .and_then(|options| options.user_assigned_id.as_ref())
+        {
+            params.push(("clientid", client_id));
+        }
+        model.url_mut().query_pairs_mut().extend_pairs(params);
+        run_supported_source_test(
+            Env::from(&[(MSI_ENDPOINT, endpoint), (MSI_SECRET, secret)][..]),
+            options,
+            ManagedIdentitySource::AzureML,
+            model,
+            format!(
+                r#"{{"access_token":"*","expires_in":"3600","expires_on":"{}","resource":"{}","token_type":"Bearer"}}"#,
+                EXPIRES_ON, LIVE_TEST_RESOURCE
+            ),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn azure_ml() {
+        run_azure_ml_test(None).await;
+    }
+
+    #[tokio::test]
+    async fn azure_ml_client_id() {
+        run_azure_ml_test(Some(ManagedIdentityCredentialOptions {
+            user_assigned_id: Some(UserAssignedId::ClientId("expected client ID".to_string())),
+            ..Default::default()
+        }))
+        .await;
+    }
+
     #[test]
-    fn azure_ml() {
-        run_unsupported_source_test(
-            Env::from(&[(MSI_ENDPOINT, "..."), (MSI_SECRET, "...")][..]),
+    fn azure_ml_object_id() {
+        run_unsupported_id_test(
+            Env::from(&[(MSI_ENDPOINT, "http://localhost"), (MSI_SECRET, "...")][..]),
             ManagedIdentitySource::AzureML,
+            UserAssignedId::ObjectId("expected object ID".to_string()),
         );
     }
 
+    #[tokio::test]
+    async fn cloudshell() {
+        let endpoint = "http://localhost:50342/oauth2/token";
+        let mut model = Request::new(endpoint.parse().unwrap(), Method::Post);
+        model.insert_header("metadata", "true");
+        run_supported_source_test(
+            Env::from(&[(MSI_ENDPOINT, endpoint)][..]),
+            None,
+            ManagedIdentitySource::CloudShell,
+            model,
+            format!(
+                r#"{{"access_token":"*","expires_in":"3600","expires_on":"{}","resource":"{}","token_type":"Bearer"}}"#,
+                EXPIRES_ON, LIVE_TEST_RESOURCE
+            ),
+        )
+        .await;
+    }
+
     #[test]
-    fn cloudshell() {
-        run_unsupported_source_test(
+    fn cloudshell_user_assigned_id() {
+        run_unsupported_id_test(
             Env::from(&[(MSI_ENDPOINT, "http://localhost")][..]),
             ManagedIdentitySource::CloudShell,
+            UserAssignedId::ClientId("expected client ID".to_string()),
         );
     }
 
@@ -453,17 +560,45 @@ mod tests {
         }
     }
 
+    fn service_fabric_env() -> Env {
+        Env::from(
+            &[
+                (
+                    IDENTITY_ENDPOINT,
+                    "https://localhost:2377/metadata/identity/oauth2/token",
+                ),
+                (IDENTITY_HEADER, "..."),
+                (IDENTITY_SERVER_THUMBPRINT, "..."),
+            ][..],
+        )
+    }
+
     #[test]
     fn service_fabric() {
-        run_unsupported_source_test(
-            Env::from(
-                &[
-                    (IDENTITY_ENDPOINT, "http://localhost"),
-                    (IDENTITY_HEADER, "..."),
-                    (IDENTITY_SERVER_THUMBPRINT, "..."),
-                ][..],
-            ),
+        let env = service_fabric_env();
+        assert!(matches!(
+            get_source(&env),
+            ManagedIdentitySource::ServiceFabric
+        ));
+
+        // The endpoint certificate can't be verified without the reqwest_rustls feature and a default crypto provider.
+        let err = ManagedIdentityCredential::new(Some(ManagedIdentityCredentialOptions {
+            credential_options: TokenCredentialOptions {
+                env,
+                ..Default::default()
+            },
+            ..Default::default()
+        }))
+        .expect_err("no crypto provider");
+        assert_eq!(err.kind(), &azure_core::error::ErrorKind::Credential);
+    }
+
+    #[test]
+    fn service_fabric_user_assigned_id() {
+        run_unsupported_id_test(
+            service_fabric_env(),
             ManagedIdentitySource::ServiceFabric,
+            UserAssignedId::ResourceId("expected resource ID".to_string()),
         );
     }
 }
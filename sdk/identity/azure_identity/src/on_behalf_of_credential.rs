@@ -0,0 +1,491 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    claims_parameter, deserialize, resolve_tenant_id, token_endpoint, validate_not_empty,
+    validate_tenant_id, ClientAssertion, EntraIdErrorResponse, EntraIdTokenResponse, TokenCache,
+    TokenCredentialOptions,
+};
+use azure_core::{
+    credentials::{AccessToken, Secret, TokenCredential, TokenRequestOptions},
+    error::ErrorKind,
+    http::{
+        headers::{self, content_type},
+        Method, Request, StatusCode, Url,
+    },
+    time::{Duration, OffsetDateTime},
+    Error,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    str,
+    sync::{Arc, Mutex},
+};
+use url::form_urlencoded;
+
+const ASSERTION_TYPE: &str = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+const ON_BEHALF_OF_CREDENTIAL: &str = "OnBehalfOfCredential";
+
+/// How an [`OnBehalfOfCredential`] authenticates the application.
+#[derive(Debug, Clone)]
+pub enum OnBehalfOfClientAuth {
+    /// A client secret.
+    Secret(Secret),
+
+    /// A base64-encoded PKCS12 certificate and its password.
+    #[cfg(feature = "client_certificate")]
+    Certificate {
+        /// The base64-encoded PKCS12 certificate.
+        certificate: Secret,
+        /// The password for the certificate.
+        password: Secret,
+        /// Whether to send the certificate chain for subject name/issuer authentication.
+        send_certificate_chain: bool,
+    },
+
+    /// A signed client assertion.
+    Assertion(Arc<dyn ClientAssertion>),
+}
+
+/// Options for constructing a new [`OnBehalfOfCredential`].
+#[derive(Debug, Default)]
+pub struct OnBehalfOfCredentialOptions {
+    /// Additional tenants for which the credential may acquire tokens.
+    ///
+    /// Add the wildcard value "*" to allow the credential to acquire tokens for any tenant in which the application is registered.
+    pub additionally_allowed_tenants: Vec<String>,
+
+    /// Options for constructing credentials.
+    pub credential_options: TokenCredentialOptions,
+}
+
+/// Authenticates a middle-tier application on behalf of a user using the
+/// [on-behalf-of flow](https://learn.microsoft.com/entra/identity-platform/v2-oauth2-on-behalf-of-flow).
+///
+/// The user assertion is typically the access token sent to the application by its caller.
+/// Use [`OnBehalfOfCredential::with_user_assertion`] to create credentials for each incoming request.
+/// These share the application's configuration and a token cache keyed by a hash of the user assertion, so repeated
+/// requests for the same user don't need a new token. A user's cached tokens are discarded once they have expired
+/// and no credential for that user assertion remains.
+#[derive(Debug)]
+pub struct OnBehalfOfCredential {
+    app: Arc<App>,
+    user_assertion: Secret,
+    cache: Arc<TokenCache>,
+}
+
+#[derive(Debug)]
+struct App {
+    tenant_id: String,
+    client_id: String,
+    client_auth: OnBehalfOfClientAuth,
+    authority_host: Url,
+    additionally_allowed_tenants: Vec<String>,
+    options: TokenCredentialOptions,
+    /// Token caches keyed by the SHA-256 hash of the user assertion, so the assertion isn't kept after its credential is dropped.
+    caches: Mutex<HashMap<[u8; 32], Arc<TokenCache>>>,
+}
+
+impl OnBehalfOfCredential {
+    /// Create a new `OnBehalfOfCredential`.
+    pub fn new(
+        tenant_id: String,
+        client_id: String,
+        user_assertion: impl Into<Secret>,
+        client_auth: OnBehalfOfClientAuth,
+        options: Option<OnBehalfOfCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        validate_tenant_id(&tenant_id)?;
+        validate_not_empty(&client_id, "no client ID specified")?;
+        if let OnBehalfOfClientAuth::Secret(secret) = &client_auth {
+            validate_not_empty(secret.secret(), "no secret specified")?;
+        }
+        let options = options.unwrap_or_default();
+        let authority_host = options.credential_options.authority_host()?;
+        token_endpoint(&authority_host, &tenant_id)?;
+
+        let app = Arc::new(App {
+            tenant_id,
+            client_id,
+            client_auth,
+            authority_host,
+            additionally_allowed_tenants: options.additionally_allowed_tenants,
+            options: options.credential_options,
+            caches: Mutex::new(HashMap::new()),
+        });
+        Self::for_app(app, user_assertion.into())
+    }
+
+    /// Create a new `OnBehalfOfCredential` for another user assertion, sharing this credential's configuration and token cache.
+    pub fn with_user_assertion(
+        &self,
+        user_assertion: impl Into<Secret>,
+    ) -> azure_core::Result<Arc<Self>> {
+        Self::for_app(self.app.clone(), user_assertion.into())
+    }
+
+    fn for_app(app: Arc<App>, user_assertion: Secret) -> azure_core::Result<Arc<Self>> {
+        validate_not_empty(user_assertion.secret(), "no user assertion specified")?;
+        let key: [u8; 32] = Sha256::digest(user_assertion.secret()).into();
+        let mut caches = app
+            .caches
+            .lock()
+            .map_err(|_| Error::message(ErrorKind::Credential, "token cache lock poisoned"))?;
+        if !caches.contains_key(&key) {
+            // Discard caches of users whose tokens have all expired and who have no remaining credential.
+            let now = OffsetDateTime::now_utc();
+            caches.retain(|_, cache| Arc::strong_count(cache) > 1 || !cache.is_expired(now));
+        }
+        let cache = caches.entry(key).or_default().clone();
+        drop(caches);
+        Ok(Arc::new(Self {
+            app,
+            user_assertion,
+            cache,
+        }))
+    }
+
+    async fn get_token_impl(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        let app = &self.app;
+        let tenant_id = resolve_tenant_id(
+            &app.tenant_id,
+            options.as_ref(),
+            &app.additionally_allowed_tenants,
+        )?;
+        let endpoint = token_endpoint(&app.authority_host, tenant_id)?;
+
+        let client_assertion = match &app.client_auth {
+            OnBehalfOfClientAuth::Secret(_) => None,
+            #[cfg(feature = "client_certificate")]
+            OnBehalfOfClientAuth::Certificate {
+                certificate,
+                password,
+                send_certificate_chain,
+            } => Some(crate::client_certificate_credential::client_assertion(
+                &app.client_id,
+                &endpoint,
+                certificate,
+                password,
+                *send_certificate_chain,
+            )?),
+            OnBehalfOfClientAuth::Assertion(assertion) => Some(assertion.secret().await?),
+        };
+
+        let body = {
+            let mut body = form_urlencoded::Serializer::new(String::new());
+            body.append_pair("assertion", self.user_assertion.secret())
+                .append_pair("client_id", &app.client_id)
+                .append_pair("grant_type", GRANT_TYPE)
+                .append_pair("requested_token_use", "on_behalf_of")
+                .append_pair("scope", &scopes.join(" "));
+            if let OnBehalfOfClientAuth::Secret(secret) = &app.client_auth {
+                body.append_pair("client_secret", secret.secret());
+            }
+            if let Some(client_assertion) = &client_assertion {
+                body.append_pair("client_assertion", client_assertion)
+                    .append_pair("client_assertion_type", ASSERTION_TYPE);
+            }
+            if let Some(claims) = claims_parameter(options.as_ref())? {
+                body.append_pair("claims", &claims);
+            }
+            body.finish()
+        };
+
+        let mut req = Request::new(endpoint, Method::Post);
+        req.insert_header(
+            headers::CONTENT_TYPE,
+            content_type::APPLICATION_X_WWW_FORM_URLENCODED,
+        );
+        req.set_body(body);
+
+        let res = app.options.http_client().execute_request(&req).await?;
+
+        match res.status() {
+            StatusCode::Ok => {
+                let token_response: EntraIdTokenResponse =
+                    deserialize(ON_BEHALF_OF_CREDENTIAL, res).await?;
+                Ok(AccessToken::new(
+                    token_response.access_token,
+                    OffsetDateTime::now_utc() + Duration::seconds(token_response.expires_in),
+                ))
+            }
+            _ => {
+                let error_response: EntraIdErrorResponse =
+                    deserialize(ON_BEHALF_OF_CREDENTIAL, res).await?;
+                let message = if error_response.error_description.is_empty() {
+                    format!("{} authentication failed.", ON_BEHALF_OF_CREDENTIAL)
+                } else {
+                    format!(
+                        "{} authentication failed. {}",
+                        ON_BEHALF_OF_CREDENTIAL, error_response.error_description
+                    )
+                };
+                Err(Error::message(ErrorKind::Credential, message))
+            }
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for OnBehalfOfCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        if scopes.is_empty() {
+            return Err(Error::message(ErrorKind::Credential, "no scopes specified"));
+        }
+        self.cache
+            .get_token(scopes, options, |s, o| self.get_token_impl(s, o))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client_assertion_credential::tests::FAKE_ASSERTION, tests::*};
+    use azure_core::{
+        authority_hosts::AZURE_PUBLIC_CLOUD,
+        http::{headers::Headers, RawResponse},
+        Bytes,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const FAKE_SECRET: &str = "fake secret";
+    const FAKE_USER_ASSERTION: &str = "fake user assertion";
+
+    #[derive(Debug)]
+    struct MockAssertion;
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl ClientAssertion for MockAssertion {
+        async fn secret(&self) -> azure_core::Result<String> {
+            Ok(FAKE_ASSERTION.to_string())
+        }
+    }
+
+    fn token_response() -> RawResponse {
+        RawResponse::from_bytes(
+            StatusCode::Ok,
+            Headers::default(),
+            Bytes::from(format!(
+                r#"{{"access_token":"{FAKE_TOKEN}","expires_in":3600,"token_type":"Bearer"}}"#
+            )),
+        )
+    }
+
+    fn credential(client_auth: OnBehalfOfClientAuth, sts: MockSts) -> Arc<OnBehalfOfCredential> {
+        OnBehalfOfCredential::new(
+            FAKE_TENANT_ID.to_string(),
+            FAKE_CLIENT_ID.to_string(),
+            FAKE_USER_ASSERTION,
+            client_auth,
+            Some(OnBehalfOfCredentialOptions {
+                credential_options: TokenCredentialOptions {
+                    http_client: Arc::new(sts),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        )
+        .expect("valid credential")
+    }
+
+    #[tokio::test]
+    async fn client_secret() {
+        let sts = MockSts::new(
+            vec![token_response()],
+            Some(Arc::new(|request: &Request| {
+                assert_eq!(
+                    request.url().as_str(),
+                    format!(
+                        "{}{}/oauth2/v2.0/token",
+                        AZURE_PUBLIC_CLOUD.as_str(),
+                        FAKE_TENANT_ID
+                    )
+                );
+                let form = form(request);
+                assert_eq!(form["assertion"], FAKE_USER_ASSERTION);
+                assert_eq!(form["client_id"], FAKE_CLIENT_ID);
+                assert_eq!(form["client_secret"], FAKE_SECRET);
+                assert_eq!(form["grant_type"], GRANT_TYPE);
+                assert_eq!(form["requested_token_use"], "on_behalf_of");
+                assert_eq!(form["scope"], LIVE_TEST_SCOPES.join(" "));
+                assert!(!form.contains_key("client_assertion"));
+                Ok(())
+            })),
+        );
+        let credential = credential(OnBehalfOfClientAuth::Secret(FAKE_SECRET.into()), sts);
+
+        // the second call is served from the cache; the mock has only one response
+        for _ in 0..2 {
+            let token = credential
+                .get_token(LIVE_TEST_SCOPES, None)
+                .await
+                .expect("token");
+            assert_eq!(token.token.secret(), FAKE_TOKEN);
+        }
+    }
+
+    #[tokio::test]
+    async fn client_assertion() {
+        let sts = MockSts::new(
+            vec![token_response()],
+            Some(Arc::new(|request: &Request| {
+                let form = form(request);
+                assert_eq!(form["assertion"], FAKE_USER_ASSERTION);
+                assert_eq!(form["client_assertion"], FAKE_ASSERTION);
+                assert_eq!(form["client_assertion_type"], ASSERTION_TYPE);
+                assert!(!form.contains_key("client_secret"));
+                Ok(())
+            })),
+        );
+        let credential = credential(
+            OnBehalfOfClientAuth::Assertion(Arc::new(MockAssertion)),
+            sts,
+        );
+        let token = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        assert_eq!(token.token.secret(), FAKE_TOKEN);
+    }
+
+    #[tokio::test]
+    async fn cache_keyed_by_user_assertion() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let requests_clone = requests.clone();
+        let sts = MockSts::new(
+            vec![token_response(), token_response()],
+            Some(Arc::new(move |request: &Request| {
+                let n = requests_clone.fetch_add(1, Ordering::SeqCst);
+                let expected = if n == 0 {
+                    FAKE_USER_ASSERTION
+                } else {
+                    "another user assertion"
+                };
+                assert_eq!(form(request)["assertion"], expected);
+                Ok(())
+            })),
+        );
+        let credential = credential(OnBehalfOfClientAuth::Secret(FAKE_SECRET.into()), sts);
+        credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+
+        // another credential for the same user shares the cached token
+        credential
+            .with_user_assertion(FAKE_USER_ASSERTION)
+            .expect("valid credential")
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("cached token");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // a different user requires a new token
+        credential
+            .with_user_assertion("another user assertion")
+            .expect("valid credential")
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_caches_are_discarded() {
+        let sts = MockSts::new(vec![token_response()], None);
+        let credential = credential(OnBehalfOfClientAuth::Secret(FAKE_SECRET.into()), sts);
+        let key = |assertion: &str| -> [u8; 32] { Sha256::digest(assertion).into() };
+        let cached = |assertion: &str| {
+            credential
+                .app
+                .caches
+                .lock()
+                .unwrap()
+                .contains_key(&key(assertion))
+        };
+
+        // A user with a valid token keeps its cache after its credential is dropped.
+        credential
+            .with_user_assertion("user with token")
+            .expect("valid credential")
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        drop(
+            credential
+                .with_user_assertion("user without token")
+                .expect("valid credential"),
+        );
+        assert!(cached("user with token"));
+        assert!(cached("user without token"));
+
+        // Caches without valid tokens and credentials are discarded when another user is added.
+        let _another = credential
+            .with_user_assertion("another user")
+            .expect("valid credential");
+        assert!(cached(FAKE_USER_ASSERTION));
+        assert!(cached("user with token"));
+        assert!(!cached("user without token"));
+        assert!(cached("another user"));
+    }
+
+    #[tokio::test]
+    async fn error_response() {
+        let sts = MockSts::new(
+            vec![RawResponse::from_bytes(
+                StatusCode::BadRequest,
+                Headers::default(),
+                Bytes::from_static(
+                    br#"{"error":"invalid_grant","error_description":"AADSTS50013: Assertion failed signature validation."}"#,
+                ),
+            )],
+            None,
+        );
+        let credential = credential(OnBehalfOfClientAuth::Secret(FAKE_SECRET.into()), sts);
+        let err = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect_err("error response");
+        assert_eq!(err.kind(), &ErrorKind::Credential);
+        assert_eq!(
+            err.to_string(),
+            "OnBehalfOfCredential authentication failed. AADSTS50013: Assertion failed signature validation."
+        );
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let new = |tenant_id: &str, client_id: &str, user_assertion: &str, secret: &str| {
+            OnBehalfOfCredential::new(
+                tenant_id.to_string(),
+                client_id.to_string(),
+                user_assertion.to_string(),
+                OnBehalfOfClientAuth::Secret(secret.to_string().into()),
+                None,
+            )
+        };
+        new(
+            "not a valid tenant",
+            FAKE_CLIENT_ID,
+            FAKE_USER_ASSERTION,
+            FAKE_SECRET,
+        )
+        .expect_err("invalid tenant ID");
+        new(FAKE_TENANT_ID, "", FAKE_USER_ASSERTION, FAKE_SECRET).expect_err("empty client ID");
+        new(FAKE_TENANT_ID, FAKE_CLIENT_ID, "", FAKE_SECRET).expect_err("empty user assertion");
+        new(FAKE_TENANT_ID, FAKE_CLIENT_ID, FAKE_USER_ASSERTION, "").expect_err("empty secret");
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{env::Env, ImdsId, ImdsManagedIdentityCredential, TokenCredentialOptions};
+use azure_core::{
+    credentials::{AccessToken, TokenCredential, TokenRequestOptions},
+    error::{Error, ErrorKind, ResultExt},
+    http::{headers::HeaderName, HttpClient, Url},
+};
+use std::sync::Arc;
+
+const ENDPOINT_ENV: &str = "IDENTITY_ENDPOINT";
+const THUMBPRINT_ENV: &str = "IDENTITY_SERVER_THUMBPRINT";
+const API_VERSION: &str = "2019-07-01-preview";
+const SECRET_HEADER: HeaderName = HeaderName::from_static("secret");
+const SECRET_ENV: &str = "IDENTITY_HEADER";
+
+/// Authenticates the managed identity of a Service Fabric application.
+///
+/// The Service Fabric managed identity endpoint uses a self-signed certificate,
+/// so the certificate must match the thumbprint in the `IDENTITY_SERVER_THUMBPRINT` environment variable.
+/// This is verified during the TLS handshake, which requires the `reqwest_rustls` feature and a default
+/// `rustls` `CryptoProvider`.
+#[derive(Debug)]
+pub(crate) struct ServiceFabricManagedIdentityCredential {
+    credential: ImdsManagedIdentityCredential,
+}
+
+impl ServiceFabricManagedIdentityCredential {
+    pub fn new(
+        id: ImdsId,
+        options: impl Into<TokenCredentialOptions>,
+    ) -> azure_core::Result<Arc<Self>> {
+        if !matches!(id, ImdsId::SystemAssigned) {
+            return Err(Error::message(
+                ErrorKind::Credential,
+                "User-assigned identities can't be specified for Service Fabric. Configure the identity in the application manifest instead.",
+            ));
+        }
+        let options = options.into();
+        let thumbprint = options
+            .env()
+            .var(THUMBPRINT_ENV)
+            .with_context(ErrorKind::Credential, || {
+                format!("Service Fabric credential requires {THUMBPRINT_ENV} environment variable")
+            })?;
+        let http_client = pinned_http_client(&thumbprint)?;
+        Self::with_http_client(options, http_client)
+    }
+
+    fn with_http_client(
+        mut options: TokenCredentialOptions,
+        http_client: Arc<dyn HttpClient>,
+    ) -> azure_core::Result<Arc<Self>> {
+        let endpoint = endpoint(options.env())?;
+        options.http_client = http_client;
+        Ok(Arc::new(Self {
+            credential: ImdsManagedIdentityCredential::new(
+                options,
+                endpoint,
+                API_VERSION,
+                SECRET_HEADER,
+                SECRET_ENV,
+                ImdsId::SystemAssigned,
+            ),
+        }))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl TokenCredential for ServiceFabricManagedIdentityCredential {
+    async fn get_token(
+        &self,
+        scopes: &[&str],
+        options: Option<TokenRequestOptions>,
+    ) -> azure_core::Result<AccessToken> {
+        self.credential.get_token(scopes, options).await
+    }
+}
+
+fn endpoint(env: &Env) -> azure_core::Result<Url> {
+    let endpoint = env
+        .var(ENDPOINT_ENV)
+        .with_context(ErrorKind::Credential, || {
+            format!("Service Fabric credential requires {ENDPOINT_ENV} environment variable")
+        })?;
+    Url::parse(&endpoint).with_context(ErrorKind::Credential, || {
+        format!(
+            "Service Fabric credential {ENDPOINT_ENV} environment variable must be a valid URL, but is '{endpoint}'"
+        )
+    })
+}
+
+#[cfg(all(feature = "reqwest_rustls", not(target_arch = "wasm32")))]
+fn pinned_http_client(thumbprint: &str) -> azure_core::Result<Arc<dyn HttpClient>> {
+    Ok(Arc::new(pinned::PinnedHttpClient::new(thumbprint)?))
+}
+
+#[cfg(not(all(feature = "reqwest_rustls", not(target_arch = "wasm32"))))]
+fn pinned_http_client(_thumbprint: &str) -> azure_core::Result<Arc<dyn HttpClient>> {
+    Err(Error::message(
+        ErrorKind::Credential,
+        "Service Fabric managed identity requires the reqwest_rustls feature to verify the endpoint certificate",
+    ))
+}
+
+#[cfg(all(feature = "reqwest_rustls", not(target_arch = "wasm32")))]
+mod pinned {
+    use azure_core::{
+        error::{Error, ErrorKind, ResultExt},
+        http::{headers::Headers, request::Body, HttpClient, RawResponse, Request},
+    };
+    use rustls::{
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{
+            verify_tls12_signature, verify_tls13_signature, CryptoProvider,
+            WebPkiSupportedAlgorithms,
+        },
+        pki_types::{CertificateDer, ServerName, UnixTime},
+        CertificateError, ClientConfig, DigitallySignedStruct, SignatureScheme,
+    };
+    use sha1::{Digest, Sha1};
+    use std::sync::Arc;
+
+    /// An [`HttpClient`] that connects only to a server presenting the certificate with a given SHA-1 thumbprint.
+    ///
+    /// The certificate is verified during the TLS handshake, so no part of a request is sent to any other server.
+    #[derive(Debug)]
+    pub(super) struct PinnedHttpClient {
+        client: reqwest::Client,
+    }
+
+    impl PinnedHttpClient {
+        pub fn new(thumbprint: &str) -> azure_core::Result<Self> {
+            let provider = CryptoProvider::get_default().cloned().ok_or_else(|| {
+                Error::message(
+                    ErrorKind::Credential,
+                    "Service Fabric managed identity requires a default rustls CryptoProvider; install one with `CryptoProvider::install_default()`",
+                )
+            })?;
+            let verifier = ThumbprintVerifier {
+                thumbprint: thumbprint.trim().to_string(),
+                algorithms: provider.signature_verification_algorithms,
+            };
+            let config = ClientConfig::builder_with_provider(provider)
+                .with_safe_default_protocol_versions()
+                .context(ErrorKind::Other, "failed to configure TLS")?
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(verifier))
+                .with_no_client_auth();
+            let client = reqwest::Client::builder()
+                .use_preconfigured_tls(config)
+                .pool_max_idle_per_host(0)
+                .build()
+                .context(ErrorKind::Other, "failed to build `reqwest` client")?;
+            Ok(Self { client })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for PinnedHttpClient {
+        async fn execute_request(&self, request: &Request) -> azure_core::Result<RawResponse> {
+            let method = reqwest::Method::from_bytes(request.method().as_ref().as_bytes())
+                .with_context(ErrorKind::DataConversion, || {
+                    format!("invalid method {}", request.method())
+                })?;
+            let mut req = self.client.request(method, request.url().clone());
+            for (name, value) in request.headers().iter() {
+                req = req.header(name.as_str(), value.as_str());
+            }
+            let req = match request.body() {
+                Body::Bytes(bytes) => req.body(bytes.clone()),
+                Body::SeekableStream(_) => {
+                    return Err(Error::message(
+                        ErrorKind::Other,
+                        "streaming request bodies aren't supported",
+                    ))
+                }
+            };
+
+            let rsp = req
+                .send()
+                .await
+                .context(ErrorKind::Io, "failed to execute `reqwest` request")?;
+
+            let status = rsp.status().as_u16().into();
+            let mut headers = Headers::new();
+            for (name, value) in rsp.headers() {
+                if let Ok(value) = value.to_str() {
+                    headers.insert(name.as_str().to_owned(), value.to_owned());
+                }
+            }
+            let body = rsp
+                .bytes()
+                .await
+                .context(ErrorKind::Io, "failed to read `reqwest` response")?;
+            Ok(RawResponse::from_bytes(status, headers, body))
+        }
+    }
+
+    /// Accepts only a server certificate with the expected SHA-1 thumbprint.
+    ///
+    /// The Service Fabric endpoint certificate is self-signed, so its chain and name are not verified,
+    /// but the handshake signature is still verified to prove the server holds the certificate's private key.
+    #[derive(Debug)]
+    pub(super) struct ThumbprintVerifier {
+        pub(super) thumbprint: String,
+        pub(super) algorithms: WebPkiSupportedAlgorithms,
+    }
+
+    impl ServerCertVerifier for ThumbprintVerifier {
+        fn verify_server_cert(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            if thumbprint(end_entity).eq_ignore_ascii_case(&self.thumbprint) {
+                return Ok(ServerCertVerified::assertion());
+            }
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls12_signature(message, cert, dss, &self.algorithms)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls13_signature(message, cert, dss, &self.algorithms)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.algorithms.supported_schemes()
+        }
+    }
+
+    /// Gets the SHA-1 thumbprint of a DER-encoded certificate.
+    fn thumbprint(certificate: &[u8]) -> String {
+        Sha1::digest(certificate)
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use azure_core::{
+        http::{headers::Headers, Method, RawResponse, StatusCode},
+        Bytes,
+    };
+    use azure_core_test::http::MockHttpClient;
+    use futures::FutureExt as _;
+
+    const ENDPOINT: &str = "https://localhost:2377/metadata/identity/oauth2/token";
+
+    #[tokio::test]
+    async fn get_token() {
+        let client = MockHttpClient::new(|req| {
+            async move {
+                assert_eq!(req.method(), &Method::Get);
+                let mut url = req.url().clone();
+                let query: Vec<_> = url.query_pairs().into_owned().collect();
+                assert_eq!(
+                    query,
+                    [
+                        ("api-version".to_string(), API_VERSION.to_string()),
+                        ("resource".to_string(), LIVE_TEST_RESOURCE.to_string()),
+                    ]
+                );
+                url.set_query(None);
+                assert_eq!(url.as_str(), ENDPOINT);
+                assert_eq!(req.headers().get_str(&SECRET_HEADER).unwrap(), "secret");
+
+                Ok(RawResponse::from_bytes(
+                    StatusCode::Ok,
+                    Headers::default(),
+                    Bytes::from(format!(
+                        r#"{{"token_type":"Bearer","access_token":"{FAKE_TOKEN}","expires_on":4102444800,"resource":"{LIVE_TEST_RESOURCE}"}}"#
+                    )),
+                ))
+            }
+            .boxed()
+        });
+        let credential = ServiceFabricManagedIdentityCredential::with_http_client(
+            TokenCredentialOptions {
+                env: Env::from(
+                    &[
+                        (ENDPOINT_ENV, ENDPOINT),
+                        (SECRET_ENV, "secret"),
+                        (THUMBPRINT_ENV, "thumbprint"),
+                    ][..],
+                ),
+                ..Default::default()
+            },
+            Arc::new(client),
+        )
+        .expect("valid credential");
+
+        let token = credential
+            .get_token(LIVE_TEST_SCOPES, None)
+            .await
+            .expect("token");
+        assert_eq!(token.token.secret(), FAKE_TOKEN);
+        assert_eq!(token.expires_on.unix_timestamp(), 4102444800);
+    }
+
+    #[cfg(all(feature = "reqwest_rustls", not(target_arch = "wasm32")))]
+    #[test]
+    fn verify_thumbprint() {
+        use pinned::ThumbprintVerifier;
+        use rustls::{
+            client::danger::ServerCertVerifier as _,
+            crypto::WebPkiSupportedAlgorithms,
+            pki_types::{CertificateDer, ServerName, UnixTime},
+            CertificateError,
+        };
+
+        fn verify(thumbprint: &str) -> Result<(), rustls::Error> {
+            let verifier = ThumbprintVerifier {
+                thumbprint: thumbprint.to_string(),
+                algorithms: WebPkiSupportedAlgorithms {
+                    all: &[],
+                    mapping: &[],
+                },
+            };
+            // SHA-1 of "abc"
+            verifier
+                .verify_server_cert(
+                    &CertificateDer::from(b"abc".as_slice()),
+                    &[],
+                    &ServerName::try_from("localhost").unwrap(),
+                    &[],
+                    UnixTime::now(),
+                )
+                .map(|_| ())
+        }
+
+        verify("A9993E364706816ABA3E25717850C26C9CD0D89D").expect("matching thumbprint");
+        verify("a9993e364706816aba3e25717850c26c9cd0d89d")
+            .expect("thumbprints are case-insensitive");
+
+        // A mismatched certificate fails the handshake, before any part of a request is sent.
+        let err =
+            verify("0000000000000000000000000000000000000000").expect_err("mismatched thumbprint");
+        assert_eq!(
+            err,
+            rustls::Error::InvalidCertificate(CertificateError::ApplicationVerificationFailure)
+        );
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    deserialize, resolve_tenant_id, token_endpoint, validate_not_empty, validate_tenant_id,
+    EntraIdErrorResponse, EntraIdTokenResponse, TokenCache, TokenCredentialOptions,
+};
+use azure_core::{
+    credentials::{AccessToken, Secret, TokenRequestOptions},
+    error::{Error, ErrorKind},
+    http::{
+        headers::{self, content_type},
+        HttpClient, Method, RawResponse, Request, StatusCode, Url,
+    },
+    time::{Duration, OffsetDateTime},
+};
+use std::sync::Arc;
+use tracing::debug;
+use url::form_urlencoded;
+
+/// Requests a refresh token along with the access token.
+const OFFLINE_ACCESS: &str = "offline_access";
+
+/// Common implementation of credentials that authenticate users.
+///
+/// After the user authenticates, the refresh token from Microsoft Entra ID is cached
+/// and redeemed for tokens with other scopes so the user needn't authenticate again.
+#[derive(Debug)]
+pub(crate) struct UserCredential {
+    name: &'static str,
+    tenant_id: String,
+    client_id: String,
+    client_secret: Option<Secret>,
+    authority_host: Url,
+    additionally_allowed_tenants: Vec<String>,
+    http_client: Arc<dyn HttpClient>,
+    pub(crate) cache: TokenCache,
+}
+
+impl UserCredential {
+    pub(crate) fn new(
+        name: &'static str,
+        tenant_id: &str,
+        client_id: String,
+        client_secret: Option<Secret>,
+        additionally_allowed_tenants: Vec<String>,
+        options: TokenCredentialOptions,
+    ) -> azure_core::Result<Self> {
+        validate_tenant_id(tenant_id)?;
+        validate_not_empty(&client_id, "no client ID specified")?;
+        let authority_host = options.authority_host()?;
+        token_endpoint(&authority_host, tenant_id)?;
+
+        Ok(Self {
+            name,
+            tenant_id: tenant_id.to_string(),
+            client_id,
+            client_secret,
+            authority_host,
+            additionally_allowed_tenants,
+            http_client: options.http_client(),
+            cache: TokenCache::new(),
+        })
+    }
+
+    pub(crate) fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub(crate) fn authority_host(&self) -> &Url {
+        &self.authority_host
+    }
+
+    pub(crate) fn tenant_id<'a>(
+        &'a self,
+        options: Option<&'a TokenRequestOptions>,
+    ) -> azure_core::Result<&'a str> {
+        resolve_tenant_id(&self.tenant_id, options, &self.additionally_allowed_tenants)
+    }
+
+    /// Gets the `scope` parameter, which always requests a refresh token.
+    pub(crate) fn scope(scopes: &[&str]) -> String {
+        let mut scope = scopes.join(" ");
+        if !scopes.contains(&OFFLINE_ACCESS) {
+            scope.push(' ');
+            scope.push_str(OFFLINE_ACCESS);
+        }
+        scope
+    }
+
+    /// Creates a request to the token endpoint with client authentication, scopes, and claims.
+    pub(crate) fn token_request(
+        &self,
+        tenant_id: &str,
+        params: &[(&str, &str)],
+        scopes: &[&str],
+        claims: Option<&str>,
+    ) -> azure_core::Result<Request> {
+        let mut body = form_urlencoded::Serializer::new(String::new());
+        body.append_pair("client_id", &self.client_id)
+            .extend_pairs(params)
+            .append_pair("scope", &Self::scope(scopes));
+        if let Some(client_secret) = &self.client_secret {
+            body.append_pair("client_secret", client_secret.secret());
+        }
+        if let Some(claims) = claims {
+            body.append_pair("claims", claims);
+        }
+
+        let mut req = Request::new(
+            token_endpoint(&self.authority_host, tenant_id)?,
+            Method::Post,
+        );
+        req.insert_header(
+            headers::CONTENT_TYPE,
+            content_type::APPLICATION_X_WWW_FORM_URLENCODED,
+        );
+        req.set_body(body.finish());
+        Ok(req)
+    }
+
+    pub(crate) async fn send(&self, req: &Request) -> azure_core::Result<RawResponse> {
+        self.http_client.execute_request(req).await
+    }
+
+    /// Redeems the tenant's cached refresh token, if any, for a new access token.
+    ///
+    /// Returns `None` when there is no refresh token or Microsoft Entra ID rejects it because it's invalid
+    /// or the user must interact, in which case the user must authenticate again.
+    /// Other failures e.g., throttling, are returned as errors and the refresh token is kept.
+    pub(crate) async fn refresh(
+        &self,
+        tenant_id: &str,
+        scopes: &[&str],
+        claims: Option<&str>,
+    ) -> azure_core::Result<Option<AccessToken>> {
+        let Some(refresh_token) = self.cache.refresh_token(tenant_id).await else {
+            return Ok(None);
+        };
+        let req = self.token_request(
+            tenant_id,
+            &[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.secret()),
+            ],
+            scopes,
+            claims,
+        )?;
+        let res = self.send(&req).await?;
+        if res.status() != StatusCode::Ok {
+            let error: EntraIdErrorResponse = deserialize(self.name, res).await?;
+            if !matches!(
+                error.error.as_str(),
+                "invalid_grant" | "interaction_required"
+            ) {
+                return Err(self.error(&error));
+            }
+            debug!(
+                "{} couldn't redeem refresh token: {}",
+                self.name, error.error_description
+            );
+            self.cache.remove_refresh_token(tenant_id).await;
+            return Ok(None);
+        }
+        self.token_from_response(tenant_id, res).await.map(Some)
+    }
+
+    /// Gets the access token from a token endpoint response, caching any refresh token for the tenant.
+    pub(crate) async fn token_from_response(
+        &self,
+        tenant_id: &str,
+        res: RawResponse,
+    ) -> azure_core::Result<AccessToken> {
+        if res.status() != StatusCode::Ok {
+            let error: EntraIdErrorResponse = deserialize(self.name, res).await?;
+            return Err(self.error(&error));
+        }
+
+        let token_response: EntraIdTokenResponse = deserialize(self.name, res).await?;
+        if let Some(refresh_token) = token_response.refresh_token {
+            self.cache
+                .set_refresh_token(tenant_id, refresh_token.into())
+                .await;
+        }
+        Ok(AccessToken::new(
+            token_response.access_token,
+            OffsetDateTime::now_utc() + Duration::seconds(token_response.expires_in),
+        ))
+    }
+
+    pub(crate) fn error(&self, error: &EntraIdErrorResponse) -> Error {
+        let message = if error.error_description.is_empty() {
+            format!("{} authentication failed.", self.name)
+        } else {
+            format!(
+                "{} authentication failed. {}",
+                self.name, error.error_description
+            )
+        };
+        Error::message(ErrorKind::Credential, message)
+    }
+}
@@ -0,0 +1,935 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    clients::pager::resume_pager,
+    generated::{clients::CertificateClient as GeneratedCertificateClient, models as generated},
+    models::{
+        BackupCertificateResult, Certificate, CertificateClientBackupCertificateOptions,
+        CertificateClientCreateCertificateOptions,
+        CertificateClientDeleteCertificateOperationOptions,
+        CertificateClientDeleteCertificateOptions, CertificateClientDeleteContactsOptions,
+        CertificateClientDeleteIssuerOptions, CertificateClientGetCertificateOperationOptions,
+        CertificateClientGetCertificateOptions, CertificateClientGetCertificatePolicyOptions,
+        CertificateClientGetContactsOptions, CertificateClientGetDeletedCertificateOptions,
+        CertificateClientGetIssuerOptions, CertificateClientImportCertificateOptions,
+        CertificateClientListCertificatePropertiesOptions,
+        CertificateClientListCertificatePropertiesVersionsOptions,
+        CertificateClientListDeletedCertificatePropertiesOptions,
+        CertificateClientListIssuerPropertiesOptions, CertificateClientMergeCertificateOptions,
+        CertificateClientPurgeDeletedCertificateOptions,
+        CertificateClientRecoverDeletedCertificateOptions,
+        CertificateClientRestoreCertificateOptions, CertificateClientSetContactsOptions,
+        CertificateClientSetIssuerOptions, CertificateClientUpdateCertificateOperationOptions,
+        CertificateClientUpdateCertificatePolicyOptions,
+        CertificateClientUpdateCertificatePropertiesOptions, CertificateClientUpdateIssuerOptions,
+        CertificateOperation, CertificatePolicy, Contacts, CreateCertificateParameters,
+        DeletedCertificate, ImportCertificateParameters, Issuer, ListCertificatePropertiesResult,
+        ListDeletedCertificatePropertiesResult, ListIssuerPropertiesResult,
+        MergeCertificateParameters, RestoreCertificateParameters, SetIssuerParameters,
+        UpdateCertificateOperationParameter, UpdateCertificatePropertiesParameters,
+        UpdateIssuerParameters,
+    },
+    CertificateClientOptions,
+};
+use azure_core::{
+    credentials::TokenCredential,
+    error::{Error, ErrorKind},
+    http::{
+        policies::{
+            ChallengeBearerTokenCredentialPolicy, ChallengeOptions, Policy,
+            PublicApiInstrumentationInformation,
+        },
+        poller::{
+            self,
+            location::{self, FinalState},
+        },
+        ClientMethodOptions, Method, NoFormat, Pager, Pipeline, Poller, PollerResult, RawResponse,
+        Request, RequestContent, Response, Url,
+    },
+    json,
+    time::Duration,
+    Result,
+};
+use std::sync::Arc;
+
+/// The key vault client performs cryptographic key operations and vault operations against the Key Vault service.
+pub struct CertificateClient {
+    pub(crate) client: Arc<GeneratedCertificateClient>,
+}
+
+impl CertificateClient {
+    /// Creates a new CertificateClient, using Entra ID authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Service host
+    /// * `credential` - An implementation of [`TokenCredential`](azure_core::credentials::TokenCredential) that can provide an
+    ///   Entra ID token to use when authenticating.
+    /// * `options` - Optional configuration for the client.
+    pub fn new(
+        endpoint: &str,
+        credential: Arc<dyn TokenCredential>,
+        options: Option<CertificateClientOptions>,
+    ) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        let mut endpoint = Url::parse(endpoint)?;
+        if !endpoint.scheme().starts_with("http") {
+            return Err(Error::message(
+                ErrorKind::Other,
+                format!("{endpoint} must use http(s)"),
+            ));
+        }
+        endpoint.set_query(None);
+        // Key Vault challenges unauthenticated requests with the tenant and scope to authenticate with.
+        let auth_policy: Arc<dyn Policy> = Arc::new(ChallengeBearerTokenCredentialPolicy::new(
+            credential,
+            vec!["https://vault.azure.net/.default"],
+            ChallengeOptions {
+                discover_challenge: true,
+                use_challenge_scope: true,
+            },
+        ));
+        Ok(Self {
+            client: Arc::new(GeneratedCertificateClient {
+                api_version: options.api_version,
+                endpoint,
+                pipeline: Pipeline::new(
+                    option_env!("CARGO_PKG_NAME"),
+                    option_env!("CARGO_PKG_VERSION"),
+                    options.client_options,
+                    Vec::default(),
+                    vec![auth_policy],
+                ),
+            }),
+        })
+    }
+
+    /// Returns the Url associated with this client.
+    pub fn endpoint(&self) -> &Url {
+        self.client.endpoint()
+    }
+    /// Backs up the specified certificate.
+    ///
+    /// Requests that a backup of the specified certificate be downloaded to the client. All versions of the certificate will
+    /// be downloaded. This operation requires the certificates/backup permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn backup_certificate(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientBackupCertificateOptions<'_>>,
+    ) -> Result<Response<BackupCertificateResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.backup_certificate",
+            });
+        self.client
+            .backup_certificate(certificate_name, Some(options))
+            .await
+    }
+
+    /// Creates a new certificate.
+    ///
+    /// If this is the first version, the certificate resource is created. This operation requires the certificates/create permission.
+    /// Returns a [`Poller`] that polls the [`CertificateOperation`] until the certificate is created or the operation fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate. The value you provide may be copied globally for the purpose of running
+    ///   the service. The value provided should not include personally identifiable or sensitive information.
+    /// * `parameters` - The parameters to create a certificate.
+    /// * `options` - Optional parameters for the request.
+    pub fn create_certificate(
+        &self,
+        certificate_name: &str,
+        parameters: RequestContent<CreateCertificateParameters>,
+        options: Option<CertificateClientCreateCertificateOptions<'_>>,
+    ) -> Result<Poller<CertificateOperation>> {
+        let mut options = options.unwrap_or_default().into_owned();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.create_certificate",
+            });
+        let client = self.client.clone();
+        let endpoint = self.client.endpoint.clone();
+        let mut pending_url = endpoint.clone();
+        let mut path = String::from("certificates/{certificate-name}/pending");
+        path = path.replace("{certificate-name}", certificate_name);
+        pending_url = pending_url.join(&path)?;
+        let frequency = options
+            .method_options
+            .frequency
+            .unwrap_or(Duration::seconds(5));
+        let continuation = options
+            .method_options
+            .continuation_token
+            .as_deref()
+            .map(Url::parse)
+            .transpose()?;
+        // A tampered continuation token must not send the credential to another host.
+        if continuation
+            .as_ref()
+            .is_some_and(|continuation| continuation.origin() != endpoint.origin())
+        {
+            return Err(Error::message(
+                ErrorKind::DataConversion,
+                format!("continuation token does not refer to {endpoint}"),
+            ));
+        }
+        let certificate_name = certificate_name.to_string();
+        let ctx = options.method_options.context;
+        Ok(Poller::from_callback_with_continuation(
+            move |next_link: Option<Url>| {
+                let client = client.clone();
+                let certificate_name = certificate_name.clone();
+                let parameters = parameters.clone();
+                let ctx = ctx.clone();
+                let pending_url = pending_url.clone();
+                async move {
+                    let rsp: RawResponse = match next_link {
+                        Some(next_link) => {
+                            let qp = next_link
+                                .query_pairs()
+                                .filter(|(name, _)| name.ne("api-version"));
+                            let mut next_link = next_link.clone();
+                            next_link
+                                .query_pairs_mut()
+                                .clear()
+                                .extend_pairs(qp)
+                                .append_pair("api-version", &client.api_version);
+                            let mut request = Request::new(next_link, Method::Get);
+                            request.insert_header("accept", "application/json");
+                            client.pipeline.send(&ctx, &mut request).await?
+                        }
+                        None => client
+                            .create_certificate(
+                                &certificate_name,
+                                parameters,
+                                Some(generated::CertificateClientCreateCertificateOptions {
+                                    method_options: ClientMethodOptions { context: ctx },
+                                }),
+                            )
+                            .await?
+                            .into(),
+                    };
+                    let (status, headers, body) = rsp.deconstruct();
+                    let bytes = body.collect().await?;
+                    let res: CertificateOperation = json::from_json(&bytes)?;
+                    let next_link = location::get_location(&headers, FinalState::Location)?
+                        .filter(|next_link| next_link.origin() == pending_url.origin())
+                        .unwrap_or(pending_url);
+                    let retry_after = poller::get_retry_after_or(&headers, frequency);
+                    let rsp = RawResponse::from_bytes(status, headers, bytes).into();
+                    Ok(match res.status {
+                        Some(status) if status.eq_ignore_ascii_case("inProgress") => {
+                            PollerResult::InProgress {
+                                response: rsp,
+                                retry_after,
+                                next: next_link,
+                            }
+                        }
+                        _ => PollerResult::Done { response: rsp },
+                    })
+                }
+            },
+            continuation,
+        ))
+    }
+
+    /// Deletes a certificate from a specified key vault.
+    ///
+    /// Deletes all versions of a certificate object along with its associated policy. Delete certificate cannot be used to remove
+    /// individual versions of a certificate object. This operation requires the certificates/delete permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn delete_certificate(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientDeleteCertificateOptions<'_>>,
+    ) -> Result<Response<DeletedCertificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.delete_certificate",
+            });
+        self.client
+            .delete_certificate(certificate_name, Some(options))
+            .await
+    }
+
+    /// Deletes the creation operation for a specific certificate.
+    ///
+    /// Deletes the creation operation for a specified certificate that is in the process of being created. The certificate is
+    /// no longer created. This operation requires the certificates/update permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn delete_certificate_operation(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientDeleteCertificateOperationOptions<'_>>,
+    ) -> Result<Response<CertificateOperation>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.delete_certificate_operation",
+            });
+        self.client
+            .delete_certificate_operation(certificate_name, Some(options))
+            .await
+    }
+
+    /// Deletes the certificate contacts for a specified key vault.
+    ///
+    /// Deletes the certificate contacts for a specified key vault certificate. This operation requires the certificates/managecontacts
+    /// permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    pub async fn delete_contacts(
+        &self,
+        options: Option<CertificateClientDeleteContactsOptions<'_>>,
+    ) -> Result<Response<Contacts>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.delete_contacts",
+            });
+        self.client.delete_contacts(Some(options)).await
+    }
+
+    /// Deletes the specified certificate issuer.
+    ///
+    /// The DeleteCertificateIssuer operation permanently removes the specified certificate issuer from the vault. This operation
+    /// requires the certificates/manageissuers/deleteissuers permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `issuer_name` - The name of the issuer.
+    /// * `options` - Optional parameters for the request.
+    pub async fn delete_issuer(
+        &self,
+        issuer_name: &str,
+        options: Option<CertificateClientDeleteIssuerOptions<'_>>,
+    ) -> Result<Response<Issuer>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.delete_issuer",
+            });
+        self.client.delete_issuer(issuer_name, Some(options)).await
+    }
+
+    /// Gets information about a certificate.
+    ///
+    /// Gets information about a specific certificate. This operation requires the certificates/get permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate in the given vault.
+    /// * `certificate_version` - The version of the certificate. This URI fragment is optional. If not specified, the latest
+    ///   version of the certificate is returned.
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_certificate(
+        &self,
+        certificate_name: &str,
+        certificate_version: &str,
+        options: Option<CertificateClientGetCertificateOptions<'_>>,
+    ) -> Result<Response<Certificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.get_certificate",
+            });
+        self.client
+            .get_certificate(certificate_name, certificate_version, Some(options))
+            .await
+    }
+
+    /// Gets the creation operation of a certificate.
+    ///
+    /// Gets the creation operation associated with a specified certificate. This operation requires the certificates/get permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_certificate_operation(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientGetCertificateOperationOptions<'_>>,
+    ) -> Result<Response<CertificateOperation>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.get_certificate_operation",
+            });
+        self.client
+            .get_certificate_operation(certificate_name, Some(options))
+            .await
+    }
+
+    /// Lists the policy for a certificate.
+    ///
+    /// The GetCertificatePolicy operation returns the specified certificate policy resources in the specified key vault. This
+    /// operation requires the certificates/get permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate in a given key vault.
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_certificate_policy(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientGetCertificatePolicyOptions<'_>>,
+    ) -> Result<Response<CertificatePolicy>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.get_certificate_policy",
+            });
+        self.client
+            .get_certificate_policy(certificate_name, Some(options))
+            .await
+    }
+
+    /// Lists the certificate contacts for a specified key vault.
+    ///
+    /// The GetCertificateContacts operation returns the set of certificate contact resources in the specified key vault. This
+    /// operation requires the certificates/managecontacts permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_contacts(
+        &self,
+        options: Option<CertificateClientGetContactsOptions<'_>>,
+    ) -> Result<Response<Contacts>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.get_contacts",
+            });
+        self.client.get_contacts(Some(options)).await
+    }
+
+    /// Retrieves information about the specified deleted certificate.
+    ///
+    /// The GetDeletedCertificate operation retrieves the deleted certificate information plus its attributes, such as retention
+    /// interval, scheduled permanent deletion and the current deletion recovery level. This operation requires the certificates/get
+    /// permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_deleted_certificate(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientGetDeletedCertificateOptions<'_>>,
+    ) -> Result<Response<DeletedCertificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.get_deleted_certificate",
+            });
+        self.client
+            .get_deleted_certificate(certificate_name, Some(options))
+            .await
+    }
+
+    /// Lists the specified certificate issuer.
+    ///
+    /// The GetCertificateIssuer operation returns the specified certificate issuer resources in the specified key vault. This
+    /// operation requires the certificates/manageissuers/getissuers permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `issuer_name` - The name of the issuer.
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_issuer(
+        &self,
+        issuer_name: &str,
+        options: Option<CertificateClientGetIssuerOptions<'_>>,
+    ) -> Result<Response<Issuer>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.get_issuer",
+            });
+        self.client.get_issuer(issuer_name, Some(options)).await
+    }
+
+    /// Imports a certificate into a specified key vault.
+    ///
+    /// Imports an existing valid certificate, containing a private key, into Azure Key Vault. This operation requires the certificates/import
+    /// permission. The certificate to be imported can be in either PFX or PEM format. If the certificate is in PEM format the
+    /// PEM file must contain the key as well as x509 certificates. Key Vault will only accept a key in PKCS#8 format.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate. The value you provide may be copied globally for the purpose of running
+    ///   the service. The value provided should not include personally identifiable or sensitive information.
+    /// * `parameters` - The parameters to import the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn import_certificate(
+        &self,
+        certificate_name: &str,
+        parameters: RequestContent<ImportCertificateParameters>,
+        options: Option<CertificateClientImportCertificateOptions<'_>>,
+    ) -> Result<Response<Certificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.import_certificate",
+            });
+        self.client
+            .import_certificate(certificate_name, parameters, Some(options))
+            .await
+    }
+
+    /// List certificates in a specified key vault
+    ///
+    /// The GetCertificates operation returns the set of certificates resources in the specified key vault. This operation requires
+    /// the certificates/list permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    pub fn list_certificate_properties(
+        &self,
+        options: Option<CertificateClientListCertificatePropertiesOptions<'_>>,
+    ) -> Result<Pager<ListCertificatePropertiesResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.list_certificate_properties",
+            });
+        if let Some(continuation_token) = options.method_options.continuation_token.take() {
+            return resume_pager(
+                &self.client.pipeline,
+                &self.client.endpoint,
+                &self.client.api_version,
+                &continuation_token,
+                options.method_options.context,
+            );
+        }
+        self.client
+            .list_certificate_properties(Some(options.into()))
+    }
+
+    /// List the versions of a certificate.
+    ///
+    /// The GetCertificateVersions operation returns the versions of a certificate in the specified key vault. This operation
+    /// requires the certificates/list permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub fn list_certificate_properties_versions(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientListCertificatePropertiesVersionsOptions<'_>>,
+    ) -> Result<Pager<ListCertificatePropertiesResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.list_certificate_properties_versions",
+            });
+        if let Some(continuation_token) = options.method_options.continuation_token.take() {
+            return resume_pager(
+                &self.client.pipeline,
+                &self.client.endpoint,
+                &self.client.api_version,
+                &continuation_token,
+                options.method_options.context,
+            );
+        }
+        self.client
+            .list_certificate_properties_versions(certificate_name, Some(options.into()))
+    }
+
+    /// Lists the deleted certificates in the specified vault currently available for recovery.
+    ///
+    /// The GetDeletedCertificates operation retrieves the certificates in the current vault which are in a deleted state and
+    /// ready for recovery or purging. This operation includes deletion-specific information. This operation requires the certificates/get/list
+    /// permission. This operation can only be enabled on soft-delete enabled vaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    pub fn list_deleted_certificate_properties(
+        &self,
+        options: Option<CertificateClientListDeletedCertificatePropertiesOptions<'_>>,
+    ) -> Result<Pager<ListDeletedCertificatePropertiesResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.list_deleted_certificate_properties",
+            });
+        if let Some(continuation_token) = options.method_options.continuation_token.take() {
+            return resume_pager(
+                &self.client.pipeline,
+                &self.client.endpoint,
+                &self.client.api_version,
+                &continuation_token,
+                options.method_options.context,
+            );
+        }
+        self.client
+            .list_deleted_certificate_properties(Some(options.into()))
+    }
+
+    /// List certificate issuers for a specified key vault.
+    ///
+    /// The GetCertificateIssuers operation returns the set of certificate issuer resources in the specified key vault. This operation
+    /// requires the certificates/manageissuers/getissuers permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    pub fn list_issuer_properties(
+        &self,
+        options: Option<CertificateClientListIssuerPropertiesOptions<'_>>,
+    ) -> Result<Pager<ListIssuerPropertiesResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.list_issuer_properties",
+            });
+        if let Some(continuation_token) = options.method_options.continuation_token.take() {
+            return resume_pager(
+                &self.client.pipeline,
+                &self.client.endpoint,
+                &self.client.api_version,
+                &continuation_token,
+                options.method_options.context,
+            );
+        }
+        self.client.list_issuer_properties(Some(options.into()))
+    }
+
+    /// Merges a certificate or a certificate chain with a key pair existing on the server.
+    ///
+    /// The MergeCertificate operation performs the merging of a certificate or certificate chain with a key pair currently available
+    /// in the service. This operation requires the certificates/create permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate.
+    /// * `parameters` - The parameters to merge certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn merge_certificate(
+        &self,
+        certificate_name: &str,
+        parameters: RequestContent<MergeCertificateParameters>,
+        options: Option<CertificateClientMergeCertificateOptions<'_>>,
+    ) -> Result<Response<Certificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.merge_certificate",
+            });
+        self.client
+            .merge_certificate(certificate_name, parameters, Some(options))
+            .await
+    }
+
+    /// Permanently deletes the specified deleted certificate.
+    ///
+    /// The PurgeDeletedCertificate operation performs an irreversible deletion of the specified certificate, without possibility
+    /// for recovery. The operation is not available if the recovery level does not specify 'Purgeable'. This operation requires
+    /// the certificate/purge permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate
+    /// * `options` - Optional parameters for the request.
+    pub async fn purge_deleted_certificate(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientPurgeDeletedCertificateOptions<'_>>,
+    ) -> Result<Response<(), NoFormat>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.purge_deleted_certificate",
+            });
+        self.client
+            .purge_deleted_certificate(certificate_name, Some(options))
+            .await
+    }
+
+    /// Recovers the deleted certificate back to its current version under /certificates.
+    ///
+    /// The RecoverDeletedCertificate operation performs the reversal of the Delete operation. The operation is applicable in
+    /// vaults enabled for soft-delete, and must be issued during the retention interval (available in the deleted certificate's
+    /// attributes). This operation requires the certificates/recover permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the deleted certificate
+    /// * `options` - Optional parameters for the request.
+    pub async fn recover_deleted_certificate(
+        &self,
+        certificate_name: &str,
+        options: Option<CertificateClientRecoverDeletedCertificateOptions<'_>>,
+    ) -> Result<Response<Certificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.recover_deleted_certificate",
+            });
+        self.client
+            .recover_deleted_certificate(certificate_name, Some(options))
+            .await
+    }
+
+    /// Restores a backed up certificate to a vault.
+    ///
+    /// Restores a backed up certificate, and all its versions, to a vault. This operation requires the certificates/restore permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameters to restore the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn restore_certificate(
+        &self,
+        parameters: RequestContent<RestoreCertificateParameters>,
+        options: Option<CertificateClientRestoreCertificateOptions<'_>>,
+    ) -> Result<Response<Certificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.restore_certificate",
+            });
+        self.client
+            .restore_certificate(parameters, Some(options))
+            .await
+    }
+
+    /// Sets the certificate contacts for the specified key vault.
+    ///
+    /// Sets the certificate contacts for the specified key vault. This operation requires the certificates/managecontacts permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `contacts` - The contacts for the key vault certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn set_contacts(
+        &self,
+        contacts: RequestContent<Contacts>,
+        options: Option<CertificateClientSetContactsOptions<'_>>,
+    ) -> Result<Response<Contacts>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.set_contacts",
+            });
+        self.client.set_contacts(contacts, Some(options)).await
+    }
+
+    /// Sets the specified certificate issuer.
+    ///
+    /// The SetCertificateIssuer operation adds or updates the specified certificate issuer. This operation requires the certificates/setissuers
+    /// permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `issuer_name` - The name of the issuer. The value you provide may be copied globally for the purpose of running the
+    ///   service. The value provided should not include personally identifiable or sensitive information.
+    /// * `parameter` - Certificate issuer set parameter.
+    /// * `options` - Optional parameters for the request.
+    pub async fn set_issuer(
+        &self,
+        issuer_name: &str,
+        parameter: RequestContent<SetIssuerParameters>,
+        options: Option<CertificateClientSetIssuerOptions<'_>>,
+    ) -> Result<Response<Issuer>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.set_issuer",
+            });
+        self.client
+            .set_issuer(issuer_name, parameter, Some(options))
+            .await
+    }
+
+    /// Updates a certificate operation.
+    ///
+    /// Updates a certificate creation operation that is already in progress. This operation requires the certificates/update
+    /// permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate.
+    /// * `certificate_operation` - The certificate operation response.
+    /// * `options` - Optional parameters for the request.
+    pub async fn update_certificate_operation(
+        &self,
+        certificate_name: &str,
+        certificate_operation: RequestContent<UpdateCertificateOperationParameter>,
+        options: Option<CertificateClientUpdateCertificateOperationOptions<'_>>,
+    ) -> Result<Response<CertificateOperation>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.update_certificate_operation",
+            });
+        self.client
+            .update_certificate_operation(certificate_name, certificate_operation, Some(options))
+            .await
+    }
+
+    /// Updates the policy for a certificate.
+    ///
+    /// Set specified members in the certificate policy. Leave others as null. This operation requires the certificates/update
+    /// permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate in the given vault.
+    /// * `certificate_policy` - The policy for the certificate.
+    /// * `options` - Optional parameters for the request.
+    pub async fn update_certificate_policy(
+        &self,
+        certificate_name: &str,
+        certificate_policy: RequestContent<CertificatePolicy>,
+        options: Option<CertificateClientUpdateCertificatePolicyOptions<'_>>,
+    ) -> Result<Response<CertificatePolicy>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.update_certificate_policy",
+            });
+        self.client
+            .update_certificate_policy(certificate_name, certificate_policy, Some(options))
+            .await
+    }
+
+    /// Updates the specified attributes associated with the given certificate.
+    ///
+    /// The UpdateCertificate operation applies the specified update on the given certificate; the only elements updated are the
+    /// certificate's attributes. This operation requires the certificates/update permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate_name` - The name of the certificate in the given key vault.
+    /// * `certificate_version` - The version of the certificate.
+    /// * `parameters` - The parameters for certificate update.
+    /// * `options` - Optional parameters for the request.
+    pub async fn update_certificate_properties(
+        &self,
+        certificate_name: &str,
+        certificate_version: &str,
+        parameters: RequestContent<UpdateCertificatePropertiesParameters>,
+        options: Option<CertificateClientUpdateCertificatePropertiesOptions<'_>>,
+    ) -> Result<Response<Certificate>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.update_certificate_properties",
+            });
+        self.client
+            .update_certificate_properties(
+                certificate_name,
+                certificate_version,
+                parameters,
+                Some(options),
+            )
+            .await
+    }
+
+    /// Updates the specified certificate issuer.
+    ///
+    /// The UpdateCertificateIssuer operation performs an update on the specified certificate issuer entity. This operation requires
+    /// the certificates/setissuers permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `issuer_name` - The name of the issuer.
+    /// * `parameter` - Certificate issuer update parameter.
+    /// * `options` - Optional parameters for the request.
+    pub async fn update_issuer(
+        &self,
+        issuer_name: &str,
+        parameter: RequestContent<UpdateIssuerParameters>,
+        options: Option<CertificateClientUpdateIssuerOptions<'_>>,
+    ) -> Result<Response<Issuer>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "CertificateClient.update_issuer",
+            });
+        self.client
+            .update_issuer(issuer_name, parameter, Some(options))
+            .await
+    }
+}
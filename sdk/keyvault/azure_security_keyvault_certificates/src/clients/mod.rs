@@ -0,0 +1,9 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+mod certificate_client;
+mod pager;
+
+pub use certificate_client::CertificateClient;
+
+pub use crate::generated::clients::CertificateClientOptions;
@@ -0,0 +1,165 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Models used by the [`CertificateClient`](crate::CertificateClient).
+
+pub use crate::generated::models::*;
+
+use crate::generated::models as generated;
+use azure_core::{
+    fmt::SafeDebug,
+    http::{ClientMethodOptions, PagerOptions, PollerOptions},
+};
+
+/// Options to be passed to [`CertificateClient::create_certificate()`](crate::CertificateClient::create_certificate())
+#[derive(Clone, Default, SafeDebug)]
+pub struct CertificateClientCreateCertificateOptions<'a> {
+    /// Allows customization of the method call.
+    pub method_options: PollerOptions<'a>,
+}
+
+impl CertificateClientCreateCertificateOptions<'_> {
+    pub fn into_owned(self) -> CertificateClientCreateCertificateOptions<'static> {
+        CertificateClientCreateCertificateOptions {
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+/// Options to be passed to [`CertificateClient::list_certificate_properties()`](crate::CertificateClient::list_certificate_properties())
+#[derive(Clone, Default, SafeDebug)]
+pub struct CertificateClientListCertificatePropertiesOptions<'a> {
+    /// Specifies whether to include certificates which are not completely provisioned.
+    pub include_pending: Option<bool>,
+
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl CertificateClientListCertificatePropertiesOptions<'_> {
+    pub fn into_owned(self) -> CertificateClientListCertificatePropertiesOptions<'static> {
+        CertificateClientListCertificatePropertiesOptions {
+            include_pending: self.include_pending,
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<CertificateClientListCertificatePropertiesOptions<'a>>
+    for generated::CertificateClientListCertificatePropertiesOptions<'a>
+{
+    fn from(options: CertificateClientListCertificatePropertiesOptions<'a>) -> Self {
+        Self {
+            include_pending: options.include_pending,
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
+
+/// Options to be passed to [`CertificateClient::list_certificate_properties_versions()`](crate::CertificateClient::list_certificate_properties_versions())
+#[derive(Clone, Default, SafeDebug)]
+pub struct CertificateClientListCertificatePropertiesVersionsOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl CertificateClientListCertificatePropertiesVersionsOptions<'_> {
+    pub fn into_owned(self) -> CertificateClientListCertificatePropertiesVersionsOptions<'static> {
+        CertificateClientListCertificatePropertiesVersionsOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<CertificateClientListCertificatePropertiesVersionsOptions<'a>>
+    for generated::CertificateClientListCertificatePropertiesVersionsOptions<'a>
+{
+    fn from(options: CertificateClientListCertificatePropertiesVersionsOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
+
+/// Options to be passed to [`CertificateClient::list_deleted_certificate_properties()`](crate::CertificateClient::list_deleted_certificate_properties())
+#[derive(Clone, Default, SafeDebug)]
+pub struct CertificateClientListDeletedCertificatePropertiesOptions<'a> {
+    /// Specifies whether to include certificates which are not completely provisioned.
+    pub include_pending: Option<bool>,
+
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl CertificateClientListDeletedCertificatePropertiesOptions<'_> {
+    pub fn into_owned(self) -> CertificateClientListDeletedCertificatePropertiesOptions<'static> {
+        CertificateClientListDeletedCertificatePropertiesOptions {
+            include_pending: self.include_pending,
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<CertificateClientListDeletedCertificatePropertiesOptions<'a>>
+    for generated::CertificateClientListDeletedCertificatePropertiesOptions<'a>
+{
+    fn from(options: CertificateClientListDeletedCertificatePropertiesOptions<'a>) -> Self {
+        Self {
+            include_pending: options.include_pending,
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
+
+/// Options to be passed to [`CertificateClient::list_issuer_properties()`](crate::CertificateClient::list_issuer_properties())
+#[derive(Clone, Default, SafeDebug)]
+pub struct CertificateClientListIssuerPropertiesOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl CertificateClientListIssuerPropertiesOptions<'_> {
+    pub fn into_owned(self) -> CertificateClientListIssuerPropertiesOptions<'static> {
+        CertificateClientListIssuerPropertiesOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<CertificateClientListIssuerPropertiesOptions<'a>>
+    for generated::CertificateClientListIssuerPropertiesOptions<'a>
+{
+    fn from(options: CertificateClientListIssuerPropertiesOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
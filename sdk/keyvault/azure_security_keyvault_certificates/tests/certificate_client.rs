@@ -3,13 +3,21 @@
 
 #![cfg_attr(target_arch = "wasm32", allow(unused_imports))]
 
-use azure_core::{http::StatusCode, sleep::sleep, time::Duration, Result};
-use azure_core_test::{recorded, Recording, TestContext, TestMode, SANITIZE_BODY_NAME};
+use azure_core::{
+    error::ErrorKind,
+    http::{PagerOptions, Poller, PollerOptions, StatusCode},
+    time::Duration,
+    Result,
+};
+use azure_core_test::{
+    credentials::MockCredential, recorded, Recording, TestContext, TestMode, SANITIZE_BODY_NAME,
+};
 use azure_security_keyvault_certificates::{
     models::{
-        CertificateOperation, CertificatePolicy, CreateCertificateParameters, CurveName,
-        IssuerParameters, KeyProperties, KeyType, UpdateCertificatePropertiesParameters,
-        X509CertificateProperties,
+        CertificateClientCreateCertificateOptions,
+        CertificateClientListCertificatePropertiesOptions, CertificateOperation, CertificatePolicy,
+        CreateCertificateParameters, CurveName, IssuerParameters, KeyProperties, KeyType,
+        UpdateCertificatePropertiesParameters, X509CertificateProperties,
     },
     CertificateClient, CertificateClientOptions, ResourceExt as _, ResourceId,
 };
@@ -20,7 +28,10 @@ use azure_security_keyvault_keys::{
 use azure_security_keyvault_test::Retry;
 use futures::TryStreamExt;
 use openssl::sha::sha256;
-use std::{collections::HashMap, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
 
 static DEFAULT_POLICY: LazyLock<CertificatePolicy> = LazyLock::new(|| CertificatePolicy {
     x509_certificate_properties: Some(X509CertificateProperties {
@@ -53,12 +64,12 @@ async fn certificate_roundtrip(ctx: TestContext) -> Result<()> {
         certificate_policy: Some(DEFAULT_POLICY.clone()),
         ..Default::default()
     };
-    let operation = client
-        .create_certificate("certificate-roundtrip", body.try_into()?, None)
-        .await?
-        .into_body()
-        .await?;
-    wait_for_certificate_completion(recording, &client, operation).await?;
+    wait_for_certificate_completion(client.create_certificate(
+        "certificate-roundtrip",
+        body.try_into()?,
+        Some(create_options(recording)),
+    )?)
+    .await?;
 
     // Get the latest version of the certificate we just created.
     let certificate = client
@@ -93,12 +104,12 @@ async fn update_certificate_properties(ctx: TestContext) -> Result<()> {
         certificate_policy: Some(DEFAULT_POLICY.clone()),
         ..Default::default()
     };
-    let operation = client
-        .create_certificate("update-properties", body.try_into()?, None)
-        .await?
-        .into_body()
-        .await?;
-    wait_for_certificate_completion(recording, &client, operation).await?;
+    wait_for_certificate_completion(client.create_certificate(
+        "update-properties",
+        body.try_into()?,
+        Some(create_options(recording)),
+    )?)
+    .await?;
 
     // Get the latest version of the certificate we just created.
     let certificate = client
@@ -156,19 +167,19 @@ async fn list_certificates(ctx: TestContext) -> Result<()> {
         certificate_policy: Some(DEFAULT_POLICY.clone()),
         ..Default::default()
     };
-    let operation1 = client
-        .create_certificate("list-certificates-1", body.clone().try_into()?, None)
-        .await?
-        .into_body()
-        .await?;
-    wait_for_certificate_completion(recording, &client, operation1).await?;
-
-    let operation2 = client
-        .create_certificate("list-certificates-2", body.try_into()?, None)
-        .await?
-        .into_body()
-        .await?;
-    wait_for_certificate_completion(recording, &client, operation2).await?;
+    wait_for_certificate_completion(client.create_certificate(
+        "list-certificates-1",
+        body.clone().try_into()?,
+        Some(create_options(recording)),
+    )?)
+    .await?;
+
+    wait_for_certificate_completion(client.create_certificate(
+        "list-certificates-2",
+        body.try_into()?,
+        Some(create_options(recording)),
+    )?)
+    .await?;
 
     // List certificates.
     let mut pager = client.list_certificate_properties(None)?.into_stream();
@@ -203,13 +214,13 @@ async fn purge_certificate(ctx: TestContext) -> Result<()> {
         certificate_policy: Some(DEFAULT_POLICY.clone()),
         ..Default::default()
     };
-    let operation = client
-        .create_certificate("purge-certificate", body.try_into()?, None)
-        .await?
-        .into_body()
-        .await?;
+    let operation = wait_for_certificate_completion(client.create_certificate(
+        "purge-certificate",
+        body.try_into()?,
+        Some(create_options(recording)),
+    )?)
+    .await?;
     let name = operation.resource_id()?.name;
-    wait_for_certificate_completion(recording, &client, operation).await?;
 
     // Delete the certificate.
     client.delete_certificate(name.as_ref(), None).await?;
@@ -279,15 +290,15 @@ async fn sign_jwt_with_ec_certificate(ctx: TestContext) -> Result<()> {
         certificate_policy: Some(policy),
         ..Default::default()
     };
-    let operation = client
-        .create_certificate("ec-certificate-signer", body.try_into()?, None)
-        .await?
-        .into_body()
-        .await?;
+    let operation = wait_for_certificate_completion(client.create_certificate(
+        "ec-certificate-signer",
+        body.try_into()?,
+        Some(create_options(recording)),
+    )?)
+    .await?;
     let ResourceId {
         vault_url, name, ..
     } = operation.resource_id()?;
-    wait_for_certificate_completion(recording, &client, operation).await?;
 
     let mut key_options = KeyClientOptions::default();
     recording.instrument(&mut key_options.client_options);
@@ -316,36 +327,79 @@ async fn sign_jwt_with_ec_certificate(ctx: TestContext) -> Result<()> {
     Ok(())
 }
 
+fn create_options(recording: &Recording) -> CertificateClientCreateCertificateOptions<'static> {
+    let mut options = CertificateClientCreateCertificateOptions::default();
+    if recording.test_mode() == TestMode::Playback {
+        options.method_options.frequency = Some(Duration::ZERO);
+    }
+    options
+}
+
 async fn wait_for_certificate_completion(
-    recording: &Recording,
-    client: &CertificateClient,
-    operation: CertificateOperation,
-) -> azure_core::Result<()> {
-    let mut operation = operation;
-    let name = operation.resource_id()?.name;
-    loop {
-        if matches!(operation.status, Some(ref status) if status == "completed") {
-            break;
-        }
+    poller: Poller<CertificateOperation>,
+) -> azure_core::Result<CertificateOperation> {
+    let operation = poller.await?.into_body().await?;
+    if let Some(err) = operation.error {
+        return Err(azure_core::Error::new(
+            azure_core::error::ErrorKind::Other,
+            err.message
+                .unwrap_or_else(|| "failed to create certificate".into()),
+        ));
+    }
 
-        if let Some(err) = operation.error {
-            return Err(azure_core::Error::new(
-                azure_core::error::ErrorKind::Other,
-                err.message
-                    .unwrap_or_else(|| "failed to create certificate".into()),
-            ));
-        }
+    Ok(operation)
+}
 
-        if recording.test_mode() != TestMode::Playback {
-            sleep(Duration::seconds(3)).await;
-        }
+#[tokio::test]
+async fn list_certificate_properties_rejects_continuation_token_for_other_host() -> Result<()> {
+    let client = CertificateClient::new(
+        "https://my-vault.vault.azure.net",
+        Arc::new(MockCredential),
+        None,
+    )?;
 
-        operation = client
-            .get_certificate_operation(&name, None)
-            .await?
-            .into_body()
-            .await?;
-    }
+    let options = CertificateClientListCertificatePropertiesOptions {
+        method_options: PagerOptions {
+            continuation_token: Some(
+                "https://my-vault.vault.azure.net.contoso.com/certificates?$skiptoken=page-2"
+                    .into(),
+            ),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = client
+        .list_certificate_properties(Some(options))
+        .expect_err("continuation token for another host");
+    assert_eq!(err.kind(), &ErrorKind::DataConversion);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_certificate_rejects_continuation_token_for_other_host() -> Result<()> {
+    let client = CertificateClient::new(
+        "https://my-vault.vault.azure.net",
+        Arc::new(MockCredential),
+        None,
+    )?;
+
+    let options = CertificateClientCreateCertificateOptions {
+        method_options: PollerOptions {
+            continuation_token: Some(
+                "https://contoso.com/certificates/my-cert/pending?api-version=7.5".into(),
+            ),
+            ..Default::default()
+        },
+    };
+    let err = client
+        .create_certificate(
+            "my-cert",
+            CreateCertificateParameters::default().try_into()?,
+            Some(options),
+        )
+        .expect_err("continuation token for another host");
+    assert_eq!(err.kind(), &ErrorKind::DataConversion);
 
     Ok(())
 }
@@ -0,0 +1,9 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+mod key_client;
+mod pager;
+
+pub use key_client::KeyClient;
+
+pub use crate::generated::clients::KeyClientOptions;
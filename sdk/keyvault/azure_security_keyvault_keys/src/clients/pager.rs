@@ -0,0 +1,77 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::{
+    error::{Error, ErrorKind},
+    http::{
+        Context, Method, Page, Pager, PagerResult, Pipeline, RawResponse, Request, Response, Url,
+    },
+    json, Result,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct NextLink {
+    #[serde(rename = "nextLink")]
+    next_link: Option<String>,
+}
+
+/// Resumes paging a collection from the `nextLink` returned by [`PageIterator::continuation_token()`](azure_core::http::PageIterator::continuation_token()).
+///
+/// The continuation token must have the same origin as the `endpoint` so that a tampered token cannot send the credential to another host.
+pub(crate) fn resume_pager<P>(
+    pipeline: &Pipeline,
+    endpoint: &Url,
+    api_version: &str,
+    continuation_token: &str,
+    context: Context<'_>,
+) -> Result<Pager<P>>
+where
+    Response<P>: Page,
+    P: Send + 'static,
+{
+    let continuation: Url = continuation_token.parse()?;
+    if continuation.origin() != endpoint.origin() {
+        return Err(Error::message(
+            ErrorKind::DataConversion,
+            format!("continuation token does not refer to {endpoint}"),
+        ));
+    }
+
+    let pipeline = pipeline.clone();
+    let api_version = api_version.to_string();
+    let ctx = context.into_owned();
+    let first = Some(continuation.clone());
+    Ok(Pager::from_callback_with_continuation(
+        move |next_link: Option<Url>| {
+            let next_link = next_link.unwrap_or_else(|| continuation.clone());
+            let qp = next_link
+                .query_pairs()
+                .filter(|(name, _)| name.ne("api-version"));
+            let mut url = next_link.clone();
+            url.query_pairs_mut()
+                .clear()
+                .extend_pairs(qp)
+                .append_pair("api-version", &api_version);
+            let mut request = Request::new(url, Method::Get);
+            request.insert_header("accept", "application/json");
+            let ctx = ctx.clone();
+            let pipeline = pipeline.clone();
+            async move {
+                let rsp: RawResponse = pipeline.send(&ctx, &mut request).await?;
+                let (status, headers, body) = rsp.deconstruct();
+                let bytes = body.collect().await?;
+                let res: NextLink = json::from_json(&bytes)?;
+                let rsp = RawResponse::from_bytes(status, headers, bytes).into();
+                Ok(match res.next_link {
+                    Some(next_link) if !next_link.is_empty() => PagerResult::More {
+                        response: rsp,
+                        next: next_link.parse()?,
+                    },
+                    _ => PagerResult::Done { response: rsp },
+                })
+            }
+        },
+        first,
+    ))
+}
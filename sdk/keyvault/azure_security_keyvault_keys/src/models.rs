@@ -0,0 +1,126 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Models used by the [`KeyClient`](crate::KeyClient).
+
+pub use crate::generated::models::*;
+
+use crate::generated::models as generated;
+use azure_core::{
+    fmt::SafeDebug,
+    http::{ClientMethodOptions, PagerOptions, PollerOptions},
+};
+
+/// Options to be passed to [`KeyClient::delete_key()`](crate::KeyClient::delete_key())
+#[derive(Clone, Default, SafeDebug)]
+pub struct KeyClientDeleteKeyOptions<'a> {
+    /// Allows customization of the method call.
+    ///
+    /// The `frequency` is how often to check whether the deleted key can be retrieved.
+    /// Deleting a key cannot be resumed from a `continuation_token`.
+    pub method_options: PollerOptions<'a>,
+}
+
+impl KeyClientDeleteKeyOptions<'_> {
+    pub fn into_owned(self) -> KeyClientDeleteKeyOptions<'static> {
+        KeyClientDeleteKeyOptions {
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+/// Options to be passed to [`KeyClient::list_deleted_key_properties()`](crate::KeyClient::list_deleted_key_properties())
+#[derive(Clone, Default, SafeDebug)]
+pub struct KeyClientListDeletedKeyPropertiesOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl KeyClientListDeletedKeyPropertiesOptions<'_> {
+    pub fn into_owned(self) -> KeyClientListDeletedKeyPropertiesOptions<'static> {
+        KeyClientListDeletedKeyPropertiesOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<KeyClientListDeletedKeyPropertiesOptions<'a>>
+    for generated::KeyClientListDeletedKeyPropertiesOptions<'a>
+{
+    fn from(options: KeyClientListDeletedKeyPropertiesOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
+
+/// Options to be passed to [`KeyClient::list_key_properties()`](crate::KeyClient::list_key_properties())
+#[derive(Clone, Default, SafeDebug)]
+pub struct KeyClientListKeyPropertiesOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl KeyClientListKeyPropertiesOptions<'_> {
+    pub fn into_owned(self) -> KeyClientListKeyPropertiesOptions<'static> {
+        KeyClientListKeyPropertiesOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<KeyClientListKeyPropertiesOptions<'a>>
+    for generated::KeyClientListKeyPropertiesOptions<'a>
+{
+    fn from(options: KeyClientListKeyPropertiesOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
+
+/// Options to be passed to [`KeyClient::list_key_properties_versions()`](crate::KeyClient::list_key_properties_versions())
+#[derive(Clone, Default, SafeDebug)]
+pub struct KeyClientListKeyPropertiesVersionsOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl KeyClientListKeyPropertiesVersionsOptions<'_> {
+    pub fn into_owned(self) -> KeyClientListKeyPropertiesVersionsOptions<'static> {
+        KeyClientListKeyPropertiesVersionsOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<KeyClientListKeyPropertiesVersionsOptions<'a>>
+    for generated::KeyClientListKeyPropertiesVersionsOptions<'a>
+{
+    fn from(options: KeyClientListKeyPropertiesVersionsOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
@@ -3,18 +3,28 @@
 
 #![cfg_attr(target_arch = "wasm32", allow(unused_imports))]
 
-use azure_core::{http::StatusCode, Result};
-use azure_core_test::{recorded, TestContext, TestMode};
+use azure_core::{
+    error::ErrorKind,
+    http::{ClientOptions, Method, PagerOptions, PollerOptions, StatusCode, TransportOptions},
+    time::Duration,
+    Result,
+};
+use azure_core_test::{
+    credentials::MockCredential,
+    http::{Expectation, MockResponse, MockTransport},
+    recorded, TestContext, TestMode,
+};
 use azure_security_keyvault_keys::{
     models::{
-        CreateKeyParameters, CurveName, EncryptionAlgorithm, KeyOperationParameters, KeyType,
-        SignParameters, SignatureAlgorithm, UpdateKeyPropertiesParameters, VerifyParameters,
+        CreateKeyParameters, CurveName, EncryptionAlgorithm, KeyClientDeleteKeyOptions,
+        KeyClientListKeyPropertiesOptions, KeyOperationParameters, KeyType, SignParameters,
+        SignatureAlgorithm, UpdateKeyPropertiesParameters, VerifyParameters,
     },
     KeyClient, KeyClientOptions, ResourceExt as _,
 };
 use azure_security_keyvault_test::Retry;
 use futures::TryStreamExt;
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 #[recorded::test]
 async fn key_roundtrip(ctx: TestContext) -> Result<()> {
@@ -179,9 +189,15 @@ async fn purge_key(ctx: TestContext) -> Result<()> {
         .await?;
     assert!(matches!(key.key, Some(ref jwk) if jwk.e == Some(vec![1, 0, 1])));
 
-    // Delete the key.
+    // Delete the key and wait until the deleted key can be retrieved.
     let name = key.resource_id()?.name;
-    client.delete_key(name.as_ref(), None).await?;
+    let mut delete_options = KeyClientDeleteKeyOptions::default();
+    if recording.test_mode() == TestMode::Playback {
+        delete_options.method_options.frequency = Some(Duration::ZERO);
+    }
+    client
+        .delete_key(name.as_ref(), Some(delete_options))?
+        .await?;
 
     // Because deletes may not happen right away, try purging in a loop.
     let mut retry = match recording.test_mode() {
@@ -386,3 +402,92 @@ async fn wrap_key_unwrap_key(ctx: TestContext) -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn list_key_properties_rejects_continuation_token_for_other_host() -> Result<()> {
+    let client = KeyClient::new(
+        "https://my-vault.vault.azure.net",
+        Arc::new(MockCredential),
+        None,
+    )?;
+
+    let options = KeyClientListKeyPropertiesOptions {
+        method_options: PagerOptions {
+            continuation_token: Some(
+                "http://my-vault.vault.azure.net/keys?$skiptoken=page-2".into(),
+            ),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = client
+        .list_key_properties(Some(options))
+        .expect_err("continuation token for another scheme");
+    assert_eq!(err.kind(), &ErrorKind::DataConversion);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_key_fetches_deleted_key() -> Result<()> {
+    let transport = MockTransport::new();
+    let delete =
+        transport.expect(
+            Expectation::new(
+                Method::Delete,
+                "https://my-vault.vault.azure.net/keys/my-key",
+            )
+            .respond(MockResponse::new(StatusCode::Ok).with_body(
+                r#"{"recoveryId":"https://my-vault.vault.azure.net/deletedkeys/my-key"}"#,
+            )),
+        );
+    let get_deleted = transport.expect(
+        Expectation::new(
+            Method::Get,
+            "https://my-vault.vault.azure.net/deletedkeys/my-key",
+        )
+        .respond(MockResponse::new(StatusCode::NotFound))
+        .respond(MockResponse::new(StatusCode::Ok).with_body(
+            r#"{"recoveryId":"https://my-vault.vault.azure.net/deletedkeys/my-key","deletedDate":1700000000}"#,
+        )),
+    );
+    let options = KeyClientOptions {
+        client_options: ClientOptions {
+            transport: Some(TransportOptions::new(Arc::new(transport.clone()))),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let client = KeyClient::new(
+        "https://my-vault.vault.azure.net",
+        Arc::new(MockCredential),
+        Some(options),
+    )?;
+
+    let options = KeyClientDeleteKeyOptions {
+        method_options: PollerOptions {
+            frequency: Some(Duration::ZERO),
+            ..Default::default()
+        },
+    };
+    let responses: Vec<_> = client
+        .delete_key("my-key", Some(options))?
+        .try_collect()
+        .await?;
+    assert_eq!(responses.len(), 2);
+    let mut responses = responses.into_iter();
+
+    // The first response is the deleted key returned by the delete operation.
+    let deleted_key = responses.next().unwrap().into_body().await?;
+    assert!(deleted_key.deleted_date.is_none());
+
+    // The final response is the deleted key returned by get_deleted_key() once it is found.
+    let deleted_key = responses.next().unwrap().into_body().await?;
+    assert!(deleted_key.deleted_date.is_some());
+    // The first request to the vault is sent without authorization to discover the authentication challenge.
+    assert_eq!(delete.calls(), 2);
+    assert_eq!(get_deleted.calls(), 2);
+    transport.verify();
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+mod pager;
+mod secret_client;
+
+pub use secret_client::SecretClient;
+
+pub use crate::generated::clients::SecretClientOptions;
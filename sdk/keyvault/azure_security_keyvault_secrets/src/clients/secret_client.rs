@@ -0,0 +1,423 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::{
+    clients::pager::resume_pager,
+    generated::clients::SecretClient as GeneratedSecretClient,
+    models::{
+        BackupSecretResult, DeletedSecret, ListDeletedSecretPropertiesResult,
+        ListSecretPropertiesResult, RestoreSecretParameters, Secret,
+        SecretClientBackupSecretOptions, SecretClientDeleteSecretOptions,
+        SecretClientGetDeletedSecretOptions, SecretClientGetSecretOptions,
+        SecretClientListDeletedSecretPropertiesOptions, SecretClientListSecretPropertiesOptions,
+        SecretClientListSecretPropertiesVersionsOptions, SecretClientPurgeDeletedSecretOptions,
+        SecretClientRecoverDeletedSecretOptions, SecretClientRestoreSecretOptions,
+        SecretClientSetSecretOptions, SecretClientUpdateSecretPropertiesOptions,
+        SetSecretParameters, UpdateSecretPropertiesParameters,
+    },
+    SecretClientOptions,
+};
+use azure_core::{
+    credentials::TokenCredential,
+    error::{Error, ErrorKind},
+    http::{
+        policies::{
+            ChallengeBearerTokenCredentialPolicy, ChallengeOptions, Policy,
+            PublicApiInstrumentationInformation,
+        },
+        NoFormat, Pager, Pipeline, RequestContent, Response, Url,
+    },
+    Result,
+};
+use std::sync::Arc;
+
+/// The key vault client performs cryptographic key operations and vault operations against the Key Vault service.
+pub struct SecretClient {
+    pub(crate) client: GeneratedSecretClient,
+}
+
+impl SecretClient {
+    /// Creates a new SecretClient, using Entra ID authentication.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Service host
+    /// * `credential` - An implementation of [`TokenCredential`](azure_core::credentials::TokenCredential) that can provide an
+    ///   Entra ID token to use when authenticating.
+    /// * `options` - Optional configuration for the client.
+    pub fn new(
+        endpoint: &str,
+        credential: Arc<dyn TokenCredential>,
+        options: Option<SecretClientOptions>,
+    ) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        let mut endpoint = Url::parse(endpoint)?;
+        if !endpoint.scheme().starts_with("http") {
+            return Err(Error::message(
+                ErrorKind::Other,
+                format!("{endpoint} must use http(s)"),
+            ));
+        }
+        endpoint.set_query(None);
+        // Key Vault challenges unauthenticated requests with the tenant and scope to authenticate with.
+        let auth_policy: Arc<dyn Policy> = Arc::new(ChallengeBearerTokenCredentialPolicy::new(
+            credential,
+            vec!["https://vault.azure.net/.default"],
+            ChallengeOptions {
+                discover_challenge: true,
+                use_challenge_scope: true,
+            },
+        ));
+        Ok(Self {
+            client: GeneratedSecretClient {
+                api_version: options.api_version,
+                endpoint,
+                pipeline: Pipeline::new(
+                    option_env!("CARGO_PKG_NAME"),
+                    option_env!("CARGO_PKG_VERSION"),
+                    options.client_options,
+                    Vec::default(),
+                    vec![auth_policy],
+                ),
+            },
+        })
+    }
+
+    /// Returns the Url associated with this client.
+    pub fn endpoint(&self) -> &Url {
+        self.client.endpoint()
+    }
+    /// Backs up the specified secret.
+    ///
+    /// Requests that a backup of the specified secret be downloaded to the client. All versions of the secret will be downloaded.
+    /// This operation requires the secrets/backup permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret.
+    /// * `options` - Optional parameters for the request.
+    pub async fn backup_secret(
+        &self,
+        secret_name: &str,
+        options: Option<SecretClientBackupSecretOptions<'_>>,
+    ) -> Result<Response<BackupSecretResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.backup_secret",
+            });
+        self.client.backup_secret(secret_name, Some(options)).await
+    }
+
+    /// Deletes a secret from a specified key vault.
+    ///
+    /// The DELETE operation applies to any secret stored in Azure Key Vault. DELETE cannot be applied to an individual version
+    /// of a secret. This operation requires the secrets/delete permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret.
+    /// * `options` - Optional parameters for the request.
+    pub async fn delete_secret(
+        &self,
+        secret_name: &str,
+        options: Option<SecretClientDeleteSecretOptions<'_>>,
+    ) -> Result<Response<DeletedSecret>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.delete_secret",
+            });
+        self.client.delete_secret(secret_name, Some(options)).await
+    }
+
+    /// Gets the specified deleted secret.
+    ///
+    /// The Get Deleted Secret operation returns the specified deleted secret along with its attributes. This operation requires
+    /// the secrets/get permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret.
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_deleted_secret(
+        &self,
+        secret_name: &str,
+        options: Option<SecretClientGetDeletedSecretOptions<'_>>,
+    ) -> Result<Response<DeletedSecret>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.get_deleted_secret",
+            });
+        self.client
+            .get_deleted_secret(secret_name, Some(options))
+            .await
+    }
+
+    /// Get a specified secret from a given key vault.
+    ///
+    /// The GET operation is applicable to any secret stored in Azure Key Vault. This operation requires the secrets/get permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret.
+    /// * `secret_version` - The version of the secret. This URI fragment is optional. If not specified, the latest version of
+    ///   the secret is returned.
+    /// * `options` - Optional parameters for the request.
+    pub async fn get_secret(
+        &self,
+        secret_name: &str,
+        secret_version: &str,
+        options: Option<SecretClientGetSecretOptions<'_>>,
+    ) -> Result<Response<Secret>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.get_secret",
+            });
+        self.client
+            .get_secret(secret_name, secret_version, Some(options))
+            .await
+    }
+
+    /// Lists deleted secrets for the specified vault.
+    ///
+    /// The Get Deleted Secrets operation returns the secrets that have been deleted for a vault enabled for soft-delete. This
+    /// operation requires the secrets/list permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    pub fn list_deleted_secret_properties(
+        &self,
+        options: Option<SecretClientListDeletedSecretPropertiesOptions<'_>>,
+    ) -> Result<Pager<ListDeletedSecretPropertiesResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.list_deleted_secret_properties",
+            });
+        if let Some(continuation_token) = options.method_options.continuation_token.take() {
+            return resume_pager(
+                &self.client.pipeline,
+                &self.client.endpoint,
+                &self.client.api_version,
+                &continuation_token,
+                options.method_options.context,
+            );
+        }
+        self.client
+            .list_deleted_secret_properties(Some(options.into()))
+    }
+
+    /// List secrets in a specified key vault.
+    ///
+    /// The Get Secrets operation is applicable to the entire vault. However, only the base secret identifier and its attributes
+    /// are provided in the response. Individual secret versions are not listed in the response. This operation requires the secrets/list
+    /// permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional parameters for the request.
+    pub fn list_secret_properties(
+        &self,
+        options: Option<SecretClientListSecretPropertiesOptions<'_>>,
+    ) -> Result<Pager<ListSecretPropertiesResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.list_secret_properties",
+            });
+        if let Some(continuation_token) = options.method_options.continuation_token.take() {
+            return resume_pager(
+                &self.client.pipeline,
+                &self.client.endpoint,
+                &self.client.api_version,
+                &continuation_token,
+                options.method_options.context,
+            );
+        }
+        self.client.list_secret_properties(Some(options.into()))
+    }
+
+    /// List all versions of the specified secret.
+    ///
+    /// The full secret identifier and attributes are provided in the response. No values are returned for the secrets. This operations
+    /// requires the secrets/list permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret.
+    /// * `options` - Optional parameters for the request.
+    pub fn list_secret_properties_versions(
+        &self,
+        secret_name: &str,
+        options: Option<SecretClientListSecretPropertiesVersionsOptions<'_>>,
+    ) -> Result<Pager<ListSecretPropertiesResult>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.list_secret_properties_versions",
+            });
+        if let Some(continuation_token) = options.method_options.continuation_token.take() {
+            return resume_pager(
+                &self.client.pipeline,
+                &self.client.endpoint,
+                &self.client.api_version,
+                &continuation_token,
+                options.method_options.context,
+            );
+        }
+        self.client
+            .list_secret_properties_versions(secret_name, Some(options.into()))
+    }
+
+    /// Permanently deletes the specified secret.
+    ///
+    /// The purge deleted secret operation removes the secret permanently, without the possibility of recovery. This operation
+    /// can only be enabled on a soft-delete enabled vault. This operation requires the secrets/purge permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret.
+    /// * `options` - Optional parameters for the request.
+    pub async fn purge_deleted_secret(
+        &self,
+        secret_name: &str,
+        options: Option<SecretClientPurgeDeletedSecretOptions<'_>>,
+    ) -> Result<Response<(), NoFormat>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.purge_deleted_secret",
+            });
+        self.client
+            .purge_deleted_secret(secret_name, Some(options))
+            .await
+    }
+
+    /// Recovers the deleted secret to the latest version.
+    ///
+    /// Recovers the deleted secret in the specified vault. This operation can only be performed on a soft-delete enabled vault.
+    /// This operation requires the secrets/recover permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the deleted secret.
+    /// * `options` - Optional parameters for the request.
+    pub async fn recover_deleted_secret(
+        &self,
+        secret_name: &str,
+        options: Option<SecretClientRecoverDeletedSecretOptions<'_>>,
+    ) -> Result<Response<Secret>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.recover_deleted_secret",
+            });
+        self.client
+            .recover_deleted_secret(secret_name, Some(options))
+            .await
+    }
+
+    /// Restores a backed up secret to a vault.
+    ///
+    /// Restores a backed up secret, and all its versions, to a vault. This operation requires the secrets/restore permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameters to restore the secret.
+    /// * `options` - Optional parameters for the request.
+    pub async fn restore_secret(
+        &self,
+        parameters: RequestContent<RestoreSecretParameters>,
+        options: Option<SecretClientRestoreSecretOptions<'_>>,
+    ) -> Result<Response<Secret>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.restore_secret",
+            });
+        self.client.restore_secret(parameters, Some(options)).await
+    }
+
+    /// Sets a secret in a specified key vault.
+    ///
+    /// The SET operation adds a secret to the Azure Key Vault. If the named secret already exists, Azure Key Vault creates a
+    /// new version of that secret. This operation requires the secrets/set permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret. The value you provide may be copied globally for the purpose of running the
+    ///   service. The value provided should not include personally identifiable or sensitive information.
+    /// * `parameters` - The parameters for setting the secret.
+    /// * `options` - Optional parameters for the request.
+    pub async fn set_secret(
+        &self,
+        secret_name: &str,
+        parameters: RequestContent<SetSecretParameters>,
+        options: Option<SecretClientSetSecretOptions<'_>>,
+    ) -> Result<Response<Secret>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.set_secret",
+            });
+        self.client
+            .set_secret(secret_name, parameters, Some(options))
+            .await
+    }
+
+    /// Updates the attributes associated with a specified secret in a given key vault.
+    ///
+    /// The UPDATE operation changes specified attributes of an existing stored secret. Attributes that are not specified in the
+    /// request are left unchanged. The value of a secret itself cannot be changed. This operation requires the secrets/set permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret_name` - The name of the secret.
+    /// * `secret_version` - The version of the secret.
+    /// * `parameters` - The parameters for update secret operation.
+    /// * `options` - Optional parameters for the request.
+    pub async fn update_secret_properties(
+        &self,
+        secret_name: &str,
+        secret_version: &str,
+        parameters: RequestContent<UpdateSecretPropertiesParameters>,
+        options: Option<SecretClientUpdateSecretPropertiesOptions<'_>>,
+    ) -> Result<Response<Secret>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "SecretClient.update_secret_properties",
+            });
+        self.client
+            .update_secret_properties(secret_name, secret_version, parameters, Some(options))
+            .await
+    }
+}
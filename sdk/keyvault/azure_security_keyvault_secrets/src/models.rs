@@ -0,0 +1,108 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Models used by the [`SecretClient`](crate::SecretClient).
+
+pub use crate::generated::models::*;
+
+use crate::generated::models as generated;
+use azure_core::{
+    fmt::SafeDebug,
+    http::{ClientMethodOptions, PagerOptions},
+};
+
+/// Options to be passed to [`SecretClient::list_deleted_secret_properties()`](crate::SecretClient::list_deleted_secret_properties())
+#[derive(Clone, Default, SafeDebug)]
+pub struct SecretClientListDeletedSecretPropertiesOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl SecretClientListDeletedSecretPropertiesOptions<'_> {
+    pub fn into_owned(self) -> SecretClientListDeletedSecretPropertiesOptions<'static> {
+        SecretClientListDeletedSecretPropertiesOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<SecretClientListDeletedSecretPropertiesOptions<'a>>
+    for generated::SecretClientListDeletedSecretPropertiesOptions<'a>
+{
+    fn from(options: SecretClientListDeletedSecretPropertiesOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
+
+/// Options to be passed to [`SecretClient::list_secret_properties()`](crate::SecretClient::list_secret_properties())
+#[derive(Clone, Default, SafeDebug)]
+pub struct SecretClientListSecretPropertiesOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl SecretClientListSecretPropertiesOptions<'_> {
+    pub fn into_owned(self) -> SecretClientListSecretPropertiesOptions<'static> {
+        SecretClientListSecretPropertiesOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<SecretClientListSecretPropertiesOptions<'a>>
+    for generated::SecretClientListSecretPropertiesOptions<'a>
+{
+    fn from(options: SecretClientListSecretPropertiesOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
+
+/// Options to be passed to [`SecretClient::list_secret_properties_versions()`](crate::SecretClient::list_secret_properties_versions())
+#[derive(Clone, Default, SafeDebug)]
+pub struct SecretClientListSecretPropertiesVersionsOptions<'a> {
+    /// Maximum number of results to return in a page. If not specified the service will return up to 25 results.
+    pub maxresults: Option<i32>,
+
+    /// Allows customization of the method call.
+    pub method_options: PagerOptions<'a>,
+}
+
+impl SecretClientListSecretPropertiesVersionsOptions<'_> {
+    pub fn into_owned(self) -> SecretClientListSecretPropertiesVersionsOptions<'static> {
+        SecretClientListSecretPropertiesVersionsOptions {
+            maxresults: self.maxresults,
+            method_options: self.method_options.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<SecretClientListSecretPropertiesVersionsOptions<'a>>
+    for generated::SecretClientListSecretPropertiesVersionsOptions<'a>
+{
+    fn from(options: SecretClientListSecretPropertiesVersionsOptions<'a>) -> Self {
+        Self {
+            maxresults: options.maxresults,
+            method_options: ClientMethodOptions {
+                context: options.method_options.context,
+            },
+        }
+    }
+}
@@ -3,15 +3,27 @@
 
 #![cfg_attr(target_arch = "wasm32", allow(unused_imports))]
 
-use azure_core::{http::StatusCode, Result};
-use azure_core_test::{recorded, TestContext, TestMode};
+use azure_core::{
+    error::ErrorKind,
+    http::{ClientOptions, Method, PagerOptions, StatusCode, TransportOptions},
+    Result,
+};
+use azure_core_test::{
+    credentials::MockCredential,
+    http::{Expectation, MockResponse, MockTransport},
+    recorded, TestContext, TestMode,
+};
 use azure_security_keyvault_secrets::{
-    models::{SetSecretParameters, UpdateSecretPropertiesParameters},
+    models::{
+        SecretClientListSecretPropertiesOptions, SetSecretParameters,
+        UpdateSecretPropertiesParameters,
+    },
     ResourceExt as _, SecretClient, SecretClientOptions,
 };
 use azure_security_keyvault_test::Retry;
 use futures::TryStreamExt;
-use std::collections::HashMap;
+use serde_json::json;
+use std::{collections::HashMap, sync::Arc};
 
 #[recorded::test]
 async fn secret_roundtrip(ctx: TestContext) -> Result<()> {
@@ -200,3 +212,71 @@ async fn purge_secret(ctx: TestContext) -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn list_secret_properties_resumes_from_continuation_token() -> Result<()> {
+    let transport = MockTransport::new();
+    let page = transport.expect(
+        Expectation::new(Method::Get, "https://my-vault.vault.azure.net/secrets")
+            .with_query("$skiptoken", "page-2")
+            .with_query("api-version", SecretClientOptions::default().api_version)
+            .respond(MockResponse::json(
+                StatusCode::Ok,
+                &json!({ "value": [{ "id": "https://my-vault.vault.azure.net/secrets/secret-2" }] }),
+            )),
+    );
+    let options = SecretClientOptions {
+        client_options: ClientOptions {
+            transport: Some(TransportOptions::new(Arc::new(transport.clone()))),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let client = SecretClient::new(
+        "https://my-vault.vault.azure.net",
+        Arc::new(MockCredential),
+        Some(options),
+    )?;
+
+    let options = SecretClientListSecretPropertiesOptions {
+        method_options: PagerOptions {
+            continuation_token: Some(
+                "https://my-vault.vault.azure.net/secrets?api-version=7.5&$skiptoken=page-2".into(),
+            ),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let secrets: Vec<_> = client
+        .list_secret_properties(Some(options))?
+        .try_collect()
+        .await?;
+    assert_eq!(secrets.len(), 1);
+    assert!(page.calls() > 0);
+    transport.verify();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_secret_properties_rejects_continuation_token_for_other_host() -> Result<()> {
+    let client = SecretClient::new(
+        "https://my-vault.vault.azure.net",
+        Arc::new(MockCredential),
+        None,
+    )?;
+
+    let options = SecretClientListSecretPropertiesOptions {
+        method_options: PagerOptions {
+            continuation_token: Some("https://contoso.com/secrets?$skiptoken=page-2".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = client
+        .list_secret_properties(Some(options))
+        .expect_err("continuation token for another host");
+    assert_eq!(err.kind(), &ErrorKind::DataConversion);
+
+    Ok(())
+}
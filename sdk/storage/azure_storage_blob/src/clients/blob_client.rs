@@ -15,14 +15,14 @@ use crate::{
         BlockBlobClientCommitBlockListOptions, BlockBlobClientUploadOptions, BlockList,
         BlockListType, BlockLookupList,
     },
-    pipeline::StorageHeadersPolicy,
+    pipeline::{new_pipeline, parse_endpoint, StorageHeadersPolicy},
     BlobClientOptions, BlockBlobClient,
 };
 use azure_core::{
     credentials::TokenCredential,
     http::{
-        policies::{BearerTokenCredentialPolicy, Policy},
-        NoFormat, RequestContent, Response, Url, XmlFormat,
+        policies::PublicApiInstrumentationInformation, NoFormat, RequestContent, Response, Url,
+        XmlFormat,
     },
     Bytes, Result,
 };
@@ -59,22 +59,13 @@ impl BlobClient {
             .per_call_policies
             .push(storage_headers_policy);
 
-        let oauth_token_policy = BearerTokenCredentialPolicy::new(
-            credential.clone(),
-            ["https://storage.azure.com/.default"],
-        );
-        options
-            .client_options
-            .per_try_policies
-            .push(Arc::new(oauth_token_policy) as Arc<dyn Policy>);
-
-        let client = GeneratedBlobClient::new(
-            endpoint,
-            credential.clone(),
-            container_name.clone(),
-            blob_name.clone(),
-            Some(options),
-        )?;
+        let client = GeneratedBlobClient {
+            blob_name,
+            container_name,
+            endpoint: parse_endpoint(endpoint)?,
+            version: options.version,
+            pipeline: new_pipeline(credential, options.client_options),
+        };
         Ok(Self {
             endpoint: endpoint.parse()?,
             client,
@@ -117,7 +108,14 @@ impl BlobClient {
         &self,
         options: Option<BlobClientGetPropertiesOptions<'_>>,
     ) -> Result<Response<BlobClientGetPropertiesResult, NoFormat>> {
-        self.client.get_properties(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobClient.get_properties",
+            });
+        self.client.get_properties(Some(options)).await
     }
 
     /// Sets system properties on the blob.
@@ -129,7 +127,14 @@ impl BlobClient {
         &self,
         options: Option<BlobClientSetPropertiesOptions<'_>>,
     ) -> Result<Response<(), NoFormat>> {
-        self.client.set_properties(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobClient.set_properties",
+            });
+        self.client.set_properties(Some(options)).await
     }
 
     /// Downloads a blob from the service, including its metadata and properties.
@@ -139,7 +144,14 @@ impl BlobClient {
         &self,
         options: Option<BlobClientDownloadOptions<'_>>,
     ) -> Result<Response<BlobClientDownloadResult, NoFormat>> {
-        self.client.download(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobClient.download",
+            });
+        self.client.download(Some(options)).await
     }
 
     /// Creates a new blob from a data source.
@@ -159,6 +171,12 @@ impl BlobClient {
         options: Option<BlockBlobClientUploadOptions<'_>>,
     ) -> Result<Response<BlockBlobClientUploadResult, NoFormat>> {
         let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobClient.upload",
+            });
 
         if !overwrite {
             options.if_none_match = Some(String::from("*"));
@@ -182,7 +200,14 @@ impl BlobClient {
         &self,
         options: Option<BlobClientSetMetadataOptions<'_>>,
     ) -> Result<Response<(), NoFormat>> {
-        self.client.set_metadata(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobClient.set_metadata",
+            });
+        self.client.set_metadata(Some(options)).await
     }
 
     /// Deletes the blob.
@@ -194,7 +219,14 @@ impl BlobClient {
         &self,
         options: Option<BlobClientDeleteOptions<'_>>,
     ) -> Result<Response<(), NoFormat>> {
-        self.client.delete(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobClient.delete",
+            });
+        self.client.delete(Some(options)).await
     }
 
     /// Sets the tier on a blob. Standard tiers are only applicable for Block blobs, while Premium tiers are only applicable
@@ -209,6 +241,13 @@ impl BlobClient {
         tier: AccessTierOptional,
         options: Option<BlobClientSetTierOptions<'_>>,
     ) -> Result<Response<(), NoFormat>> {
-        self.client.set_tier(tier, options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobClient.set_tier",
+            });
+        self.client.set_tier(tier, Some(options)).await
     }
 }
@@ -9,14 +9,14 @@ use crate::{
         BlobContainerClientGetPropertiesOptions, BlobContainerClientListBlobFlatSegmentOptions,
         BlobContainerClientSetMetadataOptions, ListBlobsFlatSegmentResponse,
     },
-    pipeline::StorageHeadersPolicy,
+    pipeline::{new_pipeline, parse_endpoint, StorageHeadersPolicy},
     BlobClient, BlobContainerClientOptions,
 };
 use azure_core::{
     credentials::TokenCredential,
     http::{
-        policies::{BearerTokenCredentialPolicy, Policy},
-        NoFormat, PageIterator, Pager, Response, Url, XmlFormat,
+        policies::PublicApiInstrumentationInformation, NoFormat, PageIterator, Pager, Response,
+        Url, XmlFormat,
     },
     Result,
 };
@@ -51,21 +51,12 @@ impl BlobContainerClient {
             .per_call_policies
             .push(storage_headers_policy);
 
-        let oauth_token_policy = BearerTokenCredentialPolicy::new(
-            credential.clone(),
-            ["https://storage.azure.com/.default"],
-        );
-        options
-            .client_options
-            .per_try_policies
-            .push(Arc::new(oauth_token_policy) as Arc<dyn Policy>);
-
-        let client = GeneratedBlobContainerClient::new(
-            endpoint,
-            credential.clone(),
-            container_name.clone(),
-            Some(options),
-        )?;
+        let client = GeneratedBlobContainerClient {
+            container_name,
+            endpoint: parse_endpoint(endpoint)?,
+            version: options.version,
+            pipeline: new_pipeline(credential, options.client_options),
+        };
 
         Ok(Self {
             endpoint: endpoint.parse()?,
@@ -104,7 +95,14 @@ impl BlobContainerClient {
         &self,
         options: Option<BlobContainerClientCreateOptions<'_>>,
     ) -> Result<Response<(), NoFormat>> {
-        self.client.create(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobContainerClient.create_container",
+            });
+        self.client.create(Some(options)).await
     }
 
     /// Sets user-defined metadata for the specified container as one or more name-value pairs. Each call to this operation
@@ -118,7 +116,14 @@ impl BlobContainerClient {
         &self,
         options: Option<BlobContainerClientSetMetadataOptions<'_>>,
     ) -> Result<Response<(), NoFormat>> {
-        self.client.set_metadata(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobContainerClient.set_metadata",
+            });
+        self.client.set_metadata(Some(options)).await
     }
 
     /// Marks the specified container for deletion. The container and any blobs contained within are later deleted during garbage collection.
@@ -130,7 +135,14 @@ impl BlobContainerClient {
         &self,
         options: Option<BlobContainerClientDeleteOptions<'_>>,
     ) -> Result<Response<(), NoFormat>> {
-        self.client.delete(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobContainerClient.delete_container",
+            });
+        self.client.delete(Some(options)).await
     }
 
     /// Returns all user-defined metadata and system properties for the specified container.
@@ -143,11 +155,20 @@ impl BlobContainerClient {
         &self,
         options: Option<BlobContainerClientGetPropertiesOptions<'_>>,
     ) -> Result<Response<BlobContainerClientGetPropertiesResult, NoFormat>> {
-        self.client.get_properties(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobContainerClient.get_properties",
+            });
+        self.client.get_properties(Some(options)).await
     }
 
     /// Returns a list of the blobs under the specified container.
     ///
+    /// To resume listing later, pass the [`PageIterator::continuation_token()`] as the `marker` option.
+    ///
     /// # Arguments
     ///
     /// * `options` - Optional configuration for the request.
@@ -155,6 +176,13 @@ impl BlobContainerClient {
         &self,
         options: Option<BlobContainerClientListBlobFlatSegmentOptions<'_>>,
     ) -> Result<PageIterator<Response<ListBlobsFlatSegmentResponse, XmlFormat>>> {
-        self.client.list_blob_flat_segment(options)
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobContainerClient.list_blobs",
+            });
+        self.client.list_blob_flat_segment(Some(options))
     }
 }
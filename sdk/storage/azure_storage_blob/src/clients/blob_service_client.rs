@@ -7,15 +7,12 @@ use crate::{
         BlobServiceClientGetPropertiesOptions, BlobServiceClientListContainersSegmentOptions,
         ListContainersSegmentResponse, StorageServiceProperties,
     },
-    pipeline::StorageHeadersPolicy,
+    pipeline::{new_pipeline, parse_endpoint, StorageHeadersPolicy},
     BlobContainerClient, BlobServiceClientOptions,
 };
 use azure_core::{
     credentials::TokenCredential,
-    http::{
-        policies::{BearerTokenCredentialPolicy, Policy},
-        PageIterator, Response, Url, XmlFormat,
-    },
+    http::{policies::PublicApiInstrumentationInformation, PageIterator, Response, Url, XmlFormat},
     Result,
 };
 use std::sync::Arc;
@@ -47,16 +44,11 @@ impl BlobServiceClient {
             .per_call_policies
             .push(storage_headers_policy);
 
-        let oauth_token_policy = BearerTokenCredentialPolicy::new(
-            credential.clone(),
-            ["https://storage.azure.com/.default"],
-        );
-        options
-            .client_options
-            .per_try_policies
-            .push(Arc::new(oauth_token_policy) as Arc<dyn Policy>);
-
-        let client = GeneratedBlobServiceClient::new(endpoint, credential.clone(), Some(options))?;
+        let client = GeneratedBlobServiceClient {
+            endpoint: parse_endpoint(endpoint)?,
+            version: options.version,
+            pipeline: new_pipeline(credential, options.client_options),
+        };
 
         Ok(Self {
             endpoint: endpoint.parse()?,
@@ -90,11 +82,20 @@ impl BlobServiceClient {
         &self,
         options: Option<BlobServiceClientGetPropertiesOptions<'_>>,
     ) -> Result<Response<StorageServiceProperties, XmlFormat>> {
-        self.client.get_properties(options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobServiceClient.get_properties",
+            });
+        self.client.get_properties(Some(options)).await
     }
 
     /// Returns a list of the containers under the specified Storage account.
     ///
+    /// To resume listing later, pass the [`PageIterator::continuation_token()`] as the `marker` option.
+    ///
     /// # Arguments
     ///
     /// * `options` - Optional configuration for the request.
@@ -102,6 +103,13 @@ impl BlobServiceClient {
         &self,
         options: Option<BlobServiceClientListContainersSegmentOptions<'_>>,
     ) -> Result<PageIterator<Response<ListContainersSegmentResponse, XmlFormat>>> {
-        self.client.list_containers_segment(options)
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlobServiceClient.list_containers",
+            });
+        self.client.list_containers_segment(Some(options))
     }
 }
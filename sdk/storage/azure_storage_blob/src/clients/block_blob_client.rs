@@ -15,14 +15,14 @@ use crate::{
         BlockBlobClientStageBlockOptions, BlockBlobClientUploadOptions, BlockList, BlockListType,
         BlockLookupList, StorageServiceProperties,
     },
-    pipeline::StorageHeadersPolicy,
+    pipeline::{new_pipeline, parse_endpoint, StorageHeadersPolicy},
     BlobClientOptions, BlockBlobClientOptions,
 };
 use azure_core::{
     credentials::TokenCredential,
     http::{
-        policies::{BearerTokenCredentialPolicy, Policy},
-        NoFormat, RequestContent, Response, Url, XmlFormat,
+        policies::PublicApiInstrumentationInformation, NoFormat, RequestContent, Response, Url,
+        XmlFormat,
     },
     Bytes, Result,
 };
@@ -59,22 +59,13 @@ impl BlockBlobClient {
             .per_call_policies
             .push(storage_headers_policy);
 
-        let oauth_token_policy = BearerTokenCredentialPolicy::new(
-            credential.clone(),
-            ["https://storage.azure.com/.default"],
-        );
-        options
-            .client_options
-            .per_try_policies
-            .push(Arc::new(oauth_token_policy) as Arc<dyn Policy>);
-
-        let client = GeneratedBlockBlobClient::new(
-            endpoint,
-            credential.clone(),
-            container_name.clone(),
-            blob_name.clone(),
-            Some(options),
-        )?;
+        let client = GeneratedBlockBlobClient {
+            blob_name,
+            container_name,
+            endpoint: parse_endpoint(endpoint)?,
+            version: options.version,
+            pipeline: new_pipeline(credential, options.client_options),
+        };
         Ok(Self {
             endpoint: endpoint.parse()?,
             client,
@@ -107,7 +98,14 @@ impl BlockBlobClient {
         blocks: RequestContent<BlockLookupList>,
         options: Option<BlockBlobClientCommitBlockListOptions<'_>>,
     ) -> Result<Response<BlockBlobClientCommitBlockListResult, NoFormat>> {
-        self.client.commit_block_list(blocks, options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlockBlobClient.commit_block_list",
+            });
+        self.client.commit_block_list(blocks, Some(options)).await
     }
 
     /// Creates a new block to be later committed as part of a blob.
@@ -126,8 +124,15 @@ impl BlockBlobClient {
         body: RequestContent<Bytes>,
         options: Option<BlockBlobClientStageBlockOptions<'_>>,
     ) -> Result<Response<BlockBlobClientStageBlockResult, NoFormat>> {
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlockBlobClient.stage_block",
+            });
         self.client
-            .stage_block(block_id, content_length, body, options)
+            .stage_block(block_id, content_length, body, Some(options))
             .await
     }
 
@@ -142,6 +147,13 @@ impl BlockBlobClient {
         list_type: BlockListType,
         options: Option<BlockBlobClientGetBlockListOptions<'_>>,
     ) -> Result<Response<BlockList, XmlFormat>> {
-        self.client.get_block_list(list_type, options).await
+        let mut options = options.unwrap_or_default();
+        options
+            .method_options
+            .context
+            .insert(PublicApiInstrumentationInformation {
+                api_name: "BlockBlobClient.get_block_list",
+            });
+        self.client.get_block_list(list_type, Some(options)).await
     }
 }
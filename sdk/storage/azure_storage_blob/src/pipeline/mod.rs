@@ -2,5 +2,57 @@
 // Licensed under the MIT License.
 
 mod storage_headers_policy;
+mod storage_should_retry;
 
+use azure_core::{
+    credentials::TokenCredential,
+    error::{Error, ErrorKind},
+    http::{
+        policies::{ChallengeBearerTokenCredentialPolicy, ChallengeOptions, Policy},
+        ClientOptions, Pipeline, Url,
+    },
+    Result,
+};
+use std::sync::Arc;
 pub use storage_headers_policy::StorageHeadersPolicy;
+use storage_should_retry::StorageShouldRetry;
+
+/// Parses the endpoint of a generated client, which must use http(s), and removes its query string.
+pub(crate) fn parse_endpoint(endpoint: &str) -> Result<Url> {
+    let mut endpoint = Url::parse(endpoint)?;
+    if !endpoint.scheme().starts_with("http") {
+        return Err(Error::message(
+            ErrorKind::Other,
+            format!("{endpoint} must use http(s)"),
+        ));
+    }
+    endpoint.set_query(None);
+    Ok(endpoint)
+}
+
+/// Creates the [`Pipeline`] of a generated client, which authenticates with an Entra ID token
+/// for the tenant in any `WWW-Authenticate` challenge returned by the service,
+/// and retries transient Storage errors unless the caller set their own `ShouldRetry`.
+pub(crate) fn new_pipeline(
+    credential: Arc<dyn TokenCredential>,
+    mut client_options: ClientOptions,
+) -> Pipeline {
+    client_options.retry = Some(
+        client_options
+            .retry
+            .unwrap_or_default()
+            .with_default_should_retry(Arc::new(StorageShouldRetry)),
+    );
+    let auth_policy: Arc<dyn Policy> = Arc::new(ChallengeBearerTokenCredentialPolicy::new(
+        credential,
+        vec!["https://storage.azure.com/.default"],
+        ChallengeOptions::default(),
+    ));
+    Pipeline::new(
+        option_env!("CARGO_PKG_NAME"),
+        option_env!("CARGO_PKG_VERSION"),
+        client_options,
+        Vec::default(),
+        vec![auth_policy],
+    )
+}
@@ -0,0 +1,107 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use azure_core::http::policies::{AttemptOutcome, DefaultShouldRetry, ShouldRetry};
+
+/// Storage error codes for transient conditions that succeed if the request is sent again.
+const RETRY_ERROR_CODES: &[&str] = &["InternalError", "OperationTimedOut", "ServerBusy"];
+
+/// The [`ShouldRetry`] registered by Storage clients unless one is set in the client's [`RetryOptions`](azure_core::http::RetryOptions).
+///
+/// In addition to the [`DefaultShouldRetry`] rules, retries responses whose `x-ms-error-code` is a transient Storage error.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct StorageShouldRetry;
+
+impl ShouldRetry for StorageShouldRetry {
+    fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool {
+        match outcome {
+            AttemptOutcome::Response {
+                error_code: Some(error_code),
+                ..
+            } if RETRY_ERROR_CODES.contains(error_code) => true,
+            _ => DefaultShouldRetry.should_retry(outcome, attempt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::new_pipeline;
+    use azure_core::http::ClientOptions;
+    use azure_core::http::{
+        headers::{Headers, ERROR_CODE},
+        Context, FixedRetryOptions, Method, Request, RetryOptions, StatusCode, TransportOptions,
+    };
+    use azure_core::time::Duration;
+    use azure_core_test::{
+        credentials::MockCredential,
+        http::{Expectation, MockResponse, MockTransport},
+    };
+    use std::sync::Arc;
+
+    fn should_retry(status: StatusCode, error_code: Option<&str>) -> bool {
+        StorageShouldRetry.should_retry(
+            &AttemptOutcome::Response {
+                status,
+                headers: &Headers::new(),
+                error_code,
+            },
+            1,
+        )
+    }
+
+    #[test]
+    fn retries_transient_error_codes() {
+        assert!(should_retry(StatusCode::Forbidden, Some("ServerBusy")));
+        assert!(should_retry(
+            StatusCode::BadRequest,
+            Some("OperationTimedOut")
+        ));
+        assert!(!should_retry(
+            StatusCode::Conflict,
+            Some("BlobAlreadyExists")
+        ));
+    }
+
+    #[test]
+    fn retries_default_statuses() {
+        assert!(should_retry(StatusCode::ServiceUnavailable, None));
+        assert!(!should_retry(StatusCode::NotFound, Some("BlobNotFound")));
+    }
+
+    #[tokio::test]
+    async fn pipeline_retries_server_busy() {
+        let transport = MockTransport::new();
+        let properties = transport.expect(
+            Expectation::new(
+                Method::Get,
+                "https://myaccount.blob.core.windows.net/container",
+            )
+            .respond(MockResponse::new(StatusCode::Forbidden).with_header(ERROR_CODE, "ServerBusy"))
+            .respond(MockResponse::new(StatusCode::Ok)),
+        );
+        let pipeline = new_pipeline(
+            Arc::new(MockCredential),
+            ClientOptions {
+                retry: Some(RetryOptions::fixed(FixedRetryOptions {
+                    delay: Duration::ZERO,
+                    ..Default::default()
+                })),
+                transport: Some(TransportOptions::new(Arc::new(transport.clone()))),
+                ..Default::default()
+            },
+        );
+
+        let mut request = Request::new(
+            "https://myaccount.blob.core.windows.net/container"
+                .parse()
+                .unwrap(),
+            Method::Get,
+        );
+        let response = pipeline.send(&Context::new(), &mut request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::Ok);
+        assert_eq!(properties.calls(), 2);
+        transport.verify();
+    }
+}
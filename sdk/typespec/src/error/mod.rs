@@ -27,6 +27,10 @@ pub enum ErrorKind {
     },
     /// An error performing IO.
     Io,
+    /// An operation did not complete before its timeout or deadline.
+    Timeout,
+    /// An operation was cancelled.
+    Cancelled,
     /// An error converting data.
     DataConversion,
     /// An error getting an API credential token.
@@ -62,6 +66,8 @@ impl Display for ErrorKind {
                 .field(&error_code.as_deref().unwrap_or("(unknown error code)"))
                 .finish(),
             ErrorKind::Io => f.write_str("Io"),
+            ErrorKind::Timeout => f.write_str("Timeout"),
+            ErrorKind::Cancelled => f.write_str("Cancelled"),
             ErrorKind::DataConversion => f.write_str("DataConversion"),
             ErrorKind::Credential => f.write_str("Credential"),
             ErrorKind::MockFramework => f.write_str("MockFramework"),
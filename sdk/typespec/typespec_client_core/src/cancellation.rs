@@ -0,0 +1,209 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Cooperative cancellation of operations.
+
+use crate::error::{Error, ErrorKind, Result};
+use futures::future::{self, Either};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    pin::{pin, Pin},
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+};
+
+/// A token used to cancel operations cooperatively.
+///
+/// Clones of a `CancellationToken` share the same state, so calling [`CancellationToken::cancel()`] on any clone
+/// cancels operations using any other clone. Once cancelled, a token cannot be reset.
+///
+/// Add a `CancellationToken` to a [`Context`](crate::http::Context) using `with_value()` to cancel a client method.
+/// The retry policy stops sending or waiting to retry a request once cancelled, and returns an error with [`ErrorKind::Cancelled`].
+/// An attempt that has already been sent is allowed to finish so its outcome is known.
+/// Unlike dropping a future, which stops an operation wherever it is awaiting, operations check for cancellation
+/// where they can stop without leaving any state such as a connection or AMQP link indeterminate.
+///
+/// # Examples
+///
+/// ```
+/// use typespec_client_core::{cancellation::CancellationToken, http::Context};
+///
+/// let token = CancellationToken::new();
+/// let ctx = Context::new().with_value(token.clone());
+///
+/// // Cancel the operation from another task e.g., when the user presses Ctrl+C.
+/// token.cancel();
+/// assert!(ctx.value::<CancellationToken>().is_some_and(CancellationToken::is_cancelled));
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    cancelled: bool,
+    next_id: u64,
+    /// Wakers of [`Cancelled`] futures waiting for cancellation.
+    wakers: HashMap<u64, Waker>,
+}
+
+impl CancellationToken {
+    /// Creates a new `CancellationToken` that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels operations using this token and wakes any tasks waiting for cancellation.
+    pub fn cancel(&self) {
+        let wakers = {
+            let mut state = self.lock();
+            state.cancelled = true;
+            std::mem::take(&mut state.wakers)
+        };
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.lock().cancelled
+    }
+
+    /// Returns an error with [`ErrorKind::Cancelled`] if this token has been cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(cancelled_error());
+        }
+        Ok(())
+    }
+
+    /// Returns a future that completes when this token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+            id: None,
+        }
+    }
+
+    /// Runs the `future` until completion, or until this token is cancelled and the future is dropped.
+    ///
+    /// Returns an error with [`ErrorKind::Cancelled`] if this token was cancelled before the future completed.
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Result<F::Output> {
+        self.check()?;
+        match future::select(pin!(future), self.cancelled()).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(cancelled_error()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// A future returned by [`CancellationToken::cancelled()`] that completes when the token is cancelled.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Cancelled {
+    token: CancellationToken,
+    id: Option<u64>,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.token.lock();
+        if state.cancelled {
+            return Poll::Ready(());
+        }
+
+        let id = match self.id {
+            Some(id) => id,
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                id
+            }
+        };
+        state.wakers.insert(id, cx.waker().clone());
+        drop(state);
+        self.id = Some(id);
+        Poll::Pending
+    }
+}
+
+impl Drop for Cancelled {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            self.token.lock().wakers.remove(&id);
+        }
+    }
+}
+
+fn cancelled_error() -> Error {
+    Error::message(ErrorKind::Cancelled, "operation was cancelled")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sleep::sleep, time::Duration};
+
+    #[test]
+    fn clones_share_state() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        assert!(clone.check().is_ok());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert_eq!(clone.check().unwrap_err().kind(), &ErrorKind::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn run_until_cancelled() {
+        let token = CancellationToken::new();
+        assert_eq!(token.run_until_cancelled(async { 1 }).await.unwrap(), 1);
+
+        let clone = token.clone();
+        let cancel = async move {
+            sleep(Duration::milliseconds(10)).await;
+            clone.cancel();
+        };
+        let (result, _) = futures::join!(
+            token.run_until_cancelled(sleep(Duration::seconds(10))),
+            cancel
+        );
+        assert_eq!(result.unwrap_err().kind(), &ErrorKind::Cancelled);
+        assert!(token.lock().wakers.is_empty());
+
+        // Futures are not run once cancelled.
+        let err = token.run_until_cancelled(async { 1 }).await.unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn dropped_futures_unregister() {
+        let token = CancellationToken::new();
+        let mut cancelled = Box::pin(token.cancelled());
+        assert!(futures::poll!(cancelled.as_mut()).is_pending());
+        assert_eq!(token.lock().wakers.len(), 1);
+
+        drop(cancelled);
+        assert!(token.lock().wakers.is_empty());
+    }
+}
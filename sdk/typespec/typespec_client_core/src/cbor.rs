@@ -0,0 +1,61 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! CBOR serialization functions.
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Serialize};
+use typespec::error::{ErrorKind, Result, ResultExt};
+
+/// Serialize a type to [CBOR](https://www.rfc-editor.org/rfc/rfc8949).
+pub fn to_cbor<T>(value: &T) -> Result<Bytes>
+where
+    T: ?Sized + Serialize,
+{
+    let mut buf = Vec::new();
+    ciborium::into_writer(value, &mut buf).with_context(ErrorKind::DataConversion, || {
+        let t = core::any::type_name::<T>();
+        format!("failed to serialize {t} into cbor")
+    })?;
+    Ok(buf.into())
+}
+
+/// Reads [CBOR](https://www.rfc-editor.org/rfc/rfc8949) from bytes.
+pub fn from_cbor<S, T>(body: S) -> Result<T>
+where
+    S: AsRef<[u8]>,
+    T: DeserializeOwned,
+{
+    ciborium::from_reader(body.as_ref()).with_context(ErrorKind::DataConversion, || {
+        let t = core::any::type_name::<T>();
+        format!("failed to deserialize cbor into a {t}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Secret {
+        name: String,
+        enabled: bool,
+    }
+
+    #[test]
+    fn round_trip() {
+        let secret = Secret {
+            name: "my_secret".to_string(),
+            enabled: true,
+        };
+        let body = to_cbor(&secret).unwrap();
+        assert_eq!(body[0], 0xa2);
+        assert_eq!(from_cbor::<_, Secret>(body).unwrap(), secret);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = from_cbor::<_, Secret>(b"{}").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+    }
+}
@@ -0,0 +1,214 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use crate::http::{
+    headers::{HeaderName, HeaderValue, Headers},
+    request::{Body, Request},
+    response::PinnedStream,
+    HttpClient, Method, RawResponse,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use http_body_util::{combinators::BoxBody, BodyExt, Full, StreamBody};
+use hyper::body::Frame;
+use hyper_tls::HttpsConnector;
+use hyper_util::{
+    client::legacy::{
+        connect::{Connect, HttpConnector},
+        Client,
+    },
+    rt::TokioExecutor,
+};
+use std::{collections::HashMap, fmt, sync::Arc};
+use tracing::{debug, warn};
+use typespec::error::{Error, ErrorKind, Result, ResultExt};
+
+/// The request body type used by a [`HyperClient`].
+pub type HyperBody = BoxBody<Bytes, Error>;
+
+/// An [`HttpClient`] with the `hyper` backend.
+///
+/// Use [`HyperClient::new`] to share an existing `hyper_util` [`Client`] and its connection pool and TLS configuration,
+/// or [`new_hyper_client`] to create a client that connects using `native-tls`.
+#[derive(Clone)]
+pub struct HyperClient<C = HttpsConnector<HttpConnector>> {
+    client: Client<C, HyperBody>,
+}
+
+impl<C> HyperClient<C> {
+    /// Create a new `HyperClient` from an existing `hyper_util` [`Client`].
+    pub fn new(client: Client<C, HyperBody>) -> Self {
+        Self { client }
+    }
+}
+
+impl<C> fmt::Debug for HyperClient<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyperClient").finish_non_exhaustive()
+    }
+}
+
+/// Create a new [`HttpClient`] with the `hyper` backend.
+pub fn new_hyper_client() -> Arc<dyn HttpClient> {
+    debug!("creating an http client using `hyper`");
+
+    // Set `pool_max_idle_per_host` to `0` to avoid an issue in the underlying
+    // `hyper` library that causes the client to hang in some cases.
+    //
+    // See <https://github.com/hyperium/hyper/issues/2312> for more details.
+    let client = Client::builder(TokioExecutor::new())
+        .pool_max_idle_per_host(0)
+        .build(HttpsConnector::new());
+
+    Arc::new(HyperClient::new(client))
+}
+
+#[async_trait]
+impl<C> HttpClient for HyperClient<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    async fn execute_request(&self, request: &Request) -> Result<RawResponse> {
+        let url = request.url().clone();
+        let method = request.method();
+        let mut req = ::hyper::Request::builder()
+            .method(from_method(*method))
+            .uri(url.as_str());
+        for (name, value) in request.headers().iter() {
+            req = req.header(name.as_str(), value.as_str());
+        }
+
+        let body: HyperBody = match request.body().clone() {
+            Body::Bytes(bytes) => Full::new(bytes).map_err(|never| match never {}).boxed(),
+            Body::SeekableStream(seekable_stream) => {
+                BodyExt::boxed(StreamBody::new(seekable_stream.map_ok(Frame::data)))
+            }
+        };
+        let hyper_request = req
+            .body(body)
+            .context(ErrorKind::Other, "failed to build `hyper` request")?;
+
+        debug!("performing request {method} '{url}' with `hyper`");
+        let rsp = self
+            .client
+            .request(hyper_request)
+            .await
+            .context(ErrorKind::Io, "failed to execute `hyper` request")?;
+
+        let status = rsp.status();
+        let headers = to_headers(rsp.headers());
+
+        let body: PinnedStream = Box::pin(rsp.into_body().into_data_stream().map_err(|error| {
+            Error::full(
+                ErrorKind::Io,
+                error,
+                "error converting `hyper` response into a byte stream",
+            )
+        }));
+
+        Ok(RawResponse::new(status.as_u16().into(), headers, body))
+    }
+}
+
+fn to_headers(map: &::hyper::HeaderMap) -> Headers {
+    let map = map
+        .iter()
+        .filter_map(|(k, v)| {
+            let key = k.as_str();
+            if let Ok(value) = v.to_str() {
+                Some((
+                    HeaderName::from(key.to_owned()),
+                    HeaderValue::from(value.to_owned()),
+                ))
+            } else {
+                warn!("header value for `{key}` is not utf8");
+                None
+            }
+        })
+        .collect::<HashMap<_, _>>();
+    Headers::from(map)
+}
+
+fn from_method(method: Method) -> ::hyper::Method {
+    match method {
+        Method::Delete => ::hyper::Method::DELETE,
+        Method::Get => ::hyper::Method::GET,
+        Method::Head => ::hyper::Method::HEAD,
+        Method::Patch => ::hyper::Method::PATCH,
+        Method::Post => ::hyper::Method::POST,
+        Method::Put => ::hyper::Method::PUT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{http::headers::CONTENT_TYPE, stream::BytesStream};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    // Accepts a single connection, returns the raw request, and responds with a chunked response.
+    async fn serve_once(listener: TcpListener) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                let len = headers
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length: ")
+                            .map(|v| v.parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= len {
+                    break;
+                }
+            }
+        }
+        socket
+            .write_all(b"HTTP/1.1 201 Created\r\ncontent-type: text/plain\r\ntransfer-encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n")
+            .await
+            .unwrap();
+        String::from_utf8(request).unwrap()
+    }
+
+    #[tokio::test]
+    async fn execute_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/path?query=value", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_once(listener));
+
+        let mut request = Request::new(url.parse().unwrap(), Method::Put);
+        request.insert_header(CONTENT_TYPE, "text/plain");
+        request.insert_header("content-length", "11");
+        request.set_body(Body::SeekableStream(Box::new(BytesStream::new(
+            "hello world",
+        ))));
+
+        let client = new_hyper_client();
+        let response = client.execute_request(&request).await.unwrap();
+        assert_eq!(response.status(), crate::http::StatusCode::Created);
+        assert_eq!(
+            response.headers().get_optional_str(&CONTENT_TYPE),
+            Some("text/plain")
+        );
+        let body = response.into_body().collect_string().await.unwrap();
+        assert_eq!(body, "hello world");
+
+        let sent = server.await.unwrap();
+        assert!(
+            sent.starts_with("PUT /path?query=value HTTP/1.1\r\n"),
+            "{sent}"
+        );
+        assert!(sent.contains("content-type: text/plain\r\n"), "{sent}");
+        assert!(sent.ends_with("\r\n\r\nhello world"), "{sent}");
+    }
+}
@@ -3,15 +3,29 @@
 
 //! Built-in HTTP clients.
 
-#[cfg(not(any(feature = "reqwest", feature = "reqwest_rustls")))]
+#[cfg(all(feature = "hyper", not(target_arch = "wasm32")))]
+mod hyper;
+#[cfg(not(any(
+    feature = "reqwest",
+    feature = "reqwest_rustls",
+    all(feature = "hyper", not(target_arch = "wasm32"))
+)))]
 mod noop;
 #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
 mod reqwest;
 
-#[cfg(not(any(feature = "reqwest", feature = "reqwest_rustls")))]
+#[cfg(all(feature = "hyper", not(target_arch = "wasm32")))]
+pub use self::hyper::{new_hyper_client, HyperBody, HyperClient};
+#[cfg(not(any(
+    feature = "reqwest",
+    feature = "reqwest_rustls",
+    all(feature = "hyper", not(target_arch = "wasm32"))
+)))]
 use self::noop::new_noop_client;
 #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
 use self::reqwest::new_reqwest_client;
+#[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+pub use self::reqwest::{new_reqwest_client_with_options, ReqwestTransportOptions, TlsIdentity};
 
 use crate::http::{RawResponse, Request};
 use async_trait::async_trait;
@@ -19,12 +33,26 @@ use std::sync::Arc;
 use typespec::error::Result;
 
 /// Create a new [`HttpClient`].
+///
+/// The `reqwest` backend is used if enabled, followed by the `hyper` backend.
 pub fn new_http_client() -> Arc<dyn HttpClient> {
     #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
     {
         new_reqwest_client()
     }
-    #[cfg(not(any(feature = "reqwest", feature = "reqwest_rustls")))]
+    #[cfg(all(
+        not(any(feature = "reqwest", feature = "reqwest_rustls")),
+        feature = "hyper",
+        not(target_arch = "wasm32")
+    ))]
+    {
+        new_hyper_client()
+    }
+    #[cfg(not(any(
+        feature = "reqwest",
+        feature = "reqwest_rustls",
+        all(feature = "hyper", not(target_arch = "wasm32"))
+    )))]
     {
         new_noop_client()
     }
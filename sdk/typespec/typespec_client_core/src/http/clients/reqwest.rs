@@ -1,11 +1,14 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use crate::http::{
-    headers::{HeaderName, HeaderValue, Headers},
-    request::{Body, Request},
-    response::PinnedStream,
-    HttpClient, Method, RawResponse,
+use crate::{
+    http::{
+        headers::{HeaderName, HeaderValue, Headers},
+        request::{Body, Request},
+        response::PinnedStream,
+        HttpClient, Method, RawResponse, Url,
+    },
+    time::Duration,
 };
 use async_trait::async_trait;
 use futures::TryStreamExt;
@@ -15,25 +18,162 @@ use typespec::error::{Error, ErrorKind, Result, ResultExt};
 
 /// Create a new [`HttpClient`] with the `reqwest` backend.
 pub fn new_reqwest_client() -> Arc<dyn HttpClient> {
+    new_reqwest_client_with_options(&ReqwestTransportOptions::default())
+        .expect("failed to build `reqwest` client")
+}
+
+/// Options to configure the [`HttpClient`] with the `reqwest` backend.
+///
+/// Pass to [`TransportOptions::new_reqwest`](crate::http::TransportOptions::new_reqwest) to create a transport with these options.
+/// Most options are not supported on WASM and are ignored.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransportOptions {
+    /// Timeout for establishing a connection.
+    pub connect_timeout: Option<Duration>,
+
+    /// Timeout for each read from a connection, which is reset after each successful read.
+    pub read_timeout: Option<Duration>,
+
+    /// Maximum number of idle connections kept per host.
+    ///
+    /// The default is `0` to avoid an issue in the underlying `hyper` library that causes connections to hang in some cases.
+    /// See <https://github.com/hyperium/hyper/issues/2312> for more details.
+    pub pool_max_idle_per_host: Option<usize>,
+
+    /// Use HTTP/2 without first negotiating it with the server.
+    ///
+    /// Requires the `reqwest_http2` feature.
+    pub http2_prior_knowledge: bool,
+
+    /// Proxy through which all requests are sent.
+    ///
+    /// If `None`, the `HTTP_PROXY`, `HTTPS_PROXY`, and `NO_PROXY` environment variables are used.
+    pub proxy: Option<Url>,
+
+    /// Comma-separated list of hosts, domains, IP addresses, or CIDR ranges that should not use the [`proxy`](ReqwestTransportOptions::proxy).
+    ///
+    /// This has the same format as the `NO_PROXY` environment variable.
+    pub no_proxy: Option<String>,
+
+    /// PEM-encoded root certificates to trust in addition to the system's root certificates.
+    pub root_certificates: Vec<Vec<u8>>,
+
+    /// Client identity used for mutual TLS authentication.
+    pub identity: Option<TlsIdentity>,
+}
+
+/// A client certificate and private key used for mutual TLS authentication.
+#[derive(Clone)]
+pub struct TlsIdentity {
+    /// PEM-encoded certificate chain, starting with the client certificate.
+    pub certificate_chain: Vec<u8>,
+
+    /// PEM-encoded PKCS #8 private key for the client certificate.
+    pub private_key: Vec<u8>,
+}
+
+impl std::fmt::Debug for TlsIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsIdentity").finish_non_exhaustive()
+    }
+}
+
+/// Create a new [`HttpClient`] with the `reqwest` backend configured with the given `options`.
+pub fn new_reqwest_client_with_options(
+    options: &ReqwestTransportOptions,
+) -> Result<Arc<dyn HttpClient>> {
     debug!("creating an http client using `reqwest`");
 
-    // Set `pool_max_idle_per_host` to `0` to avoid an issue in the underlying
-    // `hyper` library that causes the `reqwest` client to hang in some cases.
-    //
-    // See <https://github.com/hyperium/hyper/issues/2312> for more details.
-    #[cfg(not(target_arch = "wasm32"))]
-    let client = ::reqwest::ClientBuilder::new()
-        .pool_max_idle_per_host(0)
-        .build()
-        .expect("failed to build `reqwest` client");
+    let builder = ::reqwest::ClientBuilder::new();
 
-    // `reqwest` does not implement `pool_max_idle_per_host()` on WASM.
+    // `reqwest` does not implement most connection options on WASM.
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = configure(builder, options)?;
     #[cfg(target_arch = "wasm32")]
-    let client = ::reqwest::ClientBuilder::new()
+    let _ = options;
+
+    let client = builder
         .build()
-        .expect("failed to build `reqwest` client");
+        .context(ErrorKind::Other, "failed to build `reqwest` client")?;
+
+    Ok(Arc::new(client))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn configure(
+    mut builder: ::reqwest::ClientBuilder,
+    options: &ReqwestTransportOptions,
+) -> Result<::reqwest::ClientBuilder> {
+    builder = builder.pool_max_idle_per_host(options.pool_max_idle_per_host.unwrap_or(0));
+    if let Some(timeout) = options.connect_timeout {
+        builder = builder.connect_timeout(to_std_duration(timeout)?);
+    }
+    if let Some(timeout) = options.read_timeout {
+        builder = builder.read_timeout(to_std_duration(timeout)?);
+    }
+
+    if options.http2_prior_knowledge {
+        #[cfg(feature = "reqwest_http2")]
+        {
+            builder = builder.http2_prior_knowledge();
+        }
+        #[cfg(not(feature = "reqwest_http2"))]
+        return Err(Error::message(
+            ErrorKind::Other,
+            "`http2_prior_knowledge` requires the `reqwest_http2` feature",
+        ));
+    }
+
+    if let Some(proxy) = &options.proxy {
+        let proxy = ::reqwest::Proxy::all(proxy.clone())
+            .with_context(ErrorKind::DataConversion, || {
+                format!("invalid proxy URL '{proxy}'")
+            })?
+            .no_proxy(
+                options
+                    .no_proxy
+                    .as_deref()
+                    .and_then(::reqwest::NoProxy::from_string),
+            );
+        builder = builder.proxy(proxy);
+    }
+
+    for certificate in &options.root_certificates {
+        let certificate = ::reqwest::Certificate::from_pem(certificate)
+            .context(ErrorKind::DataConversion, "invalid root certificate")?;
+        builder = builder.add_root_certificate(certificate);
+    }
+
+    if let Some(identity) = &options.identity {
+        builder = builder.identity(to_identity(identity)?);
+    }
+
+    Ok(builder)
+}
 
-    Arc::new(client)
+#[cfg(not(target_arch = "wasm32"))]
+fn to_std_duration(duration: Duration) -> Result<std::time::Duration> {
+    duration
+        .try_into()
+        .with_context(ErrorKind::DataConversion, || {
+            format!("invalid timeout {duration}")
+        })
+}
+
+// `reqwest` uses `native-tls` when both TLS backends are enabled.
+#[cfg(all(not(target_arch = "wasm32"), feature = "reqwest"))]
+fn to_identity(identity: &TlsIdentity) -> Result<::reqwest::Identity> {
+    ::reqwest::Identity::from_pkcs8_pem(&identity.certificate_chain, &identity.private_key)
+        .context(ErrorKind::DataConversion, "invalid client identity")
+}
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "reqwest")))]
+fn to_identity(identity: &TlsIdentity) -> Result<::reqwest::Identity> {
+    let mut pem = identity.private_key.clone();
+    pem.push(b'\n');
+    pem.extend_from_slice(&identity.certificate_chain);
+    ::reqwest::Identity::from_pem(&pem)
+        .context(ErrorKind::DataConversion, "invalid client identity")
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -61,17 +201,26 @@ impl HttpClient for ::reqwest::Client {
         .context(ErrorKind::Other, "failed to build `reqwest` request")?;
 
         debug!("performing request {method} '{url}' with `reqwest`");
-        let rsp = self
-            .execute(reqwest_request)
-            .await
-            .context(ErrorKind::Io, "failed to execute `reqwest` request")?;
+        let rsp = self.execute(reqwest_request).await.map_err(|error| {
+            let kind = if error.is_timeout() {
+                ErrorKind::Timeout
+            } else {
+                ErrorKind::Io
+            };
+            Error::full(kind, error, "failed to execute `reqwest` request")
+        })?;
 
         let status = rsp.status();
         let headers = to_headers(rsp.headers());
 
         let body: PinnedStream = Box::pin(rsp.bytes_stream().map_err(|error| {
+            let kind = if error.is_timeout() {
+                ErrorKind::Timeout
+            } else {
+                ErrorKind::Io
+            };
             Error::full(
-                ErrorKind::Io,
+                kind,
                 error,
                 "error converting `reqwest` request into a byte stream",
             )
@@ -110,3 +259,54 @@ fn from_method(method: Method) -> ::reqwest::Method {
         Method::Put => ::reqwest::Method::PUT,
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_with_options() {
+        let options = ReqwestTransportOptions {
+            connect_timeout: Some(Duration::seconds(5)),
+            read_timeout: Some(Duration::seconds(30)),
+            pool_max_idle_per_host: Some(10),
+            proxy: Some("http://proxy.contoso.com:8080".parse().unwrap()),
+            no_proxy: Some("localhost,127.0.0.1,.contoso.com".into()),
+            ..Default::default()
+        };
+        new_reqwest_client_with_options(&options).expect("valid options");
+    }
+
+    #[test]
+    fn invalid_timeout() {
+        let options = ReqwestTransportOptions {
+            read_timeout: Some(Duration::seconds(-1)),
+            ..Default::default()
+        };
+        let err = new_reqwest_client_with_options(&options).expect_err("negative timeout");
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+    }
+
+    #[test]
+    fn invalid_root_certificate() {
+        let options = ReqwestTransportOptions {
+            root_certificates: vec![b"not a certificate".to_vec()],
+            ..Default::default()
+        };
+        let err = new_reqwest_client_with_options(&options).expect_err("invalid certificate");
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+    }
+
+    #[test]
+    fn http2_prior_knowledge() {
+        let options = ReqwestTransportOptions {
+            http2_prior_knowledge: true,
+            ..Default::default()
+        };
+        let result = new_reqwest_client_with_options(&options);
+        #[cfg(feature = "reqwest_http2")]
+        result.expect("http2 enabled");
+        #[cfg(not(feature = "reqwest_http2"))]
+        result.expect_err("http2 not enabled");
+    }
+}
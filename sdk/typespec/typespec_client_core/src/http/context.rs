@@ -1,6 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+use crate::time::OffsetDateTime;
 use std::any::{Any, TypeId};
 use std::borrow::{Borrow, Cow};
 use std::collections::HashMap;
@@ -97,6 +98,30 @@ impl<'a> Context<'a> {
             .and_then(|item| item.downcast_ref())
     }
 
+    /// Sets an absolute deadline by which the operation must complete, and returns `Self` to allow chaining.
+    ///
+    /// The retry policy will not send, retry, or wait to retry a request after the deadline,
+    /// and returns an error with [`ErrorKind::Timeout`](typespec::error::ErrorKind::Timeout) instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use typespec_client_core::{http::Context, time::{Duration, OffsetDateTime}};
+    ///
+    /// let deadline = OffsetDateTime::now_utc() + Duration::seconds(30);
+    /// let context = Context::new().with_deadline(deadline);
+    /// assert_eq!(context.deadline(), Some(deadline));
+    /// ```
+    #[must_use]
+    pub fn with_deadline(self, deadline: OffsetDateTime) -> Self {
+        self.with_value(Deadline(deadline))
+    }
+
+    /// Returns the absolute deadline by which the operation must complete, if any.
+    pub fn deadline(&self) -> Option<OffsetDateTime> {
+        self.value::<Deadline>().map(|deadline| deadline.0)
+    }
+
     /// Returns `true` if the type map is empty; otherwise, `false`.
     pub fn is_empty(&self) -> bool {
         self.type_map.is_empty()
@@ -123,6 +148,10 @@ impl<'a> Context<'a> {
     }
 }
 
+/// The absolute deadline of an operation, stored in a [`Context`].
+#[derive(Clone, Copy, Debug)]
+struct Deadline(OffsetDateTime);
+
 impl Default for Context<'_> {
     fn default() -> Self {
         Self::new()
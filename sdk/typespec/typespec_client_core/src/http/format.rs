@@ -69,6 +69,48 @@ impl<D: DeserializeOwned> DeserializeWith<XmlFormat> for D {
     }
 }
 
+/// A [`Format`] that deserializes response bodies using [CBOR](https://www.rfc-editor.org/rfc/rfc8949).
+///
+/// This format supports deserializing response bodies to:
+/// * [`ResponseBody`] - The raw response body, without any deserialization.
+/// * Any value implementing [`serde::de::DeserializeOwned`] - Deserializes the response body to the specified type using CBOR deserialization.
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone)]
+pub struct CborFormat;
+
+#[cfg(feature = "cbor")]
+impl Format for CborFormat {}
+
+#[cfg(feature = "cbor")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<D: DeserializeOwned> DeserializeWith<CborFormat> for D {
+    async fn deserialize_with(body: ResponseBody) -> typespec::Result<Self> {
+        body.cbor().await
+    }
+}
+
+/// A [`Format`] that deserializes response bodies using [MessagePack](https://msgpack.org).
+///
+/// This format supports deserializing response bodies to:
+/// * [`ResponseBody`] - The raw response body, without any deserialization.
+/// * Any value implementing [`serde::de::DeserializeOwned`] - Deserializes the response body to the specified type using MessagePack deserialization.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone)]
+pub struct MessagePackFormat;
+
+#[cfg(feature = "msgpack")]
+impl Format for MessagePackFormat {}
+
+#[cfg(feature = "msgpack")]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<D: DeserializeOwned> DeserializeWith<MessagePackFormat> for D {
+    async fn deserialize_with(body: ResponseBody) -> typespec::Result<Self> {
+        body.msgpack().await
+    }
+}
+
 /// A [`Format`] indicating that the response has no structured format.
 /// This includes responses that return raw data and that don't return a response body.
 ///
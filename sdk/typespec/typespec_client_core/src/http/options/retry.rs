@@ -3,7 +3,8 @@
 
 use crate::{
     http::policies::{
-        ExponentialRetryPolicy, FixedRetryPolicy, NoRetryPolicy, Policy, RetryPolicy,
+        DefaultShouldRetry, ExponentialRetryPolicy, FixedRetryPolicy, NoRetryPolicy, Policy,
+        RetryPolicy, ShouldRetry,
     },
     time::Duration,
 };
@@ -56,6 +57,12 @@ impl Default for RetryMode {
 pub struct RetryOptions {
     /// The algorithm to use for calculating retry delays.
     mode: RetryMode,
+
+    /// Determines whether an unsuccessful attempt should be retried.
+    should_retry: Option<Arc<dyn ShouldRetry>>,
+
+    /// The client library's default if `should_retry` is not set.
+    default_should_retry: Option<Arc<dyn ShouldRetry>>,
 }
 
 impl RetryOptions {
@@ -63,6 +70,7 @@ impl RetryOptions {
     pub fn exponential(options: ExponentialRetryOptions) -> Self {
         Self {
             mode: RetryMode::Exponential(options),
+            ..Default::default()
         }
     }
 
@@ -70,6 +78,7 @@ impl RetryOptions {
     pub fn fixed(options: FixedRetryOptions) -> Self {
         Self {
             mode: RetryMode::Fixed(options),
+            ..Default::default()
         }
     }
 
@@ -77,6 +86,7 @@ impl RetryOptions {
     pub fn custom<T: RetryPolicy + 'static>(policy: Arc<T>) -> Self {
         Self {
             mode: RetryMode::Custom(policy),
+            ..Default::default()
         }
     }
 
@@ -84,9 +94,36 @@ impl RetryOptions {
     pub fn none() -> Self {
         Self {
             mode: RetryMode::None,
+            ..Default::default()
         }
     }
 
+    /// Determine whether an unsuccessful attempt should be retried using the supplied `ShouldRetry`.
+    ///
+    /// This is not used by a [`custom`](RetryOptions::custom) retry policy, which implements [`RetryPolicy::should_retry`] instead.
+    #[must_use]
+    pub fn with_should_retry(mut self, should_retry: Arc<dyn ShouldRetry>) -> Self {
+        self.should_retry = Some(should_retry);
+        self
+    }
+
+    /// Sets the `ShouldRetry` used if one was not set with [`RetryOptions::with_should_retry`].
+    ///
+    /// Client libraries can use this to register rules specific to their service
+    /// while still allowing callers to replace them.
+    #[must_use]
+    pub fn with_default_should_retry(mut self, should_retry: Arc<dyn ShouldRetry>) -> Self {
+        self.default_should_retry = Some(should_retry);
+        self
+    }
+
+    fn should_retry(&self) -> Arc<dyn ShouldRetry> {
+        self.should_retry
+            .clone()
+            .or_else(|| self.default_should_retry.clone())
+            .unwrap_or_else(|| Arc::new(DefaultShouldRetry))
+    }
+
     pub(crate) fn to_policy(&self) -> Arc<dyn Policy> {
         match &self.mode {
             RetryMode::Exponential(options) => Arc::new(ExponentialRetryPolicy::new(
@@ -94,11 +131,15 @@ impl RetryOptions {
                 options.max_retries,
                 options.max_total_elapsed,
                 options.max_delay,
+                options.per_try_timeout,
+                self.should_retry(),
             )),
             RetryMode::Fixed(options) => Arc::new(FixedRetryPolicy::new(
                 options.delay,
                 options.max_retries,
                 options.max_total_elapsed,
+                options.per_try_timeout,
+                self.should_retry(),
             )),
             RetryMode::Custom(c) => c.clone(),
             RetryMode::None => Arc::new(NoRetryPolicy::default()),
@@ -141,6 +182,11 @@ pub struct ExponentialRetryOptions {
     ///
     /// The default is 30 seconds. For SRE reasons, this is only respected when above 1 second.
     pub max_delay: Duration,
+
+    /// The maximum time to wait for a response to each attempt before cancelling it and retrying.
+    ///
+    /// The default is `None`, which waits indefinitely.
+    pub per_try_timeout: Option<Duration>,
 }
 
 impl ExponentialRetryOptions {
@@ -149,6 +195,7 @@ impl ExponentialRetryOptions {
         max_retries: u32 => max_retries,
         max_total_elapsed: Duration => max_total_elapsed,
         max_delay: Duration => max_delay,
+        per_try_timeout: Duration => Some(per_try_timeout),
     }
 }
 
@@ -159,6 +206,7 @@ impl Default for ExponentialRetryOptions {
             max_retries: 8,
             max_total_elapsed: Duration::seconds(60),
             max_delay: Duration::seconds(30),
+            per_try_timeout: None,
         }
     }
 }
@@ -191,6 +239,11 @@ pub struct FixedRetryOptions {
     ///
     /// The default is 1 minute.
     pub max_total_elapsed: Duration,
+
+    /// The maximum time to wait for a response to each attempt before cancelling it and retrying.
+    ///
+    /// The default is `None`, which waits indefinitely.
+    pub per_try_timeout: Option<Duration>,
 }
 
 impl FixedRetryOptions {
@@ -201,6 +254,8 @@ impl FixedRetryOptions {
         max_retries: u32 => max_retries,
         #[doc = "Set the maximum permissible elapsed time since starting to retry."]
         max_total_elapsed: Duration => max_total_elapsed,
+        #[doc = "Set the maximum time to wait for a response to each attempt before cancelling it and retrying."]
+        per_try_timeout: Duration => Some(per_try_timeout),
     }
 }
 
@@ -210,6 +265,7 @@ impl Default for FixedRetryOptions {
             delay: Duration::milliseconds(200),
             max_retries: 8,
             max_total_elapsed: Duration::seconds(60),
+            per_try_timeout: None,
         }
     }
 }
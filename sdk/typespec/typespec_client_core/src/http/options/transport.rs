@@ -27,6 +27,18 @@ impl TransportOptions {
         Self { inner }
     }
 
+    /// Creates a new `TransportOptions` using a `reqwest` client configured with the given `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `options` are invalid e.g., a certificate could not be parsed.
+    #[cfg(any(feature = "reqwest", feature = "reqwest_rustls"))]
+    pub fn new_reqwest(options: &clients::ReqwestTransportOptions) -> Result<Self> {
+        Ok(Self::new(clients::new_reqwest_client_with_options(
+            options,
+        )?))
+    }
+
     /// Creates a new `TransportOptions` using the custom policy.
     ///
     /// This policy is expected to be the last policy in the pipeline.
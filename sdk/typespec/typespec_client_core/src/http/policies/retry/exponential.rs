@@ -1,8 +1,9 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
-use super::RetryPolicy;
+use super::{AttemptOutcome, RetryPolicy, ShouldRetry};
 use crate::time::Duration;
+use std::sync::Arc;
 
 /// Retry policy with exponential back-off.
 ///
@@ -11,12 +12,14 @@ use crate::time::Duration;
 /// second will be x*2, the third x*4 and so on. The policy will retry until the maximum number of
 /// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
 /// wait time is not precise.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct ExponentialRetryPolicy {
     initial_delay: Duration,
     max_retries: u32,
     max_elapsed: Duration,
     max_delay: Duration,
+    per_try_timeout: Option<Duration>,
+    should_retry: Arc<dyn ShouldRetry>,
 }
 
 impl ExponentialRetryPolicy {
@@ -25,12 +28,16 @@ impl ExponentialRetryPolicy {
         max_retries: u32,
         max_elapsed: Duration,
         max_delay: Duration,
+        per_try_timeout: Option<Duration>,
+        should_retry: Arc<dyn ShouldRetry>,
     ) -> Self {
         Self {
             initial_delay: initial_delay.max(Duration::milliseconds(1)),
             max_retries,
             max_elapsed,
             max_delay: max_delay.max(Duration::seconds(1)),
+            per_try_timeout,
+            should_retry,
         }
     }
 }
@@ -51,6 +58,14 @@ impl RetryPolicy for ExponentialRetryPolicy {
         );
         Duration::milliseconds(sleep_ms as i64)
     }
+
+    fn per_try_timeout(&self) -> Option<Duration> {
+        self.per_try_timeout
+    }
+
+    fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool {
+        self.should_retry.should_retry(outcome, attempt)
+    }
 }
 
 #[cfg(test)]
@@ -66,6 +81,8 @@ mod tests {
             options.max_retries,
             options.max_total_elapsed,
             options.max_delay,
+            options.per_try_timeout,
+            Arc::new(crate::http::policies::DefaultShouldRetry),
         );
 
         let mut elapsed_time = Duration::seconds(0);
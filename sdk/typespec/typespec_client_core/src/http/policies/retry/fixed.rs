@@ -1,7 +1,9 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+use super::{AttemptOutcome, ShouldRetry};
 use crate::time::Duration;
+use std::sync::Arc;
 
 /// Retry policy with a fixed back-off.
 ///
@@ -9,19 +11,29 @@ use crate::time::Duration;
 /// happen at least after the same, configured sleep time. The policy will retry until the maximum number of
 /// retries have been reached or the maximum allowed delay has passed (whichever comes first). The
 /// wait time is not precise.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct FixedRetryPolicy {
     delay: Duration,
     max_retries: u32,
     max_elapsed: Duration,
+    per_try_timeout: Option<Duration>,
+    should_retry: Arc<dyn ShouldRetry>,
 }
 
 impl FixedRetryPolicy {
-    pub(crate) fn new(delay: Duration, max_retries: u32, max_elapsed: Duration) -> Self {
+    pub(crate) fn new(
+        delay: Duration,
+        max_retries: u32,
+        max_elapsed: Duration,
+        per_try_timeout: Option<Duration>,
+        should_retry: Arc<dyn ShouldRetry>,
+    ) -> Self {
         Self {
             delay: delay.max(Duration::milliseconds(10)),
             max_retries,
             max_elapsed,
+            per_try_timeout,
+            should_retry,
         }
     }
 }
@@ -35,4 +47,12 @@ impl super::RetryPolicy for FixedRetryPolicy {
         let sleep_ms = self.delay.whole_milliseconds() as u64 + u64::from(rand::random::<u8>());
         Duration::milliseconds(sleep_ms as i64)
     }
+
+    fn per_try_timeout(&self) -> Option<Duration> {
+        self.per_try_timeout
+    }
+
+    fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool {
+        self.should_retry.should_retry(outcome, attempt)
+    }
 }
@@ -10,6 +10,8 @@ pub use fixed::*;
 pub use none::*;
 
 use crate::{
+    async_runtime::get_async_runtime,
+    cancellation::CancellationToken,
     error::HttpError,
     http::{
         headers::{Headers, RETRY_AFTER, RETRY_AFTER_MS, X_MS_RETRY_AFTER_MS},
@@ -20,7 +22,8 @@ use crate::{
     time::{self, Duration, OffsetDateTime},
 };
 use async_trait::async_trait;
-use std::sync::Arc;
+use futures::future::{self, Either};
+use std::{future::Future, pin::pin, sync::Arc};
 use tracing::{debug, trace};
 use typespec::error::{Error, ErrorKind, ResultExt};
 
@@ -84,6 +87,19 @@ pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
     fn is_expired(&self, duration_since_start: Duration, retry_count: u32) -> bool;
     /// Determine how long before the next retry should be attempted.
     fn sleep_duration(&self, retry_count: u32) -> Duration;
+    /// The maximum time to wait for a response to each attempt before cancelling it and retrying.
+    ///
+    /// The default is `None`, which waits indefinitely.
+    fn per_try_timeout(&self) -> Option<Duration> {
+        None
+    }
+    /// Determine whether an unsuccessful attempt should be retried.
+    ///
+    /// `attempt` is the number of the attempt that produced the `outcome`, starting at 1.
+    /// The default uses [`DefaultShouldRetry`].
+    fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool {
+        DefaultShouldRetry.should_retry(outcome, attempt)
+    }
     /// A Future that will wait until the request can be retried.
     /// `error` is the [`Error`] value the led to a retry attempt.
     /// `retry_after` is the duration to wait before retrying, if provided by the server response.
@@ -97,6 +113,66 @@ pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
     }
 }
 
+/// The number of the retry being attempted, starting at 1.
+///
+/// The retry policy adds this to the [`Context`] passed to subsequent policies when it retries a request,
+/// so per-try policies can tell retries from the first attempt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryAttempt(pub u32);
+
+/// The outcome of an unsuccessful attempt, used by a [`ShouldRetry`] to determine whether to retry.
+#[derive(Debug)]
+pub enum AttemptOutcome<'a> {
+    /// The service responded with an unsuccessful status code.
+    Response {
+        /// The status code of the response.
+        status: StatusCode,
+        /// The headers of the response.
+        headers: &'a Headers,
+        /// The error code from the `x-ms-error-code` header or response body, if any.
+        error_code: Option<&'a str>,
+    },
+
+    /// The attempt failed without a response from the service.
+    Error(&'a Error),
+}
+
+/// Determines whether an unsuccessful attempt should be retried.
+///
+/// Set a custom `ShouldRetry` using [`RetryOptions::with_should_retry`](crate::http::RetryOptions::with_should_retry),
+/// or [`RetryOptions::with_default_should_retry`](crate::http::RetryOptions::with_default_should_retry) for a client library's default.
+///
+/// # Examples
+///
+/// Retry 449 (Retry With) responses in addition to the default statuses.
+///
+/// ```
+/// use std::sync::Arc;
+/// use typespec_client_core::http::{
+///     policies::{AttemptOutcome, DefaultShouldRetry, ShouldRetry},
+///     RetryOptions, StatusCode,
+/// };
+///
+/// #[derive(Debug)]
+/// struct RetryWith;
+///
+/// impl ShouldRetry for RetryWith {
+///     fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool {
+///         matches!(outcome, AttemptOutcome::Response { status, .. } if *status == StatusCode::from(449))
+///             || DefaultShouldRetry.should_retry(outcome, attempt)
+///     }
+/// }
+///
+/// let options = RetryOptions::default().with_should_retry(Arc::new(RetryWith));
+/// ```
+pub trait ShouldRetry: std::fmt::Debug + Send + Sync {
+    /// Returns `true` if the attempt that produced the `outcome` should be retried.
+    ///
+    /// `attempt` is the number of the attempt that produced the `outcome`, starting at 1.
+    /// The [`RetryPolicy`] still stops retrying once it has expired.
+    fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool;
+}
+
 /// The status codes where a retry should be attempted.
 ///
 /// On all other 4xx and 5xx status codes no retry is attempted.
@@ -109,6 +185,59 @@ const RETRY_STATUSES: &[StatusCode] = &[
     StatusCode::GatewayTimeout,
 ];
 
+/// The default [`ShouldRetry`].
+///
+/// Retries 408, 429, 500, 502, 503, and 504 responses, and I/O errors or timeouts.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultShouldRetry;
+
+impl ShouldRetry for DefaultShouldRetry {
+    fn should_retry(&self, outcome: &AttemptOutcome<'_>, _attempt: u32) -> bool {
+        match outcome {
+            AttemptOutcome::Response { status, .. } => RETRY_STATUSES.contains(status),
+            AttemptOutcome::Error(error) => {
+                matches!(error.kind(), ErrorKind::Io | ErrorKind::Timeout)
+            }
+        }
+    }
+}
+
+/// Gets the time remaining until the `deadline`, which is zero if the deadline has passed.
+fn time_until(deadline: OffsetDateTime) -> Duration {
+    (deadline - OffsetDateTime::now_utc()).max(Duration::ZERO)
+}
+
+fn deadline_exceeded() -> Error {
+    Error::message(ErrorKind::Timeout, "operation deadline exceeded")
+}
+
+/// Runs the `future` until completion, or until the `timeout` elapses and the future is cancelled.
+///
+/// Returns `None` if the `timeout` elapsed.
+pub(crate) async fn with_timeout<F: Future>(
+    future: F,
+    timeout: Option<Duration>,
+) -> Option<F::Output> {
+    let Some(timeout) = timeout else {
+        return Some(future.await);
+    };
+    match future::select(pin!(future), get_async_runtime().sleep(timeout)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+/// Runs the `future` until completion, or until the `token` is cancelled and the future is dropped.
+async fn until_cancelled<F: Future>(
+    future: F,
+    token: Option<&CancellationToken>,
+) -> typespec::Result<F::Output> {
+    match token {
+        Some(token) => token.run_until_cancelled(future).await,
+        None => Ok(future.await),
+    }
+}
+
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl<T> Policy for T
@@ -123,15 +252,49 @@ where
     ) -> PolicyResult {
         let mut retry_count = 0;
         let mut start = None;
+        let deadline = ctx.deadline();
+        let cancellation = ctx.value::<CancellationToken>();
 
         loop {
+            if let Some(token) = cancellation {
+                token.check()?;
+            }
             if retry_count > 0 {
                 request.body.reset().await.context(
                     ErrorKind::Other,
                     "failed to reset body stream before retrying request",
                 )?;
             }
-            let result = next[0].send(ctx, request, &next[1..]).await;
+
+            let remaining = deadline.map(time_until);
+            if remaining == Some(Duration::ZERO) {
+                return Err(deadline_exceeded());
+            }
+            let timeout = match (self.per_try_timeout(), remaining) {
+                (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+                (timeout, remaining) => timeout.or(remaining),
+            };
+            let retry_ctx;
+            let attempt_ctx = if retry_count > 0 {
+                retry_ctx = ctx.clone().with_value(RetryAttempt(retry_count));
+                &retry_ctx
+            } else {
+                ctx
+            };
+            // An attempt already sent is not abandoned if cancelled, so the outcome of the request is never unknown.
+            let attempt = with_timeout(next[0].send(attempt_ctx, request, &next[1..]), timeout);
+            let result = match attempt.await {
+                Some(result) => result,
+                None if deadline.is_some_and(|d| OffsetDateTime::now_utc() >= d) => {
+                    return Err(deadline_exceeded());
+                }
+                None => Err(Error::with_message(ErrorKind::Timeout, || {
+                    format!(
+                        "request attempt timed out after {}",
+                        timeout.unwrap_or_default()
+                    )
+                })),
+            };
             // only start keeping track of time after the first request is made
             let start = start.get_or_insert_with(OffsetDateTime::now_utc);
             let (last_error, retry_after) = match result {
@@ -158,6 +321,7 @@ where
                         _ => None,
                     };
 
+                    let headers = response.headers().clone();
                     let http_error = HttpError::new(response).await;
 
                     let error_kind = ErrorKind::http_response(
@@ -165,7 +329,12 @@ where
                         http_error.error_code().map(std::borrow::ToOwned::to_owned),
                     );
 
-                    if !RETRY_STATUSES.contains(&status) {
+                    let outcome = AttemptOutcome::Response {
+                        status,
+                        headers: &headers,
+                        error_code: http_error.error_code(),
+                    };
+                    if !self.should_retry(&outcome, retry_count + 1) {
                         debug!(
                             "server returned error status which will not be retried: {}",
                             status
@@ -187,18 +356,16 @@ where
                     (Error::new(error_kind, http_error), retry_after)
                 }
                 Err(error) => {
-                    if error.kind() == &ErrorKind::Io {
+                    if self.should_retry(&AttemptOutcome::Error(&error), retry_count + 1) {
                         debug!(
-                            "io error occurred when making request which will be retried: {}",
+                            "error occurred when making request which will be retried: {}",
                             error
                         );
-                        // IO error so no Retry-After headers - leave the retry period up to the policy
+                        // no response so no Retry-After headers - leave the retry period up to the policy
                         let retry_after = None;
                         (error, retry_after)
                     } else {
-                        return Err(
-                            error.context("non-io error occurred which will not be retried")
-                        );
+                        return Err(error.context("error occurred which will not be retried"));
                     }
                 }
             };
@@ -210,7 +377,24 @@ where
             }
             retry_count += 1;
 
-            self.wait(&last_error, retry_count, retry_after).await;
+            let wait = with_timeout(
+                self.wait(&last_error, retry_count, retry_after),
+                deadline.map(time_until),
+            );
+            let Ok(waited) = until_cancelled(wait, cancellation).await else {
+                return Err(Error::full(
+                    ErrorKind::Cancelled,
+                    last_error,
+                    "operation was cancelled while waiting to retry",
+                ));
+            };
+            if waited.is_none() {
+                return Err(Error::full(
+                    ErrorKind::Timeout,
+                    last_error,
+                    "operation deadline exceeded while waiting to retry",
+                ));
+            }
         }
     }
 }
@@ -219,7 +403,9 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::http::{headers::Headers, FixedRetryOptions, Method, RawResponse, RetryOptions};
     use ::time::macros::datetime;
+    use std::sync::atomic::{AtomicU32, Ordering};
 
     // A function that returns a fixed "now" value for testing.
     fn datetime_now() -> OffsetDateTime {
@@ -276,4 +462,255 @@ mod test {
         let retry_after = get_retry_after(&headers, datetime_now);
         assert_eq!(retry_after, Some(Duration::seconds(123)));
     }
+
+    /// Hangs for the first `hangs` attempts, then responds with 200 OK.
+    #[derive(Debug)]
+    struct HangingPolicy {
+        hangs: u32,
+        attempts: AtomicU32,
+        last_retry_attempt: AtomicU32,
+    }
+
+    impl HangingPolicy {
+        fn new(hangs: u32) -> Arc<Self> {
+            Arc::new(Self {
+                hangs,
+                attempts: AtomicU32::new(0),
+                last_retry_attempt: AtomicU32::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Policy for HangingPolicy {
+        async fn send(
+            &self,
+            ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            self.last_retry_attempt.store(
+                ctx.value::<RetryAttempt>().map_or(0, |retry| retry.0),
+                Ordering::SeqCst,
+            );
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+            if attempt < self.hangs {
+                sleep(Duration::seconds(60)).await;
+            }
+            Ok(RawResponse::from_bytes(
+                StatusCode::Ok,
+                Headers::new(),
+                bytes::Bytes::new(),
+            ))
+        }
+    }
+
+    fn fixed_retry(per_try_timeout: Option<Duration>) -> Arc<dyn Policy> {
+        RetryOptions::fixed(FixedRetryOptions {
+            delay: Duration::milliseconds(10),
+            max_retries: 2,
+            per_try_timeout,
+            ..Default::default()
+        })
+        .to_policy()
+    }
+
+    fn request() -> Request {
+        Request::new("http://localhost".parse().unwrap(), Method::Get)
+    }
+
+    /// Responds with the given status codes in order, with an `x-ms-substatus` header.
+    #[derive(Debug)]
+    struct StatusPolicy {
+        responses: std::sync::Mutex<Vec<(StatusCode, &'static str)>>,
+    }
+
+    #[async_trait]
+    impl Policy for StatusPolicy {
+        async fn send(
+            &self,
+            _ctx: &Context,
+            _request: &mut Request,
+            _next: &[Arc<dyn Policy>],
+        ) -> PolicyResult {
+            let (status, substatus) = self.responses.lock().unwrap().remove(0);
+            let mut headers = Headers::new();
+            headers.insert("x-ms-substatus", substatus);
+            Ok(RawResponse::from_bytes(
+                status,
+                headers,
+                bytes::Bytes::new(),
+            ))
+        }
+    }
+
+    /// Retries 449 responses, and 410 responses with sub-status 1002 on the first attempt only.
+    #[derive(Debug)]
+    struct CustomShouldRetry;
+
+    impl ShouldRetry for CustomShouldRetry {
+        fn should_retry(&self, outcome: &AttemptOutcome<'_>, attempt: u32) -> bool {
+            match outcome {
+                AttemptOutcome::Response {
+                    status, headers, ..
+                } => {
+                    *status == StatusCode::from(449)
+                        || (*status == StatusCode::Gone
+                            && attempt == 1
+                            && headers.get_optional_str(&"x-ms-substatus".into()) == Some("1002"))
+                }
+                AttemptOutcome::Error(_) => false,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_should_retry() {
+        let transport = Arc::new(StatusPolicy {
+            responses: std::sync::Mutex::new(vec![
+                (StatusCode::Gone, "1002"),
+                (StatusCode::from(449), "0"),
+                (StatusCode::Ok, "0"),
+            ]),
+        });
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let options = RetryOptions::fixed(FixedRetryOptions {
+            delay: Duration::milliseconds(10),
+            ..Default::default()
+        });
+
+        let response = options
+            .clone()
+            .with_should_retry(Arc::new(CustomShouldRetry))
+            .to_policy()
+            .send(&Context::new(), &mut request(), &next)
+            .await
+            .expect("custom statuses should be retried");
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        // 410 is not retried on the second attempt.
+        transport
+            .responses
+            .lock()
+            .unwrap()
+            .extend([(StatusCode::from(449), "0"), (StatusCode::Gone, "1002")]);
+        let err = options
+            .clone()
+            .with_default_should_retry(Arc::new(CustomShouldRetry))
+            .to_policy()
+            .send(&Context::new(), &mut request(), &next)
+            .await
+            .expect_err("410 should not be retried");
+        assert_eq!(err.http_status(), Some(StatusCode::Gone));
+
+        // A caller's `ShouldRetry` replaces the client library's default.
+        transport
+            .responses
+            .lock()
+            .unwrap()
+            .extend([(StatusCode::from(449), "0"), (StatusCode::Ok, "0")]);
+        let err = options
+            .with_default_should_retry(Arc::new(CustomShouldRetry))
+            .with_should_retry(Arc::new(DefaultShouldRetry))
+            .to_policy()
+            .send(&Context::new(), &mut request(), &next)
+            .await
+            .expect_err("449 should not be retried");
+        assert_eq!(err.http_status(), Some(StatusCode::from(449)));
+    }
+
+    #[tokio::test]
+    async fn per_try_timeout_retries() {
+        let transport = HangingPolicy::new(1);
+        let retry = fixed_retry(Some(Duration::milliseconds(50)));
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+
+        let response = retry
+            .send(&Context::new(), &mut request(), &next)
+            .await
+            .expect("second attempt should succeed");
+        assert_eq!(response.status(), StatusCode::Ok);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(transport.last_retry_attempt.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn per_try_timeout_expires() {
+        let transport = HangingPolicy::new(u32::MAX);
+        let retry = fixed_retry(Some(Duration::milliseconds(20)));
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+
+        let err = retry
+            .send(&Context::new(), &mut request(), &next)
+            .await
+            .expect_err("all attempts should time out");
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn deadline_exceeded() {
+        let transport = HangingPolicy::new(u32::MAX);
+        let retry = fixed_retry(None);
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let ctx =
+            Context::new().with_deadline(OffsetDateTime::now_utc() + Duration::milliseconds(50));
+
+        let err = retry
+            .send(&ctx, &mut request(), &next)
+            .await
+            .expect_err("deadline should be exceeded");
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+
+        // No attempt is made after the deadline has passed.
+        let err = retry
+            .send(&ctx, &mut request(), &next)
+            .await
+            .expect_err("deadline has passed");
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+
+        let err = RetryOptions::none()
+            .to_policy()
+            .send(&ctx, &mut request(), &next)
+            .await
+            .expect_err("deadline has passed");
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cancelled() {
+        let transport = HangingPolicy::new(u32::MAX);
+        let retry = fixed_retry(Some(Duration::milliseconds(20)));
+        let next: Vec<Arc<dyn Policy>> = vec![transport.clone()];
+        let token = CancellationToken::new();
+        let ctx = Context::new().with_value(token.clone());
+
+        let cancel = async {
+            sleep(Duration::milliseconds(50)).await;
+            token.cancel();
+        };
+        let mut req = request();
+        let (result, _) = futures::join!(retry.send(&ctx, &mut req, &next), cancel);
+        assert_eq!(result.unwrap_err().kind(), &ErrorKind::Cancelled);
+        let attempts = transport.attempts.load(Ordering::SeqCst);
+        assert!(attempts > 0 && attempts < 3, "attempts: {attempts}");
+
+        // No attempt is made once cancelled.
+        let err = retry
+            .send(&ctx, &mut request(), &next)
+            .await
+            .expect_err("cancelled");
+        assert_eq!(err.kind(), &ErrorKind::Cancelled);
+
+        let err = RetryOptions::none()
+            .to_policy()
+            .send(&ctx, &mut request(), &next)
+            .await
+            .expect_err("cancelled");
+        assert_eq!(err.kind(), &ErrorKind::Cancelled);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), attempts);
+    }
 }
@@ -2,11 +2,13 @@
 // Licensed under the MIT License.
 
 use crate::{
+    cancellation::CancellationToken,
     error::HttpError,
     http::{
         policies::{Policy, PolicyResult},
         Context, Request,
     },
+    time::Duration,
 };
 use std::sync::Arc;
 use typespec::error::{Error, ErrorKind};
@@ -28,8 +30,17 @@ impl Policy for NoRetryPolicy {
         request: &mut Request,
         next: &[Arc<dyn Policy>],
     ) -> PolicyResult {
-        // just call the following policies and bubble up the error
-        let response = next[0].send(ctx, request, &next[1..]).await?;
+        // just call the following policies and bubble up the error, unless the deadline is exceeded or cancelled
+        let remaining = ctx.deadline().map(super::time_until);
+        if remaining == Some(Duration::ZERO) {
+            return Err(super::deadline_exceeded());
+        }
+        if let Some(token) = ctx.value::<CancellationToken>() {
+            token.check()?;
+        }
+        let response = super::with_timeout(next[0].send(ctx, request, &next[1..]), remaining)
+            .await
+            .ok_or_else(super::deadline_exceeded)??;
 
         if response.status().is_success() {
             Ok(response)
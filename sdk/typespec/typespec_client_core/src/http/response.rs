@@ -4,6 +4,10 @@
 //! HTTP responses.
 
 use crate::http::{headers::Headers, DeserializeWith, Format, JsonFormat, StatusCode};
+#[cfg(feature = "json")]
+use crate::json::JsonArrayStream;
+#[cfg(feature = "xml")]
+use crate::xml::XmlElementStream;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use serde::de::DeserializeOwned;
@@ -188,9 +192,19 @@ impl ResponseBody {
     }
 
     /// Collect the stream into a [`Bytes`] collection.
+    ///
+    /// If the body was received in a single chunk, that chunk is returned without copying it.
     pub async fn collect(mut self) -> crate::Result<Bytes> {
-        let mut final_result = Vec::new();
-
+        let Some(first) = self.0.next().await.transpose()? else {
+            return Ok(Bytes::new());
+        };
+        let Some(second) = self.0.next().await.transpose()? else {
+            return Ok(first);
+        };
+
+        let mut final_result = Vec::with_capacity(first.len() + second.len());
+        final_result.extend(&first);
+        final_result.extend(&second);
         while let Some(res) = self.0.next().await {
             final_result.extend(&res?);
         }
@@ -218,6 +232,50 @@ impl ResponseBody {
         crate::json::from_json(body)
     }
 
+    /// Deserialize the items of a top-level JSON array into type `T` incrementally as the body is received.
+    ///
+    /// Unlike [`ResponseBody::json`], the entire body is not collected into memory before it is deserialized.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use futures::TryStreamExt as _;
+    /// # use serde::Deserialize;
+    /// # use typespec_client_core::http::{headers::Headers, RawResponse, StatusCode};
+    /// #[derive(Deserialize)]
+    /// struct Secret {
+    ///     name: String,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> typespec_client_core::Result<()> {
+    /// let body = RawResponse::from_bytes(StatusCode::Ok, Headers::new(), r#"[{"name":"a"},{"name":"b"}]"#)
+    ///     .into_body();
+    /// let secrets: Vec<Secret> = body.json_array().try_collect().await?;
+    /// assert_eq!(secrets[1].name, "b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn json_array<T>(self) -> JsonArrayStream<Self, T>
+    where
+        T: DeserializeOwned,
+    {
+        JsonArrayStream::new(self)
+    }
+
+    /// Deserialize the items of a JSON array in the `field` of the top-level JSON object into type `T`
+    /// incrementally as the body is received e.g., the `value` of a page of items.
+    ///
+    /// If the field is missing or `null`, the stream yields no items. The rest of the body after the array is not read,
+    /// so use [`ResponseBody::json`] if you also need other fields, like a `nextLink`, that may follow the array.
+    #[cfg(feature = "json")]
+    pub fn json_array_field<T>(self, field: impl Into<String>) -> JsonArrayStream<Self, T>
+    where
+        T: DeserializeOwned,
+    {
+        JsonArrayStream::with_field(self, Some(field.into()))
+    }
+
     /// Deserialize the XML stream into type `T`.
     #[cfg(feature = "xml")]
     pub async fn xml<T>(self) -> crate::Result<T>
@@ -227,6 +285,38 @@ impl ResponseBody {
         let body = self.collect().await?;
         crate::xml::read_xml(&body)
     }
+
+    /// Deserialize each XML element named `name` into type `T` incrementally as the body is received
+    /// e.g., each `Blob` in a storage listing.
+    ///
+    /// Unlike [`ResponseBody::xml`], the entire body is not collected into memory before it is deserialized.
+    #[cfg(feature = "xml")]
+    pub fn xml_elements<T>(self, name: impl Into<String>) -> XmlElementStream<Self, T>
+    where
+        T: DeserializeOwned,
+    {
+        XmlElementStream::new(self, name)
+    }
+
+    /// Deserialize the CBOR stream into type `T`.
+    #[cfg(feature = "cbor")]
+    pub async fn cbor<T>(self) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let body = self.collect().await?;
+        crate::cbor::from_cbor(body)
+    }
+
+    /// Deserialize the MessagePack stream into type `T`.
+    #[cfg(feature = "msgpack")]
+    pub async fn msgpack<T>(self) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let body = self.collect().await?;
+        crate::msgpack::from_msgpack(body)
+    }
 }
 
 impl Stream for ResponseBody {
@@ -351,6 +441,31 @@ mod tests {
             assert_eq!(secret.yon_value, "my_value");
         }
 
+        #[tokio::test]
+        async fn deserialize_array_field() {
+            use futures::{stream, TryStreamExt as _};
+
+            let chunks = stream::iter(
+                [
+                    r#"{"value":[{"name":"a","val"#,
+                    r#"ue":"1"},{"name":"b","value":"2"}],"#,
+                    r#""nextLink":null}"#,
+                ]
+                .map(|chunk| Ok(bytes::Bytes::from_static(chunk.as_bytes()))),
+            );
+            let response: Response<GetSecretListResponse> =
+                RawResponse::new(StatusCode::Ok, Headers::new(), Box::pin(chunks)).into();
+            let secrets: Vec<GetSecretResponse> = response
+                .into_raw_body()
+                .json_array_field("value")
+                .try_collect()
+                .await
+                .expect("deserialize secrets");
+            assert_eq!(secrets.len(), 2);
+            assert_eq!(secrets[1].name, "b");
+            assert_eq!(secrets[1].value, "2");
+        }
+
         #[tokio::test]
         async fn deserialize_pageable_from_body() {
             // We need to efficiently deserialize the body twice to get the "nextLink" but return it to the caller.
@@ -422,5 +537,86 @@ mod tests {
             assert_eq!(secret.yon_name, "my_secret");
             assert_eq!(secret.yon_value, "my_value");
         }
+
+        #[tokio::test]
+        async fn deserialize_elements() {
+            use futures::TryStreamExt as _;
+
+            let response: Response<GetSecretResponse, XmlFormat> = RawResponse::from_bytes(
+                StatusCode::Ok,
+                Headers::new(),
+                "<Secrets><Secret><name>a</name><value>1</value></Secret><Secret><name>b</name><value>2</value></Secret></Secrets>",
+            )
+            .into();
+            let secrets: Vec<GetSecretResponse> = response
+                .into_raw_body()
+                .xml_elements("Secret")
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(secrets.len(), 2);
+            assert_eq!(secrets[1].name, "b");
+            assert_eq!(secrets[1].value, "2");
+        }
+    }
+
+    #[cfg(feature = "cbor")]
+    mod cbor {
+        use crate::http::headers::Headers;
+        use crate::http::CborFormat;
+        use crate::http::RawResponse;
+        use crate::http::Response;
+        use crate::http::StatusCode;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Deserialize, Serialize)]
+        struct GetSecretResponse {
+            name: String,
+            value: String,
+        }
+
+        #[tokio::test]
+        async fn deserialize_default_type() {
+            let body = crate::cbor::to_cbor(&GetSecretResponse {
+                name: "my_secret".to_string(),
+                value: "my_value".to_string(),
+            })
+            .unwrap();
+            let response: Response<GetSecretResponse, CborFormat> =
+                RawResponse::from_bytes(StatusCode::Ok, Headers::new(), body).into();
+            let secret = response.into_body().await.unwrap();
+            assert_eq!(secret.name, "my_secret");
+            assert_eq!(secret.value, "my_value");
+        }
+    }
+
+    #[cfg(feature = "msgpack")]
+    mod msgpack {
+        use crate::http::headers::Headers;
+        use crate::http::MessagePackFormat;
+        use crate::http::RawResponse;
+        use crate::http::Response;
+        use crate::http::StatusCode;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Deserialize, Serialize)]
+        struct GetSecretResponse {
+            name: String,
+            value: String,
+        }
+
+        #[tokio::test]
+        async fn deserialize_default_type() {
+            let body = crate::msgpack::to_msgpack(&GetSecretResponse {
+                name: "my_secret".to_string(),
+                value: "my_value".to_string(),
+            })
+            .unwrap();
+            let response: Response<GetSecretResponse, MessagePackFormat> =
+                RawResponse::from_bytes(StatusCode::Ok, Headers::new(), body).into();
+            let secret = response.into_body().await.unwrap();
+            assert_eq!(secret.name, "my_secret");
+            assert_eq!(secret.value, "my_value");
+        }
     }
 }
@@ -0,0 +1,424 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use bytes::{Buf as _, Bytes, BytesMut};
+use futures::{ready, Stream, StreamExt as _};
+use serde::de::DeserializeOwned;
+use std::{
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use typespec::error::{Error, ErrorKind, Result};
+
+/// A stream of items deserialized incrementally from a JSON array as the bytes of a body arrive.
+///
+/// The array can either be the top-level JSON value, or the value of a field of the top-level JSON object e.g., `value` or `Documents`.
+/// Only the bytes of the item being deserialized are buffered, so memory is bounded by the size of the largest item
+/// rather than the size of the body. Each item is deserialized directly from the buffered bytes.
+///
+/// If the field is missing or `null`, the stream yields no items. Any part of the body after the array is not read.
+pub struct JsonArrayStream<S, T> {
+    stream: S,
+    buffer: BytesMut,
+    field: Option<String>,
+    state: State,
+    scanner: Scanner,
+    eof: bool,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<S, T> JsonArrayStream<S, T>
+where
+    S: Stream<Item = Result<Bytes>> + Unpin,
+    T: DeserializeOwned,
+{
+    /// Creates a stream of the items of the top-level JSON array in `stream`.
+    pub fn new(stream: S) -> Self {
+        Self::with_field(stream, None)
+    }
+
+    /// Creates a stream of the items of the JSON array in the `field` of the top-level JSON object in `stream`.
+    pub fn with_field(stream: S, field: Option<String>) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::new(),
+            field,
+            state: State::Start,
+            scanner: Scanner::default(),
+            eof: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Parses as much of the buffer as possible until an item is deserialized or more bytes are needed.
+    fn advance(&mut self) -> Result<Progress<T>> {
+        loop {
+            if self.state == State::Done {
+                return Ok(Progress::Done);
+            }
+            let Some(b) = self.peek() else {
+                return Ok(Progress::NeedData);
+            };
+
+            self.state = match self.state {
+                State::Start => match (b, &self.field) {
+                    (b'[', None) => self.consume(State::Item { first: true }),
+                    (b'{', Some(_)) => self.consume(State::FieldName { first: true }),
+                    (_, None) => return Err(syntax_error("expected a JSON array")),
+                    (_, Some(_)) => return Err(syntax_error("expected a JSON object")),
+                },
+                State::FieldName { first } => match b {
+                    b'}' if first => State::Done,
+                    b'"' => {
+                        let Some(value) = self.scan_value()? else {
+                            return Ok(Progress::NeedData);
+                        };
+                        let name: String = serde_json::from_slice(&value)?;
+                        State::Colon {
+                            matched: self.field.as_deref() == Some(name.as_str()),
+                        }
+                    }
+                    _ => return Err(syntax_error("expected a field name")),
+                },
+                State::Colon { matched } => match b {
+                    b':' => self.consume(State::FieldValue { matched }),
+                    _ => return Err(syntax_error("expected ':' after a field name")),
+                },
+                State::FieldValue { matched: true } => match b {
+                    b'[' => self.consume(State::Item { first: true }),
+                    _ => {
+                        let Some(value) = self.scan_value()? else {
+                            return Ok(Progress::NeedData);
+                        };
+                        if value.as_ref() != b"null" {
+                            return Err(syntax_error("expected the field to be a JSON array"));
+                        }
+                        State::Done
+                    }
+                },
+                State::FieldValue { matched: false } => {
+                    if self.scan_value()?.is_none() {
+                        return Ok(Progress::NeedData);
+                    }
+                    State::AfterField
+                }
+                State::AfterField => match b {
+                    b',' => self.consume(State::FieldName { first: false }),
+                    b'}' => State::Done,
+                    _ => return Err(syntax_error("expected ',' or '}' after a field value")),
+                },
+                State::Item { first } => match b {
+                    b']' if first => State::Done,
+                    _ => {
+                        let Some(value) = self.scan_value()? else {
+                            return Ok(Progress::NeedData);
+                        };
+                        self.state = State::AfterItem;
+                        return Ok(Progress::Item(serde_json::from_slice(&value)?));
+                    }
+                },
+                State::AfterItem => match b {
+                    b',' => self.consume(State::Item { first: false }),
+                    b']' => State::Done,
+                    _ => return Err(syntax_error("expected ',' or ']' after an array item")),
+                },
+                State::Done => unreachable!(),
+            };
+        }
+    }
+
+    /// Skips whitespace and returns the next byte in the buffer, if any.
+    fn peek(&mut self) -> Option<u8> {
+        let whitespace = self
+            .buffer
+            .iter()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count();
+        self.buffer.advance(whitespace);
+        self.buffer.first().copied()
+    }
+
+    /// Consumes the next byte in the buffer and returns the next `state`.
+    fn consume(&mut self, state: State) -> State {
+        self.buffer.advance(1);
+        state
+    }
+
+    /// Splits a complete JSON value from the front of the buffer, if the buffer contains one.
+    fn scan_value(&mut self) -> Result<Option<BytesMut>> {
+        match self.scanner.scan(&self.buffer, self.eof) {
+            Some(0) => Err(syntax_error("expected a JSON value")),
+            Some(len) => Ok(Some(self.buffer.split_to(len))),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<S, T> Stream for JsonArrayStream<S, T>
+where
+    S: Stream<Item = Result<Bytes>> + Unpin,
+    T: DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let err = match this.advance() {
+                Ok(Progress::Item(item)) => return Poll::Ready(Some(Ok(item))),
+                Ok(Progress::Done) => return Poll::Ready(None),
+                Ok(Progress::NeedData) if this.eof => syntax_error("unexpected end of JSON array"),
+                Ok(Progress::NeedData) => match ready!(this.stream.poll_next_unpin(cx)) {
+                    Some(Ok(bytes)) => {
+                        this.buffer.extend_from_slice(&bytes);
+                        continue;
+                    }
+                    Some(Err(err)) => err,
+                    None => {
+                        this.eof = true;
+                        continue;
+                    }
+                },
+                Err(err) => err,
+            };
+            this.state = State::Done;
+            return Poll::Ready(Some(Err(err)));
+        }
+    }
+}
+
+impl<S, T> fmt::Debug for JsonArrayStream<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonArrayStream")
+            .field("field", &self.field)
+            .finish_non_exhaustive()
+    }
+}
+
+enum Progress<T> {
+    Item(T),
+    NeedData,
+    Done,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// Expecting the top-level array, or the top-level object containing the field.
+    Start,
+    /// Expecting a field name, or the end of the top-level object if it is the `first` field.
+    FieldName { first: bool },
+    /// Expecting the `:` after a field name, which `matched` the field containing the array.
+    Colon { matched: bool },
+    /// Expecting the array if the field name `matched`, or any value to skip.
+    FieldValue { matched: bool },
+    /// Expecting the `,` or `}` after a field value.
+    AfterField,
+    /// Expecting an array item, or the end of the array if it is the `first` item.
+    Item { first: bool },
+    /// Expecting the `,` or `]` after an array item.
+    AfterItem,
+    /// The array has ended or an error occurred.
+    Done,
+}
+
+/// Finds the end of a JSON value, resuming where it left off as more bytes are buffered.
+///
+/// The scanner only tracks nesting and strings; the value itself is validated when it is deserialized.
+#[derive(Debug, Default)]
+struct Scanner {
+    offset: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl Scanner {
+    /// Returns the length of the JSON value at the start of `buffer`, or `None` if more bytes are needed.
+    fn scan(&mut self, buffer: &[u8], eof: bool) -> Option<usize> {
+        while let Some(&b) = buffer.get(self.offset) {
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if b == b'\\' {
+                    self.escaped = true;
+                } else if b == b'"' {
+                    self.in_string = false;
+                    if self.depth == 0 {
+                        return Some(self.finish(self.offset + 1));
+                    }
+                }
+            } else {
+                match b {
+                    b'"' => self.in_string = true,
+                    b'[' | b'{' => self.depth += 1,
+                    b']' | b'}' if self.depth == 0 => return Some(self.finish(self.offset)),
+                    b']' | b'}' => {
+                        self.depth -= 1;
+                        if self.depth == 0 {
+                            return Some(self.finish(self.offset + 1));
+                        }
+                    }
+                    b',' if self.depth == 0 => return Some(self.finish(self.offset)),
+                    b if self.depth == 0 && b.is_ascii_whitespace() => {
+                        return Some(self.finish(self.offset))
+                    }
+                    _ => {}
+                }
+            }
+            self.offset += 1;
+        }
+
+        // A number or literal may end at the end of the body.
+        if eof && self.depth == 0 && !self.in_string {
+            return Some(self.finish(self.offset));
+        }
+        None
+    }
+
+    fn finish(&mut self, len: usize) -> usize {
+        *self = Self::default();
+        len
+    }
+}
+
+fn syntax_error(message: &'static str) -> Error {
+    Error::message(ErrorKind::DataConversion, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{stream, TryStreamExt as _};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        id: String,
+    }
+
+    /// Splits the `json` into chunks of `size` bytes.
+    fn chunked(json: &'static str, size: usize) -> impl Stream<Item = Result<Bytes>> + Unpin {
+        stream::iter(
+            json.as_bytes()
+                .chunks(size)
+                .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    async fn collect<T: DeserializeOwned>(
+        json: &'static str,
+        field: Option<&str>,
+        size: usize,
+    ) -> Result<Vec<T>> {
+        JsonArrayStream::with_field(chunked(json, size), field.map(ToString::to_string))
+            .try_collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn top_level_array() {
+        let json = r#" [ {"id":"a"}, {"id":"b]}\"{["} ,{"id" : "c", "nested": [[1], {"x": []}]}] "#;
+        for size in 1..=json.len() {
+            let items: Vec<Item> = collect(json, None, size).await.expect("deserialize items");
+            assert_eq!(
+                items,
+                vec![
+                    Item { id: "a".into() },
+                    Item {
+                        id: "b]}\"{[".into()
+                    },
+                    Item { id: "c".into() },
+                ],
+                "chunk size {size}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn scalars() {
+        let json = "[1, -2.5e3,true ,null,\"x\"]";
+        for size in 1..=json.len() {
+            let items: Vec<serde_json::Value> = collect(json, None, size).await.unwrap();
+            assert_eq!(
+                items,
+                vec![
+                    1.into(),
+                    (-2500.0).into(),
+                    true.into(),
+                    serde_json::Value::Null,
+                    "x".into()
+                ]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_array() {
+        let items: Vec<Item> = collect(" [ ] ", None, 1).await.unwrap();
+        assert!(items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn field() {
+        let json = r#"{"_rid":"a\"b","count":2,"nested":{"Documents":[{"id":"x"}]},"Documents":[{"id":"a"},{"id":"b"}],"nextLink":"#;
+        for size in 1..=json.len() {
+            let items: Vec<Item> = collect(json, Some("Documents"), size).await.unwrap();
+            assert_eq!(
+                items,
+                vec![Item { id: "a".into() }, Item { id: "b".into() }]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_or_null_field() {
+        let items: Vec<Item> = collect(r#"{"count":0}"#, Some("value"), 3).await.unwrap();
+        assert!(items.is_empty());
+
+        let items: Vec<Item> = collect("{}", Some("value"), 1).await.unwrap();
+        assert!(items.is_empty());
+
+        let items: Vec<Item> = collect(r#"{"value":null}"#, Some("value"), 1)
+            .await
+            .unwrap();
+        assert!(items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid() {
+        for (json, field) in [
+            (r#"{"value":[]}"#, None),
+            (r#"[{"id":"a"}"#, None),
+            (r#"[{"id":"a"},]"#, None),
+            (r#"[{"id":"a"} {"id":"b"}]"#, None),
+            (r#"[{"id":1}]"#, None),
+            (r#"[{"id":"a"#, None),
+            (r#"[]"#, Some("value")),
+            (r#"{"value":{}}"#, Some("value")),
+            (r#"{"count":,"value":[]}"#, Some("value")),
+        ] {
+            let err = collect::<Item>(json, field, 4)
+                .await
+                .expect_err("invalid JSON");
+            assert_eq!(err.kind(), &ErrorKind::DataConversion, "{json}");
+        }
+    }
+
+    #[tokio::test]
+    async fn yields_items_before_end_of_body() {
+        let chunks = stream::iter(vec![
+            Ok(Bytes::from_static(br#"[{"id":"a"},"#)),
+            Err(Error::message(ErrorKind::Io, "connection reset")),
+        ]);
+        let mut items = JsonArrayStream::<_, Item>::new(chunks);
+        assert_eq!(items.next().await.unwrap().unwrap().id, "a");
+        assert_eq!(
+            items.next().await.unwrap().unwrap_err().kind(),
+            &ErrorKind::Io
+        );
+        assert!(items.next().await.is_none());
+    }
+}
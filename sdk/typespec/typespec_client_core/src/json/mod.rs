@@ -2,6 +2,10 @@
 // Licensed under the MIT License.
 
 //! JSON serialization functions.
+
+mod array;
+
+pub use array::JsonArrayStream;
 use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
 use typespec::error::Result;
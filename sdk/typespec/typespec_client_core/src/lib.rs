@@ -8,6 +8,9 @@
 mod macros;
 pub mod async_runtime;
 pub mod base64;
+pub mod cancellation;
+#[cfg(feature = "cbor")]
+pub mod cbor;
 pub mod error;
 pub mod fmt;
 pub mod fs;
@@ -15,6 +18,9 @@ pub mod fs;
 pub mod http;
 #[cfg(feature = "json")]
 pub mod json;
+pub mod metrics;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
 pub mod sleep;
 pub mod stream;
 pub mod time;
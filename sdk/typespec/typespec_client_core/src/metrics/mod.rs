@@ -0,0 +1,86 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Client metrics trait definitions
+//!
+//! This module defines the traits that are used to implement client metrics functionality
+//! alongside [distributed tracing](crate::tracing).
+//!
+//! Notes: There are four major traits defined here:
+//! - MeterProvider: This trait is responsible for providing meters - this is the
+//!   entrypoint for client metrics in the SDK.
+//! - Meter: This trait is responsible for creating instruments.
+//! - Histogram: This trait records a distribution of values e.g., request durations.
+//! - Counter: This trait records a monotonically increasing sum e.g., the number of messages sent.
+
+use crate::tracing::AttributeValue;
+use std::sync::Arc;
+
+/// An attribute recorded with a value, using the same [`AttributeValue`] as spans.
+pub type Attribute = (&'static str, AttributeValue);
+
+/// The MeterProvider trait is the entrypoint for client metrics in the SDK.
+///
+/// It provides a method to get a meter for a specific name and package version.
+pub trait MeterProvider {
+    /// Returns a meter for the given name.
+    ///
+    /// Arguments:
+    /// - `package_name`: The name of the package for which the meter is requested.
+    /// - `package_version`: The version of the package for which the meter is requested.
+    fn get_meter(
+        &self,
+        package_name: &'static str,
+        package_version: &'static str,
+    ) -> Box<dyn Meter + Send + Sync>;
+}
+
+/// The Meter trait creates the instruments that record client metrics.
+///
+/// A meter is returned by [`MeterProvider::get_meter`] for a specific package.
+/// Create instruments once, e.g., when a client is constructed, and record values with them as needed.
+pub trait Meter {
+    /// Creates a histogram to record a distribution of values.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the instrument e.g., `http.client.request.duration`.
+    /// - `description`: A description of what the instrument records.
+    /// - `unit`: The [UCUM](https://ucum.org) unit of recorded values e.g., `s`.
+    ///
+    fn create_histogram(
+        &self,
+        name: &'static str,
+        description: &'static str,
+        unit: &'static str,
+    ) -> Arc<dyn Histogram + Send + Sync>;
+
+    /// Creates a counter to record a monotonically increasing sum.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the instrument e.g., `messaging.client.sent.messages`.
+    /// - `description`: A description of what the instrument records.
+    /// - `unit`: The [UCUM](https://ucum.org) unit of recorded values e.g., `{message}`.
+    ///
+    fn create_counter(
+        &self,
+        name: &'static str,
+        description: &'static str,
+        unit: &'static str,
+    ) -> Arc<dyn Counter + Send + Sync>;
+}
+
+/// The Histogram trait records a distribution of values, e.g., request durations.
+///
+/// A histogram is created by [`Meter::create_histogram`].
+pub trait Histogram {
+    /// Records a value with the given attributes.
+    fn record(&self, value: f64, attributes: &[Attribute]);
+}
+
+/// The Counter trait records a monotonically increasing sum, e.g., the number of messages sent.
+///
+/// A counter is created by [`Meter::create_counter`].
+pub trait Counter {
+    /// Adds a value to the sum with the given attributes.
+    fn add(&self, value: u64, attributes: &[Attribute]);
+}
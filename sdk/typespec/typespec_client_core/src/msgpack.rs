@@ -0,0 +1,63 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! MessagePack serialization functions.
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Serialize};
+use typespec::error::{ErrorKind, Result, ResultExt};
+
+/// Serialize a type to [MessagePack](https://msgpack.org).
+///
+/// Structs are serialized as maps keyed by field name, rather than arrays, so they are compatible with other implementations.
+pub fn to_msgpack<T>(value: &T) -> Result<Bytes>
+where
+    T: ?Sized + Serialize,
+{
+    let buf = rmp_serde::to_vec_named(value).with_context(ErrorKind::DataConversion, || {
+        let t = core::any::type_name::<T>();
+        format!("failed to serialize {t} into msgpack")
+    })?;
+    Ok(buf.into())
+}
+
+/// Reads [MessagePack](https://msgpack.org) from bytes.
+pub fn from_msgpack<S, T>(body: S) -> Result<T>
+where
+    S: AsRef<[u8]>,
+    T: DeserializeOwned,
+{
+    rmp_serde::from_slice(body.as_ref()).with_context(ErrorKind::DataConversion, || {
+        let t = core::any::type_name::<T>();
+        format!("failed to deserialize msgpack into a {t}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Secret {
+        name: String,
+        enabled: bool,
+    }
+
+    #[test]
+    fn round_trip() {
+        let secret = Secret {
+            name: "my_secret".to_string(),
+            enabled: true,
+        };
+        let body = to_msgpack(&secret).unwrap();
+        // A fixmap with 2 entries.
+        assert_eq!(body[0], 0x82);
+        assert_eq!(from_msgpack::<_, Secret>(body).unwrap(), secret);
+    }
+
+    #[test]
+    fn invalid() {
+        let err = from_msgpack::<_, Secret>([0xc1]).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum AttributeArray {
     Bool(Vec<bool>),
     I64(Vec<i64>),
@@ -8,6 +9,7 @@ pub enum AttributeArray {
     String(Vec<String>),
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum AttributeValue {
     Bool(bool),
     I64(i64),
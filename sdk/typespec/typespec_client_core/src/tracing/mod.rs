@@ -17,9 +17,11 @@ use std::sync::Arc;
 /// - Tracer: This trait is responsible for creating spans and managing the active span.
 /// - Span: This trait represents a single unit of work in the distributed tracing system.
 mod attributes;
+mod trace_context;
 mod with_context;
 
 pub use attributes::{AttributeArray, AttributeValue};
+pub use trace_context::TraceContext;
 pub use with_context::{FutureExt, WithContext};
 
 /// The TracerProvider trait is the entrypoint for distributed tracing in the SDK.
@@ -82,6 +84,28 @@ pub trait Tracer {
         kind: SpanKind,
         parent: Arc<dyn Span + Send + Sync>,
     ) -> Result<Arc<dyn Span + Send + Sync>>;
+
+    /// Starts a new span with the given name and type, using the current span as the parent span,
+    /// and linked to spans in other traces e.g., the spans that sent messages being received.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the span to start.
+    /// - `kind`: The type of the span to start.
+    /// - `links`: The trace contexts of the spans to link to the new span.
+    ///
+    /// # Returns
+    /// An `Arc<dyn Span + Send + Sync>` representing the started span.
+    ///
+    /// The default implementation ignores `links` and calls [`Tracer::start_span_with_current`].
+    fn start_span_with_links(
+        &self,
+        name: &'static str,
+        kind: SpanKind,
+        links: &[TraceContext],
+    ) -> Result<Arc<dyn Span + Send + Sync>> {
+        let _ = links;
+        self.start_span_with_current(name, kind)
+    }
 }
 pub enum SpanStatus {
     Unset,
@@ -108,6 +132,16 @@ pub trait Span: AsAny {
     /// The 8 byte value which identifies the span.
     fn span_id(&self) -> [u8; 8];
 
+    /// The W3C trace context of the span to propagate to services.
+    ///
+    /// The default implementation returns an invalid trace context, which is not propagated.
+    fn trace_context(&self) -> TraceContext {
+        TraceContext {
+            span_id: self.span_id(),
+            ..Default::default()
+        }
+    }
+
     /// Ends the current span.
     fn end(&self) -> crate::Result<()>;
 
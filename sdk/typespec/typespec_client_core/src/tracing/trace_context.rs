@@ -0,0 +1,124 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::fmt::Write as _;
+
+/// The W3C trace context of a span, used to propagate the span to services.
+///
+/// See <https://www.w3.org/TR/trace-context/> for more information.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TraceContext {
+    /// The 16 byte trace ID.
+    pub trace_id: [u8; 16],
+
+    /// The 8 byte span ID.
+    pub span_id: [u8; 8],
+
+    /// Whether the span is sampled.
+    pub sampled: bool,
+
+    /// Vendor-specific trace state propagated in the `tracestate` header.
+    pub trace_state: Option<String>,
+}
+
+impl TraceContext {
+    /// Whether both the trace ID and span ID are not all zeros.
+    ///
+    /// Only valid trace contexts should be propagated.
+    pub fn is_valid(&self) -> bool {
+        self.trace_id != [0; 16] && self.span_id != [0; 8]
+    }
+
+    /// Formats the `traceparent` header value e.g., `00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01`.
+    pub fn traceparent(&self) -> String {
+        let mut value = String::with_capacity(55);
+        value.push_str("00-");
+        for b in self.trace_id {
+            let _ = write!(value, "{b:02x}");
+        }
+        value.push('-');
+        for b in self.span_id {
+            let _ = write!(value, "{b:02x}");
+        }
+        value.push_str(if self.sampled { "-01" } else { "-00" });
+        value
+    }
+
+    /// Parses a `traceparent` header value, returning `None` if the value is not a valid version 00 `traceparent`.
+    ///
+    /// Only the version 00 fields are parsed from values of future versions, as the specification requires.
+    pub fn from_traceparent(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+        if version.len() != 2
+            || version.eq_ignore_ascii_case("ff")
+            || (version == "00" && parts.next().is_some())
+        {
+            return None;
+        }
+        u8::from_str_radix(version, 16).ok()?;
+
+        let context = Self {
+            trace_id: decode_hex(trace_id)?,
+            span_id: decode_hex(span_id)?,
+            sampled: decode_hex::<1>(flags)?[0] & 0x01 == 0x01,
+            trace_state: None,
+        };
+        context.is_valid().then_some(context)
+    }
+}
+
+fn decode_hex<const N: usize>(value: &str) -> Option<[u8; N]> {
+    if value.len() != N * 2 || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    #[test]
+    fn round_trip() {
+        let context = TraceContext::from_traceparent(TRACEPARENT).expect("valid traceparent");
+        assert_eq!(context.trace_id[0], 0x0a);
+        assert_eq!(context.span_id[7], 0x31);
+        assert!(context.sampled);
+        assert_eq!(context.traceparent(), TRACEPARENT);
+    }
+
+    #[test]
+    fn future_version() {
+        let context = TraceContext::from_traceparent(
+            "cc-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00-extra",
+        )
+        .expect("valid traceparent");
+        assert!(!context.sampled);
+    }
+
+    #[test]
+    fn invalid() {
+        for value in [
+            "",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra",
+            "ff-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319c-0000000000000000-01",
+            "00-0af7651916cd43dd8448eb211c80319-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319g-b7ad6b7169203331-01",
+        ] {
+            assert_eq!(TraceContext::from_traceparent(value), None, "{value}");
+        }
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use super::read_xml;
+use bytes::{Buf as _, Bytes, BytesMut};
+use futures::{ready, Stream, StreamExt as _};
+use serde::de::DeserializeOwned;
+use std::{
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use typespec::error::{Error, ErrorKind, Result};
+
+/// A stream of elements deserialized incrementally from an XML document as the bytes of a body arrive.
+///
+/// Each element with the given name e.g., `Blob` in a storage listing, is deserialized when its end tag is received.
+/// Only the bytes of the element being deserialized are buffered, so memory is bounded by the size of the largest element
+/// rather than the size of the body. Elements with the same name nested within an element are deserialized as part of it.
+pub struct XmlElementStream<S, T> {
+    stream: S,
+    buffer: BytesMut,
+    name: String,
+    /// The offset in the buffer up to which markup has been scanned.
+    offset: usize,
+    /// The depth of elements with the same name within the element being buffered, if any.
+    depth: usize,
+    eof: bool,
+    done: bool,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<S, T> XmlElementStream<S, T>
+where
+    S: Stream<Item = Result<Bytes>> + Unpin,
+    T: DeserializeOwned,
+{
+    /// Creates a stream of the elements named `name` in `stream`.
+    pub fn new(stream: S, name: impl Into<String>) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::new(),
+            name: name.into(),
+            offset: 0,
+            depth: 0,
+            eof: false,
+            done: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Scans as much of the buffer as possible until an element is deserialized or more bytes are needed.
+    fn advance(&mut self) -> Result<Option<T>> {
+        loop {
+            let Some(start) = self.buffer[self.offset..]
+                .iter()
+                .position(|&b| b == b'<')
+                .map(|i| self.offset + i)
+            else {
+                self.skip(self.buffer.len());
+                return Ok(None);
+            };
+            let Some((len, markup)) = scan_markup(&self.buffer[start..], self.name.as_bytes())
+            else {
+                self.skip(start);
+                return Ok(None);
+            };
+
+            let mut end = start + len;
+            match markup {
+                Markup::Start { self_closing } => {
+                    if self.depth == 0 {
+                        self.buffer.advance(start);
+                        end -= start;
+                    }
+                    if !self_closing {
+                        self.depth += 1;
+                    } else if self.depth == 0 {
+                        return self.split_element(end).map(Some);
+                    }
+                }
+                Markup::End if self.depth > 0 => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        return self.split_element(end).map(Some);
+                    }
+                }
+                _ => {}
+            }
+            self.skip(end);
+        }
+    }
+
+    /// Marks the buffer as scanned up to `offset`, discarding scanned bytes if no element is being buffered.
+    fn skip(&mut self, offset: usize) {
+        if self.depth == 0 {
+            self.buffer.advance(offset);
+            self.offset = 0;
+        } else {
+            self.offset = offset;
+        }
+    }
+
+    /// Splits the buffered element ending at `end` from the buffer and deserializes it.
+    fn split_element(&mut self, end: usize) -> Result<T> {
+        let element = self.buffer.split_to(end);
+        self.offset = 0;
+        read_xml(&element)
+    }
+}
+
+impl<S, T> Stream for XmlElementStream<S, T>
+where
+    S: Stream<Item = Result<Bytes>> + Unpin,
+    T: DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            let err = match this.advance() {
+                Ok(Some(element)) => return Poll::Ready(Some(Ok(element))),
+                Ok(None) if this.eof => {
+                    this.done = true;
+                    if this.depth == 0 {
+                        return Poll::Ready(None);
+                    }
+                    Error::message(ErrorKind::DataConversion, "unexpected end of XML element")
+                }
+                Ok(None) => match ready!(this.stream.poll_next_unpin(cx)) {
+                    Some(Ok(bytes)) => {
+                        this.buffer.extend_from_slice(&bytes);
+                        continue;
+                    }
+                    Some(Err(err)) => err,
+                    None => {
+                        this.eof = true;
+                        continue;
+                    }
+                },
+                Err(err) => err,
+            };
+            this.done = true;
+            return Poll::Ready(Some(Err(err)));
+        }
+    }
+}
+
+impl<S, T> fmt::Debug for XmlElementStream<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XmlElementStream")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Markup {
+    /// A start tag of an element with the name being streamed.
+    Start { self_closing: bool },
+    /// An end tag of an element with the name being streamed.
+    End,
+    /// Any other tag, comment, CDATA section, processing instruction, or declaration.
+    Other,
+}
+
+/// Returns the length and kind of the markup at the start of `buffer`, or `None` if more bytes are needed.
+fn scan_markup(buffer: &[u8], name: &[u8]) -> Option<(usize, Markup)> {
+    for (prefix, terminator) in [
+        (&b"<!--"[..], &b"-->"[..]),
+        (b"<![CDATA[", b"]]>"),
+        (b"<?", b"?>"),
+    ] {
+        if has_prefix(buffer, prefix)? {
+            let len = find(&buffer[prefix.len()..], terminator)? + prefix.len() + terminator.len();
+            return Some((len, Markup::Other));
+        }
+    }
+
+    // Find the end of the tag, ignoring '>' in quoted attribute values.
+    let mut quote = None;
+    let len = buffer.iter().position(|&b| match quote {
+        Some(q) if b == q => {
+            quote = None;
+            false
+        }
+        Some(_) => false,
+        None if b == b'"' || b == b'\'' => {
+            quote = Some(b);
+            false
+        }
+        None => b == b'>',
+    })? + 1;
+
+    let tag = &buffer[1..len - 1];
+    let (end, tag) = match tag.strip_prefix(b"/") {
+        Some(tag) => (true, tag),
+        None => (false, tag),
+    };
+    let tag_name = tag
+        .split(|b| b.is_ascii_whitespace() || *b == b'/')
+        .next()
+        .unwrap_or_default();
+    let markup = match (tag_name == name, end) {
+        (true, true) => Markup::End,
+        (true, false) => Markup::Start {
+            self_closing: tag.ends_with(b"/"),
+        },
+        (false, _) => Markup::Other,
+    };
+    Some((len, markup))
+}
+
+/// Whether `buffer` starts with `prefix`, or `None` if `buffer` is too short to tell.
+fn has_prefix(buffer: &[u8], prefix: &[u8]) -> Option<bool> {
+    if buffer.len() >= prefix.len() {
+        Some(buffer.starts_with(prefix))
+    } else if prefix.starts_with(buffer) {
+        None
+    } else {
+        Some(false)
+    }
+}
+
+fn find(buffer: &[u8], needle: &[u8]) -> Option<usize> {
+    buffer
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{stream, TryStreamExt as _};
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    #[serde(default, rename_all = "PascalCase")]
+    struct Blob {
+        name: String,
+        properties: Option<Properties>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Properties {
+        #[serde(rename = "Content-Length")]
+        content_length: u64,
+    }
+
+    fn chunked(xml: &'static str, size: usize) -> impl Stream<Item = Result<Bytes>> + Unpin {
+        stream::iter(
+            xml.as_bytes()
+                .chunks(size)
+                .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    async fn collect(xml: &'static str, size: usize) -> Result<Vec<Blob>> {
+        XmlElementStream::new(chunked(xml, size), "Blob")
+            .try_collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn storage_listing() {
+        let xml = concat!(
+            "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+            "<EnumerationResults ServiceEndpoint=\"https://account.blob.core.windows.net/\" ContainerName=\"a>b\">",
+            "<!-- <Blob><Name>comment</Name></Blob> -->",
+            "<Blobs>",
+            "<Blob><Name>a.txt</Name><Properties><Content-Length>1</Content-Length></Properties></Blob>",
+            "<BlobPrefix><Name>dir/</Name></BlobPrefix>",
+            "<Blob>\n  <Name><![CDATA[<Blob>]]></Name>\n</Blob>",
+            "<Blob/>",
+            "</Blobs>",
+            "<NextMarker>2</NextMarker>",
+            "</EnumerationResults>",
+        );
+        for size in 1..=xml.len() {
+            let blobs = collect(xml, size).await.expect("deserialize blobs");
+            assert_eq!(
+                blobs,
+                vec![
+                    Blob {
+                        name: "a.txt".to_string(),
+                        properties: Some(Properties { content_length: 1 }),
+                    },
+                    Blob {
+                        name: "<Blob>".to_string(),
+                        properties: None,
+                    },
+                    Blob::default(),
+                ],
+                "chunk size {size}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn no_elements() {
+        let blobs = collect("<EnumerationResults><Blobs /></EnumerationResults>", 3)
+            .await
+            .unwrap();
+        assert!(blobs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn truncated() {
+        let err = collect("<Blobs><Blob><Name>a</Name></Blo", 4)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::DataConversion);
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            scan_markup(b"<Blob>", b"Blob"),
+            Some((
+                6,
+                Markup::Start {
+                    self_closing: false
+                }
+            ))
+        );
+        assert_eq!(
+            scan_markup(b"<Blob a='/>'/>", b"Blob"),
+            Some((14, Markup::Start { self_closing: true }))
+        );
+        assert_eq!(scan_markup(b"</Blob >", b"Blob"), Some((8, Markup::End)));
+        assert_eq!(
+            scan_markup(b"<BlobPrefix>", b"Blob"),
+            Some((12, Markup::Other))
+        );
+        assert_eq!(
+            scan_markup(b"<!-- <Blob> -->", b"Blob"),
+            Some((15, Markup::Other))
+        );
+        assert_eq!(scan_markup(b"<!-", b"Blob"), None);
+        assert_eq!(scan_markup(b"<Blob a=\">", b"Blob"), None);
+    }
+}
@@ -2,7 +2,11 @@
 // Licensed under the MIT License.
 
 //! XML serialization functions.
+
+mod elements;
+
 use bytes::Bytes;
+pub use elements::XmlElementStream;
 pub use quick_xml::serde_helpers::text_content as content;
 use quick_xml::{
     de::{from_reader, from_str},